@@ -86,6 +86,7 @@ impl AddContextServerModal {
                                 args,
                                 env: None,
                             }),
+                            endpoint: None,
                             settings: Some(json!({})),
                         },
                     );
@@ -0,0 +1,2 @@
+alter table project_repositories
+    add column stash_entries varchar;
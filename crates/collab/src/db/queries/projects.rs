@@ -348,10 +348,11 @@ impl Database {
                                         .unwrap(),
                                 )),
 
-                                // Old clients do not use abs path, entry ids or head_commit_details.
+                                // Old clients do not use abs path, entry ids, head_commit_details or stash entries.
                                 abs_path: ActiveValue::set(String::new()),
                                 entry_ids: ActiveValue::set("[]".into()),
                                 head_commit_details: ActiveValue::set(None),
+                                stash_entries: ActiveValue::set(None),
                             }
                         }),
                     )
@@ -497,6 +498,9 @@ impl Database {
                         .as_ref()
                         .map(|details| serde_json::to_string(details).unwrap()),
                 ),
+                stash_entries: ActiveValue::Set(Some(
+                    serde_json::to_string(&update.stash_entries).unwrap(),
+                )),
                 current_merge_conflicts: ActiveValue::Set(Some(
                     serde_json::to_string(&update.current_merge_conflicts).unwrap(),
                 )),
@@ -513,6 +517,7 @@ impl Database {
                     project_repository::Column::AbsPath,
                     project_repository::Column::CurrentMergeConflicts,
                     project_repository::Column::HeadCommitDetails,
+                    project_repository::Column::StashEntries,
                 ])
                 .to_owned(),
             )
@@ -943,6 +948,13 @@ impl Database {
                     .transpose()?
                     .unwrap_or_default();
 
+                let stash_entries = db_repository_entry
+                    .stash_entries
+                    .as_ref()
+                    .map(|stash_entries| serde_json::from_str(&stash_entries))
+                    .transpose()?
+                    .unwrap_or_default();
+
                 let entry_ids = serde_json::from_str(&db_repository_entry.entry_ids)
                     .context("failed to deserialize repository's entry ids")?;
 
@@ -970,6 +982,9 @@ impl Database {
                         current_merge_conflicts,
                         branch_summary,
                         head_commit_details,
+                        stash_entries,
+                        in_progress_operation: None,
+                        rebase_progress: None,
                         scan_id: db_repository_entry.scan_id as u64,
                         is_last_update: true,
                     });
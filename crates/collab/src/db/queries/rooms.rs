@@ -762,6 +762,13 @@ impl Database {
                         .transpose()?
                         .unwrap_or_default();
 
+                    let stash_entries = db_repository
+                        .stash_entries
+                        .as_ref()
+                        .map(|stash_entries| serde_json::from_str(&stash_entries))
+                        .transpose()?
+                        .unwrap_or_default();
+
                     let entry_ids = serde_json::from_str(&db_repository.entry_ids)
                         .context("failed to deserialize repository's entry ids")?;
 
@@ -786,6 +793,9 @@ impl Database {
                             current_merge_conflicts,
                             branch_summary,
                             head_commit_details,
+                            stash_entries,
+                            in_progress_operation: None,
+                            rebase_progress: None,
                             project_id: project_id.to_proto(),
                             id: db_repository.id as u64,
                             abs_path: db_repository.abs_path,
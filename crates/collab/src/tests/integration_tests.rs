@@ -3554,6 +3554,68 @@ async fn test_local_settings(
     });
 }
 
+#[gpui::test]
+async fn test_debug_watch_expressions(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b), (&client_c, cx_c)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(path!("/dir"), json!({ "main.rs": "fn main() {}" }))
+        .await;
+    let (project_a, _) = client_a.build_local_project(path!("/dir"), cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    executor.run_until_parked();
+
+    let dap_store_a = project_a.read_with(cx_a, |project, _| project.dap_store());
+    let dap_store_b = project_b.read_with(cx_b, |project, _| project.dap_store());
+
+    // As client A, add a watch expression. As client B, see it.
+    dap_store_a.update(cx_a, |dap_store, cx| {
+        dap_store.add_watch_expression("a".into(), cx);
+    });
+    executor.run_until_parked();
+    dap_store_b.read_with(cx_b, |dap_store, _| {
+        assert_eq!(dap_store.watch_expressions(), ["a"]);
+    });
+
+    // As client B, edit the watch expressions. As client A, see the edits.
+    dap_store_b.update(cx_b, |dap_store, cx| {
+        dap_store.add_watch_expression("b".into(), cx);
+        dap_store.add_watch_expression("c".into(), cx);
+        dap_store.remove_watch_expression(0, cx);
+    });
+    executor.run_until_parked();
+    dap_store_a.read_with(cx_a, |dap_store, _| {
+        assert_eq!(dap_store.watch_expressions(), ["b", "c"]);
+    });
+    dap_store_b.read_with(cx_b, |dap_store, _| {
+        assert_eq!(dap_store.watch_expressions(), ["b", "c"]);
+    });
+
+    // As client C, join the project and see the existing watch expressions.
+    let project_c = client_c.join_remote_project(project_id, cx_c).await;
+    executor.run_until_parked();
+    project_c.read_with(cx_c, |project, cx| {
+        assert_eq!(project.dap_store().read(cx).watch_expressions(), ["b", "c"]);
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_buffer_conflict_after_save(
     executor: BackgroundExecutor,
@@ -2,6 +2,7 @@ use anyhow::{Context, Result, anyhow};
 use collections::HashMap;
 use futures::{FutureExt, StreamExt, channel::oneshot, select};
 use gpui::{AppContext as _, AsyncApp, BackgroundExecutor, Task};
+use http_client::HttpClient;
 use parking_lot::Mutex;
 use postage::barrier;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
@@ -18,7 +19,8 @@ use std::{
 };
 use util::TryFutureExt;
 
-use crate::transport::{StdioTransport, Transport};
+use crate::ServerEndpoint;
+use crate::transport::{HttpTransport, StdioTransport, Transport};
 
 const JSON_RPC_VERSION: &str = "2.0";
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
@@ -135,12 +137,11 @@ pub struct ModelContextServerBinary {
 }
 
 impl Client {
-    /// Creates a new Client instance for a context server.
+    /// Creates a new Client instance for a context server that runs locally.
     ///
-    /// This function initializes a new Client by spawning a child process for the context server,
-    /// setting up communication channels, and initializing handlers for input/output operations.
-    /// It takes a server ID, binary information, and an async app context as input.
-    pub fn new(
+    /// This function spawns a child process for the context server, and communicates
+    /// with it over its stdin and stdout.
+    pub fn stdio(
         server_id: ContextServerId,
         binary: ModelContextServerBinary,
         cx: AsyncApp,
@@ -158,7 +159,36 @@ impl Client {
             .unwrap_or_else(String::new);
 
         let transport = Arc::new(StdioTransport::new(binary, &cx)?);
+        Ok(Self::new(server_id, server_name.into(), transport, cx))
+    }
+
+    /// Creates a new Client instance for a remote context server, communicating with it
+    /// over HTTP.
+    pub fn http(
+        server_id: ContextServerId,
+        endpoint: &ServerEndpoint,
+        http_client: Arc<dyn HttpClient>,
+        cx: AsyncApp,
+    ) -> Result<Self> {
+        log::info!("connecting to context server (url={:?})", endpoint.url);
+
+        let transport = Arc::new(HttpTransport::new(endpoint, http_client, &cx)?);
+        Ok(Self::new(
+            server_id,
+            endpoint.url.as_str().into(),
+            transport,
+            cx,
+        ))
+    }
 
+    /// Sets up the communication channels with the context server over the transport,
+    /// and initializes handlers for input/output operations.
+    fn new(
+        server_id: ContextServerId,
+        name: Arc<str>,
+        transport: Arc<dyn Transport>,
+        cx: AsyncApp,
+    ) -> Self {
         let (outbound_tx, outbound_rx) = channel::unbounded::<String>();
         let (output_done_tx, output_done_rx) = barrier::channel();
 
@@ -197,18 +227,18 @@ impl Client {
             .log_err()
         });
 
-        Ok(Self {
+        Self {
             server_id,
             notification_handlers,
             response_handlers,
-            name: server_name.into(),
+            name,
             next_id: Default::default(),
             outbound_tx,
             executor: cx.background_executor().clone(),
             io_tasks: Mutex::new(Some((input_task, output_task))),
             output_done_rx: Mutex::new(Some(output_done_rx)),
             transport,
-        })
+        }
     }
 
     /// Handles input from the server's stdout.
@@ -8,7 +8,9 @@ mod transport;
 pub mod types;
 
 use command_palette_hooks::CommandPaletteFilter;
-pub use context_server_settings::{ContextServerSettings, ServerCommand, ServerConfig};
+pub use context_server_settings::{
+    ContextServerSettings, HttpTransportKind, ServerCommand, ServerConfig, ServerEndpoint,
+};
 use gpui::{App, actions};
 
 pub use crate::context_server_tool::ContextServerTool;
@@ -64,18 +64,23 @@ impl ContextServer {
 
     pub async fn start(self: Arc<Self>, cx: &AsyncApp) -> Result<()> {
         log::info!("starting context server {}", self.id);
-        let Some(command) = &self.config.command else {
-            bail!("no command specified for server {}", self.id);
+        let server_id = client::ContextServerId(self.id.clone());
+        let client = match (&self.config.endpoint, &self.config.command) {
+            (Some(endpoint), _) => {
+                let http_client = cx.update(|cx| cx.http_client())?;
+                Client::http(server_id, endpoint, http_client, cx.clone())?
+            }
+            (None, Some(command)) => Client::stdio(
+                server_id,
+                client::ModelContextServerBinary {
+                    executable: Path::new(&command.path).to_path_buf(),
+                    args: command.args.clone(),
+                    env: command.env.clone(),
+                },
+                cx.clone(),
+            )?,
+            (None, None) => bail!("no command or endpoint specified for server {}", self.id),
         };
-        let client = Client::new(
-            client::ContextServerId(self.id.clone()),
-            client::ModelContextServerBinary {
-                executable: Path::new(&command.path).to_path_buf(),
-                args: command.args.clone(),
-                env: command.env.clone(),
-            },
-            cx.clone(),
-        )?;
 
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
@@ -267,7 +272,7 @@ impl ContextServerManager {
             registry.read_with(cx, |registry, _| registry.context_server_factories())?
         {
             let config = desired_servers.entry(id).or_default();
-            if config.command.is_none() {
+            if config.command.is_none() && config.endpoint.is_none() {
                 if let Some(extension_command) = factory(project.clone(), &cx).await.log_err() {
                     config.command = Some(extension_command);
                 }
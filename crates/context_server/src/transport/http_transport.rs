@@ -0,0 +1,763 @@
+use std::{mem, pin::Pin, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result, anyhow};
+use async_trait::async_trait;
+use collections::HashMap;
+use context_server_settings::{HttpTransportKind, ServerEndpoint};
+use futures::{AsyncBufReadExt as _, AsyncReadExt as _, Stream, io::BufReader};
+use gpui::{AsyncApp, BackgroundExecutor, Task};
+use http_client::{
+    AsyncBody, HttpClient, Method, Request, Response, StatusCode, http::header, http::request,
+};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use smol::channel;
+use url::Url;
+
+use crate::transport::Transport;
+use crate::types;
+
+const SESSION_ID_HEADER: &str = "Mcp-Session-Id";
+const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+/// How long to wait before reconnecting to the event stream of the server after it closes,
+/// doubled after each failed attempt up to [`MAX_RECONNECT_DELAY`].
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Communicates with a context server behind HTTP, using either the streamable HTTP transport
+/// or the legacy one based on server-sent events.
+pub struct HttpTransport {
+    connection: Arc<Connection>,
+    outbound_tx: channel::Sender<String>,
+    inbound_rx: channel::Receiver<String>,
+    error_rx: channel::Receiver<String>,
+    executor: BackgroundExecutor,
+    _tasks: Vec<Task<()>>,
+}
+
+impl HttpTransport {
+    pub fn new(
+        endpoint: &ServerEndpoint,
+        http_client: Arc<dyn HttpClient>,
+        cx: &AsyncApp,
+    ) -> Result<Self> {
+        let url = Url::parse(&endpoint.url)
+            .with_context(|| format!("invalid context server URL {:?}", endpoint.url))?;
+
+        let (outbound_tx, outbound_rx) = channel::unbounded::<String>();
+        let (inbound_tx, inbound_rx) = channel::unbounded::<String>();
+        let (error_tx, error_rx) = channel::unbounded::<String>();
+
+        let connection = Arc::new(Connection {
+            http_client,
+            url,
+            headers: endpoint.headers.clone(),
+            session_id: Mutex::new(None),
+            initialization: Mutex::new(Vec::new()),
+            inbound_tx,
+            error_tx,
+        });
+        let executor = cx.background_executor().clone();
+        let tasks = match endpoint.transport {
+            HttpTransportKind::StreamableHttp => vec![
+                executor.spawn(
+                    connection
+                        .clone()
+                        .send_streamable_http(outbound_rx, executor.clone()),
+                ),
+            ],
+            HttpTransportKind::Sse => {
+                let (endpoint_tx, endpoint_rx) = channel::unbounded();
+                vec![
+                    executor.spawn(
+                        connection
+                            .clone()
+                            .receive_sse(endpoint_tx, executor.clone()),
+                    ),
+                    executor.spawn(connection.clone().send_sse(outbound_rx, endpoint_rx)),
+                ]
+            }
+        };
+
+        Ok(Self {
+            connection,
+            outbound_tx,
+            inbound_rx,
+            error_rx,
+            executor,
+            _tasks: tasks,
+        })
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn send(&self, message: String) -> Result<()> {
+        Ok(self.outbound_tx.send(message).await?)
+    }
+
+    fn receive(&self) -> Pin<Box<dyn Stream<Item = String> + Send>> {
+        Box::pin(self.inbound_rx.clone())
+    }
+
+    fn receive_err(&self) -> Pin<Box<dyn Stream<Item = String> + Send>> {
+        Box::pin(self.error_rx.clone())
+    }
+}
+
+impl Drop for HttpTransport {
+    fn drop(&mut self) {
+        if let Some(session_id) = self.connection.session_id.lock().take() {
+            let connection = self.connection.clone();
+            self.executor
+                .spawn(async move { connection.end_session(session_id).await })
+                .detach();
+        }
+    }
+}
+
+/// The endpoint that the legacy transport posts messages to, announced by the server on its
+/// event stream.
+struct MessageEndpoint {
+    url: Url,
+    /// Whether the endpoint belongs to a new connection, whose session has to be initialized again.
+    reconnected: bool,
+}
+
+/// The parts of an outgoing JSON-RPC message that the transport cares about.
+#[derive(Deserialize)]
+struct OutgoingMessage {
+    id: Option<Value>,
+    method: Option<String>,
+}
+
+struct Connection {
+    http_client: Arc<dyn HttpClient>,
+    url: Url,
+    headers: HashMap<String, String>,
+    session_id: Mutex<Option<String>>,
+    /// The messages that initialized the session, sent again to start a new session when the
+    /// server loses the current one.
+    initialization: Mutex<Vec<String>>,
+    inbound_tx: channel::Sender<String>,
+    error_tx: channel::Sender<String>,
+}
+
+impl Connection {
+    /// Posts each message to the server with the streamable HTTP transport, forwarding the
+    /// responses as they arrive, and listens to the server's own messages once the session is
+    /// initialized.
+    async fn send_streamable_http(
+        self: Arc<Self>,
+        outbound_rx: channel::Receiver<String>,
+        executor: BackgroundExecutor,
+    ) {
+        let mut server_messages = None;
+        while let Ok(message) = outbound_rx.recv().await {
+            let outgoing = self.remember_initialization(&message);
+            match self.post_to_session(&message).await {
+                Ok(response) => {
+                    let this = self.clone();
+                    executor
+                        .spawn(async move {
+                            if let Err(error) = this.forward_response(response).await {
+                                this.report_error(&error);
+                            }
+                        })
+                        .detach();
+                }
+                Err(error) => self.message_failed(outgoing.as_ref(), &error),
+            }
+
+            let is_initialized = outgoing.as_ref().and_then(|m| m.method.as_deref())
+                == Some(types::NotificationType::Initialized.as_str());
+            if is_initialized && server_messages.is_none() {
+                server_messages =
+                    Some(executor.spawn(self.clone().receive_streamable_http(executor.clone())));
+            }
+        }
+    }
+
+    /// Listens to the messages that the server sends on its own, reconnecting with the ID of the
+    /// last received event whenever the stream closes.
+    async fn receive_streamable_http(self: Arc<Self>, executor: BackgroundExecutor) {
+        let mut last_event_id = None::<String>;
+        let mut delay = RECONNECT_DELAY;
+        loop {
+            let result: Result<bool> = async {
+                let mut request = self
+                    .request(Method::GET, &self.url)
+                    .header(header::ACCEPT, "text/event-stream");
+                if let Some(last_event_id) = &last_event_id {
+                    request = request.header(LAST_EVENT_ID_HEADER, last_event_id);
+                }
+                let response = self
+                    .http_client
+                    .send(request.body(AsyncBody::empty())?)
+                    .await?;
+                if response.status() == StatusCode::METHOD_NOT_ALLOWED {
+                    return Ok(false);
+                }
+                let response = self.check_response(response).await?;
+                delay = RECONNECT_DELAY;
+                read_events(response.into_body(), |event| {
+                    if let Some(id) = &event.id {
+                        last_event_id = Some(id.clone());
+                    }
+                    self.forward_event(event)
+                })
+                .await?;
+                Ok(true)
+            }
+            .await;
+
+            match result {
+                // The server does not offer a stream of its own messages.
+                Ok(false) => return,
+                Ok(true) => log::info!("context server event stream closed, reconnecting"),
+                Err(error) => self.report_error(&error),
+            }
+            if self.inbound_tx.is_closed() {
+                return;
+            }
+            executor.timer(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+
+    /// Connects to the event stream of the server with the legacy transport, forwarding its
+    /// messages and announcing the endpoint to post messages to, and reconnects whenever the
+    /// stream closes.
+    async fn receive_sse(
+        self: Arc<Self>,
+        endpoint_tx: channel::Sender<MessageEndpoint>,
+        executor: BackgroundExecutor,
+    ) {
+        let mut connected_before = false;
+        let mut delay = RECONNECT_DELAY;
+        loop {
+            let result: Result<()> = async {
+                let request = self
+                    .request(Method::GET, &self.url)
+                    .header(header::ACCEPT, "text/event-stream")
+                    .body(AsyncBody::empty())?;
+                let response = self.http_client.send(request).await?;
+                let response = self.check_response(response).await?;
+                delay = RECONNECT_DELAY;
+                read_events(response.into_body(), |event| match event.event.as_deref() {
+                    Some("endpoint") => {
+                        let url = self.url.join(event.data.trim()).with_context(|| {
+                            format!("invalid context server endpoint {:?}", event.data)
+                        })?;
+                        endpoint_tx
+                            .try_send(MessageEndpoint {
+                                url,
+                                reconnected: mem::replace(&mut connected_before, true),
+                            })
+                            .map_err(|_| anyhow!("context server transport closed"))
+                    }
+                    _ => self.forward_event(event),
+                })
+                .await
+            }
+            .await;
+
+            match result {
+                Ok(()) => log::info!("context server event stream closed, reconnecting"),
+                Err(error) => self.report_error(&error),
+            }
+            if self.inbound_tx.is_closed() {
+                return;
+            }
+            executor.timer(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+
+    /// Posts each message to the endpoint of the latest connection with the legacy transport,
+    /// whose responses arrive on the event stream.
+    async fn send_sse(
+        self: Arc<Self>,
+        outbound_rx: channel::Receiver<String>,
+        endpoint_rx: channel::Receiver<MessageEndpoint>,
+    ) {
+        let mut endpoint = None::<Url>;
+        while let Ok(message) = outbound_rx.recv().await {
+            let outgoing = self.remember_initialization(&message);
+            let result: Result<()> = async {
+                while endpoint.is_none() || !endpoint_rx.is_empty() {
+                    let new_endpoint = endpoint_rx.recv().await?;
+                    if new_endpoint.reconnected {
+                        self.start_new_session(&new_endpoint.url, &message).await?;
+                    }
+                    endpoint = Some(new_endpoint.url);
+                }
+                let endpoint = endpoint.as_ref().context("no context server endpoint")?;
+                let response = self.post(endpoint, message.clone()).await?;
+                let mut response = self.check_response(response).await?;
+                response.body_mut().read_to_end(&mut Vec::new()).await?;
+                Ok(())
+            }
+            .await;
+            if let Err(error) = result {
+                self.message_failed(outgoing.as_ref(), &error);
+            }
+        }
+    }
+
+    /// Posts the message to the server, starting a new session first if the server has lost the
+    /// current one.
+    async fn post_to_session(&self, message: &str) -> Result<Response<AsyncBody>> {
+        let had_session = self.session_id.lock().is_some();
+        let response = self.post(&self.url, message.to_string()).await?;
+        if had_session && response.status() == StatusCode::NOT_FOUND {
+            log::info!("context server session expired, starting a new one");
+            self.start_new_session(&self.url, message).await?;
+            let response = self.post(&self.url, message.to_string()).await?;
+            return self.check_response(response).await;
+        }
+        self.check_response(response).await
+    }
+
+    /// Sends the messages that initialized the previous session again, up to the message that is
+    /// being sent, ignoring their responses that were handled already.
+    async fn start_new_session(&self, url: &Url, message_to_send: &str) -> Result<()> {
+        self.session_id.lock().take();
+        let initialization = self
+            .initialization
+            .lock()
+            .iter()
+            .take_while(|message| *message != message_to_send)
+            .cloned()
+            .collect::<Vec<_>>();
+        for message in initialization {
+            let response = self.post(url, message).await?;
+            let mut response = self.check_response(response).await?;
+            response.body_mut().read_to_end(&mut Vec::new()).await?;
+        }
+        Ok(())
+    }
+
+    async fn end_session(&self, session_id: String) {
+        let request = self
+            .request(Method::DELETE, &self.url)
+            .header(SESSION_ID_HEADER, session_id)
+            .body(AsyncBody::empty());
+        match request {
+            Ok(request) => {
+                if let Err(error) = self.http_client.send(request).await {
+                    log::warn!("failed to end context server session: {error:#}");
+                }
+            }
+            Err(error) => log::warn!("failed to end context server session: {error:#}"),
+        }
+    }
+
+    /// Records the messages that initialize a session, starting over with each `initialize`
+    /// request, and returns the parts of the message the transport cares about.
+    fn remember_initialization(&self, message: &str) -> Option<OutgoingMessage> {
+        let outgoing = serde_json::from_str::<OutgoingMessage>(message).ok()?;
+        let mut initialization = self.initialization.lock();
+        match outgoing.method.as_deref() {
+            Some(method) if method == types::RequestType::Initialize.as_str() => {
+                self.session_id.lock().take();
+                initialization.clear();
+                initialization.push(message.to_string());
+            }
+            Some(method) if method == types::NotificationType::Initialized.as_str() => {
+                initialization.push(message.to_string());
+            }
+            _ => {}
+        }
+        Some(outgoing)
+    }
+
+    fn request(&self, method: Method, url: &Url) -> request::Builder {
+        let mut request = Request::builder().method(method).uri(url.as_str());
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some(session_id) = self.session_id.lock().as_ref() {
+            request = request.header(SESSION_ID_HEADER, session_id);
+        }
+        request
+    }
+
+    async fn post(&self, url: &Url, message: String) -> Result<Response<AsyncBody>> {
+        let request = self
+            .request(Method::POST, url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ACCEPT, "application/json, text/event-stream")
+            .body(AsyncBody::from(message))?;
+        self.http_client.send(request).await
+    }
+
+    /// Fails on unsuccessful responses, and remembers the session that the server assigned.
+    async fn check_response(
+        &self,
+        mut response: Response<AsyncBody>,
+    ) -> Result<Response<AsyncBody>> {
+        if !response.status().is_success() {
+            let mut body = String::new();
+            response.body_mut().read_to_string(&mut body).await.ok();
+            anyhow::bail!(
+                "context server responded with {}: {}",
+                response.status(),
+                body.trim()
+            );
+        }
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_ID_HEADER)
+            .and_then(|session_id| session_id.to_str().ok())
+        {
+            *self.session_id.lock() = Some(session_id.to_string());
+        }
+        Ok(response)
+    }
+
+    /// Forwards the messages that the server responded with to a posted message, either as a
+    /// single JSON message, a batch of them, or an event stream.
+    async fn forward_response(&self, response: Response<AsyncBody>) -> Result<()> {
+        let is_event_stream = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("text/event-stream"));
+        if is_event_stream {
+            return read_events(response.into_body(), |event| self.forward_event(event)).await;
+        }
+
+        let mut body = String::new();
+        response.into_body().read_to_string(&mut body).await?;
+        let body = body.trim();
+        if body.is_empty() {
+            return Ok(());
+        }
+        match serde_json::from_str::<Value>(body).context("parsing context server response")? {
+            Value::Array(messages) => {
+                for message in messages {
+                    self.forward(message.to_string())?;
+                }
+                Ok(())
+            }
+            _ => self.forward(body.to_string()),
+        }
+    }
+
+    fn forward_event(&self, event: ServerSentEvent) -> Result<()> {
+        match event.event.as_deref() {
+            None | Some("message") if !event.data.is_empty() => self.forward(event.data),
+            _ => Ok(()),
+        }
+    }
+
+    fn forward(&self, message: String) -> Result<()> {
+        self.inbound_tx
+            .try_send(message)
+            .map_err(|_| anyhow!("context server transport closed"))
+    }
+
+    /// Reports the error, and answers the message with it if it is a request, so that the client
+    /// does not wait for its response until it times out.
+    fn message_failed(&self, outgoing: Option<&OutgoingMessage>, error: &anyhow::Error) {
+        if let Some(OutgoingMessage {
+            id: Some(id),
+            method: Some(_),
+        }) = outgoing
+        {
+            let response = json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "message": format!("{error:#}") },
+            });
+            self.forward(response.to_string()).ok();
+        }
+        self.report_error(error);
+    }
+
+    fn report_error(&self, error: &anyhow::Error) {
+        self.error_tx.try_send(format!("{error:#}")).ok();
+    }
+}
+
+/// An event of a `text/event-stream` response.
+#[derive(Debug, Default, PartialEq)]
+struct ServerSentEvent {
+    event: Option<String>,
+    data: String,
+    id: Option<String>,
+}
+
+#[derive(Default)]
+struct EventParser {
+    event: ServerSentEvent,
+    has_data: bool,
+}
+
+impl EventParser {
+    /// Parses a line of the event stream without its line ending, returning the event that an
+    /// empty line completes.
+    fn push_line(&mut self, line: &str) -> Option<ServerSentEvent> {
+        if line.is_empty() {
+            let event = mem::take(&mut self.event);
+            return mem::take(&mut self.has_data).then_some(event);
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event.event = Some(value.to_string()),
+            "data" => {
+                if mem::replace(&mut self.has_data, true) {
+                    self.event.data.push('\n');
+                }
+                self.event.data.push_str(value);
+            }
+            "id" => self.event.id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Reads the events of an event stream until it ends or handling an event fails.
+async fn read_events(
+    body: AsyncBody,
+    mut handle_event: impl FnMut(ServerSentEvent) -> Result<()>,
+) -> Result<()> {
+    let mut reader = BufReader::new(body);
+    let mut parser = EventParser::default();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(());
+        }
+        if let Some(event) = parser.push_line(line.trim_end_matches(['\r', '\n'])) {
+            handle_event(event)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use http_client::FakeHttpClient;
+
+    #[test]
+    fn test_parsing_events() {
+        let mut parser = EventParser::default();
+        let mut events = Vec::new();
+        for line in [
+            ": a comment",
+            "event: endpoint",
+            "data: /messages?session_id=1",
+            "",
+            "id: 7",
+            "data: {\"first\":",
+            "data:\"line\"}",
+            "",
+            "",
+        ] {
+            events.extend(parser.push_line(line));
+        }
+
+        assert_eq!(
+            events,
+            vec![
+                ServerSentEvent {
+                    event: Some("endpoint".to_string()),
+                    data: "/messages?session_id=1".to_string(),
+                    id: None,
+                },
+                ServerSentEvent {
+                    event: None,
+                    data: "{\"first\":\n\"line\"}".to_string(),
+                    id: Some("7".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_streamable_http_sessions(cx: &mut TestAppContext) {
+        let requests = Arc::new(Mutex::new(Vec::<(Method, Option<String>, String)>::new()));
+        let http_client = FakeHttpClient::create({
+            let requests = requests.clone();
+            move |request| {
+                let requests = requests.clone();
+                async move {
+                    let method = request.method().clone();
+                    let session_id = request
+                        .headers()
+                        .get(SESSION_ID_HEADER)
+                        .map(|session_id| session_id.to_str().unwrap().to_string());
+                    assert_eq!(
+                        request.headers().get("Authorization").unwrap(),
+                        "Bearer token"
+                    );
+                    let mut body = String::new();
+                    request.into_body().read_to_string(&mut body).await?;
+                    let sessions_started = requests
+                        .lock()
+                        .iter()
+                        .filter(|(_, _, body)| body.contains("\"initialize\""))
+                        .count();
+                    requests
+                        .lock()
+                        .push((method.clone(), session_id.clone(), body.clone()));
+
+                    let response = Response::builder();
+                    Ok(if method == Method::GET {
+                        response.status(405).body(AsyncBody::empty())?
+                    } else if body.contains("\"initialize\"") {
+                        response
+                            .header(
+                                SESSION_ID_HEADER,
+                                format!("session-{}", sessions_started + 1),
+                            )
+                            .header(header::CONTENT_TYPE, "application/json")
+                            .body(r#"{"jsonrpc":"2.0","id":0,"result":{}}"#.into())?
+                    } else if !body.contains("\"id\"") {
+                        response.status(202).body(AsyncBody::empty())?
+                    } else if session_id.as_deref() == Some("session-1") {
+                        // The server has forgotten the first session.
+                        response.status(404).body(AsyncBody::empty())?
+                    } else {
+                        response
+                            .header(header::CONTENT_TYPE, "text/event-stream")
+                            .body(
+                                "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n\n"
+                                    .into(),
+                            )?
+                    })
+                }
+            }
+        });
+
+        let endpoint = ServerEndpoint {
+            url: "http://localhost:8080/mcp".to_string(),
+            headers: HashMap::from_iter([(
+                "Authorization".to_string(),
+                "Bearer token".to_string(),
+            )]),
+            transport: HttpTransportKind::StreamableHttp,
+        };
+        let transport = HttpTransport::new(&endpoint, http_client, &cx.to_async()).unwrap();
+        for message in [
+            r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{}}"#,
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized","params":{}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#,
+        ] {
+            transport.send(message.to_string()).await.unwrap();
+        }
+        cx.run_until_parked();
+
+        let (posts, gets) = mem::take(&mut *requests.lock())
+            .into_iter()
+            .partition::<Vec<_>, _>(|(method, _, _)| *method == Method::POST);
+        assert_eq!(
+            gets.into_iter()
+                .map(|(_, session_id, _)| session_id)
+                .collect::<Vec<_>>(),
+            vec![Some("session-1".to_string())]
+        );
+        assert_eq!(
+            posts
+                .into_iter()
+                .map(|(_, session_id, body)| {
+                    let method = serde_json::from_str::<OutgoingMessage>(&body)
+                        .unwrap()
+                        .method
+                        .unwrap();
+                    (session_id, method)
+                })
+                .collect::<Vec<_>>(),
+            [
+                (None, "initialize"),
+                (Some("session-1"), "notifications/initialized"),
+                (Some("session-1"), "tools/list"),
+                (None, "initialize"),
+                (Some("session-2"), "notifications/initialized"),
+                (Some("session-2"), "tools/list"),
+            ]
+            .map(|(session_id, method)| (session_id.map(ToOwned::to_owned), method.to_string()))
+        );
+
+        let mut messages = received_messages(&transport);
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                r#"{"jsonrpc":"2.0","id":0,"result":{}}"#.to_string(),
+                r#"{"jsonrpc":"2.0","id":1,"result":{}}"#.to_string(),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_sse_endpoint(cx: &mut TestAppContext) {
+        let posted = Arc::new(Mutex::new(Vec::<(String, String)>::new()));
+        let http_client = FakeHttpClient::create({
+            let posted = posted.clone();
+            move |request| {
+                let posted = posted.clone();
+                async move {
+                    if request.method() == Method::GET {
+                        assert_eq!(request.uri().to_string(), "http://localhost:8080/sse");
+                        return Ok(Response::builder()
+                            .header(header::CONTENT_TYPE, "text/event-stream")
+                            .body(
+                                "event: endpoint\ndata: /messages?session_id=1\n\n\
+                                 event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":0,\"result\":{}}\n\n"
+                                    .into(),
+                            )?);
+                    }
+                    let uri = request.uri().to_string();
+                    let mut body = String::new();
+                    request.into_body().read_to_string(&mut body).await?;
+                    posted.lock().push((uri, body));
+                    Ok(Response::builder().status(202).body(AsyncBody::empty())?)
+                }
+            }
+        });
+
+        let endpoint = ServerEndpoint {
+            url: "http://localhost:8080/sse".to_string(),
+            headers: HashMap::default(),
+            transport: HttpTransportKind::Sse,
+        };
+        let transport = HttpTransport::new(&endpoint, http_client, &cx.to_async()).unwrap();
+        let request = r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{}}"#;
+        transport.send(request.to_string()).await.unwrap();
+        cx.run_until_parked();
+
+        assert_eq!(
+            *posted.lock(),
+            vec![(
+                "http://localhost:8080/messages?session_id=1".to_string(),
+                request.to_string()
+            )]
+        );
+        assert_eq!(
+            received_messages(&transport),
+            vec![r#"{"jsonrpc":"2.0","id":0,"result":{}}"#.to_string()]
+        );
+    }
+
+    fn received_messages(transport: &HttpTransport) -> Vec<String> {
+        let mut messages = Vec::new();
+        while let Ok(message) = transport.inbound_rx.try_recv() {
+            messages.push(message);
+        }
+        messages
+    }
+}
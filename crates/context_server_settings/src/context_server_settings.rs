@@ -18,6 +18,8 @@ pub struct ServerConfig {
     ///
     /// This will override the command set by an extension.
     pub command: Option<ServerCommand>,
+    /// The HTTP endpoint of a remote context server to connect to, instead of running a command.
+    pub endpoint: Option<ServerEndpoint>,
     /// The settings for this context server.
     ///
     /// Consult the documentation for the context server to see what settings
@@ -40,6 +42,31 @@ pub struct ServerCommand {
     pub env: Option<HashMap<String, String>>,
 }
 
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ServerEndpoint {
+    /// The URL of the server's MCP endpoint, or of its event stream with the `sse` transport.
+    pub url: String,
+    /// The headers to send with every request, such as `Authorization`.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// The HTTP transport that the server implements.
+    ///
+    /// Default: streamable_http
+    #[serde(default)]
+    pub transport: HttpTransportKind,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpTransportKind {
+    /// Post each message to the URL, receiving the responses in the response bodies.
+    #[default]
+    StreamableHttp,
+    /// Receive the messages from the event stream at the URL, and post them to the endpoint
+    /// that the stream announces, as in the legacy HTTP with SSE transport.
+    Sse,
+}
+
 #[derive(Deserialize, Serialize, Default, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct ContextServerSettings {
     /// Settings for context servers used in the Assistant.
@@ -78,21 +105,47 @@ impl Settings for ContextServerSettings {
                 }
             }
         }
+        #[derive(Deserialize)]
+        struct VsCodeServerEndpoint {
+            url: String,
+            headers: Option<HashMap<String, String>>,
+            #[serde(rename = "type")]
+            kind: Option<String>,
+        }
+        impl From<VsCodeServerEndpoint> for ServerEndpoint {
+            fn from(endpoint: VsCodeServerEndpoint) -> Self {
+                Self {
+                    url: endpoint.url,
+                    headers: endpoint.headers.unwrap_or_default(),
+                    transport: match endpoint.kind.as_deref() {
+                        Some("sse") => HttpTransportKind::Sse,
+                        _ => HttpTransportKind::StreamableHttp,
+                    },
+                }
+            }
+        }
         if let Some(mcp) = vscode.read_value("mcp").and_then(|v| v.as_object()) {
             current
                 .context_servers
                 .extend(mcp.iter().filter_map(|(k, v)| {
-                    Some((
-                        k.clone().into(),
+                    let config = if let Ok(command) =
+                        serde_json::from_value::<VsCodeServerCommand>(v.clone())
+                    {
+                        ServerConfig {
+                            command: Some(command.into()),
+                            ..ServerConfig::default()
+                        }
+                    } else {
                         ServerConfig {
-                            command: Some(
-                                serde_json::from_value::<VsCodeServerCommand>(v.clone())
+                            endpoint: Some(
+                                serde_json::from_value::<VsCodeServerEndpoint>(v.clone())
                                     .ok()?
                                     .into(),
                             ),
-                            settings: None,
-                        },
-                    ))
+                            ..ServerConfig::default()
+                        }
+                    };
+                    Some((k.clone().into(), config))
                 }));
         }
     }
@@ -159,6 +159,7 @@ impl DebugTaskDefinition {
             stop_on_entry: self.stop_on_entry,
             tcp_connection: self.tcp_connection.clone(),
             initialize_args: self.initialize_args.clone(),
+            inputs: Vec::new(),
         }
     }
 
@@ -1,8 +1,9 @@
 use crate::persistence::DebuggerPaneItem;
 use crate::{
     ClearAllBreakpoints, Continue, CreateDebuggingSession, Disconnect, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    Pause, Restart, StepBack, StepInto, StepOut, StepOver, Stop, ToggleIgnoreBreakpoints,
+    FocusConsole, FocusDisassembly, FocusFrames, FocusLoadedSources, FocusMemory, FocusModules,
+    FocusTerminal, FocusVariables, FocusWatches, Pause, Restart, StepBack, StepInto,
+    StepIntoInstruction, StepOut, StepOver, StepOverInstruction, Stop, ToggleIgnoreBreakpoints,
     persistence,
 };
 use crate::{new_session_modal::NewSessionModal, session::DebugSession};
@@ -25,8 +26,8 @@ use project::debugger::session::{Session, SessionStateEvent};
 use project::{Project, debugger::session::ThreadStatus};
 use rpc::proto::{self};
 use settings::Settings;
-use std::any::TypeId;
-use task::{DebugScenario, TaskContext};
+use std::{any::TypeId, borrow::Cow};
+use task::{DebugScenario, TaskContext, VariableName};
 use ui::{ContextMenu, Divider, DropdownMenu, Tooltip, prelude::*};
 use workspace::SplitDirection;
 use workspace::{
@@ -117,6 +118,8 @@ impl DebugPanel {
             TypeId::of::<StepOver>(),
             TypeId::of::<StepInto>(),
             TypeId::of::<StepOut>(),
+            TypeId::of::<StepOverInstruction>(),
+            TypeId::of::<StepIntoInstruction>(),
             TypeId::of::<editor::actions::DebuggerRunToCursor>(),
             TypeId::of::<editor::actions::DebuggerEvaluateSelectedText>(),
         ];
@@ -414,6 +417,31 @@ impl DebugPanel {
         let task_store = project.task_store().downgrade();
         let workspace = self.workspace.clone();
         cx.spawn_in(window, async move |_, cx| {
+            let mut scenario = scenario;
+            let mut task_context = task_context;
+            let inputs = scenario
+                .unresolved_inputs(&task_context)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            if !inputs.is_empty() {
+                let label = scenario.label.to_string();
+                let Some(input_values) = workspace
+                    .update_in(cx, |workspace, window, cx| {
+                        workspace.request_task_input_values(None, label, inputs, window, cx)
+                    })?
+                    .await?
+                else {
+                    anyhow::bail!("Debug scenario {:?} was cancelled", scenario.label);
+                };
+                for (id, value) in input_values {
+                    task_context
+                        .task_variables
+                        .insert(VariableName::Input(Cow::Owned(id)), value);
+                }
+                scenario.resolve_inputs(&task_context);
+            }
+
             let DebugScenario {
                 adapter,
                 label,
@@ -422,6 +450,7 @@ impl DebugPanel {
                 initialize_args,
                 tcp_connection,
                 stop_on_entry,
+                inputs: _,
             } = scenario;
             let request = if let Some(mut request) = request {
                 // Resolve task variables within the request.
@@ -998,6 +1027,16 @@ impl DebugPanel {
         }
     }
 
+    fn show_item(&mut self, item: DebuggerPaneItem, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(session) = self.active_session() {
+            session.update(cx, |session, cx| {
+                session.running_state().update(cx, |running, cx| {
+                    running.show_pane_item(item, window, cx);
+                });
+            });
+        }
+    }
+
     fn activate_session(
         &mut self,
         session_item: Entity<DebugSession>,
@@ -1205,6 +1244,33 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusMemory, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.show_item(DebuggerPaneItem::Memory, window, cx);
+                    })
+                    .ok();
+                }
+            })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.show_item(DebuggerPaneItem::Disassembly, window, cx);
+                    })
+                    .ok();
+                }
+            })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusWatches, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.show_item(DebuggerPaneItem::Watches, window, cx);
+                    })
+                    .ok();
+                }
+            })
             .when(self.active_session.is_some(), |this| {
                 this.on_mouse_down(
                     MouseButton::Right,
@@ -100,6 +100,7 @@ impl NewSessionModal {
                 _ => None,
             },
             build: None,
+            inputs: Vec::new(),
         }
     }
 
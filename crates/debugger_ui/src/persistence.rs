@@ -10,8 +10,9 @@ use workspace::{Member, Pane, PaneAxis, Workspace};
 
 use crate::session::running::{
     self, DebugTerminal, RunningState, SubView, breakpoint_list::BreakpointList, console::Console,
-    loaded_source_list::LoadedSourceList, module_list::ModuleList,
-    stack_frame_list::StackFrameList, variable_list::VariableList,
+    disassembly_view::DisassemblyView, loaded_source_list::LoadedSourceList,
+    memory_view::MemoryView, module_list::ModuleList, stack_frame_list::StackFrameList,
+    variable_list::VariableList, watch_list::WatchList,
 };
 
 #[derive(Clone, Hash, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -23,6 +24,9 @@ pub(crate) enum DebuggerPaneItem {
     Modules,
     LoadedSources,
     Terminal,
+    Memory,
+    Disassembly,
+    Watches,
 }
 
 impl DebuggerPaneItem {
@@ -35,6 +39,9 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Modules,
             DebuggerPaneItem::LoadedSources,
             DebuggerPaneItem::Terminal,
+            DebuggerPaneItem::Memory,
+            DebuggerPaneItem::Disassembly,
+            DebuggerPaneItem::Watches,
         ];
         VARIANTS
     }
@@ -45,6 +52,12 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources => capabilities
                 .supports_loaded_sources_request
                 .unwrap_or_default(),
+            DebuggerPaneItem::Memory => capabilities
+                .supports_read_memory_request
+                .unwrap_or_default(),
+            DebuggerPaneItem::Disassembly => capabilities
+                .supports_disassemble_request
+                .unwrap_or_default(),
             _ => true,
         }
     }
@@ -58,6 +71,9 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Modules => SharedString::new_static("Modules"),
             DebuggerPaneItem::LoadedSources => SharedString::new_static("Sources"),
             DebuggerPaneItem::Terminal => SharedString::new_static("Terminal"),
+            DebuggerPaneItem::Memory => SharedString::new_static("Memory"),
+            DebuggerPaneItem::Disassembly => SharedString::new_static("Disassembly"),
+            DebuggerPaneItem::Watches => SharedString::new_static("Watch"),
         }
     }
 }
@@ -88,6 +104,7 @@ pub(crate) struct SerializedPane {
 }
 
 const DEBUGGER_PANEL_PREFIX: &str = "debugger_panel_";
+const DEBUGGER_WATCHES_PREFIX: &str = "debugger_watches_";
 
 pub(crate) async fn serialize_pane_layout(
     adapter_name: SharedString,
@@ -162,6 +179,38 @@ pub(crate) async fn get_serialized_pane_layout(
         .and_then(|value| serde_json::from_str::<SerializedPaneLayout>(&value).ok())
 }
 
+/// The key watch expressions are stored under, derived from the project's visible worktrees.
+pub(crate) fn watch_expressions_key(project: &Entity<Project>, cx: &App) -> Option<String> {
+    let mut paths = project
+        .read(cx)
+        .visible_worktrees(cx)
+        .map(|worktree| worktree.read(cx).abs_path().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    if paths.is_empty() {
+        return None;
+    }
+    paths.sort();
+    Some(format!("{DEBUGGER_WATCHES_PREFIX}{}", paths.join(",")))
+}
+
+pub(crate) async fn serialize_watch_expressions(
+    key: String,
+    expressions: Vec<String>,
+) -> anyhow::Result<()> {
+    KEY_VALUE_STORE
+        .write_kvp(key, serde_json::to_string(&expressions)?)
+        .await
+}
+
+pub(crate) async fn get_serialized_watch_expressions(key: &str) -> Vec<String> {
+    KEY_VALUE_STORE
+        .read_kvp(key)
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str::<Vec<String>>(&value).ok())
+        .unwrap_or_default()
+}
+
 pub(crate) fn deserialize_pane_layout(
     serialized: SerializedPaneLayout,
     workspace: &WeakEntity<Workspace>,
@@ -173,6 +222,9 @@ pub(crate) fn deserialize_pane_layout(
     breakpoint_list: &Entity<BreakpointList>,
     loaded_sources: &Entity<LoadedSourceList>,
     terminal: &Entity<DebugTerminal>,
+    memory_view: &Entity<MemoryView>,
+    disassembly_view: &Entity<DisassemblyView>,
+    watch_list: &Entity<WatchList>,
     subscriptions: &mut HashMap<EntityId, Subscription>,
     window: &mut Window,
     cx: &mut Context<RunningState>,
@@ -196,6 +248,9 @@ pub(crate) fn deserialize_pane_layout(
                     breakpoint_list,
                     loaded_sources,
                     terminal,
+                    memory_view,
+                    disassembly_view,
+                    watch_list,
                     subscriptions,
                     window,
                     cx,
@@ -285,6 +340,27 @@ pub(crate) fn deserialize_pane_layout(
                         None,
                         cx,
                     )),
+                    DebuggerPaneItem::Memory => Box::new(SubView::new(
+                        memory_view.focus_handle(cx),
+                        memory_view.clone().into(),
+                        DebuggerPaneItem::Memory,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                        disassembly_view.focus_handle(cx),
+                        disassembly_view.clone().into(),
+                        DebuggerPaneItem::Disassembly,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::Watches => Box::new(SubView::new(
+                        watch_list.focus_handle(cx),
+                        watch_list.clone().into(),
+                        DebuggerPaneItem::Watches,
+                        None,
+                        cx,
+                    )),
                 })
                 .collect();
 
@@ -1,9 +1,12 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
 pub(crate) mod loaded_source_list;
+pub(crate) mod memory_view;
 pub(crate) mod module_list;
 pub mod stack_frame_list;
 pub mod variable_list;
+pub(crate) mod watch_list;
 
 use std::{any::Any, ops::ControlFlow, path::PathBuf, sync::Arc, time::Duration};
 
@@ -15,15 +18,17 @@ use breakpoint_list::BreakpointList;
 use collections::{HashMap, IndexMap};
 use console::Console;
 use dap::{
-    Capabilities, RunInTerminalRequestArguments, Thread, client::SessionId,
+    Capabilities, RunInTerminalRequestArguments, SteppingGranularity, Thread, client::SessionId,
     debugger_settings::DebuggerSettings,
 };
+use disassembly_view::DisassemblyView;
 use futures::{SinkExt, channel::mpsc};
 use gpui::{
     Action as _, AnyView, AppContext, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
     NoAction, Pixels, Point, Subscription, Task, WeakEntity,
 };
 use loaded_source_list::LoadedSourceList;
+use memory_view::MemoryView;
 use module_list::ModuleList;
 use project::{
     Project,
@@ -42,7 +47,8 @@ use ui::{
     Styled, Tab, Tooltip, VisibleOnHover, VisualContext, Window, div, h_flex, v_flex,
 };
 use util::ResultExt;
-use variable_list::VariableList;
+use variable_list::{VariableList, VariableListEvent};
+use watch_list::WatchList;
 use workspace::{
     ActivePaneDecorator, DraggedTab, Item, ItemHandle, Member, Pane, PaneGroup, SplitDirection,
     Workspace, item::TabContentParams, move_item, pane::Event,
@@ -63,6 +69,9 @@ pub struct RunningState {
     module_list: Entity<module_list::ModuleList>,
     console: Entity<Console>,
     breakpoint_list: Entity<BreakpointList>,
+    memory_view: Entity<MemoryView>,
+    disassembly_view: Entity<DisassemblyView>,
+    watch_list: Entity<WatchList>,
     panes: PaneGroup,
     active_pane: Option<Entity<Pane>>,
     pane_close_subscriptions: HashMap<EntityId, Subscription>,
@@ -511,7 +520,13 @@ impl RunningState {
         let session_id = session.read(cx).session_id();
         let weak_state = cx.weak_entity();
         let stack_frame_list = cx.new(|cx| {
-            StackFrameList::new(workspace.clone(), session.clone(), weak_state, window, cx)
+            StackFrameList::new(
+                workspace.clone(),
+                session.clone(),
+                weak_state.clone(),
+                window,
+                cx,
+            )
         });
 
         let debug_terminal = cx.new(DebugTerminal::empty);
@@ -535,8 +550,33 @@ impl RunningState {
 
         let breakpoint_list = BreakpointList::new(session.clone(), workspace.clone(), &project, cx);
 
+        let memory_view = cx.new(|cx| MemoryView::new(session.clone(), window, cx));
+
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(session.clone(), stack_frame_list.clone(), weak_state, cx)
+        });
+
+        let watch_list = cx.new(|cx| {
+            WatchList::new(
+                session.clone(),
+                stack_frame_list.clone(),
+                &project,
+                window,
+                cx,
+            )
+        });
+
         let _subscriptions = vec![
             cx.observe(&module_list, |_, _, cx| cx.notify()),
+            cx.subscribe_in(
+                &variable_list,
+                window,
+                |this, _, event, window, cx| match event {
+                    VariableListEvent::ViewMemory(memory_reference) => {
+                        this.view_memory(memory_reference.clone(), window, cx)
+                    }
+                },
+            ),
             cx.subscribe_in(&session, window, |this, _, event, window, cx| {
                 match event {
                     SessionEvent::Stopped(thread_id) => {
@@ -565,6 +605,12 @@ impl RunningState {
                         {
                             this.remove_pane_item(DebuggerPaneItem::LoadedSources, window, cx);
                         }
+                        if !capabilities.supports_read_memory_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Memory, window, cx);
+                        }
+                        if !capabilities.supports_disassemble_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                        }
                     }
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
@@ -592,6 +638,9 @@ impl RunningState {
                 &breakpoint_list,
                 &loaded_source_list,
                 &debug_terminal,
+                &memory_view,
+                &disassembly_view,
+                &watch_list,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -606,6 +655,7 @@ impl RunningState {
                 &workspace,
                 &stack_frame_list,
                 &variable_list,
+                &watch_list,
                 &module_list,
                 &loaded_source_list,
                 &console,
@@ -633,6 +683,9 @@ impl RunningState {
             module_list,
             console,
             breakpoint_list,
+            memory_view,
+            disassembly_view,
+            watch_list,
             loaded_sources_list: loaded_source_list,
             pane_close_subscriptions,
             debug_terminal,
@@ -734,6 +787,8 @@ impl RunningState {
                 show_summary: false,
                 show_command: false,
                 show_rerun: false,
+                problem_matchers: Vec::new(),
+                rerun_on: Vec::new(),
             })
         } else {
             TerminalKind::Shell(cwd.map(|c| c.to_path_buf()))
@@ -836,6 +891,27 @@ impl RunningState {
                 None,
                 cx,
             )),
+            DebuggerPaneItem::Memory => Box::new(SubView::new(
+                self.memory_view.focus_handle(cx),
+                self.memory_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
+            DebuggerPaneItem::Watches => Box::new(SubView::new(
+                self.watch_list.focus_handle(cx),
+                self.watch_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
         }
     }
 
@@ -856,6 +932,20 @@ impl RunningState {
         })
     }
 
+    /// Activates the item, adding it to the last pane first if it isn't open.
+    pub(crate) fn show_pane_item(
+        &mut self,
+        item_kind: DebuggerPaneItem,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !item_kind.is_supported(self.session.read(cx).capabilities()) {
+            return;
+        }
+        self.ensure_pane_item(item_kind, window, cx);
+        self.activate_item(item_kind, window, cx);
+    }
+
     pub(crate) fn add_pane_item(
         &mut self,
         item_kind: DebuggerPaneItem,
@@ -1043,6 +1133,34 @@ impl RunningState {
         &self.variable_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn memory_view(&self) -> &Entity<MemoryView> {
+        &self.memory_view
+    }
+
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
+    #[cfg(test)]
+    pub(crate) fn watch_list(&self) -> &Entity<WatchList> {
+        &self.watch_list
+    }
+
+    /// Shows the memory view at the given memory reference, opening it if needed.
+    pub(crate) fn view_memory(
+        &mut self,
+        memory_reference: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_pane_item(DebuggerPaneItem::Memory, window, cx);
+        self.memory_view.update(cx, |memory_view, cx| {
+            memory_view.go_to(memory_reference, window, cx)
+        });
+    }
+
     pub fn capabilities(&self, cx: &App) -> Capabilities {
         self.session().read(cx).capabilities().clone()
     }
@@ -1133,6 +1251,26 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn step_over_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_over(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    pub(crate) fn step_in_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_in(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
     pub(crate) fn step_back(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
@@ -1252,6 +1390,7 @@ impl RunningState {
         workspace: &WeakEntity<Workspace>,
         stack_frame_list: &Entity<StackFrameList>,
         variable_list: &Entity<VariableList>,
+        watch_list: &Entity<WatchList>,
         module_list: &Entity<ModuleList>,
         loaded_source_list: &Entity<LoadedSourceList>,
         console: &Entity<Console>,
@@ -1309,6 +1448,20 @@ impl RunningState {
                 window,
                 cx,
             );
+            this.add_item(
+                Box::new(SubView::new(
+                    watch_list.focus_handle(cx),
+                    watch_list.clone().into(),
+                    DebuggerPaneItem::Watches,
+                    None,
+                    cx,
+                )),
+                false,
+                false,
+                None,
+                window,
+                cx,
+            );
             this.add_item(
                 Box::new(SubView::new(
                     module_list.focus_handle(cx),
@@ -0,0 +1,309 @@
+use std::ops::Range;
+
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent, parse_memory_address};
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+
+/// The number of instructions shown before the instruction pointer.
+const INSTRUCTIONS_BEFORE: i64 = 32;
+const INSTRUCTION_COUNT: u64 = 96;
+
+/// Shows the disassembly around the selected stack frame's instruction pointer.
+pub struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: Entity<StackFrameList>,
+    state: WeakEntity<RunningState>,
+    focus_handle: FocusHandle,
+    instruction_pointer: Option<String>,
+    instructions: Vec<dap::DisassembledInstruction>,
+    /// The index of the instruction the instruction pointer points at.
+    current_instruction: Option<usize>,
+    error: Option<SharedString>,
+    list_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    /// Whether the instructions have to be fetched again the next time the view is rendered,
+    /// so that no requests are sent while it is hidden.
+    invalidate: bool,
+    _load_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        state: WeakEntity<RunningState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_) => {
+                    this.invalidate = true;
+                    cx.notify();
+                }
+            }),
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) => {
+                    this.invalidate = true;
+                    cx.notify();
+                }
+                _ => {}
+            }),
+        ];
+
+        let list_handle = UniformListScrollHandle::default();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(list_handle.clone()),
+            list_handle,
+            session,
+            stack_frame_list,
+            state,
+            focus_handle: cx.focus_handle(),
+            instruction_pointer: None,
+            instructions: Vec::new(),
+            current_instruction: None,
+            error: None,
+            invalidate: true,
+            _load_task: Task::ready(()),
+            _subscriptions,
+        }
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .selected_instruction_pointer();
+        if instruction_pointer == self.instruction_pointer && !self.instructions.is_empty() {
+            return;
+        }
+        self.instruction_pointer = instruction_pointer.clone();
+
+        let Some(instruction_pointer) = instruction_pointer else {
+            self.instructions.clear();
+            self.current_instruction = None;
+            self.error = None;
+            return;
+        };
+
+        let task = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer.clone(),
+                -INSTRUCTIONS_BEFORE,
+                INSTRUCTION_COUNT,
+                cx,
+            )
+        });
+        self._load_task = cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(instructions) => {
+                        let instruction_pointer = parse_memory_address(&instruction_pointer);
+                        this.current_instruction = instructions.iter().position(|instruction| {
+                            instruction_pointer.is_some()
+                                && parse_memory_address(&instruction.address) == instruction_pointer
+                        });
+                        if let Some(ix) = this.current_instruction {
+                            this.list_handle.scroll_to_item(ix, ScrollStrategy::Center);
+                        }
+                        this.instructions = instructions;
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.instructions.clear();
+                        this.current_instruction = None;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn instructions(&self) -> &[dap::DisassembledInstruction] {
+        &self.instructions
+    }
+
+    #[cfg(test)]
+    pub(crate) fn current_instruction(&self) -> Option<usize> {
+        self.current_instruction
+    }
+
+    fn render_instructions(
+        &mut self,
+        range: Range<usize>,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| {
+                let instruction = self.instructions.get(ix)?;
+                let is_current = self.current_instruction == Some(ix);
+                Some(
+                    h_flex()
+                        .id(("disassembly-instruction", ix))
+                        .gap_4()
+                        .px_2()
+                        .font_buffer(cx)
+                        .text_ui_sm(cx)
+                        .when(is_current, |this| {
+                            this.bg(cx.theme().colors().editor_debugger_active_line_background)
+                        })
+                        .child(
+                            div()
+                                .text_color(cx.theme().colors().text_muted)
+                                .child(instruction.address.clone()),
+                        )
+                        .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                            this.child(
+                                div()
+                                    .w(rems(10.))
+                                    .overflow_hidden()
+                                    .text_color(cx.theme().colors().text_muted)
+                                    .child(bytes),
+                            )
+                        })
+                        .child(div().flex_1().child(instruction.instruction.clone()))
+                        .when_some(instruction.symbol.clone(), |this, symbol| {
+                            this.child(
+                                div()
+                                    .text_color(cx.theme().colors().text_muted)
+                                    .child(symbol),
+                            )
+                        })
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_controls(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.clone();
+        h_flex()
+            .p_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                IconButton::new("disassembly-step-over", IconName::DebugStepOver)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Step Over Instruction"))
+                    .on_click({
+                        let state = state.clone();
+                        move |_, _, cx| {
+                            state
+                                .update(cx, |state, cx| state.step_over_instruction(cx))
+                                .ok();
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("disassembly-step-into", IconName::DebugStepInto)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Step Into Instruction"))
+                    .on_click(move |_, _, cx| {
+                        state
+                            .update(cx, |state, cx| state.step_in_instruction(cx))
+                            .ok();
+                    }),
+            )
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("disassembly-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.invalidate {
+            self.invalidate = false;
+            self.refresh(cx);
+        }
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_controls(cx))
+            .map(|this| {
+                if let Some(error) = self.error.clone() {
+                    this.child(
+                        div()
+                            .p_2()
+                            .child(Label::new(error).color(Color::Error).size(LabelSize::Small)),
+                    )
+                } else if self.instruction_pointer.is_none() {
+                    this.child(
+                        div().p_2().child(
+                            Label::new("The selected stack frame has no instruction pointer")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        div()
+                            .relative()
+                            .flex_1()
+                            .child(
+                                uniform_list(
+                                    cx.entity().clone(),
+                                    "disassembly-view",
+                                    self.instructions.len(),
+                                    |this, range, _, cx| this.render_instructions(range, cx),
+                                )
+                                .track_scroll(self.list_handle.clone())
+                                .size_full(),
+                            )
+                            .child(self.render_vertical_scrollbar(cx)),
+                    )
+                }
+            })
+    }
+}
@@ -0,0 +1,431 @@
+use std::ops::Range;
+
+use editor::{Editor, EditorElement, EditorStyle};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, Stateful, Subscription, Task,
+    TextStyle, UniformListScrollHandle, uniform_list,
+};
+use menu::Confirm;
+use project::debugger::session::{MemoryChunk, Session, SessionEvent};
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+
+const BYTES_PER_ROW: usize = 16;
+/// The number of bytes read at a time.
+const PAGE_SIZE: u64 = 1024;
+
+/// A hex view of the debuggee's memory around a memory reference, such as a variable's
+/// `memoryReference` or an address.
+pub struct MemoryView {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    reference_editor: Entity<Editor>,
+    /// Takes hex bytes to write at the first byte shown.
+    write_editor: Entity<Editor>,
+    memory_reference: Option<String>,
+    /// The offset from `memory_reference` of the first byte shown.
+    offset: i64,
+    chunk: Option<MemoryChunk>,
+    error: Option<SharedString>,
+    list_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    _load_task: Task<()>,
+    _write_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl MemoryView {
+    pub fn new(session: Entity<Session>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let reference_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Memory reference or address", cx);
+            editor
+        });
+        let write_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Hex bytes to write, such as 4A 65", cx);
+            editor
+        });
+
+        let _subscription = cx.subscribe(&session, |this, _, event, cx| match event {
+            SessionEvent::Stopped(_) | SessionEvent::Variables => this.load(cx),
+            _ => {}
+        });
+
+        let list_handle = UniformListScrollHandle::default();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(list_handle.clone()),
+            list_handle,
+            session,
+            focus_handle: cx.focus_handle(),
+            reference_editor,
+            write_editor,
+            memory_reference: None,
+            offset: 0,
+            chunk: None,
+            error: None,
+            _load_task: Task::ready(()),
+            _write_task: Task::ready(()),
+            _subscription,
+        }
+    }
+
+    /// Shows the memory starting at the given memory reference.
+    pub(crate) fn go_to(
+        &mut self,
+        memory_reference: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.reference_editor.update(cx, |editor, cx| {
+            editor.set_text(memory_reference.clone(), window, cx);
+        });
+        self.memory_reference = Some(memory_reference);
+        self.offset = 0;
+        self.load(cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.write_editor.focus_handle(cx).is_focused(window) {
+            self.write(window, cx);
+            return;
+        }
+        let memory_reference = self.reference_editor.read(cx).text(cx).trim().to_string();
+        if !memory_reference.is_empty() {
+            self.go_to(memory_reference, window, cx);
+        }
+    }
+
+    /// Writes the bytes from the write editor at the first byte shown, then shows the memory
+    /// again once the session has reloaded it.
+    fn write(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(memory_reference) = self.memory_reference.clone() else {
+            return;
+        };
+        let text = self.write_editor.read(cx).text(cx);
+        let data = match parse_hex_bytes(&text) {
+            Some(data) if !data.is_empty() => data,
+            _ => {
+                self.error = Some(format!("{:?} isn't a list of hex bytes", text.trim()).into());
+                cx.notify();
+                return;
+            }
+        };
+        let task = self.session.update(cx, |session, cx| {
+            session.write_memory(memory_reference, self.offset, &data, cx)
+        });
+        self._write_task = cx.spawn_in(window, async move |this, cx| {
+            let result = task.await;
+            this.update_in(cx, |this, window, cx| {
+                match result {
+                    Ok(_) => {
+                        this.write_editor
+                            .update(cx, |editor, cx| editor.clear(window, cx));
+                    }
+                    Err(error) => {
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn move_by_page(&mut self, pages: i64, cx: &mut Context<Self>) {
+        self.offset += pages * PAGE_SIZE as i64;
+        self.load(cx);
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        let Some(memory_reference) = self.memory_reference.clone() else {
+            return;
+        };
+        let task = self.session.update(cx, |session, cx| {
+            session.read_memory(memory_reference, self.offset, PAGE_SIZE, cx)
+        });
+        self._load_task = cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(chunk) => {
+                        this.chunk = Some(chunk);
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.chunk = None;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn row_count(&self) -> usize {
+        self.chunk
+            .as_ref()
+            .map_or(0, |chunk| chunk_len(chunk).div_ceil(BYTES_PER_ROW))
+    }
+
+    /// The address of the row and its bytes, where bytes that couldn't be read are `None`.
+    fn row(&self, ix: usize) -> Option<(u64, Vec<Option<u8>>)> {
+        let chunk = self.chunk.as_ref()?;
+        let len = chunk_len(chunk);
+        let start = ix * BYTES_PER_ROW;
+        if start >= len {
+            return None;
+        }
+        let bytes = (start..len.min(start + BYTES_PER_ROW))
+            .map(|ix| chunk.data.get(ix).copied())
+            .collect();
+        // The chunk may end past the last address, which then wraps around.
+        Some((chunk.address.wrapping_add(start as u64), bytes))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn write_editor(&self) -> &Entity<Editor> {
+        &self.write_editor
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rows(&self) -> Vec<String> {
+        (0..self.row_count())
+            .filter_map(|ix| self.row(ix))
+            .map(|(address, bytes)| {
+                let (hex, ascii) = format_bytes(&bytes);
+                format!("{address:016X}  {hex}  {ascii}")
+            })
+            .collect()
+    }
+
+    fn render_rows(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| {
+                let (address, bytes) = self.row(ix)?;
+                let (hex, ascii) = format_bytes(&bytes);
+                Some(
+                    h_flex()
+                        .id(("memory-row", ix))
+                        .gap_4()
+                        .px_2()
+                        .font_buffer(cx)
+                        .text_ui_sm(cx)
+                        .child(
+                            div()
+                                .text_color(cx.theme().colors().text_muted)
+                                .child(format!("{address:016X}")),
+                        )
+                        .child(div().child(hex))
+                        .child(
+                            div()
+                                .text_color(cx.theme().colors().text_muted)
+                                .child(ascii),
+                        )
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_editor(&self, editor: &Entity<Editor>, cx: &Context<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.buffer_font.family.clone(),
+            font_features: settings.buffer_font.features.clone(),
+            font_size: settings.buffer_font_size(cx).into(),
+            font_weight: settings.buffer_font.weight,
+            line_height: relative(settings.buffer_line_height.value()),
+            ..Default::default()
+        };
+        EditorElement::new(
+            editor,
+            EditorStyle {
+                background: cx.theme().colors().editor_background,
+                local_player: cx.theme().players().local(),
+                text: text_style,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("memory-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+/// The number of bytes in the chunk, readable or not, up to the number of bytes requested.
+fn chunk_len(chunk: &MemoryChunk) -> usize {
+    let unreadable_bytes = usize::try_from(chunk.unreadable_bytes).unwrap_or(usize::MAX);
+    chunk
+        .data
+        .len()
+        .saturating_add(unreadable_bytes)
+        .min(PAGE_SIZE as usize)
+}
+
+/// Parses bytes written as hex, such as `4A 65` or `4A65`.
+fn parse_hex_bytes(text: &str) -> Option<Vec<u8>> {
+    let digits = text
+        .split_whitespace()
+        .flat_map(|word| word.trim_start_matches("0x").chars())
+        .collect::<Vec<_>>();
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| Some((pair[0].to_digit(16)? * 16 + pair[1].to_digit(16)?) as u8))
+        .collect()
+}
+
+/// Formats bytes as hex and as ASCII, using `??` and `.` for unreadable and unprintable bytes.
+fn format_bytes(bytes: &[Option<u8>]) -> (String, String) {
+    let hex = bytes
+        .iter()
+        .map(|byte| match byte {
+            Some(byte) => format!("{byte:02X}"),
+            None => "??".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let ascii = bytes
+        .iter()
+        .map(|byte| match byte {
+            Some(byte) if byte.is_ascii_graphic() || *byte == b' ' => *byte as char,
+            _ => '.',
+        })
+        .collect();
+    (hex, ascii)
+}
+
+impl Focusable for MemoryView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MemoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_reference = self.memory_reference.is_some();
+        let supports_write = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_write_memory_request
+            .unwrap_or_default();
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .key_context("MemoryView")
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(
+                h_flex()
+                    .p_1()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(self.render_editor(&self.reference_editor, cx)),
+                    )
+                    .child(
+                        IconButton::new("memory-view-previous-page", IconName::ChevronLeft)
+                            .icon_size(IconSize::Small)
+                            .disabled(!has_reference)
+                            .tooltip(Tooltip::text("Previous Page"))
+                            .on_click(cx.listener(|this, _, _, cx| this.move_by_page(-1, cx))),
+                    )
+                    .child(
+                        IconButton::new("memory-view-next-page", IconName::ChevronRight)
+                            .icon_size(IconSize::Small)
+                            .disabled(!has_reference)
+                            .tooltip(Tooltip::text("Next Page"))
+                            .on_click(cx.listener(|this, _, _, cx| this.move_by_page(1, cx))),
+                    ),
+            )
+            .when(has_reference && supports_write, |this| {
+                this.child(
+                    h_flex()
+                        .p_1()
+                        .gap_1()
+                        .border_b_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(
+                            div()
+                                .flex_1()
+                                .child(self.render_editor(&self.write_editor, cx)),
+                        )
+                        .child(
+                            IconButton::new("memory-view-write", IconName::Check)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Write Bytes"))
+                                .on_click(
+                                    cx.listener(|this, _, window, cx| this.write(window, cx)),
+                                ),
+                        ),
+                )
+            })
+            .map(|this| {
+                if let Some(error) = self.error.clone() {
+                    this.child(
+                        div()
+                            .p_2()
+                            .child(Label::new(error).color(Color::Error).size(LabelSize::Small)),
+                    )
+                } else {
+                    this.child(
+                        div()
+                            .relative()
+                            .flex_1()
+                            .child(
+                                uniform_list(
+                                    cx.entity().clone(),
+                                    "memory-view",
+                                    self.row_count(),
+                                    |this, range, _, cx| this.render_rows(range, cx),
+                                )
+                                .track_scroll(self.list_handle.clone())
+                                .size_full(),
+                            )
+                            .child(self.render_vertical_scrollbar(cx)),
+                    )
+                }
+            })
+    }
+}
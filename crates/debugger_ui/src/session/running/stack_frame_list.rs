@@ -136,6 +136,21 @@ impl StackFrameList {
         self.selected_stack_frame_id
     }
 
+    /// The instruction pointer of the selected stack frame, if the adapter reported one.
+    pub(crate) fn selected_instruction_pointer(&self) -> Option<String> {
+        let selected_stack_frame_id = self.selected_stack_frame_id?;
+        self.entries
+            .iter()
+            .find_map(|entry| match entry {
+                StackFrameEntry::Normal(dap) => (dap.id == selected_stack_frame_id).then_some(dap),
+                StackFrameEntry::Collapsed(daps) => {
+                    daps.iter().find(|dap| dap.id == selected_stack_frame_id)
+                }
+            })?
+            .instruction_pointer_reference
+            .clone()
+    }
+
     pub(super) fn schedule_refresh(
         &mut self,
         select_first: bool,
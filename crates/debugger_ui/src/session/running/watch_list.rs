@@ -0,0 +1,346 @@
+use std::ops::Range;
+
+use editor::{Editor, EditorElement, EditorStyle};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, Stateful, Subscription, Task,
+    TextStyle, UniformListScrollHandle, uniform_list,
+};
+use menu::Confirm;
+use project::{
+    Project,
+    debugger::{
+        dap_store::{DapStore, DapStoreEvent},
+        session::{Session, SessionEvent},
+    },
+};
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use util::ResultExt;
+
+use crate::persistence;
+
+use super::stack_frame_list::{StackFrameList, StackFrameListEvent};
+
+struct WatchEntry {
+    expression: SharedString,
+    /// The result of the last evaluation, if the expression has been evaluated in the
+    /// selected stack frame.
+    value: Option<Result<SharedString, SharedString>>,
+}
+
+/// Expressions that are re-evaluated in the selected stack frame every time the debuggee stops.
+pub struct WatchList {
+    session: Entity<Session>,
+    dap_store: Entity<DapStore>,
+    stack_frame_list: Entity<StackFrameList>,
+    focus_handle: FocusHandle,
+    expression_editor: Entity<Editor>,
+    entries: Vec<WatchEntry>,
+    /// The key the watch expressions are persisted under, if the project has any worktrees.
+    serialization_key: Option<String>,
+    list_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    _evaluate_task: Task<()>,
+    _serialize_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl WatchList {
+    pub fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        project: &Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let expression_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Add watch expression", cx);
+            editor
+        });
+
+        let dap_store = project.read(cx).dap_store();
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_) => this.evaluate(cx),
+            }),
+            cx.subscribe(&session, |this, _, event, cx| {
+                if let SessionEvent::Stopped(_) = event {
+                    this.evaluate(cx);
+                }
+            }),
+            cx.subscribe(&dap_store, |this, _, event, cx| {
+                if let DapStoreEvent::WatchExpressionsUpdated = event {
+                    this.evaluate(cx);
+                    this.serialize(cx);
+                }
+            }),
+        ];
+
+        let serialization_key = persistence::watch_expressions_key(project, cx);
+        // Guests of a shared project get their watch expressions from the host.
+        let should_restore =
+            project.read(cx).is_local() && dap_store.read(cx).watch_expressions().is_empty();
+        if let Some(key) = serialization_key.clone().filter(|_| should_restore) {
+            let dap_store = dap_store.clone();
+            cx.spawn(async move |_, cx| {
+                let expressions = persistence::get_serialized_watch_expressions(&key).await;
+                if !expressions.is_empty() {
+                    dap_store
+                        .update(cx, |dap_store, cx| {
+                            dap_store.set_watch_expressions(expressions, cx)
+                        })
+                        .ok();
+                }
+            })
+            .detach();
+        }
+
+        let list_handle = UniformListScrollHandle::default();
+
+        let mut this = Self {
+            scrollbar_state: ScrollbarState::new(list_handle.clone()),
+            list_handle,
+            session,
+            dap_store,
+            stack_frame_list,
+            focus_handle: cx.focus_handle(),
+            expression_editor,
+            entries: Vec::new(),
+            serialization_key,
+            _evaluate_task: Task::ready(()),
+            _serialize_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.evaluate(cx);
+        this
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let expression = self.expression_editor.read(cx).text(cx).trim().to_string();
+        if expression.is_empty() {
+            return;
+        }
+        self.expression_editor.update(cx, |editor, cx| {
+            editor.clear(window, cx);
+        });
+        self.dap_store.update(cx, |dap_store, cx| {
+            dap_store.add_watch_expression(expression, cx);
+        });
+    }
+
+    fn remove(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.dap_store.update(cx, |dap_store, cx| {
+            dap_store.remove_watch_expression(ix, cx);
+        });
+    }
+
+    fn evaluate(&mut self, cx: &mut Context<Self>) {
+        let expressions = self.dap_store.read(cx).watch_expressions().to_vec();
+        self.entries = expressions
+            .iter()
+            .map(|expression| WatchEntry {
+                expression: expression.clone().into(),
+                value: self
+                    .entries
+                    .iter()
+                    .find(|entry| entry.expression.as_ref() == expression)
+                    .and_then(|entry| entry.value.clone()),
+            })
+            .collect();
+        cx.notify();
+
+        let Some(frame_id) = self.stack_frame_list.read(cx).selected_stack_frame_id() else {
+            return;
+        };
+
+        let tasks = self.session.update(cx, |session, _| {
+            expressions
+                .into_iter()
+                .map(|expression| session.evaluate_watch(expression, Some(frame_id)))
+                .collect::<Vec<_>>()
+        });
+        self._evaluate_task = cx.spawn(async move |this, cx| {
+            let values = futures::future::join_all(tasks).await;
+            this.update(cx, |this, cx| {
+                for (entry, value) in this.entries.iter_mut().zip(values) {
+                    entry.value = Some(
+                        value
+                            .map(|response| response.result.into())
+                            .map_err(|error| error.to_string().into()),
+                    );
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.serialization_key.clone() else {
+            return;
+        };
+        let expressions = self.dap_store.read(cx).watch_expressions().to_vec();
+        self._serialize_task = cx.background_spawn(async move {
+            persistence::serialize_watch_expressions(key, expressions)
+                .await
+                .log_err();
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn entries(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| match &entry.value {
+                Some(Ok(value)) => format!("{} = {}", entry.expression, value),
+                Some(Err(error)) => format!("{}: {}", entry.expression, error),
+                None => entry.expression.to_string(),
+            })
+            .collect()
+    }
+
+    fn render_entries(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| {
+                let entry = self.entries.get(ix)?;
+                Some(
+                    h_flex()
+                        .id(("watch-entry", ix))
+                        .group("watch-entry")
+                        .w_full()
+                        .gap_1()
+                        .px_2()
+                        .font_buffer(cx)
+                        .text_ui_sm(cx)
+                        .child(entry.expression.clone())
+                        .map(|this| match entry.value.clone() {
+                            Some(Ok(value)) => this.child(
+                                div()
+                                    .flex_1()
+                                    .overflow_hidden()
+                                    .text_color(cx.theme().colors().text_muted)
+                                    .child(format!("= {value}")),
+                            ),
+                            Some(Err(error)) => this.child(
+                                div()
+                                    .flex_1()
+                                    .overflow_hidden()
+                                    .child(Label::new(error).color(Color::Error)),
+                            ),
+                            None => this.child(div().flex_1()),
+                        })
+                        .child(
+                            div()
+                                .invisible()
+                                .group_hover("watch-entry", |this| this.visible())
+                                .child(
+                                    IconButton::new(("remove-watch", ix), IconName::Close)
+                                        .icon_size(IconSize::XSmall)
+                                        .tooltip(Tooltip::text("Remove Watch Expression"))
+                                        .on_click(
+                                            cx.listener(move |this, _, _, cx| this.remove(ix, cx)),
+                                        ),
+                                ),
+                        )
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_expression_editor(&self, cx: &Context<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.buffer_font.family.clone(),
+            font_features: settings.buffer_font.features.clone(),
+            font_size: settings.buffer_font_size(cx).into(),
+            font_weight: settings.buffer_font.weight,
+            line_height: relative(settings.buffer_line_height.value()),
+            ..Default::default()
+        };
+        EditorElement::new(
+            &self.expression_editor,
+            EditorStyle {
+                background: cx.theme().colors().editor_background,
+                local_player: cx.theme().players().local(),
+                text: text_style,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("watch-list-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+impl Focusable for WatchList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for WatchList {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .key_context("WatchList")
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(
+                div()
+                    .p_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.render_expression_editor(cx)),
+            )
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "watch-list",
+                            self.entries.len(),
+                            |this, range, _, cx| this.render_entries(range, cx),
+                        )
+                        .track_scroll(self.list_handle.clone())
+                        .size_full(),
+                    )
+                    .child(self.render_vertical_scrollbar(cx)),
+            )
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StackFrame, StoppedEvent,
+    requests::{Disassemble, Initialize, ReadMemory, Scopes, StackTrace, Threads, WriteMemory},
+};
+use gpui::{BackgroundExecutor, Focusable as _, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use util::path;
+
+#[gpui::test]
+async fn test_memory_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<ReadMemory, _>(move |_, args| {
+        assert_eq!("0x1000", args.memory_reference);
+        assert_eq!(Some(0), args.offset);
+
+        Ok(dap::ReadMemoryResponse {
+            address: "0x1000".into(),
+            unreadable_bytes: Some(3),
+            // "Hello, World!"
+            data: Some("SGVsbG8sIFdvcmxkIQ==".into()),
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |state, window, cx| {
+        assert_eq!(
+            Some(&false),
+            state.pane_items_status(cx).get(&DebuggerPaneItem::Memory),
+            "The memory view is only opened on demand"
+        );
+        state.view_memory("0x1000".into(), window, cx);
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        assert_eq!(
+            Some(&true),
+            state.pane_items_status(cx).get(&DebuggerPaneItem::Memory)
+        );
+        assert_eq!(
+            vec!["0000000000001000  48 65 6C 6C 6F 2C 20 57 6F 72 6C 64 21 ?? ?? ??  Hello, World!..."],
+            state.memory_view().read(cx).rows()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_memory_view_writes_memory(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                supports_write_memory_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let written = Arc::new(AtomicBool::new(false));
+    client.on_request::<ReadMemory, _>({
+        let written = written.clone();
+        move |_, _| {
+            Ok(dap::ReadMemoryResponse {
+                address: "0x1000".into(),
+                unreadable_bytes: None,
+                data: Some(if written.load(Ordering::SeqCst) {
+                    // "Jello"
+                    "SmVsbG8=".into()
+                } else {
+                    // "Hello"
+                    "SGVsbG8=".into()
+                }),
+            })
+        }
+    });
+    client.on_request::<WriteMemory, _>({
+        let written = written.clone();
+        move |_, args| {
+            assert_eq!("0x1000", args.memory_reference);
+            assert_eq!(Some(0), args.offset);
+            // [0x4A]
+            assert_eq!("Sg==", args.data);
+            written.store(true, Ordering::SeqCst);
+
+            Ok(dap::WriteMemoryResponse {
+                offset: None,
+                bytes_written: Some(1),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |state, window, cx| {
+        state.view_memory("0x1000".into(), window, cx);
+    });
+
+    cx.run_until_parked();
+
+    let memory_view = running_state.update(cx, |state, _| state.memory_view().clone());
+    memory_view.update(cx, |memory_view, _| {
+        assert_eq!(
+            vec!["0000000000001000  48 65 6C 6C 6F  Hello"],
+            memory_view.rows()
+        );
+    });
+
+    let write_editor = memory_view.update(cx, |memory_view, _| memory_view.write_editor().clone());
+    write_editor.update_in(cx, |editor, window, cx| {
+        editor.set_text("4A", window, cx);
+        window.focus(&editor.focus_handle(cx));
+    });
+    cx.dispatch_action(menu::Confirm);
+
+    cx.run_until_parked();
+
+    assert!(written.load(Ordering::SeqCst));
+    memory_view.update(cx, |memory_view, cx| {
+        assert_eq!(
+            vec!["0000000000001000  4A 65 6C 6C 6F  Jello"],
+            memory_view.rows()
+        );
+        assert_eq!("", memory_view.write_editor().read(cx).text(cx));
+    });
+}
+
+#[gpui::test]
+async fn test_disassembly_view_follows_instruction_pointer(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 0,
+                column: 0,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some("0x1004".into()),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let disassemble_requests = Arc::new(AtomicUsize::new(0));
+    client.on_request::<Disassemble, _>({
+        let disassemble_requests = disassemble_requests.clone();
+        move |_, args| {
+            assert_eq!("0x1004", args.memory_reference);
+            disassemble_requests.fetch_add(1, Ordering::SeqCst);
+
+            let instruction = |address: &str, instruction: &str| dap::DisassembledInstruction {
+                address: address.into(),
+                instruction_bytes: None,
+                instruction: instruction.into(),
+                symbol: None,
+                location: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                presentation_hint: None,
+            };
+
+            Ok(dap::DisassembleResponse {
+                instructions: vec![
+                    instruction("0x1000", "push rbp"),
+                    instruction("0x1001", "mov rbp, rsp"),
+                    instruction("0x1004", "mov eax, 0"),
+                    instruction("0x1009", "pop rbp"),
+                ],
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |state, window, cx| {
+        let threads = state
+            .session()
+            .update(cx, |session, cx| session.threads(cx));
+        state.select_current_thread(&threads, window, cx);
+    });
+
+    cx.run_until_parked();
+
+    assert_eq!(
+        0,
+        disassemble_requests.load(Ordering::SeqCst),
+        "The instructions are not fetched while the disassembly view is hidden"
+    );
+
+    running_state.update_in(cx, |state, window, cx| {
+        state.show_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    assert_eq!(1, disassemble_requests.load(Ordering::SeqCst));
+    running_state.update(cx, |state, cx| {
+        let disassembly_view = state.disassembly_view().read(cx);
+        assert_eq!(4, disassembly_view.instructions().len());
+        assert_eq!(Some(2), disassembly_view.current_instruction());
+    });
+}
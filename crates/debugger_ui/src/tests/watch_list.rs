@@ -0,0 +1,148 @@
+use crate::tests::{
+    active_debug_session_panel, init_test, init_test_workspace, start_debug_session,
+};
+use dap::{
+    EvaluateArgumentsContext, StackFrame, StoppedEvent,
+    requests::{Evaluate, Scopes, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use util::path;
+
+#[gpui::test]
+async fn test_watch_expressions_are_evaluated_on_stop(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 0,
+                column: 0,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let stop_count = Arc::new(AtomicUsize::new(0));
+    client.on_request::<Evaluate, _>({
+        let stop_count = stop_count.clone();
+        move |_, args| {
+            assert!(matches!(
+                args.context,
+                Some(EvaluateArgumentsContext::Watch)
+            ));
+            assert_eq!(Some(1), args.frame_id);
+
+            let count = stop_count.load(Ordering::SeqCst);
+            Ok(dap::EvaluateResponse {
+                result: format!("{}{count}", args.expression),
+                type_: None,
+                presentation_hint: None,
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                value_location_reference: None,
+            })
+        }
+    });
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .update(cx, |item, _| item.running_state().clone());
+
+    let dap_store = project.read_with(cx, |project, _| project.dap_store());
+    dap_store.update(cx, |dap_store, cx| {
+        dap_store.add_watch_expression("a".into(), cx);
+        dap_store.add_watch_expression("b".into(), cx);
+        dap_store.add_watch_expression("a".into(), cx);
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        assert_eq!(
+            vec!["a", "b"],
+            state.watch_list().read(cx).entries(),
+            "Watch expressions aren't evaluated until the debuggee stops"
+        );
+    });
+
+    for count in 1..=2 {
+        stop_count.store(count, Ordering::SeqCst);
+        client
+            .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+                reason: dap::StoppedEventReason::Pause,
+                description: None,
+                thread_id: Some(1),
+                preserve_focus_hint: None,
+                text: None,
+                all_threads_stopped: None,
+                hit_breakpoint_ids: None,
+            }))
+            .await;
+
+        cx.run_until_parked();
+
+        running_state.update_in(cx, |state, window, cx| {
+            let threads = state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+            state.select_current_thread(&threads, window, cx);
+        });
+
+        cx.run_until_parked();
+
+        running_state.update(cx, |state, cx| {
+            assert_eq!(
+                vec![format!("a = a{count}"), format!("b = b{count}")],
+                state.watch_list().read(cx).entries()
+            );
+        });
+    }
+
+    dap_store.update(cx, |dap_store, cx| {
+        dap_store.remove_watch_expression(0, cx);
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        assert_eq!(vec!["b = b2"], state.watch_list().read(cx).entries());
+    });
+}
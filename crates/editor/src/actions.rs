@@ -291,6 +291,8 @@ actions!(
         FindPreviousMatch,
         Fold,
         FoldAll,
+        FoldAllComments,
+        FoldAllImports,
         FoldFunctionBodies,
         FoldRecursive,
         FoldSelectedRanges,
@@ -400,6 +402,7 @@ actions!(
         SelectToStartOfParagraph,
         SelectUp,
         ShowCharacterPalette,
+        ShowColorPresentations,
         ShowEditPrediction,
         ShowSignatureHelp,
         ShowWordCompletions,
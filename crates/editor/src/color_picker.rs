@@ -0,0 +1,268 @@
+use std::rc::Rc;
+
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Rgba, Subscription,
+    Task, WeakEntity, Window, rems,
+};
+use language::Buffer;
+use multi_buffer::ExcerptId;
+use project::{ColorPresentation, DocumentColor};
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+
+use crate::{
+    Editor, EditorEvent, SemanticsProvider,
+    actions::{Cancel, SelectAll},
+    mouse_context_menu::MenuPosition,
+};
+
+/// A [`ColorPicker`] shown next to the color it changes.
+pub(crate) struct ColorPickerPopover {
+    pub(crate) position: MenuPosition,
+    pub(crate) picker: Entity<ColorPicker>,
+    _dismiss_subscription: Subscription,
+}
+
+impl ColorPickerPopover {
+    pub(crate) fn new(
+        position: MenuPosition,
+        picker: Entity<ColorPicker>,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) -> Self {
+        let picker_focus = picker.read(cx).focus_handle.clone();
+        window.focus(&picker.focus_handle(cx));
+        let _dismiss_subscription = cx.subscribe_in(
+            &picker,
+            window,
+            move |editor, _, _: &DismissEvent, window, cx| {
+                editor.color_picker.take();
+                if picker_focus.contains_focused(window, cx) {
+                    window.focus(&editor.focus_handle(cx));
+                }
+                cx.notify();
+            },
+        );
+        Self {
+            position,
+            picker,
+            _dismiss_subscription,
+        }
+    }
+}
+
+/// Lets the user type a new color for a color that a language server found, and lists the
+/// ways that the language server can write the new color in place of the old one.
+pub(crate) struct ColorPicker {
+    editor: WeakEntity<Editor>,
+    buffer: Entity<Buffer>,
+    excerpt_id: ExcerptId,
+    provider: Rc<dyn SemanticsProvider>,
+    /// The color found by the language server, with the color picked so far.
+    color: DocumentColor,
+    pub(crate) hex_input: Entity<Editor>,
+    pub(crate) presentations: Vec<ColorPresentation>,
+    selected_index: usize,
+    presentations_task: Task<()>,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ColorPicker {
+    pub(crate) fn new(
+        editor: WeakEntity<Editor>,
+        buffer: Entity<Buffer>,
+        excerpt_id: ExcerptId,
+        provider: Rc<dyn SemanticsProvider>,
+        color: DocumentColor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let hex_input = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text(color_to_hex(&color.color), window, cx);
+            editor.select_all(&SelectAll, window, cx);
+            editor
+        });
+        let focus_handle = cx.focus_handle();
+        let _subscriptions = vec![
+            cx.subscribe(&hex_input, |picker, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    picker.hex_edited(cx);
+                }
+            }),
+            cx.on_focus_out(&focus_handle, window, |_, _, _, cx| {
+                cx.emit(DismissEvent);
+            }),
+        ];
+        let mut picker = Self {
+            editor,
+            buffer,
+            excerpt_id,
+            provider,
+            color,
+            hex_input,
+            presentations: Vec::new(),
+            selected_index: 0,
+            presentations_task: Task::ready(()),
+            focus_handle,
+            _subscriptions,
+        };
+        picker.fetch_presentations(cx);
+        picker
+    }
+
+    fn hex_edited(&mut self, cx: &mut Context<Self>) {
+        let text = self.hex_input.read(cx).text(cx);
+        let Ok(color) = Rgba::try_from(text.as_str()) else {
+            return;
+        };
+        self.color.color = lsp::Color {
+            red: color.r,
+            green: color.g,
+            blue: color.b,
+            alpha: color.a,
+        };
+        self.fetch_presentations(cx);
+        cx.notify();
+    }
+
+    /// Asks the language server for the ways to write the picked color.
+    fn fetch_presentations(&mut self, cx: &mut Context<Self>) {
+        let Some(presentations) =
+            self.provider
+                .color_presentations(&self.buffer, self.color.clone(), cx)
+        else {
+            return;
+        };
+        self.presentations_task = cx.spawn(async move |picker, cx| {
+            let presentations = presentations.await.log_err().unwrap_or_default();
+            picker
+                .update(cx, |picker, cx| {
+                    picker.presentations = presentations;
+                    picker.selected_index = 0;
+                    cx.notify();
+                })
+                .ok();
+        });
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.apply_presentation(self.selected_index, window, cx);
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.presentations.is_empty() {
+            self.selected_index = (self.selected_index + 1) % self.presentations.len();
+            cx.notify();
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.presentations.is_empty() {
+            self.selected_index = self
+                .selected_index
+                .checked_sub(1)
+                .unwrap_or(self.presentations.len() - 1);
+            cx.notify();
+        }
+    }
+
+    fn apply_presentation(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(presentation) = self.presentations.get(ix) else {
+            return;
+        };
+        let excerpt_id = self.excerpt_id;
+        self.editor
+            .update(cx, |editor, cx| {
+                editor.apply_color_presentation(excerpt_id, presentation, window, cx);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for ColorPicker {}
+
+impl Focusable for ColorPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.hex_input.focus_handle(cx)
+    }
+}
+
+impl Render for ColorPicker {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let color = self.color.color;
+        let preview = Rgba {
+            r: color.red,
+            g: color.green,
+            b: color.blue,
+            a: color.alpha,
+        };
+        v_flex()
+            .key_context("ColorPicker")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .w(rems(16.))
+            .p_1()
+            .gap_1()
+            .elevation_2(cx)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .px_1()
+                    .child(
+                        div()
+                            .flex_none()
+                            .size_4()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .bg(preview),
+                    )
+                    .child(self.hex_input.clone()),
+            )
+            .children(
+                self.presentations
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, presentation)| {
+                        ListItem::new(ix)
+                            .inset(true)
+                            .spacing(ListItemSpacing::Sparse)
+                            .toggle_state(ix == self.selected_index)
+                            .on_click(cx.listener(move |picker, _, window, cx| {
+                                picker.apply_presentation(ix, window, cx)
+                            }))
+                            .child(Label::new(presentation.label.clone()))
+                    }),
+            )
+    }
+}
+
+/// Writes the color as `#rrggbb`, or `#rrggbbaa` if it is translucent.
+fn color_to_hex(color: &lsp::Color) -> String {
+    let channel = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    let mut hex = format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue)
+    );
+    if color.alpha < 1. {
+        hex.push_str(&format!("{:02x}", channel(color.alpha)));
+    }
+    hex
+}
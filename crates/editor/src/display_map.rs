@@ -52,6 +52,7 @@ use multi_buffer::{
     Anchor, AnchorRangeExt, ExcerptId, MultiBuffer, MultiBufferPoint, MultiBufferRow,
     MultiBufferSnapshot, RowInfo, ToOffset, ToPoint,
 };
+use project::FoldingRange;
 use serde::Deserialize;
 use std::{
     any::TypeId,
@@ -81,6 +82,19 @@ pub trait ToDisplayPoint {
 
 type TextHighlights = TreeMap<TypeId, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>;
 type InlayHighlights = TreeMap<TypeId, TreeMap<InlayId, (HighlightStyle, InlayHighlight)>>;
+type SemanticTokenHighlights = Arc<[(Range<Anchor>, HighlightStyle)]>;
+type FoldingSources = Arc<HashMap<BufferId, FoldingSource>>;
+
+/// Where the foldable ranges of a buffer come from when it has no explicit creases.
+/// Buffers without a folding source are folded by indentation.
+#[derive(Clone, Debug)]
+pub enum FoldingSource {
+    /// Blocks delimited by the language's brackets, falling back to indentation
+    /// for rows that don't open a block.
+    TreeSitter,
+    /// The folding ranges reported by a language server, sorted by their start.
+    Lsp(Arc<[FoldingRange]>),
+}
 
 /// Decides how text in a [`MultiBuffer`] should be displayed in a buffer, handling inlay hints,
 /// folding, hard tabs, soft wrapping, custom blocks (like diagnostics), and highlighting.
@@ -104,8 +118,13 @@ pub struct DisplayMap {
     text_highlights: TextHighlights,
     /// Regions of inlays that should be highlighted.
     inlay_highlights: InlayHighlights,
+    /// Styles of the language servers' semantic tokens, sorted by their start.
+    /// These are layered over the syntax highlighting and under the text highlights.
+    semantic_token_highlights: SemanticTokenHighlights,
     /// A container for explicitly foldable ranges, which supersede indentation based fold range suggestions.
     crease_map: CreaseMap,
+    /// How to suggest fold ranges for the buffers that have no explicit creases.
+    folding_sources: FoldingSources,
     pub(crate) fold_placeholder: FoldPlaceholder,
     pub clip_at_line_ends: bool,
     pub(crate) masked: bool,
@@ -147,6 +166,8 @@ impl DisplayMap {
             fold_placeholder,
             text_highlights: Default::default(),
             inlay_highlights: Default::default(),
+            semantic_token_highlights: Default::default(),
+            folding_sources: Default::default(),
             clip_at_line_ends: false,
             masked: false,
         }
@@ -174,6 +195,8 @@ impl DisplayMap {
             crease_snapshot: self.crease_map.snapshot(),
             text_highlights: self.text_highlights.clone(),
             inlay_highlights: self.inlay_highlights.clone(),
+            semantic_token_highlights: self.semantic_token_highlights.clone(),
+            folding_sources: self.folding_sources.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
             masked: self.masked,
             fold_placeholder: self.fold_placeholder.clone(),
@@ -498,6 +521,17 @@ impl DisplayMap {
         let highlights = self.text_highlights.get(&type_id)?;
         Some((highlights.0, &highlights.1))
     }
+    pub(crate) fn set_semantic_token_highlights(
+        &mut self,
+        highlights: Vec<(Range<Anchor>, HighlightStyle)>,
+    ) {
+        self.semantic_token_highlights = highlights.into();
+    }
+
+    pub(crate) fn set_folding_sources(&mut self, sources: HashMap<BufferId, FoldingSource>) {
+        self.folding_sources = Arc::new(sources);
+    }
+
     pub fn clear_highlights(&mut self, type_id: TypeId) -> bool {
         let mut cleared = self.text_highlights.remove(&type_id).is_some();
         cleared |= self.inlay_highlights.remove(&type_id).is_some();
@@ -608,6 +642,7 @@ impl DisplayMap {
 pub(crate) struct Highlights<'a> {
     pub text_highlights: Option<&'a TextHighlights>,
     pub inlay_highlights: Option<&'a InlayHighlights>,
+    pub semantic_token_highlights: Option<&'a [(Range<Anchor>, HighlightStyle)]>,
     pub styles: HighlightStyles,
 }
 
@@ -742,6 +777,8 @@ pub struct DisplaySnapshot {
     block_snapshot: BlockSnapshot,
     text_highlights: TextHighlights,
     inlay_highlights: InlayHighlights,
+    semantic_token_highlights: SemanticTokenHighlights,
+    folding_sources: FoldingSources,
     clip_at_line_ends: bool,
     masked: bool,
     pub(crate) fold_placeholder: FoldPlaceholder,
@@ -912,6 +949,7 @@ impl DisplaySnapshot {
             Highlights {
                 text_highlights: Some(&self.text_highlights),
                 inlay_highlights: Some(&self.inlay_highlights),
+                semantic_token_highlights: Some(&self.semantic_token_highlights),
                 styles: highlight_styles,
             },
         )
@@ -1227,6 +1265,63 @@ impl DisplaySnapshot {
             .unwrap_or(false)
     }
 
+    /// Whether a fold can be created starting at the given row.
+    pub fn starts_fold(&self, buffer_row: MultiBufferRow) -> bool {
+        match self.folding_source_range(buffer_row) {
+            Some(range) => range.is_some(),
+            None => self.starts_indent(buffer_row),
+        }
+    }
+
+    /// Returns the range that the folding source of the row's buffer suggests folding
+    /// at the given row, or `None` if the row should be folded by indentation instead.
+    fn folding_source_range(&self, buffer_row: MultiBufferRow) -> Option<Option<Range<Point>>> {
+        if self.folding_sources.is_empty() {
+            return None;
+        }
+        let (buffer, buffer_point, excerpt_id) = self
+            .buffer_snapshot
+            .point_to_buffer_point(Point::new(buffer_row.0, 0))?;
+        let row = buffer_point.row;
+        let end_row = match self.folding_sources.get(&buffer.remote_id())? {
+            FoldingSource::Lsp(ranges) => {
+                let start_ix = ranges.partition_point(|range| {
+                    buffer.summary_for_anchor::<Point>(&range.range.start).row < row
+                });
+                ranges[start_ix..]
+                    .iter()
+                    .take_while(|range| {
+                        buffer.summary_for_anchor::<Point>(&range.range.start).row == row
+                    })
+                    .map(|range| buffer.summary_for_anchor::<Point>(&range.range.end).row)
+                    .max()
+            }
+            FoldingSource::TreeSitter => {
+                let line = Point::new(row, 0)..Point::new(row, buffer.line_len(row));
+                // Keep the line of the closing bracket visible.
+                let close_row = buffer
+                    .bracket_ranges(line)
+                    .filter(|pair| buffer.offset_to_point(pair.open_range.start).row == row)
+                    .map(|pair| buffer.offset_to_point(pair.close_range.start).row)
+                    .filter(|close_row| *close_row > row)
+                    .max()?;
+                Some(close_row - 1)
+            }
+        };
+
+        let end_row = end_row.filter(|end_row| *end_row > row);
+        Some(end_row.and_then(|end_row| {
+            let end = buffer.anchor_before(Point::new(end_row, buffer.line_len(end_row)));
+            let end = self
+                .buffer_snapshot
+                .anchor_in_excerpt(excerpt_id, end)?
+                .to_point(&self.buffer_snapshot);
+            let start =
+                MultiBufferPoint::new(buffer_row.0, self.buffer_snapshot.line_len(buffer_row));
+            (end.row > start.row).then_some(start..end)
+        }))
+    }
+
     pub fn crease_for_buffer_row(&self, buffer_row: MultiBufferRow) -> Option<Crease<Point>> {
         let start = MultiBufferPoint::new(buffer_row.0, self.buffer_snapshot.line_len(buffer_row));
         if let Some(crease) = self
@@ -1263,6 +1358,15 @@ impl DisplaySnapshot {
                     render_toggle: render_toggle.clone(),
                 }),
             }
+        } else if let Some(range) = self.folding_source_range(buffer_row) {
+            let range = range.filter(|_| !self.is_line_folded(buffer_row))?;
+            Some(Crease::Inline {
+                range,
+                placeholder: self.fold_placeholder.clone(),
+                render_toggle: None,
+                render_trailer: None,
+                metadata: None,
+            })
         } else if self.starts_indent(MultiBufferRow(start.row))
             && !self.is_line_folded(MultiBufferRow(start.row))
         {
@@ -2604,6 +2708,164 @@ pub mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_chunks_with_semantic_token_highlights(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| init_test(cx, |_| {}));
+
+        let theme = SyntaxTheme::new_test(vec![]);
+        let buffer = cx.new(|cx| Buffer::local("onetwo three", cx));
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let buffer_snapshot = buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx));
+
+        let map = cx.new(|cx| {
+            DisplayMap::new(
+                buffer,
+                font("Courier"),
+                px(16.0),
+                None,
+                1,
+                1,
+                FoldPlaceholder::test(),
+                cx,
+            )
+        });
+
+        let color_style = |color| HighlightStyle {
+            color: Some(color),
+            ..Default::default()
+        };
+        let anchor_range = |range: Range<usize>| {
+            buffer_snapshot.anchor_after(range.start)..buffer_snapshot.anchor_before(range.end)
+        };
+
+        enum MyType {}
+
+        map.update(cx, |map, _| {
+            map.set_semantic_token_highlights(vec![
+                (anchor_range(0..3), color_style(Hsla::red())),
+                (anchor_range(3..6), color_style(Hsla::green())),
+                (anchor_range(7..12), color_style(Hsla::red())),
+            ]);
+            map.highlight_text(
+                TypeId::of::<MyType>(),
+                vec![anchor_range(9..10)],
+                color_style(Hsla::blue()),
+            );
+        });
+
+        // Adjacent tokens are styled independently, and text highlights take precedence.
+        assert_eq!(
+            cx.update(|cx| chunks(DisplayRow(0)..DisplayRow(1), &map, &theme, cx)),
+            [
+                ("one".to_string(), None, Some(Hsla::red())),
+                ("two".to_string(), None, Some(Hsla::green())),
+                (" ".to_string(), None, None),
+                ("th".to_string(), None, Some(Hsla::red())),
+                ("r".to_string(), None, Some(Hsla::blue())),
+                ("ee".to_string(), None, Some(Hsla::red())),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_folding_sources(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| init_test(cx, |_| {}));
+
+        let text = r#"
+            use a;
+            use b;
+            fn a() {
+                b(
+                    1,
+                );
+            }
+            fn b() {
+            }
+        "#
+        .unindent();
+        let language = Arc::new(
+            Language::new(
+                LanguageConfig {
+                    name: "Rust".into(),
+                    ..Default::default()
+                },
+                Some(tree_sitter_rust::LANGUAGE.into()),
+            )
+            .with_brackets_query(r#"("(" @open ")" @close) ("{" @open "}" @close)"#)
+            .unwrap(),
+        );
+
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(language, cx));
+        cx.condition(&buffer, |buf, _| !buf.is_parsing()).await;
+        let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+        let text_snapshot = buffer.read_with(cx, |buffer, _| buffer.text_snapshot());
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+
+        let map = cx.new(|cx| {
+            DisplayMap::new(
+                buffer,
+                font("Courier"),
+                px(16.0),
+                None,
+                1,
+                1,
+                FoldPlaceholder::test(),
+                cx,
+            )
+        });
+        let fold_range = |row: u32, map: &Entity<DisplayMap>, cx: &mut gpui::TestAppContext| {
+            map.update(cx, |map, cx| {
+                map.snapshot(cx)
+                    .crease_for_buffer_row(MultiBufferRow(row))
+                    .map(|crease| crease.range().clone())
+            })
+        };
+
+        // Indentation
+        assert_eq!(fold_range(0, &map, cx), None);
+        assert_eq!(
+            fold_range(2, &map, cx),
+            Some(Point::new(2, 8)..Point::new(5, 6))
+        );
+
+        // Tree-sitter keeps the line of the closing bracket visible.
+        map.update(cx, |map, _| {
+            map.set_folding_sources(HashMap::from_iter([(buffer_id, FoldingSource::TreeSitter)]))
+        });
+        assert_eq!(fold_range(0, &map, cx), None);
+        assert_eq!(
+            fold_range(2, &map, cx),
+            Some(Point::new(2, 8)..Point::new(5, 6))
+        );
+        assert_eq!(
+            fold_range(3, &map, cx),
+            Some(Point::new(3, 6)..Point::new(4, 10))
+        );
+        assert_eq!(fold_range(7, &map, cx), None);
+
+        // LSP ranges replace the indentation based ones.
+        let lsp_range = |start: u32, end: u32| FoldingRange {
+            range: text_snapshot.anchor_after(Point::new(start, 0))
+                ..text_snapshot.anchor_before(Point::new(end, text_snapshot.line_len(end))),
+            kind: Some(lsp::FoldingRangeKind::Imports),
+        };
+        map.update(cx, |map, _| {
+            map.set_folding_sources(HashMap::from_iter([(
+                buffer_id,
+                FoldingSource::Lsp(Arc::from([lsp_range(0, 1), lsp_range(3, 4)])),
+            )]))
+        });
+        assert_eq!(
+            fold_range(0, &map, cx),
+            Some(Point::new(0, 6)..Point::new(1, 6))
+        );
+        assert_eq!(fold_range(2, &map, cx), None);
+        assert_eq!(
+            fold_range(3, &map, cx),
+            Some(Point::new(3, 6)..Point::new(4, 10))
+        );
+    }
+
     #[gpui::test]
     fn test_clip_point(cx: &mut gpui::App) {
         init_test(cx, |_| {});
@@ -21,6 +21,11 @@ pub struct CustomHighlightsChunks<'a> {
     highlight_endpoints: Peekable<vec::IntoIter<HighlightEndpoint>>,
     active_highlights: BTreeMap<TypeId, HighlightStyle>,
     text_highlights: Option<&'a TreeMap<TypeId, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>>,
+
+    semantic_tokens: Peekable<vec::IntoIter<(Range<usize>, HighlightStyle)>>,
+    /// The end offset and style of the semantic token containing the current offset.
+    active_semantic_token: Option<(usize, HighlightStyle)>,
+    semantic_token_highlights: Option<&'a [(Range<Anchor>, HighlightStyle)]>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -36,6 +41,7 @@ impl<'a> CustomHighlightsChunks<'a> {
         range: Range<usize>,
         language_aware: bool,
         text_highlights: Option<&'a TreeMap<TypeId, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>>,
+        semantic_token_highlights: Option<&'a [(Range<Anchor>, HighlightStyle)]>,
         multibuffer_snapshot: &'a MultiBufferSnapshot,
     ) -> Self {
         Self {
@@ -50,6 +56,13 @@ impl<'a> CustomHighlightsChunks<'a> {
                 multibuffer_snapshot,
             ),
             active_highlights: Default::default(),
+            semantic_token_highlights,
+            semantic_tokens: create_semantic_token_ranges(
+                &range,
+                semantic_token_highlights,
+                multibuffer_snapshot,
+            ),
+            active_semantic_token: None,
             multibuffer_snapshot,
         }
     }
@@ -57,11 +70,49 @@ impl<'a> CustomHighlightsChunks<'a> {
     pub fn seek(&mut self, new_range: Range<usize>) {
         self.highlight_endpoints =
             create_highlight_endpoints(&new_range, self.text_highlights, self.multibuffer_snapshot);
+        self.semantic_tokens = create_semantic_token_ranges(
+            &new_range,
+            self.semantic_token_highlights,
+            self.multibuffer_snapshot,
+        );
         self.offset = new_range.start;
         self.buffer_chunks.seek(new_range);
         self.buffer_chunk.take();
-        self.active_highlights.clear()
+        self.active_highlights.clear();
+        self.active_semantic_token.take();
+    }
+}
+
+fn create_semantic_token_ranges(
+    range: &Range<usize>,
+    semantic_token_highlights: Option<&[(Range<Anchor>, HighlightStyle)]>,
+    buffer: &MultiBufferSnapshot,
+) -> iter::Peekable<vec::IntoIter<(Range<usize>, HighlightStyle)>> {
+    let mut ranges = Vec::new();
+    if let Some(semantic_token_highlights) = semantic_token_highlights {
+        let start = buffer.anchor_after(range.start);
+        let end = buffer.anchor_after(range.end);
+        let start_ix = match semantic_token_highlights.binary_search_by(|(probe, _)| {
+            if probe.end.cmp(&start, &buffer).is_gt() {
+                cmp::Ordering::Greater
+            } else {
+                cmp::Ordering::Less
+            }
+        }) {
+            Ok(i) | Err(i) => i,
+        };
+
+        for (token_range, style) in &semantic_token_highlights[start_ix..] {
+            if token_range.start.cmp(&end, &buffer).is_ge() {
+                break;
+            }
+            ranges.push((
+                token_range.start.to_offset(&buffer)..token_range.end.to_offset(&buffer),
+                *style,
+            ));
+        }
     }
+    ranges.into_iter().peekable()
 }
 
 fn create_highlight_endpoints(
@@ -131,6 +182,27 @@ impl<'a> Iterator for CustomHighlightsChunks<'a> {
             }
         }
 
+        if self
+            .active_semantic_token
+            .is_some_and(|(end, _)| end <= self.offset)
+        {
+            self.active_semantic_token = None;
+        }
+        while let Some((range, style)) = self.semantic_tokens.peek().cloned() {
+            if range.start <= self.offset {
+                if range.end > self.offset {
+                    self.active_semantic_token = Some((range.end, style));
+                }
+                self.semantic_tokens.next();
+            } else {
+                next_highlight_endpoint = next_highlight_endpoint.min(range.start);
+                break;
+            }
+        }
+        if let Some((end, _)) = self.active_semantic_token {
+            next_highlight_endpoint = next_highlight_endpoint.min(end);
+        }
+
         let chunk = self
             .buffer_chunk
             .get_or_insert_with(|| self.buffer_chunks.next().unwrap());
@@ -148,8 +220,12 @@ impl<'a> Iterator for CustomHighlightsChunks<'a> {
             text: prefix,
             ..chunk.clone()
         };
-        if !self.active_highlights.is_empty() {
-            let mut highlight_style = HighlightStyle::default();
+        if self.active_semantic_token.is_some() || !self.active_highlights.is_empty() {
+            // Text highlights take precedence over the semantic token styles.
+            let mut highlight_style = self
+                .active_semantic_token
+                .map(|(_, style)| style)
+                .unwrap_or_default();
             for active_highlight in self.active_highlights.values() {
                 highlight_style.highlight(*active_highlight);
             }
@@ -42,6 +42,9 @@ pub struct Inlay {
 }
 
 impl Inlay {
+    /// The text of a color swatch, whose square is colored by an inlay highlight.
+    pub const COLOR_SWATCH: &str = "■ ";
+
     pub fn hint(id: usize, position: Anchor, hint: &project::InlayHint) -> Self {
         let mut text = hint.text();
         if hint.padding_right && !text.ends_with(' ') {
@@ -72,6 +75,14 @@ impl Inlay {
             text: text.into(),
         }
     }
+
+    pub fn color(id: usize, position: Anchor) -> Self {
+        Self {
+            id: InlayId::Color(id),
+            position,
+            text: Self::COLOR_SWATCH.into(),
+        }
+    }
 }
 
 impl sum_tree::Item for Transform {
@@ -296,6 +307,7 @@ impl<'a> Iterator for InlayChunks<'a> {
                     }
                     InlayId::Hint(_) => self.highlight_styles.inlay_hint,
                     InlayId::DebuggerValue(_) => self.highlight_styles.inlay_hint,
+                    InlayId::Color(_) => None,
                 };
                 let next_inlay_highlight_endpoint;
                 let offset_in_inlay = self.output_offset - self.transforms.start().0;
@@ -1008,6 +1020,7 @@ impl InlaySnapshot {
             buffer_range,
             language_aware,
             highlights.text_highlights,
+            highlights.semantic_token_highlights,
             &self.buffer,
         );
 
@@ -0,0 +1,314 @@
+use std::{mem, ops::Range, time::Duration};
+
+use collections::{HashMap, HashSet};
+use gpui::{Context, HighlightStyle, Pixels, Point, Rgba, Window};
+use language::language_settings::language_settings;
+use multi_buffer::{Anchor, ExcerptId, ToOffset as _};
+use project::{ColorPresentation, DocumentColor};
+use text::BufferId;
+use util::{ResultExt as _, post_inc};
+
+use crate::{
+    Editor, EditorSnapshot, InlayId, PointForPosition, ShowColorPresentations,
+    color_picker::{ColorPicker, ColorPickerPopover},
+    display_map::Inlay,
+    hover_links::InlayHighlight,
+    mouse_context_menu::MenuPosition,
+};
+
+const UPDATE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// A swatch shown before one of the colors that the language servers found in
+/// the editor's buffers.
+#[derive(Clone, Debug)]
+pub(crate) struct ColorSwatch {
+    inlay_id: InlayId,
+    buffer_id: BufferId,
+    range: Range<Anchor>,
+    color: DocumentColor,
+}
+
+/// Fetches the colors of every buffer in the editor that shows document colors in
+/// its language settings, and shows a swatch before each of them.
+pub(super) fn refresh_document_colors(editor: &mut Editor, cx: &mut Context<Editor>) {
+    if !editor.mode.is_full() {
+        return;
+    }
+
+    let buffers = editor
+        .buffer
+        .read(cx)
+        .all_buffers()
+        .into_iter()
+        .filter(|buffer| {
+            let buffer = buffer.read(cx);
+            language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            )
+            .document_colors
+        })
+        .collect::<Vec<_>>();
+    let buffer_ids = buffers
+        .iter()
+        .map(|buffer| buffer.read(cx).remote_id())
+        .collect::<HashSet<_>>();
+    let colors_count = editor.document_colors.len();
+    editor
+        .document_colors
+        .retain(|buffer_id, _| buffer_ids.contains(buffer_id));
+    if editor.document_colors.len() != colors_count {
+        update_color_swatches(editor, cx);
+    }
+
+    let Some(provider) = editor.semantics_provider.clone() else {
+        return;
+    };
+    if buffers.is_empty() {
+        editor.document_colors_task = None;
+        return;
+    }
+
+    editor.document_colors_task = Some(cx.spawn(async move |editor, cx| {
+        cx.background_executor().timer(UPDATE_DEBOUNCE).await;
+
+        let Some(tasks) = cx
+            .update(|cx| {
+                buffers
+                    .iter()
+                    .filter_map(|buffer| {
+                        let task = provider.document_colors(buffer, cx)?;
+                        Some((buffer.read(cx).remote_id(), task))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .ok()
+        else {
+            return;
+        };
+
+        let mut document_colors = HashMap::default();
+        for (buffer_id, task) in tasks {
+            if let Some(colors) = task.await.log_err() {
+                document_colors.insert(buffer_id, colors);
+            }
+        }
+
+        editor
+            .update(cx, |editor, cx| {
+                for (buffer_id, colors) in document_colors {
+                    if colors.is_empty() {
+                        editor.document_colors.remove(&buffer_id);
+                    } else {
+                        editor.document_colors.insert(buffer_id, colors);
+                    }
+                }
+                update_color_swatches(editor, cx);
+            })
+            .ok();
+    }));
+}
+
+/// Replaces the color swatches with ones for the current document colors in all of
+/// the editor's excerpts.
+pub(super) fn update_color_swatches(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let snapshot = editor.buffer.read(cx).snapshot(cx);
+    let mut swatches = Vec::new();
+    let mut inlays = Vec::new();
+    for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+        let Some(colors) = editor.document_colors.get(&buffer.remote_id()) else {
+            continue;
+        };
+        for color in colors {
+            if color
+                .range
+                .start
+                .cmp(&excerpt_range.context.start, buffer)
+                .is_lt()
+                || color
+                    .range
+                    .end
+                    .cmp(&excerpt_range.context.end, buffer)
+                    .is_gt()
+            {
+                continue;
+            }
+            let Some((start, end)) = snapshot
+                .anchor_in_excerpt(excerpt_id, color.range.start)
+                .zip(snapshot.anchor_in_excerpt(excerpt_id, color.range.end))
+            else {
+                continue;
+            };
+            let inlay = Inlay::color(post_inc(&mut editor.next_inlay_id), start);
+            swatches.push(ColorSwatch {
+                inlay_id: inlay.id,
+                buffer_id: buffer.remote_id(),
+                range: start..end,
+                color: color.clone(),
+            });
+            inlays.push(inlay);
+        }
+    }
+
+    let old_inlay_ids = mem::take(&mut editor.color_swatches)
+        .into_iter()
+        .map(|swatch| swatch.inlay_id)
+        .collect::<Vec<_>>();
+    editor.splice_inlays(&old_inlay_ids, inlays, cx);
+    editor.clear_highlights::<ColorSwatch>(cx);
+    for swatch in &swatches {
+        let color = swatch.color.color;
+        let style = HighlightStyle {
+            color: Some(
+                Rgba {
+                    r: color.red,
+                    g: color.green,
+                    b: color.blue,
+                    a: color.alpha,
+                }
+                .into(),
+            ),
+            ..HighlightStyle::default()
+        };
+        let square_len = Inlay::COLOR_SWATCH.chars().next().map_or(0, char::len_utf8);
+        editor.highlight_inlays::<ColorSwatch>(
+            vec![InlayHighlight {
+                inlay: swatch.inlay_id,
+                inlay_position: swatch.range.start,
+                range: 0..square_len,
+            }],
+            style,
+            cx,
+        );
+    }
+    editor.color_swatches = swatches;
+}
+
+impl Editor {
+    /// Returns the color swatch at the given position, if any.
+    pub(crate) fn color_swatch_at(
+        &self,
+        point_for_position: &PointForPosition,
+        snapshot: &EditorSnapshot,
+    ) -> Option<ColorSwatch> {
+        if point_for_position.as_valid().is_some()
+            || point_for_position.column_overshoot_after_line_end != 0
+        {
+            return None;
+        }
+
+        // Swatches are inlays, so a click on one lands between two valid positions.
+        let buffer_snapshot = &snapshot.buffer_snapshot;
+        let previous_valid = buffer_snapshot.anchor_at(
+            point_for_position.previous_valid.to_point(snapshot),
+            text::Bias::Left,
+        );
+        let next_valid = buffer_snapshot.anchor_at(
+            point_for_position.next_valid.to_point(snapshot),
+            text::Bias::Right,
+        );
+        self.color_swatches
+            .iter()
+            .find(|swatch| {
+                swatch
+                    .range
+                    .start
+                    .cmp(&previous_valid, buffer_snapshot)
+                    .is_ge()
+                    && swatch.range.start.cmp(&next_valid, buffer_snapshot).is_le()
+            })
+            .cloned()
+    }
+
+    /// Shows a picker for a new color for the color that contains the newest cursor.
+    pub fn show_color_presentations(
+        &mut self,
+        _: &ShowColorPresentations,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let head = self.selections.newest_anchor().head().to_offset(&snapshot);
+        let Some(swatch) = self
+            .color_swatches
+            .iter()
+            .find(|swatch| {
+                swatch.range.start.to_offset(&snapshot) <= head
+                    && head <= swatch.range.end.to_offset(&snapshot)
+            })
+            .cloned()
+        else {
+            return;
+        };
+        self.show_color_picker(swatch, None, window, cx);
+    }
+
+    /// Shows a picker for a new color for the swatch, at the given position or below the color.
+    pub(crate) fn show_color_picker(
+        &mut self,
+        swatch: ColorSwatch,
+        position: Option<Point<Pixels>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(provider) = self.semantics_provider.clone() else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).buffer(swatch.buffer_id) else {
+            return;
+        };
+        let menu_position = match position {
+            Some(position) => {
+                MenuPosition::pinned_to_editor(self, swatch.range.start, position, window, cx)
+            }
+            None => {
+                let character_size = self.character_size(window);
+                Some(MenuPosition::PinnedToEditor {
+                    source: swatch.range.start,
+                    offset: gpui::point(character_size.width, character_size.height),
+                })
+            }
+        };
+        let Some(menu_position) = menu_position else {
+            return;
+        };
+
+        let editor = cx.weak_entity();
+        let picker = cx.new(|cx| {
+            ColorPicker::new(
+                editor,
+                buffer,
+                swatch.range.start.excerpt_id,
+                provider,
+                swatch.color,
+                window,
+                cx,
+            )
+        });
+        self.color_picker = Some(ColorPickerPopover::new(menu_position, picker, window, cx));
+        cx.notify();
+    }
+
+    pub(crate) fn apply_color_presentation(
+        &mut self,
+        excerpt_id: ExcerptId,
+        presentation: &ColorPresentation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let edits = presentation
+            .edits
+            .iter()
+            .filter_map(|(range, new_text)| {
+                let start = snapshot.anchor_in_excerpt(excerpt_id, range.start)?;
+                let end = snapshot.anchor_in_excerpt(excerpt_id, range.end)?;
+                Some((start..end, new_text.clone()))
+            })
+            .collect::<Vec<_>>();
+        self.transact(window, cx, |editor, _, cx| {
+            editor.edit(edits, cx);
+        });
+    }
+}
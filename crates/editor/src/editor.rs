@@ -16,10 +16,13 @@ pub mod actions;
 mod blink_manager;
 mod clangd_ext;
 mod code_context_menus;
+mod color_picker;
 pub mod display_map;
+mod document_colors;
 mod editor_settings;
 mod editor_settings_controls;
 mod element;
+mod folding_ranges;
 mod git;
 mod highlight_matching_bracket;
 mod hover_links;
@@ -37,6 +40,8 @@ mod proposed_changes_editor;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
+mod sticky_scroll;
 pub mod tasks;
 
 #[cfg(test)]
@@ -46,6 +51,7 @@ mod editor_tests;
 #[cfg(test)]
 mod inline_completion_tests;
 mod signature_help;
+mod snippet_variables;
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
 
@@ -111,8 +117,8 @@ use language::{
     IndentKind, IndentSize, Language, OffsetRangeExt, Point, Selection, SelectionGoal, TextObject,
     TransactionId, TreeSitterOptions, WordsQuery,
     language_settings::{
-        self, InlayHintSettings, LspInsertMode, RewrapBehavior, WordsCompletionMode,
-        all_language_settings, language_settings,
+        self, InlayHintSettings, LspInsertMode, RewrapBehavior, SelectionRangesSource,
+        WordsCompletionMode, all_language_settings, language_settings,
     },
     point_from_lsp, text_diff_with_options,
 };
@@ -174,7 +180,8 @@ use selections_collection::{
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsLocation, SettingsStore, update_settings_file};
 use smallvec::SmallVec;
-use snippet::Snippet;
+use snippet::{Snippet, Transform};
+use snippet_variables::SnippetVariables;
 use std::sync::Arc;
 use std::{
     any::TypeId,
@@ -274,6 +281,7 @@ pub enum InlayId {
     InlineCompletion(usize),
     Hint(usize),
     DebuggerValue(usize),
+    Color(usize),
 }
 
 impl InlayId {
@@ -282,6 +290,7 @@ impl InlayId {
             Self::InlineCompletion(id) => *id,
             Self::Hint(id) => *id,
             Self::DebuggerValue(id) => *id,
+            Self::Color(id) => *id,
         }
     }
 }
@@ -849,6 +858,7 @@ pub struct Editor {
     autoclose_regions: Vec<AutocloseRegion>,
     snippet_stack: InvalidationStack<SnippetState>,
     select_syntax_node_history: SelectSyntaxNodeHistory,
+    selection_ranges_task: Option<Task<()>>,
     ime_transaction: Option<TransactionId>,
     active_diagnostics: ActiveDiagnostic,
     show_inline_diagnostics: bool,
@@ -891,6 +901,7 @@ pub struct Editor {
     context_menu: RefCell<Option<CodeContextMenu>>,
     context_menu_options: Option<ContextMenuOptions>,
     mouse_context_menu: Option<MouseContextMenu>,
+    color_picker: Option<color_picker::ColorPickerPopover>,
     completion_tasks: Vec<(CompletionId, Task<Option<()>>)>,
     inline_blame_popover: Option<InlineBlamePopover>,
     signature_help_state: SignatureHelpState,
@@ -904,6 +915,16 @@ pub struct Editor {
     document_highlights_task: Option<Task<()>>,
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
+    /// The language servers' semantic tokens for each buffer that has them enabled.
+    semantic_tokens: HashMap<BufferId, Vec<project::SemanticToken>>,
+    semantic_tokens_tasks: HashMap<BufferId, Task<()>>,
+    /// The language servers' folding ranges for each buffer that folds by them.
+    folding_ranges: HashMap<BufferId, Arc<[project::FoldingRange]>>,
+    folding_ranges_task: Option<Task<()>>,
+    /// The language servers' colors for each buffer that has document colors enabled.
+    document_colors: HashMap<BufferId, Vec<project::DocumentColor>>,
+    document_colors_task: Option<Task<()>>,
+    color_swatches: Vec<document_colors::ColorSwatch>,
     pending_rename: Option<RenameState>,
     searchable: bool,
     cursor_shape: CursorShape,
@@ -1222,6 +1243,18 @@ struct SnippetState {
     ranges: Vec<Vec<Range<Anchor>>>,
     active_index: usize,
     choices: Vec<Option<Vec<String>>>,
+    /// For each tabstop, the ranges that show its text after a transform.
+    transformed_ranges: Vec<Vec<TransformedRange>>,
+}
+
+/// A range that shows the text of a snippet's tabstop after a transform.
+#[derive(Debug)]
+struct TransformedRange {
+    range: Range<Anchor>,
+    /// The tabstop's range in the snippet inserted at the same selection, whose text
+    /// is transformed.
+    source: Range<Anchor>,
+    transform: Transform,
 }
 
 #[doc(hidden)]
@@ -1537,6 +1570,9 @@ impl Editor {
                             editor
                                 .refresh_inlay_hints(InlayHintRefreshReason::RefreshRequested, cx);
                         }
+                        project::Event::RefreshSemanticTokens => {
+                            semantic_tokens::refresh_semantic_tokens(editor, None, cx);
+                        }
                         project::Event::SnippetEdit(id, snippet_edits) => {
                             if let Some(buffer) = editor.buffer.read(cx).buffer(*id) {
                                 let focus_handle = editor.focus_handle(cx);
@@ -1653,6 +1689,7 @@ impl Editor {
             autoclose_regions: Default::default(),
             snippet_stack: Default::default(),
             select_syntax_node_history: SelectSyntaxNodeHistory::default(),
+            selection_ranges_task: None,
             ime_transaction: Default::default(),
             active_diagnostics: ActiveDiagnostic::None,
             show_inline_diagnostics: ProjectSettings::get_global(cx).diagnostics.inline.enabled,
@@ -1690,6 +1727,7 @@ impl Editor {
             context_menu: RefCell::new(None),
             context_menu_options: None,
             mouse_context_menu: None,
+            color_picker: None,
             completion_tasks: Default::default(),
             inline_blame_popover: Default::default(),
             signature_help_state: SignatureHelpState::default(),
@@ -1704,6 +1742,13 @@ impl Editor {
             debounced_selection_highlight_task: Default::default(),
             document_highlights_task: Default::default(),
             linked_editing_range_task: Default::default(),
+            semantic_tokens: Default::default(),
+            semantic_tokens_tasks: HashMap::default(),
+            folding_ranges: Default::default(),
+            folding_ranges_task: None,
+            document_colors: Default::default(),
+            document_colors_task: None,
+            color_swatches: Vec::new(),
             pending_rename: Default::default(),
             searchable: true,
             cursor_shape: EditorSettings::get_global(cx)
@@ -1815,6 +1860,9 @@ impl Editor {
                 }));
         }
         this.tasks_update_task = Some(this.refresh_runnables(window, cx));
+        semantic_tokens::refresh_semantic_tokens(&mut this, None, cx);
+        folding_ranges::refresh_folding_ranges(&mut this, cx);
+        document_colors::refresh_document_colors(&mut this, cx);
         this._subscriptions.extend(project_subscriptions);
 
         this._subscriptions.push(cx.subscribe_in(
@@ -3312,6 +3360,10 @@ impl Editor {
             return true;
         }
 
+        if self.color_picker.take().is_some() {
+            return true;
+        }
+
         if is_user_requested && self.discard_inline_completion(true, cx) {
             return true;
         }
@@ -4939,7 +4991,13 @@ impl Editor {
         let snippet;
         let new_text;
         if completion.is_snippet() {
-            snippet = Some(Snippet::parse(&completion.new_text).log_err()?);
+            let variables = SnippetVariables::new(self, cx);
+            snippet = Some(
+                Snippet::parse_with_variables(&completion.new_text, &|name| {
+                    variables.resolve(name)
+                })
+                .log_err()?,
+            );
             new_text = snippet.as_ref().unwrap().text.clone();
         } else {
             snippet = None;
@@ -8346,6 +8404,7 @@ impl Editor {
             is_end_tabstop: bool,
             ranges: Vec<Range<T>>,
             choices: Option<Vec<String>>,
+            transformed_ranges: Vec<TransformedRange>,
         }
 
         let tabstops = self.buffer.update(cx, |buffer, cx| {
@@ -8385,10 +8444,50 @@ impl Editor {
                         .collect::<Vec<_>>();
                     tabstop_ranges.sort_unstable_by(|a, b| a.start.cmp(&b.start, snapshot));
 
+                    // Transformed ranges are replaced as a whole, so text typed right
+                    // before them shouldn't end up inside them. Each one transforms the
+                    // tabstop's text inserted at the same selection.
+                    let mut transformed_ranges = tabstop
+                        .ranges
+                        .first()
+                        .into_iter()
+                        .flat_map(|source_range| {
+                            tabstop.transformed_ranges.iter().flat_map(
+                                move |(transformed_range, transform)| {
+                                    let mut delta = 0_isize;
+                                    insertion_ranges.iter().map(move |insertion_range| {
+                                        let insertion_start =
+                                            insertion_range.start as isize + delta;
+                                        delta += snippet.text.len() as isize
+                                            - insertion_range.len() as isize;
+
+                                        let offset = |snippet_offset: isize| {
+                                            ((insertion_start + snippet_offset) as usize)
+                                                .min(snapshot.len())
+                                        };
+                                        TransformedRange {
+                                            range: snapshot
+                                                .anchor_after(offset(transformed_range.start))
+                                                ..snapshot
+                                                    .anchor_after(offset(transformed_range.end)),
+                                            source: snapshot
+                                                .anchor_before(offset(source_range.start))
+                                                ..snapshot.anchor_after(offset(source_range.end)),
+                                            transform: transform.clone(),
+                                        }
+                                    })
+                                },
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    transformed_ranges
+                        .sort_unstable_by(|a, b| a.range.start.cmp(&b.range.start, snapshot));
+
                     Tabstop {
                         is_end_tabstop,
                         ranges: tabstop_ranges,
                         choices: tabstop.choices.clone(),
+                        transformed_ranges,
                     }
                 })
                 .collect::<Vec<_>>()
@@ -8404,24 +8503,31 @@ impl Editor {
                 }
             }
 
+            let is_end_tabstop = tabstop.is_end_tabstop;
+            let choices = tabstops
+                .iter()
+                .map(|tabstop| tabstop.choices.clone())
+                .collect();
+            let mut ranges = Vec::with_capacity(tabstops.len());
+            let mut transformed_ranges = Vec::with_capacity(tabstops.len());
+            for tabstop in tabstops {
+                ranges.push(tabstop.ranges);
+                transformed_ranges.push(tabstop.transformed_ranges);
+            }
+            let mut snippet_state = SnippetState {
+                active_index: 0,
+                ranges,
+                choices,
+                transformed_ranges,
+            };
+            for index in 0..snippet_state.ranges.len() {
+                self.apply_snippet_transforms(&mut snippet_state, index, cx);
+            }
+
             // If we're already at the last tabstop and it's at the end of the snippet,
             // we're done, we don't need to keep the state around.
-            if !tabstop.is_end_tabstop {
-                let choices = tabstops
-                    .iter()
-                    .map(|tabstop| tabstop.choices.clone())
-                    .collect();
-
-                let ranges = tabstops
-                    .into_iter()
-                    .map(|tabstop| tabstop.ranges)
-                    .collect::<Vec<_>>();
-
-                self.snippet_stack.push(SnippetState {
-                    active_index: 0,
-                    ranges,
-                    choices,
-                });
+            if !is_end_tabstop {
+                self.snippet_stack.push(snippet_state);
             }
 
             // Check whether the just-entered snippet ends with an auto-closable bracket.
@@ -8491,6 +8597,7 @@ impl Editor {
         cx: &mut Context<Self>,
     ) -> bool {
         if let Some(mut snippet) = self.snippet_stack.pop() {
+            let previous_index = snippet.active_index;
             match bias {
                 Bias::Left => {
                     if snippet.active_index > 0 {
@@ -8509,6 +8616,7 @@ impl Editor {
                     }
                 }
             }
+            self.apply_snippet_transforms(&mut snippet, previous_index, cx);
             if let Some(current_ranges) = snippet.ranges.get(snippet.active_index) {
                 self.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
                     s.select_anchor_ranges(current_ranges.iter().cloned())
@@ -8531,6 +8639,47 @@ impl Editor {
         false
     }
 
+    /// Replaces the transformed ranges of the snippet's tabstop with its current
+    /// text, transformed.
+    fn apply_snippet_transforms(
+        &mut self,
+        snippet: &mut SnippetState,
+        tabstop_index: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(transformed_ranges) = snippet.transformed_ranges.get_mut(tabstop_index) else {
+            return;
+        };
+        if transformed_ranges.is_empty() {
+            return;
+        }
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let edits = transformed_ranges
+            .iter()
+            .map(|transformed| {
+                let text = snapshot
+                    .text_for_range(transformed.source.clone())
+                    .collect::<String>();
+                let range = transformed.range.start.to_offset(&snapshot)
+                    ..transformed.range.end.to_offset(&snapshot);
+                (range, transformed.transform.apply(&text))
+            })
+            .collect::<Vec<_>>();
+        self.buffer.update(cx, |buffer, cx| {
+            buffer.edit(edits.iter().cloned(), None, cx);
+        });
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut delta = 0_isize;
+        for (transformed, (old_range, new_text)) in transformed_ranges.iter_mut().zip(&edits) {
+            let start = (old_range.start as isize + delta) as usize;
+            transformed.range =
+                snapshot.anchor_after(start)..snapshot.anchor_after(start + new_text.len());
+            delta += new_text.len() as isize - old_range.len() as isize;
+        }
+    }
+
     pub fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.transact(window, cx, |this, window, cx| {
             this.select_all(&SelectAll, window, cx);
@@ -12935,9 +13084,9 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(visible_row_count) = self.visible_row_count() else {
+        if self.visible_row_count().is_none() {
             return;
-        };
+        }
         let old_selections: Box<[_]> = self.selections.all::<usize>(cx).into();
         if old_selections.is_empty() {
             return;
@@ -13030,10 +13179,150 @@ impl Editor {
             })
             .collect::<Vec<_>>();
 
+        let prefers_language_server = buffer
+            .language_settings_at(old_selections[old_selections.len() - 1].head(), cx)
+            .selection_ranges
+            == SelectionRangesSource::LanguageServer;
+        if self.semantics_provider.is_some() && (prefers_language_server || !selected_larger_node) {
+            self.selection_ranges_task = Some(self.select_larger_selection_range(
+                old_selections,
+                new_selections,
+                window,
+                cx,
+            ));
+            return;
+        }
+
         if !selected_larger_node {
             return; // don't put this call in the history
         }
 
+        self.finish_select_larger_syntax_node(old_selections, new_selections, window, cx);
+    }
+
+    /// Expands each selection to the innermost range of its language server's selection
+    /// ranges that contains it, keeping the given expansion of the selections that the
+    /// language servers can't expand any further.
+    fn select_larger_selection_range(
+        &mut self,
+        old_selections: Box<[Selection<usize>]>,
+        mut new_selections: Vec<Selection<usize>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let Some(provider) = self.semantics_provider.clone() else {
+            return Task::ready(());
+        };
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut selections_by_buffer = HashMap::default();
+        for (ix, selection) in old_selections.iter().enumerate() {
+            let start = snapshot.anchor_before(selection.start);
+            let end = snapshot.anchor_after(selection.end);
+            if start.excerpt_id != end.excerpt_id {
+                continue;
+            }
+            let Some(buffer) = start
+                .buffer_id
+                .and_then(|buffer_id| multi_buffer.buffer(buffer_id))
+            else {
+                continue;
+            };
+            selections_by_buffer
+                .entry(buffer.read(cx).remote_id())
+                .or_insert_with(|| (buffer, Vec::new()))
+                .1
+                .push((ix, start.excerpt_id, start.text_anchor));
+        }
+        let tasks = selections_by_buffer
+            .into_values()
+            .filter_map(|(buffer, selections)| {
+                let positions = selections
+                    .iter()
+                    .map(|(_, _, position)| *position)
+                    .collect();
+                let task = provider.selection_ranges(&buffer, positions, cx)?;
+                Some((selections, task))
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let mut selection_ranges = Vec::new();
+            for (selections, task) in tasks {
+                let Some(ranges) = task.await.log_err() else {
+                    continue;
+                };
+                selection_ranges.extend(
+                    selections
+                        .into_iter()
+                        .zip(ranges)
+                        .map(|((ix, excerpt_id, _), ranges)| (ix, excerpt_id, ranges)),
+                );
+            }
+
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    // The user may have moved on while the language servers were queried.
+                    let current_selections = editor.selections.all::<usize>(cx);
+                    if current_selections.len() != old_selections.len()
+                        || current_selections
+                            .iter()
+                            .zip(old_selections.iter())
+                            .any(|(current, old)| current.range() != old.range())
+                    {
+                        return;
+                    }
+
+                    let snapshot = editor.buffer.read(cx).snapshot(cx);
+                    for (ix, excerpt_id, ranges) in selection_ranges {
+                        let old_range = old_selections[ix].range();
+                        let larger_range = ranges.into_iter().find_map(|range| {
+                            let start = snapshot
+                                .anchor_in_excerpt(excerpt_id, range.start)?
+                                .to_offset(&snapshot);
+                            let end = snapshot
+                                .anchor_in_excerpt(excerpt_id, range.end)?
+                                .to_offset(&snapshot);
+                            (start <= old_range.start
+                                && end >= old_range.end
+                                && (start..end) != old_range)
+                                .then_some(start..end)
+                        });
+                        if let Some(range) = larger_range {
+                            new_selections[ix].start = range.start;
+                            new_selections[ix].end = range.end;
+                        }
+                    }
+
+                    if new_selections
+                        .iter()
+                        .zip(old_selections.iter())
+                        .any(|(new, old)| new.range() != old.range())
+                    {
+                        editor.finish_select_larger_syntax_node(
+                            old_selections,
+                            new_selections,
+                            window,
+                            cx,
+                        );
+                    }
+                })
+                .ok();
+        })
+    }
+
+    fn finish_select_larger_syntax_node(
+        &mut self,
+        old_selections: Box<[Selection<usize>]>,
+        mut new_selections: Vec<Selection<usize>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(visible_row_count) = self.visible_row_count() else {
+            return;
+        };
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+
         // scroll based on transformation done to the last selection created by the user
         let (last_old, last_new) = old_selections
             .last()
@@ -13048,13 +13337,11 @@ impl Editor {
             should_newest_selection_be_reversed
         };
 
-        if selected_larger_node {
-            self.select_syntax_node_history.disable_clearing = true;
-            self.change_selections(None, window, cx, |s| {
-                s.select(new_selections.clone());
-            });
-            self.select_syntax_node_history.disable_clearing = false;
-        }
+        self.select_syntax_node_history.disable_clearing = true;
+        self.change_selections(None, window, cx, |s| {
+            s.select(new_selections.clone());
+        });
+        self.select_syntax_node_history.disable_clearing = false;
 
         let start_row = last_new.start.to_display_point(&display_map).row().0;
         let end_row = last_new.end.to_display_point(&display_map).row().0;
@@ -15467,6 +15754,26 @@ impl Editor {
         }
     }
 
+    pub fn fold_all_comments(
+        &mut self,
+        _: &actions::FoldAllComments,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let creases = folding_ranges::creases_of_kind(self, &lsp::FoldingRangeKind::Comment, cx);
+        self.fold_creases(creases, true, window, cx);
+    }
+
+    pub fn fold_all_imports(
+        &mut self,
+        _: &actions::FoldAllImports,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let creases = folding_ranges::creases_of_kind(self, &lsp::FoldingRangeKind::Imports, cx);
+        self.fold_creases(creases, true, window, cx);
+    }
+
     pub fn fold_function_bodies(
         &mut self,
         _: &actions::FoldFunctionBodies,
@@ -17809,6 +18116,15 @@ impl Editor {
                     (telemetry, is_via_ssh)
                 };
                 refresh_linked_ranges(self, window, cx);
+                semantic_tokens::refresh_semantic_tokens(
+                    self,
+                    buffer_edited
+                        .as_ref()
+                        .map(|buffer| buffer.read(cx).remote_id()),
+                    cx,
+                );
+                folding_ranges::refresh_folding_ranges(self, cx);
+                document_colors::refresh_document_colors(self, cx);
                 telemetry.log_edit_event("editor", is_via_ssh);
             }
             multi_buffer::Event::ExcerptsAdded {
@@ -17835,12 +18151,18 @@ impl Editor {
                     excerpts: excerpts.clone(),
                 });
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                semantic_tokens::refresh_semantic_tokens(self, None, cx);
+                folding_ranges::refresh_folding_ranges(self, cx);
+                document_colors::refresh_document_colors(self, cx);
             }
             multi_buffer::Event::ExcerptsRemoved {
                 ids,
                 removed_buffer_ids,
             } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::ExcerptsRemoved(ids.clone()), cx);
+                semantic_tokens::refresh_semantic_tokens(self, None, cx);
+                folding_ranges::refresh_folding_ranges(self, cx);
+                document_colors::refresh_document_colors(self, cx);
                 let buffer = self.buffer.read(cx);
                 self.registered_buffers
                     .retain(|buffer_id, _| buffer.buffer(*buffer_id).is_some());
@@ -17863,6 +18185,8 @@ impl Editor {
             }
             multi_buffer::Event::ExcerptsExpanded { ids } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                semantic_tokens::update_semantic_token_highlights(self, cx);
+                document_colors::update_color_swatches(self, cx);
                 cx.emit(EditorEvent::ExcerptsExpanded { ids: ids.clone() })
             }
             multi_buffer::Event::Reparsed(buffer_id) => {
@@ -17876,6 +18200,9 @@ impl Editor {
             }
             multi_buffer::Event::LanguageChanged(buffer_id) => {
                 linked_editing_ranges::refresh_linked_ranges(self, window, cx);
+                semantic_tokens::refresh_semantic_tokens(self, None, cx);
+                folding_ranges::refresh_folding_ranges(self, cx);
+                document_colors::refresh_document_colors(self, cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
                 cx.notify();
@@ -17932,6 +18259,14 @@ impl Editor {
             cx.emit(EditorEvent::CursorShapeChanged);
         }
 
+        // The theme may have changed, so restyle the current tokens before refetching them.
+        if !self.semantic_tokens.is_empty() {
+            semantic_tokens::update_semantic_token_highlights(self, cx);
+        }
+        semantic_tokens::refresh_semantic_tokens(self, None, cx);
+        folding_ranges::refresh_folding_ranges(self, cx);
+        document_colors::refresh_document_colors(self, cx);
+
         let project_settings = ProjectSettings::get_global(cx);
         self.serialize_dirty_buffers = project_settings.session.restore_unsaved_buffers;
 
@@ -19298,6 +19633,52 @@ pub trait SemanticsProvider {
 
     fn supports_inlay_hints(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
 
+    fn semantic_tokens(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<text::Anchor>>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::SemanticToken>>>>;
+
+    fn folding_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::FoldingRange>>>>;
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>>;
+
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::DocumentLink>>>>;
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: project::DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<project::DocumentLink>>>;
+
+    fn document_colors(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::DocumentColor>>>>;
+
+    fn color_presentations(
+        &self,
+        buffer: &Entity<Buffer>,
+        color: project::DocumentColor,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::ColorPresentation>>>>;
+
     fn document_highlights(
         &self,
         buffer: &Entity<Buffer>,
@@ -19776,6 +20157,72 @@ impl SemanticsProvider for Entity<Project> {
         }))
     }
 
+    fn semantic_tokens(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<text::Anchor>>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::SemanticToken>>>> {
+        Some(self.update(cx, |project, cx| project.semantic_tokens(buffer, range, cx)))
+    }
+
+    fn folding_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::FoldingRange>>>> {
+        Some(self.update(cx, |project, cx| project.folding_ranges(buffer, cx)))
+    }
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.selection_ranges(buffer, positions, cx)
+        }))
+    }
+
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::DocumentLink>>>> {
+        Some(self.update(cx, |project, cx| project.document_links(buffer, cx)))
+    }
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: project::DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<project::DocumentLink>>> {
+        Some(self.update(cx, |project, cx| {
+            project.resolve_document_link(buffer, link, cx)
+        }))
+    }
+
+    fn document_colors(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::DocumentColor>>>> {
+        Some(self.update(cx, |project, cx| project.document_colors(buffer, cx)))
+    }
+
+    fn color_presentations(
+        &self,
+        buffer: &Entity<Buffer>,
+        color: project::DocumentColor,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<project::ColorPresentation>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.color_presentations(buffer, color, cx)
+        }))
+    }
+
     fn resolve_inlay_hint(
         &self,
         hint: InlayHint,
@@ -20140,7 +20587,7 @@ impl EditorSnapshot {
             }
         }
 
-        is_foldable |= self.starts_indent(buffer_row);
+        is_foldable |= self.starts_fold(buffer_row);
 
         if folded || (is_foldable && (row_contains_cursor || self.gutter_hovered)) {
             Some(
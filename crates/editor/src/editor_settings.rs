@@ -15,7 +15,9 @@ pub struct EditorSettings {
     pub hover_popover_delay: u64,
     pub toolbar: Toolbar,
     pub scrollbar: Scrollbar,
+    pub minimap: Minimap,
     pub gutter: Gutter,
+    pub sticky_scroll: StickyScroll,
     pub scroll_beyond_last_line: ScrollBeyondLastLine,
     pub vertical_scroll_margin: f32,
     pub autoscroll_on_clicks: bool,
@@ -96,6 +98,18 @@ pub struct JupyterContent {
     pub enabled: Option<bool>,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct StickyScroll {
+    /// Whether to pin the first lines of the scopes enclosing the top of the editor.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// The maximum number of nested scopes to pin.
+    ///
+    /// Default: 5
+    pub max_depth: usize,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Toolbar {
     pub breadcrumbs: bool,
@@ -141,6 +155,25 @@ pub enum ShowScrollbar {
     Never,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct Minimap {
+    pub show: ShowMinimap,
+}
+
+/// When to show the minimap in the editor.
+///
+/// Default: never
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShowMinimap {
+    /// Always show the minimap, next to the text.
+    Always,
+    /// Show the minimap over the text while the mouse hovers over it.
+    OnHover,
+    /// Never show the minimap.
+    Never,
+}
+
 /// Forcefully enable or disable the scrollbar for each axis
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -300,8 +333,12 @@ pub struct EditorSettingsContent {
     pub toolbar: Option<ToolbarContent>,
     /// Scrollbar related settings
     pub scrollbar: Option<ScrollbarContent>,
+    /// Minimap related settings
+    pub minimap: Option<MinimapContent>,
     /// Gutter related settings
     pub gutter: Option<GutterContent>,
+    /// Sticky scroll related settings
+    pub sticky_scroll: Option<StickyScrollContent>,
     /// Whether the editor will scroll beyond the last line.
     ///
     /// Default: one_page
@@ -456,6 +493,28 @@ pub struct ScrollbarAxesContent {
     vertical: Option<bool>,
 }
 
+/// Minimap related settings
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct MinimapContent {
+    /// When to show the minimap in the editor.
+    ///
+    /// Default: never
+    pub show: Option<ShowMinimap>,
+}
+
+/// Sticky scroll related settings
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct StickyScrollContent {
+    /// Whether to pin the first lines of the scopes enclosing the top of the editor.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The maximum number of nested scopes to pin.
+    ///
+    /// Default: 5
+    pub max_depth: Option<usize>,
+}
+
 /// Gutter related settings
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct GutterContent {
@@ -567,6 +626,27 @@ impl Settings for EditorSettings {
                 current.gutter = Some(gutter)
             }
         }
+        if let Some(enabled) = vscode.read_bool("editor.minimap.enabled") {
+            let show = if !enabled {
+                ShowMinimap::Never
+            } else if vscode.read_bool("editor.minimap.autohide") == Some(true) {
+                ShowMinimap::OnHover
+            } else {
+                ShowMinimap::Always
+            };
+            current.minimap = Some(MinimapContent { show: Some(show) });
+        }
+
+        let mut sticky_scroll = StickyScrollContent::default();
+        vscode.bool_setting("editor.stickyScroll.enabled", &mut sticky_scroll.enabled);
+        vscode.usize_setting(
+            "editor.stickyScroll.maxLineCount",
+            &mut sticky_scroll.max_depth,
+        );
+        if sticky_scroll != StickyScrollContent::default() {
+            current.sticky_scroll = Some(sticky_scroll);
+        }
+
         if let Some(b) = vscode.read_bool("editor.scrollBeyondLastLine") {
             current.scroll_beyond_last_line = Some(if b {
                 ScrollBeyondLastLine::OnePage
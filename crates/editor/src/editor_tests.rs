@@ -6585,6 +6585,72 @@ async fn test_select_larger_smaller_syntax_node_for_string(cx: &mut TestAppConte
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_smaller_syntax_node_with_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new(
+        Language::new(
+            LanguageConfig {
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["txt".into()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        ),
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.lsp
+        .set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(|_, _| async move {
+            let range = |start, end| {
+                lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, end))
+            };
+            Ok(Some(vec![lsp::SelectionRange {
+                range: range(9, 14),
+                parent: Some(Box::new(lsp::SelectionRange {
+                    range: range(4, 15),
+                    parent: Some(Box::new(lsp::SelectionRange {
+                        range: range(0, 20),
+                        parent: None,
+                    })),
+                })),
+            }]))
+        });
+
+    cx.set_state("one (two ˇthree) four");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state("one (two «threeˇ») four");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state("one «ˇ(two three)» four");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state("«ˇone (two three) four»");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+    });
+    cx.assert_editor_state("one «ˇ(two three)» four");
+}
+
 #[gpui::test]
 async fn test_fold_function_bodies(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -8058,6 +8124,94 @@ async fn test_snippets(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_snippet_transforms(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let buffer = cx.update(|cx| MultiBuffer::build_simple("", cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| build_editor(buffer, window, cx));
+
+    editor.update_in(cx, |editor, window, cx| {
+        let snippet =
+            Snippet::parse("let ${1:name} = ${1/(.*)/${1:/pascalcase}/}::new();$0").unwrap();
+        editor.insert_snippet(&[0..0], snippet, window, cx).unwrap();
+        assert_eq!(editor.text(cx), "let name = Name::new();");
+
+        // The transform is only applied when leaving the tabstop.
+        editor.handle_input("my_widget", window, cx);
+        assert_eq!(editor.text(cx), "let my_widget = Name::new();");
+        editor.move_to_next_snippet_tabstop(window, cx);
+        assert_eq!(editor.text(cx), "let my_widget = MyWidget::new();");
+        assert_eq!(editor.selections.ranges::<usize>(cx), [32..32],);
+    });
+}
+
+#[gpui::test]
+async fn test_snippet_transforms_per_selection(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let buffer = cx.update(|cx| MultiBuffer::build_simple("\n", cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| build_editor(buffer, window, cx));
+
+    editor.update_in(cx, |editor, window, cx| {
+        let snippet = Snippet::parse("let ${1:name} = ${1/(.*)/${1:/pascalcase}/};").unwrap();
+        editor
+            .insert_snippet(&[0..0, 1..1], snippet, window, cx)
+            .unwrap();
+        assert_eq!(editor.text(cx), "let name = Name;\nlet name = Name;");
+
+        // Each transform uses the tabstop's text at its own selection.
+        editor.change_selections(None, window, cx, |s| s.select_ranges([4..8]));
+        editor.handle_input("my_widget", window, cx);
+        editor.move_to_next_snippet_tabstop(window, cx);
+        assert_eq!(
+            editor.text(cx),
+            "let my_widget = MyWidget;\nlet name = Name;"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_snippet_variables(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "main.rs": "" }))
+        .await;
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        build_editor_with_project(project.clone(), buffer, window, cx)
+    });
+    cx.write_to_clipboard(ClipboardItem::new_string("copied".into()));
+
+    editor.update_in(cx, |editor, window, cx| {
+        let variables = SnippetVariables::new(editor, cx);
+        let snippet = Snippet::parse_with_variables(
+            "// $TM_FILENAME_BASE in $WORKSPACE_NAME, line $TM_LINE_NUMBER: $CLIPBOARD$0",
+            &|name| variables.resolve(name),
+        )
+        .unwrap();
+        editor.insert_snippet(&[0..0], snippet, window, cx).unwrap();
+        assert_eq!(editor.text(cx), "// main in dir, line 1: copied");
+
+        let variables = SnippetVariables::new(editor, cx);
+        assert_eq!(variables.resolve("TM_FILENAME").as_deref(), Some("main.rs"));
+        assert_eq!(
+            variables.resolve("RELATIVE_FILEPATH").as_deref(),
+            Some("main.rs")
+        );
+        assert_eq!(variables.resolve("CLIPBOARD").as_deref(), Some("copied"));
+        assert_eq!(variables.resolve("UNKNOWN"), None);
+    });
+}
+
 #[gpui::test]
 async fn test_document_format_during_save(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -18874,6 +19028,102 @@ async fn test_rename_without_prepare(cx: &mut TestAppContext) {
     "});
 }
 
+#[gpui::test]
+async fn test_color_picker(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            color_provider: Some(lsp::ColorProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    let color_range = lsp::Range::new(lsp::Position::new(0, 11), lsp::Position::new(0, 18));
+    let red = lsp::Color {
+        red: 1.,
+        green: 0.,
+        blue: 0.,
+        alpha: 1.,
+    };
+    let green = lsp::Color {
+        red: 0.,
+        green: 1.,
+        blue: 0.,
+        alpha: 1.,
+    };
+    cx.set_request_handler::<lsp::request::DocumentColor, _, _>(move |_, _, _| async move {
+        Ok(vec![lsp::ColorInformation {
+            range: color_range,
+            color: red,
+        }])
+    });
+    let requested_colors = Arc::new(Mutex::new(Vec::new()));
+    cx.set_request_handler::<lsp::request::ColorPresentationRequest, _, _>({
+        let requested_colors = requested_colors.clone();
+        move |_, params, _| {
+            requested_colors.lock().push(params.color);
+            let label = format!(
+                "#{:02x}{:02x}{:02x}",
+                (params.color.red * 255.) as u8,
+                (params.color.green * 255.) as u8,
+                (params.color.blue * 255.) as u8,
+            );
+            async move {
+                Ok(vec![lsp::ColorPresentation {
+                    label: label.clone(),
+                    text_edit: Some(lsp::TextEdit::new(params.range, label)),
+                    additional_text_edits: None,
+                }])
+            }
+        }
+    });
+
+    cx.set_state(indoc! {r#"
+        ˇlet red = "#ff0000";
+    "#});
+    cx.executor().advance_clock(Duration::from_millis(200));
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, _| assert_eq!(editor.color_swatches.len(), 1));
+
+    // The swatch is shown before the color, right after the opening quote.
+    let swatch_position = cx.pixel_position_for(DisplayPoint::new(DisplayRow(0), 11));
+    cx.simulate_click(
+        swatch_position + gpui::point(px(2.), px(2.)),
+        gpui::Modifiers::none(),
+    );
+    cx.run_until_parked();
+    let hex_input = cx.update_editor(|editor, _, cx| {
+        let color_picker = editor
+            .color_picker
+            .as_ref()
+            .expect("clicking a swatch should open a color picker");
+        color_picker.picker.read(cx).hex_input.clone()
+    });
+    cx.update(|window, cx| {
+        assert_eq!(hex_input.read(cx).text(cx), "#ff0000");
+        assert!(hex_input.read(cx).is_focused(window));
+    });
+    assert_eq!(requested_colors.lock().as_slice(), &[red]);
+
+    cx.update(|window, cx| {
+        hex_input.update(cx, |hex_input, cx| {
+            hex_input.set_text("#00ff00", window, cx);
+        })
+    });
+    cx.run_until_parked();
+    assert_eq!(requested_colors.lock().as_slice(), &[red, green]);
+
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+    assert_eq!(cx.buffer_text(), "let red = \"#00ff00\";\n");
+    cx.update_editor(|editor, window, _| {
+        assert!(editor.color_picker.is_none());
+        assert!(editor.is_focused(window));
+    });
+}
+
 #[gpui::test]
 async fn test_tree_sitter_brackets_newline_insertion(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -19559,6 +19809,68 @@ async fn test_hide_mouse_context_menu_on_modal_opened(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_sticky_scopes(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_outline_query(
+            r#"
+            (impl_item "impl" @context type: (_) @name) @item
+            (function_item "fn" @context name: (_) @name) @item
+            "#,
+        )
+        .unwrap(),
+    );
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    cx.set_state(indoc! {"
+        ˇimpl Foo {
+            fn first() {
+                a();
+                b();
+                c();
+            }
+
+            fn second() {
+                d();
+            }
+        }
+        fn after() {}
+    "});
+    cx.executor().run_until_parked();
+
+    cx.update_editor(|editor, window, cx| {
+        let snapshot = editor.snapshot(window, cx);
+        let sticky_rows = |scroll_top: f32, max_depth: usize| {
+            snapshot
+                .sticky_scopes(scroll_top, max_depth)
+                .into_iter()
+                .map(|scope| (scope.row.0, scope.end_row.0))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sticky_rows(0., 5), Vec::new(), "no scope was scrolled past");
+        assert_eq!(sticky_rows(1., 5), vec![(0, 10)]);
+        assert_eq!(
+            sticky_rows(1.5, 5),
+            vec![(0, 10)],
+            "the function's first line is still visible below the impl's"
+        );
+        assert_eq!(sticky_rows(2., 5), vec![(0, 10), (1, 5)]);
+        assert_eq!(sticky_rows(4., 5), vec![(0, 10), (1, 5)]);
+        assert_eq!(sticky_rows(4., 1), vec![(0, 10)], "limited by max depth");
+        assert_eq!(sticky_rows(6., 5), vec![(0, 10)]);
+        assert_eq!(sticky_rows(9., 5), vec![(0, 10), (7, 9)]);
+        assert_eq!(sticky_rows(11., 5), Vec::new(), "outside of any scope");
+    });
+}
+
 fn empty_range(row: usize, column: usize) -> Range<DisplayPoint> {
     let point = DisplayPoint::new(DisplayRow(row as u32), column as u32);
     point..point
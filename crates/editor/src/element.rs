@@ -1,21 +1,22 @@
 use crate::{
-    ActiveDiagnostic, BlockId, COLUMNAR_SELECTION_MODIFIERS, CURSORS_VISIBLE_FOR,
-    ChunkRendererContext, ChunkReplacement, ConflictsOurs, ConflictsOursMarker, ConflictsOuter,
-    ConflictsTheirs, ConflictsTheirsMarker, ContextMenuPlacement, CursorShape, CustomBlockId,
-    DisplayDiffHunk, DisplayPoint, DisplayRow, DocumentHighlightRead, DocumentHighlightWrite,
-    EditDisplayMode, Editor, EditorMode, EditorSettings, EditorSnapshot, EditorStyle,
-    FILE_HEADER_HEIGHT, FocusedBlock, GutterDimensions, HalfPageDown, HalfPageUp, HandleInput,
-    HoveredCursor, InlayHintRefreshReason, InlineCompletion, JumpData, LineDown, LineHighlight,
-    LineUp, MAX_LINE_LEN, MIN_LINE_NUMBER_DIGITS, MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts,
-    PageDown, PageUp, PhantomBreakpointIndicator, Point, RowExt, RowRangeExt, SelectPhase,
-    SelectedTextHighlight, Selection, SoftWrap, StickyHeaderExcerpt, ToPoint, ToggleFold,
+    ActiveDiagnostic, BackgroundHighlight, BlockId, COLUMNAR_SELECTION_MODIFIERS,
+    CURSORS_VISIBLE_FOR, ChunkRendererContext, ChunkReplacement, ConflictsOurs,
+    ConflictsOursMarker, ConflictsOuter, ConflictsTheirs, ConflictsTheirsMarker,
+    ContextMenuPlacement, CursorShape, CustomBlockId, DisplayDiffHunk, DisplayPoint, DisplayRow,
+    DocumentHighlightRead, DocumentHighlightWrite, EditDisplayMode, Editor, EditorMode,
+    EditorSettings, EditorSnapshot, EditorStyle, FILE_HEADER_HEIGHT, FocusedBlock,
+    GutterDimensions, HalfPageDown, HalfPageUp, HandleInput, HoveredCursor, InlayHintRefreshReason,
+    InlineCompletion, JumpData, LineDown, LineHighlight, LineUp, MAX_LINE_LEN,
+    MIN_LINE_NUMBER_DIGITS, MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts, PageDown, PageUp,
+    PhantomBreakpointIndicator, Point, RowExt, RowRangeExt, SelectPhase, SelectedTextHighlight,
+    Selection, SoftWrap, StickyHeaderExcerpt, ToPoint, ToggleFold,
     code_context_menus::{CodeActionsMenu, MENU_ASIDE_MAX_WIDTH, MENU_ASIDE_MIN_WIDTH, MENU_GAP},
     display_map::{
         Block, BlockContext, BlockStyle, DisplaySnapshot, FoldId, HighlightedChunk, ToDisplayPoint,
     },
     editor_settings::{
         CurrentLineHighlight, DoubleClickInMultibuffer, MultiCursorModifier, ScrollBeyondLastLine,
-        ScrollbarAxes, ScrollbarDiagnostics, ShowScrollbar,
+        Scrollbar, ScrollbarAxes, ScrollbarDiagnostics, ShowMinimap, ShowScrollbar,
     },
     git::blame::{BlameRenderer, GitBlame, GlobalBlameRenderer},
     hover_popover::{
@@ -38,15 +39,15 @@ use git::{
     status::FileStatus,
 };
 use gpui::{
-    Action, Along, AnyElement, App, AppContext, AvailableSpace, Axis as ScrollbarAxis, BorderStyle,
-    Bounds, ClickEvent, ContentMask, Context, Corner, Corners, CursorStyle, DispatchPhase, Edges,
-    Element, ElementInputHandler, Entity, Focusable as _, FontId, GlobalElementId, Hitbox, Hsla,
-    InteractiveElement, IntoElement, Keystroke, Length, ModifiersChangedEvent, MouseButton,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
-    ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement,
-    Style, Styled, TextRun, TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill,
-    linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
-    transparent_black,
+    Action, Along, AnyElement, AnyView, App, AppContext, AvailableSpace, Axis as ScrollbarAxis,
+    BorderStyle, Bounds, ClickEvent, ContentMask, Context, Corner, Corners, CursorStyle,
+    DispatchPhase, Edges, Element, ElementInputHandler, Entity, Focusable as _, FontId,
+    GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Keystroke, Length,
+    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
+    ParentElement, Pixels, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString,
+    Size, StatefulInteractiveElement, Style, Styled, StyledText, TextRun, TextStyleRefinement,
+    WeakEntity, Window, anchored, deferred, div, fill, linear_color_stop, linear_gradient, outline,
+    point, px, quad, relative, size, solid_background, transparent_black,
 };
 use itertools::Itertools;
 use language::language_settings::{
@@ -76,9 +77,9 @@ use std::{
     sync::Arc,
     time::Duration,
 };
-use sum_tree::Bias;
+use sum_tree::{Bias, TreeMap};
 use text::BufferId;
-use theme::{ActiveTheme, Appearance, BufferLineHeight, PlayerColor};
+use theme::{ActiveTheme, Appearance, BufferLineHeight, PlayerColor, Theme};
 use ui::{ButtonLike, KeyBinding, POPOVER_Y_PADDING, Tooltip, h_flex, prelude::*};
 use unicode_segmentation::UnicodeSegmentation;
 use util::{RangeExt, ResultExt, debug_panic};
@@ -400,6 +401,8 @@ impl EditorElement {
         register_action(editor, window, Editor::fold);
         register_action(editor, window, Editor::fold_at_level);
         register_action(editor, window, Editor::fold_all);
+        register_action(editor, window, Editor::fold_all_comments);
+        register_action(editor, window, Editor::fold_all_imports);
         register_action(editor, window, Editor::fold_function_bodies);
         register_action(editor, window, Editor::fold_recursive);
         register_action(editor, window, Editor::toggle_fold);
@@ -526,6 +529,7 @@ impl EditorElement {
             }
         });
         register_action(editor, window, Editor::show_signature_help);
+        register_action(editor, window, Editor::show_color_presentations);
         register_action(editor, window, Editor::next_edit_prediction);
         register_action(editor, window, Editor::previous_edit_prediction);
         register_action(editor, window, Editor::show_inline_completion);
@@ -666,6 +670,16 @@ impl EditorElement {
         }
 
         let point_for_position = position_map.point_for_position(event.position);
+        if click_count == 1 && !modifiers.modified() {
+            if let Some(swatch) =
+                editor.color_swatch_at(&point_for_position, &position_map.snapshot)
+            {
+                editor.show_color_picker(swatch, Some(event.position), window, cx);
+                cx.stop_propagation();
+                return;
+            }
+        }
+
         let position = point_for_position.previous_valid;
         if modifiers == COLUMNAR_SELECTION_MODIFIERS {
             editor.select(
@@ -1495,6 +1509,145 @@ impl EditorElement {
         ))
     }
 
+    fn layout_minimap(
+        &self,
+        snapshot: &EditorSnapshot,
+        bounds: Bounds<Pixels>,
+        scroll_position: gpui::Point<f32>,
+        height_in_lines: f32,
+        max_scroll_top: f32,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<MinimapLayout> {
+        if !snapshot.mode.is_full() {
+            return None;
+        }
+        let settings = EditorSettings::get_global(cx);
+        let show_minimap = match settings.minimap.show {
+            ShowMinimap::Always => true,
+            ShowMinimap::OnHover => {
+                let scroll_manager = &self.editor.read(cx).scroll_manager;
+                scroll_manager.minimap_hovered() || scroll_manager.is_dragging_minimap()
+            }
+            ShowMinimap::Never => false,
+        };
+        if !show_minimap {
+            return None;
+        }
+
+        let minimap_bounds = MinimapLayout::bounds(bounds, self.style.scrollbar_width);
+        let row_height = MinimapLayout::LINE_HEIGHT;
+        let max_row = snapshot.max_point().row();
+        let rows_in_minimap = minimap_bounds.size.height / row_height;
+        // When the buffer does not fit into the minimap, the minimap scrolls along with
+        // the editor, so that both reach the end of the buffer at the same time.
+        let minimap_scroll_range = (max_row.next_row().as_f32() - rows_in_minimap).max(0.);
+        let (scroll_top, thumb_row_height) = if max_scroll_top > 0. {
+            let minimap_rows_per_row = minimap_scroll_range / max_scroll_top;
+            (
+                (scroll_position.y / max_scroll_top).clamp(0., 1.) * minimap_scroll_range,
+                (row_height * (1. - minimap_rows_per_row)).max(row_height * 0.1),
+            )
+        } else {
+            (0., row_height)
+        };
+
+        let mut minimap = MinimapLayout {
+            hitbox: window.insert_hitbox(minimap_bounds, false),
+            scroll_top,
+            visible_rows: height_in_lines,
+            thumb_row_height,
+            thumb_bounds: Bounds::new(
+                point(
+                    minimap_bounds.origin.x,
+                    minimap_bounds.origin.y + (scroll_position.y - scroll_top) * row_height,
+                ),
+                size(minimap_bounds.size.width, height_in_lines * row_height),
+            ),
+            text_quads: Vec::new(),
+            marker_quads: Vec::new(),
+        };
+
+        // Only the rows that are visible in the minimap are laid out, so that large
+        // buffers are as fast to render as small ones.
+        let start_row = DisplayRow(scroll_top as u32);
+        let end_row =
+            DisplayRow(((scroll_top + rows_in_minimap).ceil() as u32).min(max_row.next_row().0));
+        let mut text_quads = Vec::new();
+        let mut row = start_row;
+        let mut column = 0;
+        for chunk in snapshot.highlighted_chunks(start_row..end_row, false, &self.style) {
+            let color = chunk
+                .style
+                .and_then(|style| style.color)
+                .unwrap_or(self.style.text.color)
+                .opacity(0.6);
+            if chunk.replacement.is_some() {
+                text_quads.extend(minimap.text_quad(row, column..column + 1, color));
+                column += 1;
+                continue;
+            }
+
+            for (ix, line) in chunk.text.split('\n').enumerate() {
+                if ix > 0 {
+                    row = row.next_row();
+                    column = 0;
+                }
+                let mut word_start = None;
+                for character in line.chars() {
+                    match (character.is_whitespace(), word_start) {
+                        (false, None) => word_start = Some(column),
+                        (true, Some(start)) => {
+                            text_quads.extend(minimap.text_quad(row, start..column, color));
+                            word_start = None;
+                        }
+                        _ => {}
+                    }
+                    column += 1;
+                }
+                if let Some(start) = word_start {
+                    text_quads.extend(minimap.text_quad(row, start..column, color));
+                }
+            }
+        }
+        minimap.text_quads = text_quads;
+
+        if self.editor.read(cx).is_singleton(cx) {
+            let start = DisplayPoint::new(start_row, 0).to_point(&snapshot.display_snapshot);
+            let end = if end_row > max_row {
+                snapshot.buffer_snapshot.max_point()
+            } else {
+                DisplayPoint::new(end_row, 0).to_point(&snapshot.display_snapshot)
+            };
+            let marker_row_ranges = marker_row_ranges(
+                snapshot,
+                start..end,
+                &self.editor.read(cx).background_highlights,
+                cx.theme(),
+                &settings.scrollbar,
+            );
+            minimap.marker_quads = marker_row_ranges
+                .into_iter()
+                .flat_map(|(column, row_ranges)| {
+                    row_ranges
+                        .into_iter()
+                        .map(move |row_range| (column, row_range))
+                })
+                .map(|(column, row_range)| {
+                    // Git diff markers are shown in a strip along the edge of the minimap,
+                    // like in the gutter, while the others are shown behind the text.
+                    if column == 0 {
+                        minimap.marker_quad(row_range, MinimapLayout::MARKER_WIDTH, 1.)
+                    } else {
+                        minimap.marker_quad(row_range, minimap_bounds.size.width, 0.4)
+                    }
+                })
+                .collect();
+        }
+
+        Some(minimap)
+    }
+
     fn prepaint_crease_toggles(
         &self,
         crease_toggles: &mut [Option<AnyElement>],
@@ -3462,6 +3615,133 @@ impl EditorElement {
         header
     }
 
+    fn layout_sticky_scopes(
+        &self,
+        scroll_top: f32,
+        top_offset: Pixels,
+        line_height: Pixels,
+        snapshot: &EditorSnapshot,
+        hitbox: &Hitbox,
+        gutter_hitbox: &Hitbox,
+        gutter_dimensions: &GutterDimensions,
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        let settings = EditorSettings::get_global(cx);
+        if !settings.sticky_scroll.enabled || !snapshot.mode.is_full() {
+            return None;
+        }
+
+        let scopes = snapshot.sticky_scopes(scroll_top, settings.sticky_scroll.max_depth);
+        let last_ix = scopes.len().checked_sub(1)?;
+        let show_line_numbers = snapshot
+            .show_line_numbers
+            .unwrap_or(settings.gutter.line_numbers);
+        let colors = cx.theme().colors();
+        let text_left = content_origin.x - hitbox.origin.x - scroll_pixel_position.x;
+
+        let mut rows = Vec::with_capacity(scopes.len());
+        for (ix, scope) in scopes.into_iter().enumerate() {
+            // Once the end of a scope is scrolled to its pinned line, the line is pushed
+            // up with it instead of covering the code that follows the scope.
+            let top = (ix as f32).min(scope.end_row.as_f32() - scroll_top) * line_height;
+            if top + line_height <= Pixels::ZERO {
+                continue;
+            }
+
+            let mut text = String::new();
+            let mut highlights = Vec::new();
+            for chunk in
+                snapshot.highlighted_chunks(scope.row..scope.row.next_row(), true, &self.style)
+            {
+                let chunk_text = if chunk.replacement.is_some() {
+                    "⋯"
+                } else {
+                    chunk.text.trim_end_matches('\n')
+                };
+                if let Some(style) = chunk.style {
+                    highlights.push((text.len()..text.len() + chunk_text.len(), style));
+                }
+                text.push_str(chunk_text);
+            }
+
+            let line_number = show_line_numbers
+                .then(|| snapshot.row_infos(scope.row).next()?.buffer_row)
+                .flatten();
+
+            let editor = self.editor.clone();
+            rows.push(
+                div()
+                    .id(ix)
+                    .absolute()
+                    .top(top)
+                    .left_0()
+                    .w(hitbox.size.width)
+                    .h(line_height)
+                    .overflow_hidden()
+                    .bg(colors.editor_background)
+                    .when(ix == last_ix, |row| {
+                        row.border_b_1().border_color(colors.border_variant)
+                    })
+                    .occlude()
+                    .cursor_pointer()
+                    .child(
+                        div()
+                            .absolute()
+                            .left(text_left)
+                            .h(line_height)
+                            .whitespace_nowrap()
+                            .child(
+                                StyledText::new(text)
+                                    .with_default_highlights(&self.style.text, highlights),
+                            ),
+                    )
+                    .when(gutter_hitbox.size.width > Pixels::ZERO, |row| {
+                        row.child(
+                            h_flex()
+                                .absolute()
+                                .left_0()
+                                .w(gutter_hitbox.size.width)
+                                .h(line_height)
+                                .justify_end()
+                                .pr(gutter_dimensions.right_padding)
+                                .bg(colors.editor_background)
+                                .text_color(colors.editor_line_number)
+                                .font(self.style.text.font())
+                                .text_size(self.style.text.font_size)
+                                .when_some(line_number, |gutter, line_number| {
+                                    gutter.child((line_number + 1).to_string())
+                                }),
+                        )
+                    })
+                    .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                        cx.stop_propagation();
+                        editor.update(cx, |editor, cx| {
+                            editor.jump_to_sticky_scope(&scope, ix, window, cx);
+                        });
+                    })
+                    .into_any_element(),
+            );
+        }
+
+        // Outer scopes are added last so that they are painted over the inner ones
+        // that are pushed up behind them.
+        let mut sticky_scopes = div()
+            .relative()
+            .w(hitbox.size.width)
+            .children(rows.into_iter().rev())
+            .into_any_element();
+        let origin = point(hitbox.origin.x, hitbox.origin.y + top_offset);
+        let available_space = size(
+            AvailableSpace::Definite(hitbox.size.width),
+            AvailableSpace::MinContent,
+        );
+        sticky_scopes.prepaint_as_root(origin, available_space, window, cx);
+        Some(sticky_scopes)
+    }
+
     fn layout_cursor_popovers(
         &self,
         line_height: Pixels,
@@ -3980,6 +4260,60 @@ impl EditorElement {
         content_origin: gpui::Point<Pixels>,
         window: &mut Window,
         cx: &mut App,
+    ) -> Option<AnyElement> {
+        let (menu_position, context_menu) = self.editor.update(cx, |editor, _| {
+            let mouse_context_menu = editor.mouse_context_menu.as_ref()?;
+            Some((
+                mouse_context_menu.position,
+                AnyView::from(mouse_context_menu.context_menu.clone()),
+            ))
+        })?;
+        self.layout_editor_popover(
+            menu_position,
+            context_menu,
+            editor_snapshot,
+            visible_range,
+            content_origin,
+            window,
+            cx,
+        )
+    }
+
+    fn layout_color_picker(
+        &self,
+        editor_snapshot: &EditorSnapshot,
+        visible_range: Range<DisplayRow>,
+        content_origin: gpui::Point<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        let (menu_position, picker) = self.editor.update(cx, |editor, _| {
+            let color_picker = editor.color_picker.as_ref()?;
+            Some((
+                color_picker.position,
+                AnyView::from(color_picker.picker.clone()),
+            ))
+        })?;
+        self.layout_editor_popover(
+            menu_position,
+            picker,
+            editor_snapshot,
+            visible_range,
+            content_origin,
+            window,
+            cx,
+        )
+    }
+
+    fn layout_editor_popover(
+        &self,
+        menu_position: MenuPosition,
+        popover: AnyView,
+        editor_snapshot: &EditorSnapshot,
+        visible_range: Range<DisplayRow>,
+        content_origin: gpui::Point<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
     ) -> Option<AnyElement> {
         let position = self.editor.update(cx, |editor, _cx| {
             let visible_start_point = editor.display_to_pixel_point(
@@ -3993,8 +4327,7 @@ impl EditorElement {
                 window,
             )?;
 
-            let mouse_context_menu = editor.mouse_context_menu.as_ref()?;
-            let (source_display_point, position) = match mouse_context_menu.position {
+            let (source_display_point, position) = match menu_position {
                 MenuPosition::PinnedToScreen(point) => (None, point),
                 MenuPosition::PinnedToEditor { source, offset } => {
                     let source_display_point = source.to_display_point(editor_snapshot);
@@ -4025,22 +4358,15 @@ impl EditorElement {
             ..Default::default()
         };
         window.with_text_style(Some(text_style), |window| {
-            let mut element = self.editor.update(cx, |editor, _| {
-                let mouse_context_menu = editor.mouse_context_menu.as_ref()?;
-                let context_menu = mouse_context_menu.context_menu.clone();
-
-                Some(
-                    deferred(
-                        anchored()
-                            .position(position)
-                            .child(context_menu)
-                            .anchor(Corner::TopLeft)
-                            .snap_to_window_with_margin(px(8.)),
-                    )
-                    .with_priority(1)
-                    .into_any(),
-                )
-            })?;
+            let mut element = deferred(
+                anchored()
+                    .position(position)
+                    .child(popover)
+                    .anchor(Corner::TopLeft)
+                    .snap_to_window_with_margin(px(8.)),
+            )
+            .with_priority(1)
+            .into_any();
 
             element.prepaint_as_root(position, AvailableSpace::min_size(), window, cx);
             Some(element)
@@ -5099,6 +5425,152 @@ impl EditorElement {
         }
     }
 
+    fn paint_minimap(&mut self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
+        if layout.mode.is_full()
+            && EditorSettings::get_global(cx).minimap.show == ShowMinimap::OnHover
+        {
+            let minimap_bounds =
+                MinimapLayout::bounds(layout.hitbox.bounds, self.style.scrollbar_width);
+            window.on_mouse_event({
+                let editor = self.editor.clone();
+                move |event: &MouseMoveEvent, phase, _, cx| {
+                    if phase == DispatchPhase::Capture {
+                        return;
+                    }
+                    editor.update(cx, |editor, cx| {
+                        editor
+                            .scroll_manager
+                            .set_minimap_hovered(minimap_bounds.contains(&event.position), cx);
+                    });
+                }
+            });
+        }
+
+        let Some(minimap) = &layout.minimap else {
+            return;
+        };
+
+        let colors = cx.theme().colors();
+        window.paint_layer(minimap.hitbox.bounds, |window| {
+            window.paint_quad(quad(
+                minimap.hitbox.bounds,
+                Corners::default(),
+                colors.editor_background,
+                Edges {
+                    left: ScrollbarLayout::BORDER_WIDTH,
+                    ..Edges::default()
+                },
+                colors.scrollbar_track_border,
+                BorderStyle::Solid,
+            ));
+            window.with_content_mask(
+                Some(ContentMask {
+                    bounds: minimap.hitbox.bounds,
+                }),
+                |window| {
+                    for quad in minimap.marker_quads.iter().chain(&minimap.text_quads) {
+                        window.paint_quad(quad.clone());
+                    }
+
+                    const CURSORS_LIMIT: usize = 100;
+                    if EditorSettings::get_global(cx).scrollbar.cursors
+                        && layout.cursors.len() <= CURSORS_LIMIT
+                    {
+                        for (cursor, color) in &layout.cursors {
+                            window.paint_quad(minimap.marker_quad(
+                                ColoredRange {
+                                    start: cursor.row(),
+                                    end: cursor.row(),
+                                    color: *color,
+                                },
+                                minimap.hitbox.size.width,
+                                1.,
+                            ));
+                        }
+                    }
+
+                    window.paint_quad(fill(
+                        minimap.thumb_bounds,
+                        colors.scrollbar_thumb_background.opacity(0.5),
+                    ));
+                },
+            );
+        });
+        window.set_cursor_style(CursorStyle::Arrow, Some(&minimap.hitbox));
+
+        window.on_mouse_event({
+            let editor = self.editor.clone();
+            let minimap = minimap.clone();
+            move |event: &MouseDownEvent, phase, window, cx| {
+                if phase == DispatchPhase::Capture
+                    || event.button != MouseButton::Left
+                    || !minimap.hitbox.is_hovered(window)
+                {
+                    return;
+                }
+
+                editor.update(cx, |editor, cx| {
+                    editor.scroll_manager.set_dragging_minimap(true, cx);
+                    // Clicking outside of the thumb centers the editor on the clicked row,
+                    // while dragging the thumb moves it along with the mouse.
+                    if !minimap.thumb_bounds.contains(&event.position) {
+                        let row = minimap.row_for_position(event.position.y);
+                        let scroll_position = editor.scroll_position(cx);
+                        editor.set_scroll_position(
+                            point(scroll_position.x, (row - minimap.visible_rows / 2.).max(0.)),
+                            window,
+                            cx,
+                        );
+                    }
+                    cx.stop_propagation();
+                });
+            }
+        });
+
+        if self.editor.read(cx).scroll_manager.is_dragging_minimap() {
+            window.on_mouse_event({
+                let editor = self.editor.clone();
+                let thumb_row_height = minimap.thumb_row_height;
+                let mut mouse_position = window.mouse_position();
+                move |event: &MouseMoveEvent, phase, window, cx| {
+                    if phase == DispatchPhase::Capture {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        if event.pressed_button == Some(MouseButton::Left) {
+                            let scroll_position = editor.scroll_position(cx);
+                            let delta = (event.position.y - mouse_position.y) / thumb_row_height;
+                            editor.set_scroll_position(
+                                point(scroll_position.x, (scroll_position.y + delta).max(0.)),
+                                window,
+                                cx,
+                            );
+                            cx.stop_propagation();
+                        } else {
+                            editor.scroll_manager.set_dragging_minimap(false, cx);
+                        }
+                    });
+                    mouse_position = event.position;
+                }
+            });
+
+            window.on_mouse_event({
+                let editor = self.editor.clone();
+                move |_: &MouseUpEvent, phase, _, cx| {
+                    if phase == DispatchPhase::Capture {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_dragging_minimap(false, cx);
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        }
+    }
+
     fn paint_scrollbars(&mut self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
         let Some(scrollbars_layout) = &layout.scrollbars_layout else {
             return;
@@ -5333,140 +5805,16 @@ impl EditorElement {
                         .background_spawn(async move {
                             let max_point = snapshot.display_snapshot.buffer_snapshot.max_point();
                             let mut marker_quads = Vec::new();
-                            if scrollbar_settings.git_diff {
-                                let marker_row_ranges =
-                                    snapshot.buffer_snapshot.diff_hunks().map(|hunk| {
-                                        let start_display_row =
-                                            MultiBufferPoint::new(hunk.row_range.start.0, 0)
-                                                .to_display_point(&snapshot.display_snapshot)
-                                                .row();
-                                        let mut end_display_row =
-                                            MultiBufferPoint::new(hunk.row_range.end.0, 0)
-                                                .to_display_point(&snapshot.display_snapshot)
-                                                .row();
-                                        if end_display_row != start_display_row {
-                                            end_display_row.0 -= 1;
-                                        }
-                                        let color = match &hunk.status().kind {
-                                            DiffHunkStatusKind::Added => {
-                                                theme.colors().version_control_added
-                                            }
-                                            DiffHunkStatusKind::Modified => {
-                                                theme.colors().version_control_modified
-                                            }
-                                            DiffHunkStatusKind::Deleted => {
-                                                theme.colors().version_control_deleted
-                                            }
-                                        };
-                                        ColoredRange {
-                                            start: start_display_row,
-                                            end: end_display_row,
-                                            color,
-                                        }
-                                    });
-
-                                marker_quads.extend(
-                                    scrollbar_layout
-                                        .marker_quads_for_ranges(marker_row_ranges, Some(0)),
-                                );
-                            }
-
-                            for (background_highlight_id, (_, background_ranges)) in
-                                background_highlights.iter()
-                            {
-                                let is_search_highlights = *background_highlight_id
-                                    == TypeId::of::<BufferSearchHighlights>();
-                                let is_text_highlights = *background_highlight_id
-                                    == TypeId::of::<SelectedTextHighlight>();
-                                let is_symbol_occurrences = *background_highlight_id
-                                    == TypeId::of::<DocumentHighlightRead>()
-                                    || *background_highlight_id
-                                        == TypeId::of::<DocumentHighlightWrite>();
-                                if (is_search_highlights && scrollbar_settings.search_results)
-                                    || (is_text_highlights && scrollbar_settings.selected_text)
-                                    || (is_symbol_occurrences && scrollbar_settings.selected_symbol)
-                                {
-                                    let mut color = theme.status().info;
-                                    if is_symbol_occurrences {
-                                        color.fade_out(0.5);
-                                    }
-                                    let marker_row_ranges = background_ranges.iter().map(|range| {
-                                        let display_start = range
-                                            .start
-                                            .to_display_point(&snapshot.display_snapshot);
-                                        let display_end =
-                                            range.end.to_display_point(&snapshot.display_snapshot);
-                                        ColoredRange {
-                                            start: display_start.row(),
-                                            end: display_end.row(),
-                                            color,
-                                        }
-                                    });
-                                    marker_quads.extend(
-                                        scrollbar_layout
-                                            .marker_quads_for_ranges(marker_row_ranges, Some(1)),
-                                    );
-                                }
-                            }
-
-                            if scrollbar_settings.diagnostics != ScrollbarDiagnostics::None {
-                                let diagnostics = snapshot
-                                    .buffer_snapshot
-                                    .diagnostics_in_range::<Point>(Point::zero()..max_point)
-                                    // Don't show diagnostics the user doesn't care about
-                                    .filter(|diagnostic| {
-                                        match (
-                                            scrollbar_settings.diagnostics,
-                                            diagnostic.diagnostic.severity,
-                                        ) {
-                                            (ScrollbarDiagnostics::All, _) => true,
-                                            (
-                                                ScrollbarDiagnostics::Error,
-                                                DiagnosticSeverity::ERROR,
-                                            ) => true,
-                                            (
-                                                ScrollbarDiagnostics::Warning,
-                                                DiagnosticSeverity::ERROR
-                                                | DiagnosticSeverity::WARNING,
-                                            ) => true,
-                                            (
-                                                ScrollbarDiagnostics::Information,
-                                                DiagnosticSeverity::ERROR
-                                                | DiagnosticSeverity::WARNING
-                                                | DiagnosticSeverity::INFORMATION,
-                                            ) => true,
-                                            (_, _) => false,
-                                        }
-                                    })
-                                    // We want to sort by severity, in order to paint the most severe diagnostics last.
-                                    .sorted_by_key(|diagnostic| {
-                                        std::cmp::Reverse(diagnostic.diagnostic.severity)
-                                    });
-
-                                let marker_row_ranges = diagnostics.into_iter().map(|diagnostic| {
-                                    let start_display = diagnostic
-                                        .range
-                                        .start
-                                        .to_display_point(&snapshot.display_snapshot);
-                                    let end_display = diagnostic
-                                        .range
-                                        .end
-                                        .to_display_point(&snapshot.display_snapshot);
-                                    let color = match diagnostic.diagnostic.severity {
-                                        DiagnosticSeverity::ERROR => theme.status().error,
-                                        DiagnosticSeverity::WARNING => theme.status().warning,
-                                        DiagnosticSeverity::INFORMATION => theme.status().info,
-                                        _ => theme.status().hint,
-                                    };
-                                    ColoredRange {
-                                        start: start_display.row(),
-                                        end: end_display.row(),
-                                        color,
-                                    }
-                                });
+                            for (column, marker_row_ranges) in marker_row_ranges(
+                                &snapshot,
+                                Point::zero()..max_point,
+                                &background_highlights,
+                                &theme,
+                                &scrollbar_settings,
+                            ) {
                                 marker_quads.extend(
                                     scrollbar_layout
-                                        .marker_quads_for_ranges(marker_row_ranges, Some(2)),
+                                        .marker_quads_for_ranges(marker_row_ranges, Some(column)),
                                 );
                             }
 
@@ -5609,6 +5957,12 @@ impl EditorElement {
         }
     }
 
+    fn paint_color_picker(&mut self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
+        if let Some(color_picker) = layout.color_picker.as_mut() {
+            color_picker.paint(window, cx);
+        }
+    }
+
     fn paint_scroll_wheel_listener(
         &mut self,
         layout: &EditorLayout,
@@ -6808,8 +7162,18 @@ impl Element for EditorElement {
                         .unwrap_or_default();
                     let text_width = bounds.size.width - gutter_dimensions.width;
 
-                    let editor_width =
-                        text_width - gutter_dimensions.margin - em_width - style.scrollbar_width;
+                    let minimap_width = if snapshot.mode.is_full()
+                        && EditorSettings::get_global(cx).minimap.show == ShowMinimap::Always
+                    {
+                        MinimapLayout::WIDTH
+                    } else {
+                        Pixels::ZERO
+                    };
+                    let editor_width = text_width
+                        - gutter_dimensions.margin
+                        - em_width
+                        - style.scrollbar_width
+                        - minimap_width;
 
                     snapshot = self.editor.update(cx, |editor, cx| {
                         editor.last_bounds = Some(bounds);
@@ -7324,6 +7688,29 @@ impl Element for EditorElement {
                         scroll_position.y * line_height,
                     );
 
+                    // The sticky buffer header covers the first rows of the editor, so
+                    // sticky scopes are pinned below it.
+                    let sticky_header_rows = if sticky_buffer_header.is_some() {
+                        FILE_HEADER_HEIGHT
+                    } else {
+                        0
+                    };
+                    let sticky_scopes = window.with_element_namespace("sticky_scopes", |window| {
+                        self.layout_sticky_scopes(
+                            scroll_position.y + sticky_header_rows as f32,
+                            sticky_header_rows as f32 * line_height,
+                            line_height,
+                            &snapshot,
+                            &hitbox,
+                            &gutter_hitbox,
+                            &gutter_dimensions,
+                            content_origin,
+                            scroll_pixel_position,
+                            window,
+                            cx,
+                        )
+                    });
+
                     let indent_guides = self.layout_indent_guides(
                         content_origin,
                         text_hitbox.origin,
@@ -7506,6 +7893,16 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let minimap = self.layout_minimap(
+                        &snapshot,
+                        bounds,
+                        scroll_position,
+                        height_in_lines,
+                        max_scroll_top,
+                        window,
+                        cx,
+                    );
+
                     let gutter_settings = EditorSettings::get_global(cx).gutter;
 
                     let mut code_actions_indicator = None;
@@ -7662,6 +8059,13 @@ impl Element for EditorElement {
                         window,
                         cx,
                     );
+                    let color_picker = self.layout_color_picker(
+                        &snapshot,
+                        start_row..end_row,
+                        content_origin,
+                        window,
+                        cx,
+                    );
 
                     window.with_element_namespace("crease_toggles", |window| {
                         self.prepaint_crease_toggles(
@@ -7762,6 +8166,7 @@ impl Element for EditorElement {
                         display_hunks,
                         content_origin,
                         scrollbars_layout,
+                        minimap,
                         active_rows,
                         highlighted_rows,
                         highlighted_ranges,
@@ -7779,6 +8184,7 @@ impl Element for EditorElement {
                         inline_completion_popover,
                         diff_hunk_controls,
                         mouse_context_menu,
+                        color_picker,
                         test_indicators,
                         breakpoints,
                         code_actions_indicator,
@@ -7787,6 +8193,7 @@ impl Element for EditorElement {
                         tab_invisible,
                         space_invisible,
                         sticky_buffer_header,
+                        sticky_scopes,
                         expand_toggles,
                     }
                 })
@@ -7848,15 +8255,23 @@ impl Element for EditorElement {
                         });
                     }
 
+                    window.with_element_namespace("sticky_scopes", |window| {
+                        if let Some(mut sticky_scopes) = layout.sticky_scopes.take() {
+                            sticky_scopes.paint(window, cx)
+                        }
+                    });
+
                     window.with_element_namespace("blocks", |window| {
                         if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
                             sticky_header.paint(window, cx)
                         }
                     });
 
+                    self.paint_minimap(layout, window, cx);
                     self.paint_scrollbars(layout, window, cx);
                     self.paint_inline_completion_popover(layout, window, cx);
                     self.paint_mouse_context_menu(layout, window, cx);
+                    self.paint_color_picker(layout, window, cx);
                 });
             })
         })
@@ -7933,6 +8348,7 @@ pub struct EditorLayout {
     gutter_hitbox: Hitbox,
     content_origin: gpui::Point<Pixels>,
     scrollbars_layout: Option<EditorScrollbars>,
+    minimap: Option<MinimapLayout>,
     mode: EditorMode,
     wrap_guides: SmallVec<[(Pixels, bool); 2]>,
     indent_guides: Option<Vec<IndentGuideLayout>>,
@@ -7961,9 +8377,11 @@ pub struct EditorLayout {
     crease_trailers: Vec<Option<CreaseTrailerLayout>>,
     inline_completion_popover: Option<AnyElement>,
     mouse_context_menu: Option<AnyElement>,
+    color_picker: Option<AnyElement>,
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
     sticky_buffer_header: Option<AnyElement>,
+    sticky_scopes: Option<AnyElement>,
 }
 
 impl EditorLayout {
@@ -7983,6 +8401,140 @@ struct ColoredRange<T> {
     color: Hsla,
 }
 
+/// Returns the display rows of the diff hunks, highlights and diagnostics within
+/// the given range of the buffer that are marked in the scrollbar and the minimap,
+/// along with the scrollbar column that each group of markers is shown in.
+fn marker_row_ranges(
+    snapshot: &EditorSnapshot,
+    range: Range<Point>,
+    background_highlights: &TreeMap<TypeId, BackgroundHighlight>,
+    theme: &Theme,
+    scrollbar_settings: &Scrollbar,
+) -> Vec<(usize, Vec<ColoredRange<DisplayRow>>)> {
+    let buffer_snapshot = &snapshot.display_snapshot.buffer_snapshot;
+    let mut marker_row_ranges = Vec::new();
+    if scrollbar_settings.git_diff {
+        let hunk_row_ranges = buffer_snapshot
+            .diff_hunks_in_range(range.clone())
+            .map(|hunk| {
+                let start_display_row = MultiBufferPoint::new(hunk.row_range.start.0, 0)
+                    .to_display_point(&snapshot.display_snapshot)
+                    .row();
+                let mut end_display_row = MultiBufferPoint::new(hunk.row_range.end.0, 0)
+                    .to_display_point(&snapshot.display_snapshot)
+                    .row();
+                if end_display_row != start_display_row {
+                    end_display_row.0 -= 1;
+                }
+                let color = match &hunk.status().kind {
+                    DiffHunkStatusKind::Added => theme.colors().version_control_added,
+                    DiffHunkStatusKind::Modified => theme.colors().version_control_modified,
+                    DiffHunkStatusKind::Deleted => theme.colors().version_control_deleted,
+                };
+                ColoredRange {
+                    start: start_display_row,
+                    end: end_display_row,
+                    color,
+                }
+            })
+            .collect();
+        marker_row_ranges.push((0, hunk_row_ranges));
+    }
+
+    let search_range =
+        buffer_snapshot.anchor_before(range.start)..buffer_snapshot.anchor_after(range.end);
+    for (background_highlight_id, (_, background_ranges)) in background_highlights.iter() {
+        let is_search_highlights =
+            *background_highlight_id == TypeId::of::<BufferSearchHighlights>();
+        let is_text_highlights = *background_highlight_id == TypeId::of::<SelectedTextHighlight>();
+        let is_symbol_occurrences = *background_highlight_id
+            == TypeId::of::<DocumentHighlightRead>()
+            || *background_highlight_id == TypeId::of::<DocumentHighlightWrite>();
+        if (is_search_highlights && scrollbar_settings.search_results)
+            || (is_text_highlights && scrollbar_settings.selected_text)
+            || (is_symbol_occurrences && scrollbar_settings.selected_symbol)
+        {
+            let mut color = theme.status().info;
+            if is_symbol_occurrences {
+                color.fade_out(0.5);
+            }
+            let start_ix = background_ranges.partition_point(|probe| {
+                probe.end.cmp(&search_range.start, buffer_snapshot).is_lt()
+            });
+            let highlight_row_ranges = background_ranges[start_ix..]
+                .iter()
+                .take_while(|range| range.start.cmp(&search_range.end, buffer_snapshot).is_le())
+                .map(|range| {
+                    let display_start = range.start.to_display_point(&snapshot.display_snapshot);
+                    let display_end = range.end.to_display_point(&snapshot.display_snapshot);
+                    ColoredRange {
+                        start: display_start.row(),
+                        end: display_end.row(),
+                        color,
+                    }
+                })
+                .collect();
+            marker_row_ranges.push((1, highlight_row_ranges));
+        }
+    }
+
+    if scrollbar_settings.diagnostics != ScrollbarDiagnostics::None {
+        let diagnostics = buffer_snapshot
+            .diagnostics_in_range::<Point>(range)
+            // Don't show diagnostics the user doesn't care about
+            .filter(|diagnostic| {
+                match (
+                    scrollbar_settings.diagnostics,
+                    diagnostic.diagnostic.severity,
+                ) {
+                    (ScrollbarDiagnostics::All, _) => true,
+                    (ScrollbarDiagnostics::Error, DiagnosticSeverity::ERROR) => true,
+                    (
+                        ScrollbarDiagnostics::Warning,
+                        DiagnosticSeverity::ERROR | DiagnosticSeverity::WARNING,
+                    ) => true,
+                    (
+                        ScrollbarDiagnostics::Information,
+                        DiagnosticSeverity::ERROR
+                        | DiagnosticSeverity::WARNING
+                        | DiagnosticSeverity::INFORMATION,
+                    ) => true,
+                    (_, _) => false,
+                }
+            })
+            // We want to sort by severity, in order to paint the most severe diagnostics last.
+            .sorted_by_key(|diagnostic| std::cmp::Reverse(diagnostic.diagnostic.severity));
+
+        let diagnostic_row_ranges = diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let start_display = diagnostic
+                    .range
+                    .start
+                    .to_display_point(&snapshot.display_snapshot);
+                let end_display = diagnostic
+                    .range
+                    .end
+                    .to_display_point(&snapshot.display_snapshot);
+                let color = match diagnostic.diagnostic.severity {
+                    DiagnosticSeverity::ERROR => theme.status().error,
+                    DiagnosticSeverity::WARNING => theme.status().warning,
+                    DiagnosticSeverity::INFORMATION => theme.status().info,
+                    _ => theme.status().hint,
+                };
+                ColoredRange {
+                    start: start_display.row(),
+                    end: end_display.row(),
+                    color,
+                }
+            })
+            .collect();
+        marker_row_ranges.push((2, diagnostic_row_ranges));
+    }
+
+    marker_row_ranges
+}
+
 impl Along for ScrollbarAxes {
     type Unit = bool;
 
@@ -8102,6 +8654,80 @@ impl EditorScrollbars {
     }
 }
 
+/// A downscaled view of the buffer shown next to the vertical scrollbar, in which
+/// every display row is a few pixels tall and every character a pixel wide.
+#[derive(Clone)]
+struct MinimapLayout {
+    hitbox: Hitbox,
+    /// The display row shown at the top of the minimap.
+    scroll_top: f32,
+    /// The number of rows visible in the editor.
+    visible_rows: f32,
+    /// How far the thumb moves when the editor scrolls by a row.
+    thumb_row_height: Pixels,
+    thumb_bounds: Bounds<Pixels>,
+    text_quads: Vec<PaintQuad>,
+    marker_quads: Vec<PaintQuad>,
+}
+
+impl MinimapLayout {
+    const WIDTH: Pixels = px(100.0);
+    const LINE_HEIGHT: Pixels = px(2.0);
+    const COLUMN_WIDTH: Pixels = px(1.0);
+    const MARKER_WIDTH: Pixels = px(3.0);
+
+    fn bounds(editor_bounds: Bounds<Pixels>, scrollbar_width: Pixels) -> Bounds<Pixels> {
+        Bounds::from_corner_and_size(
+            Corner::TopRight,
+            editor_bounds.top_right() - point(scrollbar_width, Pixels::ZERO),
+            size(Self::WIDTH, editor_bounds.size.height),
+        )
+    }
+
+    fn row_for_position(&self, y: Pixels) -> f32 {
+        self.scroll_top + (y - self.hitbox.origin.y) / Self::LINE_HEIGHT
+    }
+
+    fn row_top(&self, row: DisplayRow) -> Pixels {
+        self.hitbox.origin.y + (row.as_f32() - self.scroll_top) * Self::LINE_HEIGHT
+    }
+
+    fn text_quad(&self, row: DisplayRow, columns: Range<u32>, color: Hsla) -> Option<PaintQuad> {
+        let left = self.hitbox.origin.x + ScrollbarLayout::BORDER_WIDTH;
+        let start = left + columns.start as f32 * Self::COLUMN_WIDTH;
+        let end = (left + columns.end as f32 * Self::COLUMN_WIDTH).min(self.hitbox.right());
+        if start >= end {
+            return None;
+        }
+        let top = self.row_top(row);
+        Some(fill(
+            Bounds::from_corners(
+                point(start, top),
+                point(end, top + Self::LINE_HEIGHT * 0.75),
+            ),
+            color,
+        ))
+    }
+
+    fn marker_quad(
+        &self,
+        row_range: ColoredRange<DisplayRow>,
+        width: Pixels,
+        opacity: f32,
+    ) -> PaintQuad {
+        fill(
+            Bounds::from_corners(
+                point(self.hitbox.origin.x, self.row_top(row_range.start)),
+                point(
+                    self.hitbox.origin.x + width,
+                    self.row_top(row_range.end) + Self::LINE_HEIGHT,
+                ),
+            ),
+            row_range.color.opacity(opacity),
+        )
+    }
+}
+
 #[derive(Clone)]
 struct ScrollbarLayout {
     hitbox: Hitbox,
@@ -8724,11 +9350,13 @@ mod tests {
     use crate::{
         Editor, MultiBuffer,
         display_map::{BlockPlacement, BlockProperties},
+        editor_settings::MinimapContent,
         editor_tests::{init_test, update_test_language_settings},
     };
     use gpui::{TestAppContext, VisualTestContext};
     use language::language_settings;
     use log::info;
+    use settings::SettingsStore;
     use std::num::NonZeroU32;
     use util::test::sample_text;
 
@@ -8950,6 +9578,68 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_minimap_layout(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.minimap = Some(MinimapContent {
+                        show: Some(ShowMinimap::Always),
+                    });
+                });
+            });
+        });
+
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple(&"fn main() {}\n".repeat(1000), cx);
+            Editor::new(EditorMode::full(), buffer, None, window, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|_, cx| editor.read(cx).style().unwrap().clone());
+        let rows_with_text = |minimap: &MinimapLayout| {
+            minimap
+                .text_quads
+                .iter()
+                .map(|quad| quad.bounds.origin.y)
+                .dedup()
+                .count()
+        };
+
+        let (_, state) = cx.draw(
+            point(px(500.), px(500.)),
+            size(px(500.), px(500.)),
+            |_, _| EditorElement::new(&editor, style.clone()),
+        );
+        let minimap = state.minimap.as_ref().unwrap();
+        assert_eq!(minimap.hitbox.size.width, MinimapLayout::WIDTH);
+        assert_eq!(minimap.scroll_top, 0.);
+        assert_eq!(minimap.thumb_bounds.origin.y, minimap.hitbox.origin.y);
+        assert_eq!(
+            rows_with_text(minimap),
+            250,
+            "only the rows that fit into the minimap are laid out"
+        );
+
+        window
+            .update(cx, |editor, window, cx| {
+                editor.set_scroll_position(point(0., 1000.), window, cx);
+            })
+            .unwrap();
+        let (_, state) = cx.draw(
+            point(px(500.), px(500.)),
+            size(px(500.), px(500.)),
+            |_, _| EditorElement::new(&editor, style),
+        );
+        let minimap = state.minimap.as_ref().unwrap();
+        assert_eq!(
+            minimap.scroll_top, 751.,
+            "the minimap reaches the end of the buffer along with the editor"
+        );
+        assert_eq!(rows_with_text(minimap), 249);
+    }
+
     #[gpui::test]
     fn test_all_invisibles_drawing(cx: &mut TestAppContext) {
         const TAB_SIZE: u32 = 4;
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use collections::{HashMap, HashSet};
+use gpui::Context;
+use language::{
+    Point,
+    language_settings::{FoldingStrategy, language_settings},
+};
+use multi_buffer::{MultiBufferRow, ToPoint as _};
+use util::ResultExt as _;
+
+use crate::{
+    Editor,
+    display_map::{Crease, FoldingSource},
+};
+
+const UPDATE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Fetches the folding ranges of every buffer in the editor that folds by the
+/// language server in its language settings, and hands the folding sources of all
+/// buffers to the display map.
+pub(super) fn refresh_folding_ranges(editor: &mut Editor, cx: &mut Context<Editor>) {
+    if !editor.mode.is_full() {
+        return;
+    }
+
+    let buffers = editor
+        .buffer
+        .read(cx)
+        .all_buffers()
+        .into_iter()
+        .filter(|buffer| folding_strategy(buffer.read(cx), cx) == FoldingStrategy::Lsp)
+        .collect::<Vec<_>>();
+    let buffer_ids = buffers
+        .iter()
+        .map(|buffer| buffer.read(cx).remote_id())
+        .collect::<HashSet<_>>();
+    editor
+        .folding_ranges
+        .retain(|buffer_id, _| buffer_ids.contains(buffer_id));
+    update_folding_sources(editor, cx);
+
+    let Some(provider) = editor.semantics_provider.clone() else {
+        return;
+    };
+    if buffers.is_empty() {
+        editor.folding_ranges_task = None;
+        return;
+    }
+
+    editor.folding_ranges_task = Some(cx.spawn(async move |editor, cx| {
+        cx.background_executor().timer(UPDATE_DEBOUNCE).await;
+
+        let Some(tasks) = cx
+            .update(|cx| {
+                buffers
+                    .iter()
+                    .filter_map(|buffer| {
+                        let task = provider.folding_ranges(buffer, cx)?;
+                        Some((buffer.read(cx).remote_id(), task))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .ok()
+        else {
+            return;
+        };
+
+        let mut folding_ranges = HashMap::default();
+        for (buffer_id, task) in tasks {
+            if let Some(ranges) = task.await.log_err() {
+                folding_ranges.insert(buffer_id, ranges);
+            }
+        }
+
+        editor
+            .update(cx, |editor, cx| {
+                for (buffer_id, ranges) in folding_ranges {
+                    if ranges.is_empty() {
+                        editor.folding_ranges.remove(&buffer_id);
+                    } else {
+                        editor.folding_ranges.insert(buffer_id, ranges.into());
+                    }
+                }
+                update_folding_sources(editor, cx);
+            })
+            .ok();
+    }));
+}
+
+/// Resolves the folding source of every buffer in the editor from its language
+/// settings. Buffers that fold by the language server but have no folding ranges
+/// yet are folded by indentation.
+fn update_folding_sources(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let mut sources = HashMap::default();
+    for buffer in editor.buffer.read(cx).all_buffers() {
+        let buffer = buffer.read(cx);
+        let buffer_id = buffer.remote_id();
+        match folding_strategy(buffer, cx) {
+            FoldingStrategy::Indentation => {}
+            FoldingStrategy::TreeSitter => {
+                sources.insert(buffer_id, FoldingSource::TreeSitter);
+            }
+            FoldingStrategy::Lsp => {
+                if let Some(ranges) = editor.folding_ranges.get(&buffer_id) {
+                    sources.insert(buffer_id, FoldingSource::Lsp(ranges.clone()));
+                }
+            }
+        }
+    }
+
+    editor.display_map.update(cx, |display_map, _| {
+        display_map.set_folding_sources(sources);
+    });
+    cx.notify();
+}
+
+/// Returns creases for the language servers' folding ranges of the given kind in
+/// all of the editor's excerpts.
+pub(super) fn creases_of_kind(
+    editor: &Editor,
+    kind: &lsp::FoldingRangeKind,
+    cx: &mut Context<Editor>,
+) -> Vec<Crease<Point>> {
+    let snapshot = editor.buffer.read(cx).snapshot(cx);
+    let placeholder = editor.display_map.read(cx).fold_placeholder.clone();
+
+    let mut creases = Vec::new();
+    for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+        let Some(ranges) = editor.folding_ranges.get(&buffer.remote_id()) else {
+            continue;
+        };
+        for range in ranges.iter() {
+            if range.kind.as_ref() != Some(kind)
+                || range
+                    .range
+                    .end
+                    .cmp(&excerpt_range.context.start, buffer)
+                    .is_le()
+                || range
+                    .range
+                    .start
+                    .cmp(&excerpt_range.context.end, buffer)
+                    .is_ge()
+            {
+                continue;
+            }
+            let Some((start, end)) = snapshot
+                .anchor_in_excerpt(excerpt_id, range.range.start)
+                .zip(snapshot.anchor_in_excerpt(excerpt_id, range.range.end))
+            else {
+                continue;
+            };
+            let start = start.to_point(&snapshot);
+            let start = Point::new(start.row, snapshot.line_len(MultiBufferRow(start.row)));
+            let end = end.to_point(&snapshot);
+            if end.row > start.row {
+                creases.push(Crease::simple(start..end, placeholder.clone()));
+            }
+        }
+    }
+    creases
+}
+
+fn folding_strategy(buffer: &language::Buffer, cx: &gpui::App) -> FoldingStrategy {
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .folding_strategy
+}
@@ -1,6 +1,7 @@
 use crate::{
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToTypeDefinition, GotoDefinitionKind, InlayId, Navigated, PointForPosition, SelectPhase,
+    SemanticsProvider,
     editor_settings::{GoToDefinitionFallback, MultiCursorModifier},
     hover_popover::{self, InlayHover},
     scroll::ScrollAmount,
@@ -14,7 +15,7 @@ use project::{
     ResolveState, ResolvedPath,
 };
 use settings::Settings;
-use std::ops::Range;
+use std::{ops::Range, rc::Rc};
 use theme::ActiveTheme as _;
 use util::{ResultExt, TryFutureExt as _, maybe};
 
@@ -538,7 +539,29 @@ pub fn show_link_definition(
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, buffer_position, cx.clone()) {
+                    let document_link = match &provider {
+                        Some(provider) => {
+                            find_document_link(
+                                provider,
+                                &buffer,
+                                project.clone(),
+                                buffer_position,
+                                cx,
+                            )
+                            .await
+                        }
+                        None => None,
+                    };
+                    if let Some((link_range, link)) = document_link {
+                        let range = maybe!({
+                            let start = snapshot.anchor_in_excerpt(excerpt_id, link_range.start)?;
+                            let end = snapshot.anchor_in_excerpt(excerpt_id, link_range.end)?;
+                            Some(RangeInEditor::Text(start..end))
+                        });
+                        Some((range, vec![link]))
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, buffer_position, cx.clone())
+                    {
                         this.update(cx, |_, _| {
                             let range = maybe!({
                                 let start =
@@ -658,6 +681,49 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Finds the language server's document link at the given position, resolving its
+/// target if the server didn't report one.
+async fn find_document_link(
+    provider: &Rc<dyn SemanticsProvider>,
+    buffer: &Entity<language::Buffer>,
+    project: Option<Entity<Project>>,
+    position: text::Anchor,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let links = cx
+        .update(|_, cx| provider.document_links(buffer, cx))
+        .ok()??
+        .await
+        .log_err()?;
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot()).ok()?;
+    let link = links.into_iter().find(|link| {
+        link.range.start.cmp(&position, &snapshot).is_le()
+            && link.range.end.cmp(&position, &snapshot).is_ge()
+    })?;
+    let link = if link.target.is_some() {
+        link
+    } else {
+        cx.update(|_, cx| provider.resolve_document_link(buffer, link, cx))
+            .ok()??
+            .await
+            .log_err()?
+    };
+
+    let target = link.target?;
+    let url = lsp::Url::parse(&target).ok()?;
+    if url.scheme() != "file" {
+        return Some((link.range, HoverLink::Url(target)));
+    }
+    let path = url.to_file_path().ok()?;
+    let resolved_path = project?
+        .update(cx, |project, cx| {
+            project.resolve_abs_path(&path.to_string_lossy(), cx)
+        })
+        .ok()?
+        .await?;
+    Some((link.range, HoverLink::File(resolved_path)))
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -1507,6 +1573,57 @@ mod tests {
         assert_eq!(cx.opened_url(), Some("https://zed.dev/releases".into()));
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            // See the ˇdocs for details.
+        "});
+
+        cx.lsp
+            .set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(|_, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: lsp::Range::new(lsp::Position::new(0, 11), lsp::Position::new(0, 15)),
+                    target: None,
+                    tooltip: None,
+                    data: Some(serde_json::json!({ "page": "docs" })),
+                }]))
+            });
+        cx.lsp
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(|link, _| async move {
+                assert_eq!(link.data, Some(serde_json::json!({ "page": "docs" })));
+                Ok(lsp::DocumentLink {
+                    target: Some("https://zed.dev/docs".parse().unwrap()),
+                    ..link
+                })
+            });
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            // See the doˇcs for details.
+        "});
+
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        cx.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            // See the «docsˇ» for details.
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://zed.dev/docs".into()));
+    }
+
     #[gpui::test]
     async fn test_surrounding_filename(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
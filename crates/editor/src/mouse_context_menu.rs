@@ -12,7 +12,7 @@ use std::ops::Range;
 use text::PointUtf16;
 use workspace::OpenInTerminal;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum MenuPosition {
     /// When the editor is scrolled, the context menu stays on the exact
     /// same position on the screen, never disappearing.
@@ -25,6 +25,29 @@ pub enum MenuPosition {
     },
 }
 
+impl MenuPosition {
+    /// Pins the given screen position to the editor, relative to the source position.
+    pub(crate) fn pinned_to_editor(
+        editor: &mut Editor,
+        source: multi_buffer::Anchor,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) -> Option<Self> {
+        let editor_snapshot = editor.snapshot(window, cx);
+        let content_origin = editor.last_bounds?.origin
+            + Point {
+                x: editor.gutter_dimensions.width,
+                y: Pixels(0.0),
+            };
+        let source_position = editor.to_pixel_point(source, &editor_snapshot, window)?;
+        Some(MenuPosition::PinnedToEditor {
+            source,
+            offset: position - (source_position + content_origin),
+        })
+    }
+}
+
 pub struct MouseContextMenu {
     pub(crate) position: MenuPosition,
     pub(crate) context_menu: Entity<ui::ContextMenu>,
@@ -50,17 +73,7 @@ impl MouseContextMenu {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) -> Option<Self> {
-        let editor_snapshot = editor.snapshot(window, cx);
-        let content_origin = editor.last_bounds?.origin
-            + Point {
-                x: editor.gutter_dimensions.width,
-                y: Pixels(0.0),
-            };
-        let source_position = editor.to_pixel_point(source, &editor_snapshot, window)?;
-        let menu_position = MenuPosition::PinnedToEditor {
-            source,
-            offset: position - (source_position + content_origin),
-        };
+        let menu_position = MenuPosition::pinned_to_editor(editor, source, position, window, cx)?;
         return Some(MouseContextMenu::new(
             editor,
             menu_position,
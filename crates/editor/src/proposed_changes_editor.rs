@@ -464,6 +464,66 @@ impl SemanticsProvider for BranchBufferSemanticsProvider {
         None
     }
 
+    fn semantic_tokens(
+        &self,
+        _: &Entity<Buffer>,
+        _: Option<Range<text::Anchor>>,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<project::SemanticToken>>>> {
+        None
+    }
+
+    fn folding_ranges(
+        &self,
+        _: &Entity<Buffer>,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<project::FoldingRange>>>> {
+        None
+    }
+
+    fn selection_ranges(
+        &self,
+        _: &Entity<Buffer>,
+        _: Vec<text::Anchor>,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        None
+    }
+
+    fn document_links(
+        &self,
+        _: &Entity<Buffer>,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<project::DocumentLink>>>> {
+        None
+    }
+
+    fn resolve_document_link(
+        &self,
+        _: &Entity<Buffer>,
+        _: project::DocumentLink,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<project::DocumentLink>>> {
+        None
+    }
+
+    fn document_colors(
+        &self,
+        _: &Entity<Buffer>,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<project::DocumentColor>>>> {
+        None
+    }
+
+    fn color_presentations(
+        &self,
+        _: &Entity<Buffer>,
+        _: project::DocumentColor,
+        _: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<project::ColorPresentation>>>> {
+        None
+    }
+
     fn resolve_inlay_hint(
         &self,
         hint: project::InlayHint,
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use collections::HashSet;
+use gpui::Context;
+use language::language_settings::language_settings;
+use text::BufferId;
+use theme::ActiveTheme as _;
+use util::ResultExt as _;
+
+use crate::Editor;
+
+const UPDATE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Fetches the semantic tokens of the buffers in the editor that have them enabled in
+/// their language settings, either all of them or only the one given, and re-styles the
+/// editor's highlights with them.
+pub(super) fn refresh_semantic_tokens(
+    editor: &mut Editor,
+    for_buffer: Option<BufferId>,
+    cx: &mut Context<Editor>,
+) {
+    if !editor.mode.is_full() {
+        return;
+    }
+    let Some(provider) = editor.semantics_provider.clone() else {
+        return;
+    };
+
+    let buffers = editor
+        .buffer
+        .read(cx)
+        .all_buffers()
+        .into_iter()
+        .filter(|buffer| {
+            let buffer = buffer.read(cx);
+            language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            )
+            .semantic_tokens
+        })
+        .collect::<Vec<_>>();
+    if for_buffer.is_none() {
+        let buffer_ids = buffers
+            .iter()
+            .map(|buffer| buffer.read(cx).remote_id())
+            .collect::<HashSet<_>>();
+        editor
+            .semantic_tokens_tasks
+            .retain(|buffer_id, _| buffer_ids.contains(buffer_id));
+        let tokens_len = editor.semantic_tokens.len();
+        editor
+            .semantic_tokens
+            .retain(|buffer_id, _| buffer_ids.contains(buffer_id));
+        if editor.semantic_tokens.len() != tokens_len {
+            update_semantic_token_highlights(editor, cx);
+        }
+    }
+
+    for buffer in buffers {
+        let buffer_id = buffer.read(cx).remote_id();
+        if for_buffer.is_some_and(|for_buffer| for_buffer != buffer_id) {
+            continue;
+        }
+        let provider = provider.clone();
+        let task = cx.spawn(async move |editor, cx| {
+            cx.background_executor().timer(UPDATE_DEBOUNCE).await;
+
+            let Some(task) = cx
+                .update(|cx| provider.semantic_tokens(&buffer, None, cx))
+                .ok()
+                .flatten()
+            else {
+                return;
+            };
+            let Some(tokens) = task.await.log_err() else {
+                return;
+            };
+
+            editor
+                .update(cx, |editor, cx| {
+                    editor.semantic_tokens.insert(buffer_id, tokens);
+                    update_semantic_token_highlights(editor, cx);
+                })
+                .ok();
+        });
+        editor.semantic_tokens_tasks.insert(buffer_id, task);
+    }
+}
+
+/// Resolves the styles of the editor's semantic tokens with the current theme and
+/// hands them to the display map.
+pub(super) fn update_semantic_token_highlights(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let syntax = cx.theme().syntax().clone();
+    let snapshot = editor.buffer.read(cx).snapshot(cx);
+
+    let mut highlights = Vec::new();
+    for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+        let Some(tokens) = editor.semantic_tokens.get(&buffer.remote_id()) else {
+            continue;
+        };
+        for token in tokens {
+            if token
+                .range
+                .end
+                .cmp(&excerpt_range.context.start, buffer)
+                .is_le()
+                || token
+                    .range
+                    .start
+                    .cmp(&excerpt_range.context.end, buffer)
+                    .is_ge()
+            {
+                continue;
+            }
+            let Some(style) = syntax.semantic_token_style(&token.token_type, &token.modifiers)
+            else {
+                continue;
+            };
+            let Some((start, end)) = snapshot
+                .anchor_in_excerpt(excerpt_id, token.range.start)
+                .zip(snapshot.anchor_in_excerpt(excerpt_id, token.range.end))
+            else {
+                continue;
+            };
+            highlights.push((start..end, style));
+        }
+    }
+    highlights.sort_by(|(a, _), (b, _)| a.start.cmp(&b.start, &snapshot));
+
+    editor.display_map.update(cx, |display_map, _| {
+        display_map.set_semantic_token_highlights(highlights);
+    });
+    cx.notify();
+}
@@ -0,0 +1,183 @@
+use std::{
+    cell::{OnceCell, RefCell},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use chrono::{DateTime, Local};
+use collections::HashMap;
+use gpui::{App, Entity};
+use language::{BufferSnapshot, File, LanguageScope, Point, Selection, ToOffset as _};
+use multi_buffer::MultiBufferSnapshot;
+use project::Worktree;
+use rand::Rng as _;
+use uuid::Uuid;
+
+use crate::Editor;
+
+/// The values of the TextMate variables, such as `$TM_FILENAME` or
+/// `$CURRENT_YEAR`, that a snippet inserted at the newest selection can refer to.
+/// Each variable is computed the first time the snippet refers to it.
+pub(crate) struct SnippetVariables<'a> {
+    editor: &'a Editor,
+    selection: Selection<Point>,
+    snapshot: MultiBufferSnapshot,
+    now: OnceCell<DateTime<Local>>,
+    values: RefCell<HashMap<String, Option<String>>>,
+    cx: &'a App,
+}
+
+impl<'a> SnippetVariables<'a> {
+    pub(crate) fn new(editor: &'a Editor, cx: &'a mut App) -> Self {
+        let selection = editor.selections.newest::<Point>(cx);
+        let snapshot = editor.buffer.read(cx).snapshot(cx);
+        Self {
+            editor,
+            selection,
+            snapshot,
+            now: OnceCell::new(),
+            values: RefCell::default(),
+            cx,
+        }
+    }
+
+    /// Returns the value of the variable, generating a new one for each occurrence
+    /// of the random variables.
+    pub(crate) fn resolve(&self, name: &str) -> Option<String> {
+        match name {
+            "RANDOM" => Some(format!("{:06}", rand::thread_rng().gen_range(0..1_000_000))),
+            "RANDOM_HEX" => Some(format!(
+                "{:06x}",
+                rand::thread_rng().gen_range(0..0x1000000)
+            )),
+            "UUID" => Some(Uuid::new_v4().to_string()),
+            _ => {
+                if let Some(value) = self.values.borrow().get(name) {
+                    return value.clone();
+                }
+                let value = self.compute(name);
+                self.values
+                    .borrow_mut()
+                    .insert(name.to_string(), value.clone());
+                value
+            }
+        }
+    }
+
+    fn compute(&self, name: &str) -> Option<String> {
+        let cx = self.cx;
+        match name {
+            "TM_SELECTED_TEXT" => Some(
+                self.snapshot
+                    .text_for_range(self.selection.range())
+                    .collect(),
+            ),
+            "TM_CURRENT_LINE" => {
+                let (buffer, head) = self.buffer_head()?;
+                let line_start = Point::new(head.row, 0);
+                let line_end = Point::new(head.row, buffer.line_len(head.row));
+                Some(buffer.text_for_range(line_start..line_end).collect())
+            }
+            "TM_CURRENT_WORD" => {
+                let (buffer, head) = self.buffer_head()?;
+                let (word_range, _) = buffer.surrounding_word(head.to_offset(buffer));
+                Some(buffer.text_for_range(word_range).collect())
+            }
+            "TM_LINE_INDEX" => Some(self.buffer_head()?.1.row.to_string()),
+            "TM_LINE_NUMBER" => Some((self.buffer_head()?.1.row + 1).to_string()),
+            "TM_FILEPATH" => Some(self.file_path()?.to_string_lossy().into_owned()),
+            "TM_FILENAME" => Some(self.file()?.file_name(cx).to_string_lossy().into_owned()),
+            "TM_FILENAME_BASE" => {
+                let file = self.file()?;
+                Some(file.path().file_stem()?.to_string_lossy().into_owned())
+            }
+            "TM_DIRECTORY" => {
+                let path = self.file_path()?;
+                Some(path.parent()?.to_string_lossy().into_owned())
+            }
+            "RELATIVE_FILEPATH" => Some(self.file()?.path().to_string_lossy().into_owned()),
+            "WORKSPACE_NAME" => Some(self.worktree()?.read(cx).root_name().to_string()),
+            "WORKSPACE_FOLDER" => Some(
+                self.worktree()?
+                    .read(cx)
+                    .abs_path()
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+            "LINE_COMMENT" => {
+                let scope = self.language_scope()?;
+                let prefix = scope.line_comment_prefixes().first()?;
+                Some(prefix.trim_end().to_string())
+            }
+            "BLOCK_COMMENT_START" => {
+                let scope = self.language_scope()?;
+                let (start, _) = scope.block_comment_delimiters()?;
+                Some(start.trim_end().to_string())
+            }
+            "BLOCK_COMMENT_END" => {
+                let scope = self.language_scope()?;
+                let (_, end) = scope.block_comment_delimiters()?;
+                Some(end.trim_start().to_string())
+            }
+            "CLIPBOARD" => cx.read_from_clipboard()?.text(),
+            "CURSOR_INDEX" => Some("0".to_string()),
+            "CURSOR_NUMBER" => Some("1".to_string()),
+            "CURRENT_SECONDS_UNIX" => Some(self.now().timestamp().to_string()),
+            _ => {
+                let format = match name {
+                    "CURRENT_YEAR" => "%Y",
+                    "CURRENT_YEAR_SHORT" => "%y",
+                    "CURRENT_MONTH" => "%m",
+                    "CURRENT_MONTH_NAME" => "%B",
+                    "CURRENT_MONTH_NAME_SHORT" => "%b",
+                    "CURRENT_DATE" => "%d",
+                    "CURRENT_DAY_NAME" => "%A",
+                    "CURRENT_DAY_NAME_SHORT" => "%a",
+                    "CURRENT_HOUR" => "%H",
+                    "CURRENT_MINUTE" => "%M",
+                    "CURRENT_SECOND" => "%S",
+                    "CURRENT_TIMEZONE_OFFSET" => "%:z",
+                    _ => return None,
+                };
+                Some(self.now().format(format).to_string())
+            }
+        }
+    }
+
+    /// The time that all the date and time variables of the snippet refer to.
+    fn now(&self) -> &DateTime<Local> {
+        self.now.get_or_init(Local::now)
+    }
+
+    fn buffer_head(&self) -> Option<(&BufferSnapshot, Point)> {
+        let (buffer, head, _) = self.snapshot.point_to_buffer_point(self.selection.head())?;
+        Some((buffer, head))
+    }
+
+    fn file(&self) -> Option<&Arc<dyn File>> {
+        self.buffer_head()?.0.file()
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        let file = self.file()?;
+        Some(
+            file.as_local()
+                .map(|file| file.abs_path(self.cx))
+                .unwrap_or_else(|| file.full_path(self.cx)),
+        )
+    }
+
+    fn worktree(&self) -> Option<Entity<Worktree>> {
+        let file = self.file()?;
+        self.editor
+            .project
+            .as_ref()?
+            .read(self.cx)
+            .worktree_for_id(file.worktree_id(self.cx), self.cx)
+    }
+
+    fn language_scope(&self) -> Option<LanguageScope> {
+        let (buffer, head) = self.buffer_head()?;
+        buffer.language_scope_at(head)
+    }
+}
@@ -0,0 +1,92 @@
+use gpui::{Context, Window};
+use multi_buffer::Anchor;
+use text::Bias;
+
+use crate::{
+    DisplayPoint, Editor, EditorSnapshot, RowExt as _,
+    display_map::{DisplayRow, ToDisplayPoint as _},
+    scroll::Autoscroll,
+};
+
+/// An outline item whose first line stays pinned to the top of the editor while
+/// the rest of it is scrolled through.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StickyScope {
+    /// Where the item starts.
+    pub start: Anchor,
+    /// The display row of the item's first line.
+    pub row: DisplayRow,
+    /// The display row of the item's last line.
+    pub end_row: DisplayRow,
+}
+
+impl EditorSnapshot {
+    /// Returns the outline items that enclose the row at the top of the editor, from
+    /// the outermost to the innermost, whose first lines have been scrolled past.
+    ///
+    /// In multibuffers, only the items in the excerpt at the top of the editor are
+    /// considered.
+    pub fn sticky_scopes(&self, scroll_top: f32, max_depth: usize) -> Vec<StickyScope> {
+        let mut scopes = Vec::new();
+        if max_depth == 0 {
+            return scopes;
+        }
+
+        let top_row = DisplayRow(scroll_top.max(0.) as u32);
+        let top_offset =
+            DisplayPoint::new(top_row, 0).to_offset(&self.display_snapshot, Bias::Left);
+        let Some((_, items)) = self.buffer_snapshot.symbols_containing(top_offset, None) else {
+            return scopes;
+        };
+
+        for item in items {
+            let row = item
+                .range
+                .start
+                .to_display_point(&self.display_snapshot)
+                .row();
+            let end_row = item
+                .range
+                .end
+                .to_display_point(&self.display_snapshot)
+                .row();
+            // Each pinned line covers another row at the top, so deeper items are only
+            // pinned once their first line is hidden behind the lines above them.
+            if end_row <= row
+                || end_row < top_row
+                || row.as_f32() >= scroll_top + scopes.len() as f32
+            {
+                continue;
+            }
+
+            scopes.push(StickyScope {
+                start: item.range.start,
+                row,
+                end_row,
+            });
+            if scopes.len() == max_depth {
+                break;
+            }
+        }
+        scopes
+    }
+}
+
+impl Editor {
+    /// Moves the cursor to the start of the sticky scope, scrolling it to where its
+    /// sticky line was displayed.
+    pub(crate) fn jump_to_sticky_scope(
+        &mut self,
+        scope: &StickyScope,
+        depth: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.change_selections(
+            Some(Autoscroll::top_relative(depth)),
+            window,
+            cx,
+            |selections| selections.select_anchor_ranges([scope.start..scope.start]),
+        );
+    }
+}
@@ -6,9 +6,12 @@ use git::{
     blame::Blame,
     repository::{
         AskPassDelegate, Branch, CommitDetails, CommitOptions, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RepoPath, ResetMode,
+        GitRepositoryCheckpoint, LogOptions, PushOptions, RebaseProgress, RebaseTodoItem, Remote,
+        RepoPath, ResetMode, SequencerAction, SequencerOperation, Tag,
     },
+    stash::GitStash,
     status::{FileStatus, GitStatus, StatusCode, TrackedStatus, UnmergedStatus},
+    worktree::GitWorktree,
 };
 use gpui::{AsyncApp, BackgroundExecutor};
 use ignore::gitignore::GitignoreBuilder;
@@ -36,6 +39,7 @@ pub struct FakeGitRepositoryState {
     pub branches: HashSet<String>,
     pub simulated_index_write_error_message: Option<String>,
     pub refs: HashMap<String, String>,
+    pub stash_entries: GitStash,
 }
 
 impl FakeGitRepositoryState {
@@ -50,6 +54,7 @@ impl FakeGitRepositoryState {
             branches: Default::default(),
             simulated_index_write_error_message: Default::default(),
             refs: HashMap::from_iter([("HEAD".into(), "abc".into())]),
+            stash_entries: Default::default(),
         }
     }
 }
@@ -112,6 +117,10 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn log(&self, _options: LogOptions) -> BoxFuture<Result<Vec<CommitDetails>>> {
+        unimplemented!()
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -179,10 +188,42 @@ impl GitRepository for FakeGitRepository {
         self.common_dir_path.clone()
     }
 
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>> {
+        let Some(workdir_path) = self.dot_git_path.parent().map(PathBuf::from) else {
+            return future::ready(Ok(Vec::new())).boxed();
+        };
+        self.with_state_async(false, move |state| {
+            Ok(vec![GitWorktree {
+                path: workdir_path,
+                head_sha: None,
+                branch: state.current_branch_name.clone().map(Into::into),
+                is_main: true,
+                is_locked: false,
+            }])
+        })
+    }
+
+    fn create_worktree(
+        &self,
+        _path: PathBuf,
+        _commit: Option<String>,
+        _new_branch: Option<String>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn remove_worktree(&self, _path: PathBuf, _force: bool) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
     fn merge_message(&self) -> BoxFuture<Option<String>> {
         async move { None }.boxed()
     }
 
+    fn rebase_progress(&self) -> BoxFuture<Option<RebaseProgress>> {
+        async move { None }.boxed()
+    }
+
     fn status(&self, path_prefixes: &[RepoPath]) -> BoxFuture<Result<GitStatus>> {
         let workdir_path = self.dot_git_path.parent().unwrap();
 
@@ -344,6 +385,57 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn delete_branch(&self, name: String, _force: bool) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.current_branch_name.as_ref() == Some(&name) {
+                return Err(anyhow!("cannot delete the current branch {name}"));
+            }
+            if !state.branches.remove(&name) {
+                return Err(anyhow!("no such branch: {name}"));
+            }
+            Ok(())
+        })
+    }
+
+    fn rename_branch(&self, old_name: String, new_name: String) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, move |state| {
+            if !state.branches.remove(&old_name) {
+                return Err(anyhow!("no such branch: {old_name}"));
+            }
+            if state.current_branch_name.as_ref() == Some(&old_name) {
+                state.current_branch_name = Some(new_name.clone());
+            }
+            state.branches.insert(new_name);
+            Ok(())
+        })
+    }
+
+    fn set_upstream(
+        &self,
+        _branch_name: String,
+        _upstream: Option<String>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn tags(&self) -> BoxFuture<Result<Vec<Tag>>> {
+        unimplemented!()
+    }
+
+    fn create_tag(
+        &self,
+        _name: String,
+        _commit: Option<String>,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn delete_tag(&self, _name: String) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
     fn blame(&self, path: RepoPath, _content: Rope) -> BoxFuture<Result<git::blame::Blame>> {
         self.with_state_async(false, move |state| {
             state
@@ -380,6 +472,89 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn stash_entries(&self) -> BoxFuture<Result<GitStash>> {
+        self.with_state_async(false, |state| Ok(state.stash_entries.clone()))
+    }
+
+    fn stash_paths(
+        &self,
+        _paths: Vec<RepoPath>,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_pop(
+        &self,
+        _index: Option<usize>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_apply(
+        &self,
+        _index: Option<usize>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_drop(
+        &self,
+        index: Option<usize>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, move |state| {
+            let index = index.unwrap_or(0);
+            let mut entries = state.stash_entries.entries.to_vec();
+            anyhow::ensure!(index < entries.len(), "stash@{{{index}}} does not exist");
+            entries.remove(index);
+            for entry in &mut entries[index..] {
+                entry.index -= 1;
+            }
+            state.stash_entries = GitStash {
+                entries: entries.into(),
+            };
+            Ok(())
+        })
+    }
+
+    fn cherry_pick(
+        &self,
+        _commits: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn revert(
+        &self,
+        _commits: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase(
+        &self,
+        _onto: Option<String>,
+        _todo: Option<Vec<RebaseTodoItem>>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn sequencer_action(
+        &self,
+        _operation: SequencerOperation,
+        _action: SequencerAction,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
     fn push(
         &self,
         _branch: String,
@@ -412,6 +587,17 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn push_tag(
+        &self,
+        _tag_name: String,
+        _remote: String,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<git::repository::RemoteCommandOutput>> {
+        unimplemented!()
+    }
+
     fn get_remotes(&self, _branch: Option<String>) -> BoxFuture<Result<Vec<Remote>>> {
         unimplemented!()
     }
@@ -1497,6 +1497,15 @@ impl FakeFs {
         .unwrap();
     }
 
+    pub fn set_stash_entries_for_repo(&self, dot_git: &Path, entries: Vec<git::stash::StashEntry>) {
+        self.with_git_state(dot_git, true, |state| {
+            state.stash_entries = git::stash::GitStash {
+                entries: entries.into(),
+            };
+        })
+        .unwrap();
+    }
+
     /// Put the given git repository into a state with the given status,
     /// by mutating the head, index, and unmerged state.
     pub fn set_status_for_repo(&self, dot_git: &Path, statuses: &[(&Path, FileStatus)]) {
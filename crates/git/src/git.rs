@@ -3,7 +3,9 @@ pub mod commit;
 mod hosting_provider;
 mod remote;
 pub mod repository;
+pub mod stash;
 pub mod status;
+pub mod worktree;
 
 pub use crate::hosting_provider::*;
 pub use crate::remote::*;
@@ -12,6 +14,7 @@ pub use git2 as libgit;
 use gpui::action_with_deprecated_aliases;
 use gpui::actions;
 use gpui::impl_action_with_deprecated_aliases;
+use gpui::impl_actions;
 pub use repository::WORK_DIRECTORY_REPO_PATH;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -42,6 +45,8 @@ actions!(
         // repo-wide
         StageAll,
         UnstageAll,
+        StashAll,
+        StashPop,
         RestoreTrackedFiles,
         TrashUntrackedFiles,
         Uncommit,
@@ -55,6 +60,15 @@ actions!(
         ExpandCommitEditor,
         GenerateCommitMessage,
         Init,
+        ViewHistory,
+        FileHistory,
+        CherryPickCommit,
+        RevertCommit,
+        InteractiveRebase,
+        ContinueOperation,
+        AbortOperation,
+        SkipOperation,
+        OpenWorktree,
     ]
 );
 
@@ -64,6 +78,14 @@ pub struct RestoreFile {
     pub skip_prompt: bool,
 }
 
+/// Pushes the named tag to the current branch's remote.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PushTag {
+    pub name: String,
+}
+
+impl_actions!(git, [PushTag]);
 impl_action_with_deprecated_aliases!(git, RestoreFile, ["editor::RevertFile"]);
 action_with_deprecated_aliases!(git, Restore, ["editor::RevertSelectedHunks"]);
 action_with_deprecated_aliases!(git, Blame, ["editor::ToggleGitBlame"]);
@@ -1,5 +1,7 @@
 use crate::commit::parse_git_diff_name_status;
+use crate::stash::GitStash;
 use crate::status::{GitStatus, StatusCode};
+use crate::worktree::{GitWorktree, parse_worktree_list};
 use crate::{Oid, SHORT_SHA_LENGTH};
 use anyhow::{Context as _, Result, anyhow, bail};
 use collections::HashMap;
@@ -33,6 +35,8 @@ use uuid::Uuid;
 pub use askpass::{AskPassDelegate, AskPassResult, AskPassSession};
 
 pub const REMOTE_CANCELLED_BY_USER: &str = "Operation cancelled by user";
+pub const BRANCH_NOT_MERGED: &str = "Branch is not fully merged";
+pub const WORKTREE_HAS_CHANGES: &str = "Worktree has uncommitted changes";
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Branch {
@@ -141,6 +145,21 @@ pub struct CommitDetails {
     pub author_name: SharedString,
 }
 
+/// Options for [`GitRepository::log`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogOptions {
+    /// The number of commits to skip, used for pagination.
+    pub skip: usize,
+    /// The maximum number of commits to return.
+    pub limit: usize,
+    /// Only include commits that touch this path, following renames.
+    pub path: Option<RepoPath>,
+    /// Only include commits whose author matches this pattern (case-insensitive).
+    pub author: Option<String>,
+    /// Only include commits whose message matches this pattern (case-insensitive).
+    pub message: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct CommitDiff {
     pub files: Vec<CommitFile>,
@@ -164,6 +183,96 @@ pub struct Remote {
     pub name: SharedString,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points to.
+    pub sha: SharedString,
+    /// The subject of the tag's message, for annotated tags.
+    pub message: Option<SharedString>,
+    /// This is a unix timestamp
+    pub timestamp: i64,
+}
+
+/// A multi-step operation that can stop midway, typically because of conflicts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SequencerOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+}
+
+impl SequencerOperation {
+    pub fn subcommand(&self) -> &'static str {
+        match self {
+            SequencerOperation::Merge => "merge",
+            SequencerOperation::Rebase => "rebase",
+            SequencerOperation::CherryPick => "cherry-pick",
+            SequencerOperation::Revert => "revert",
+        }
+    }
+}
+
+/// How to proceed with an in-progress [`SequencerOperation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SequencerAction {
+    Continue,
+    Abort,
+    /// Skip the commit that is currently being applied. Merges can't be skipped.
+    Skip,
+}
+
+impl SequencerAction {
+    pub fn flag(&self) -> &'static str {
+        match self {
+            SequencerAction::Continue => "--continue",
+            SequencerAction::Abort => "--abort",
+            SequencerAction::Skip => "--skip",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RebaseTodoAction {
+    Pick,
+    /// Stop after applying the commit so that it can be amended.
+    Edit,
+    /// Meld the commit into the previous one, discarding its message.
+    Fixup,
+    Drop,
+}
+
+impl RebaseTodoAction {
+    pub fn command(&self) -> &'static str {
+        match self {
+            RebaseTodoAction::Pick => "pick",
+            RebaseTodoAction::Edit => "edit",
+            RebaseTodoAction::Fixup => "fixup",
+            RebaseTodoAction::Drop => "drop",
+        }
+    }
+}
+
+/// A line of an interactive rebase todo list.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RebaseTodoItem {
+    pub action: RebaseTodoAction,
+    pub sha: SharedString,
+}
+
+/// The state of a rebase that is in progress.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RebaseProgress {
+    /// The branch being rebased, or `None` when rebasing a detached HEAD.
+    pub head_name: Option<SharedString>,
+    /// The commit that the branch is being rebased onto.
+    pub onto: Option<SharedString>,
+    /// The 1-based index of the step that is currently being applied.
+    pub current_step: usize,
+    pub total_steps: usize,
+}
+
 pub enum ResetMode {
     /// Reset the branch pointer, leave index and worktree unchanged (this will make it look like things that were
     /// committed are now staged).
@@ -213,6 +322,9 @@ pub trait GitRepository: Send + Sync {
 
     fn merge_message(&self) -> BoxFuture<Option<String>>;
 
+    /// Returns the progress of the rebase in progress, if any.
+    fn rebase_progress(&self) -> BoxFuture<Option<RebaseProgress>>;
+
     fn status(&self, path_prefixes: &[RepoPath]) -> BoxFuture<Result<GitStatus>>;
 
     fn branches(&self) -> BoxFuture<Result<Vec<Branch>>>;
@@ -220,6 +332,30 @@ pub trait GitRepository: Send + Sync {
     fn change_branch(&self, name: String) -> BoxFuture<Result<()>>;
     fn create_branch(&self, name: String) -> BoxFuture<Result<()>>;
 
+    /// Deletes a local branch. Unless `force` is set, this fails with [`BRANCH_NOT_MERGED`] if
+    /// the branch isn't merged into its upstream, or into HEAD if it has none.
+    fn delete_branch(&self, name: String, force: bool) -> BoxFuture<Result<()>>;
+    fn rename_branch(&self, old_name: String, new_name: String) -> BoxFuture<Result<()>>;
+
+    /// Sets the upstream of a local branch, such as `origin/main`, or unsets it if `None`.
+    fn set_upstream(&self, branch_name: String, upstream: Option<String>) -> BoxFuture<Result<()>>;
+
+    /// Returns the repository's tags, most recent first.
+    fn tags(&self) -> BoxFuture<Result<Vec<Tag>>>;
+
+    /// Creates a tag pointing at the given commit (or HEAD).
+    ///
+    /// The tag is annotated if a message is given, and lightweight otherwise.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<Result<()>>;
+
     fn reset(
         &self,
         commit: String,
@@ -237,6 +373,10 @@ pub trait GitRepository: Send + Sync {
     fn show(&self, commit: String) -> BoxFuture<Result<CommitDetails>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>>;
+
+    /// Returns the commits reachable from HEAD, newest first, filtered by the given options.
+    fn log(&self, options: LogOptions) -> BoxFuture<Result<Vec<CommitDetails>>>;
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<Result<crate::blame::Blame>>;
 
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
@@ -245,6 +385,24 @@ pub trait GitRepository: Send + Sync {
 
     fn main_repository_path(&self) -> PathBuf;
 
+    /// Returns the working trees of the repository, starting with the main one.
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>>;
+
+    /// Adds a linked working tree at `path`, checking out `commit` (or HEAD).
+    ///
+    /// If `new_branch` is given, a branch with that name is created at the commit and
+    /// checked out instead.
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        commit: Option<String>,
+        new_branch: Option<String>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Removes a linked working tree. Unless `force` is set, this fails with
+    /// [`WORKTREE_HAS_CHANGES`] if the worktree has uncommitted changes or untracked files.
+    fn remove_worktree(&self, path: PathBuf, force: bool) -> BoxFuture<Result<()>>;
+
     /// Updates the index to match the worktree at the given paths.
     ///
     /// If any of the paths have been deleted from the worktree, they will be removed from the index if found there.
@@ -270,6 +428,75 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>>;
 
+    /// Returns the entries of the repository's stash, most recent first.
+    fn stash_entries(&self) -> BoxFuture<Result<GitStash>>;
+
+    /// Stashes the changes to the given paths, including untracked files.
+    ///
+    /// If no paths are given, all changes in the worktree and index are stashed.
+    fn stash_paths(
+        &self,
+        paths: Vec<RepoPath>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Applies the stash entry at the given index (or the latest one) and removes it from the stash.
+    fn stash_pop(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Applies the stash entry at the given index (or the latest one), keeping it in the stash.
+    fn stash_apply(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Removes the stash entry at the given index (or the latest one) without applying it.
+    fn stash_drop(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Applies the changes introduced by the given commits on top of HEAD, oldest first.
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Creates new commits that undo the changes introduced by the given commits.
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Rebases the current branch onto the given commit, or every commit of the branch
+    /// from its root commit if none is given.
+    ///
+    /// If a todo list is given, it replaces the one generated by git, making this an
+    /// interactive rebase. Items are applied in order, oldest first. Merge commits
+    /// can't be part of the todo list.
+    fn rebase(
+        &self,
+        onto: Option<String>,
+        todo: Option<Vec<RebaseTodoItem>>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Continues, aborts or skips the current step of an in-progress operation.
+    fn sequencer_action(
+        &self,
+        operation: SequencerOperation,
+        action: SequencerAction,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
     fn push(
         &self,
         branch_name: String,
@@ -302,6 +529,17 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<Result<RemoteCommandOutput>>;
 
+    fn push_tag(
+        &self,
+        tag_name: String,
+        remote_name: String,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
     fn get_remotes(&self, branch_name: Option<String>) -> BoxFuture<Result<Vec<Remote>>>;
 
     /// returns a list of remote branches that contain HEAD
@@ -376,6 +614,118 @@ impl RealGitRepository {
             .context("failed to read git work directory")
             .map(Path::to_path_buf)
     }
+
+    /// Runs a `git stash` subcommand that operates on a single stash entry.
+    fn run_stash_command(
+        &self,
+        subcommand: &'static str,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(["stash", subcommand, "--quiet"])
+                    .args(index.map(|index| format!("stash@{{{index}}}")))
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to run git stash {subcommand}:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn run_ref_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(&args)
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to run git {}:\n{}",
+                        args.join(" "),
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    /// Runs a command that may stop midway to let the user resolve conflicts.
+    ///
+    /// Git would otherwise open an editor for commit messages, so the default messages are
+    /// accepted as-is.
+    fn run_sequencer_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+        extra_env: HashMap<String, String>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                run_sequencer_command(
+                    &git_binary_path,
+                    &working_directory?,
+                    &args,
+                    &env,
+                    &extra_env,
+                )
+                .await
+            })
+            .boxed()
+    }
+}
+
+/// Runs a git command that may stop to let the user resolve conflicts, such as a rebase,
+/// without opening an editor for commit messages.
+async fn run_sequencer_command(
+    git_binary_path: &Path,
+    working_directory: &Path,
+    args: &[String],
+    env: &HashMap<String, String>,
+    extra_env: &HashMap<String, String>,
+) -> Result<()> {
+    let output = new_smol_command(git_binary_path)
+        .current_dir(working_directory)
+        .envs(env.iter())
+        .envs(extra_env.iter())
+        .env("GIT_EDITOR", "true")
+        .args(args)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to run git {}:\n{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(())
 }
 
 #[derive(Clone, Debug)]
@@ -400,6 +750,102 @@ impl GitRepository for RealGitRepository {
         repo.commondir().into()
     }
 
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["worktree", "list", "--porcelain"])
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list git worktrees:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                parse_worktree_list(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        commit: Option<String>,
+        new_branch: Option<String>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut command = new_smol_command(&git_binary_path);
+                command
+                    .current_dir(&working_directory?)
+                    .args(["worktree", "add"]);
+                if let Some(new_branch) = new_branch {
+                    command.arg("-b").arg(new_branch);
+                }
+                let output = command.arg("--").arg(&path).args(commit).output().await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to create git worktree:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn remove_worktree(&self, path: PathBuf, force: bool) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                if !force {
+                    // Git's own refusal is localized, so look for the changes it would refuse
+                    // to lose ourselves.
+                    let output = new_smol_command(&git_binary_path)
+                        .current_dir(&path)
+                        .args(["status", "--porcelain"])
+                        .output()
+                        .await?;
+                    if !output.status.success() {
+                        return Err(anyhow!(
+                            "Failed to get the status of git worktree:\n{}",
+                            String::from_utf8_lossy(&output.stderr)
+                        ));
+                    }
+                    if !output.stdout.is_empty() {
+                        bail!("{WORKTREE_HAS_CHANGES}: {}", path.display());
+                    }
+                }
+
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["worktree", "remove"])
+                    .args(force.then_some("--force"))
+                    .arg("--")
+                    .arg(&path)
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to remove git worktree:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
     fn show(&self, commit: String) -> BoxFuture<Result<CommitDetails>> {
         let working_directory = self.working_directory();
         self.executor
@@ -436,6 +882,45 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn log(&self, options: LogOptions) -> BoxFuture<Result<Vec<CommitDetails>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(&git_binary_path);
+                cmd.current_dir(&working_directory?).args([
+                    "--no-optional-locks",
+                    "log",
+                    "--format=%H%x00%B%x00%at%x00%ae%x00%an%x1e",
+                ]);
+                cmd.arg(format!("--skip={}", options.skip))
+                    .arg(format!("--max-count={}", options.limit));
+
+                if let Some(author) = &options.author {
+                    cmd.arg(format!("--author={author}"));
+                }
+                if let Some(message) = &options.message {
+                    cmd.arg(format!("--grep={message}"));
+                }
+                if options.author.is_some() || options.message.is_some() {
+                    cmd.arg("--regexp-ignore-case");
+                }
+                if let Some(path) = &options.path {
+                    cmd.arg("--follow").arg("--").arg(path.to_unix_style());
+                }
+
+                let output = cmd.output().await?;
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to load git log:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                parse_log_output(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>> {
         let Some(working_directory) = self.repository.lock().workdir().map(ToOwned::to_owned)
         else {
@@ -779,6 +1264,47 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn rebase_progress(&self) -> BoxFuture<Option<RebaseProgress>> {
+        let path = self.path();
+        self.executor
+            .spawn(async move {
+                // `git rebase` keeps its state in `rebase-merge`, unless the `apply`
+                // backend is used, in which case it shares `rebase-apply` with `git am`.
+                let (state_dir, current_step_file, total_steps_file) =
+                    if path.join("rebase-merge").is_dir() {
+                        (path.join("rebase-merge"), "msgnum", "end")
+                    } else if path.join("rebase-apply").join("rebasing").exists() {
+                        (path.join("rebase-apply"), "next", "last")
+                    } else {
+                        return None;
+                    };
+                let read = |name: &str| {
+                    std::fs::read_to_string(state_dir.join(name))
+                        .ok()
+                        .map(|contents| contents.trim().to_string())
+                        .filter(|contents| !contents.is_empty())
+                };
+                Some(RebaseProgress {
+                    head_name: read("head-name")
+                        .filter(|name| name != "detached HEAD")
+                        .map(|name| {
+                            name.strip_prefix("refs/heads/")
+                                .unwrap_or(&name)
+                                .to_string()
+                                .into()
+                        }),
+                    onto: read("onto").map(SharedString::from),
+                    current_step: read(current_step_file)
+                        .and_then(|step| step.parse().ok())
+                        .unwrap_or_default(),
+                    total_steps: read(total_steps_file)
+                        .and_then(|steps| steps.parse().ok())
+                        .unwrap_or_default(),
+                })
+            })
+            .boxed()
+    }
+
     fn status(&self, path_prefixes: &[RepoPath]) -> BoxFuture<Result<GitStatus>> {
         let git_binary_path = self.git_binary_path.clone();
         let working_directory = self.working_directory();
@@ -911,6 +1437,139 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn delete_branch(&self, name: String, force: bool) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                if !force {
+                    // Git's own refusal is localized, so check merged-ness the way it does: into
+                    // the branch's upstream if it has one, and into HEAD otherwise.
+                    let git = GitBinary::new(
+                        git_binary_path.clone(),
+                        working_directory.clone(),
+                        executor,
+                    );
+                    let upstream = git
+                        .run([
+                            "rev-parse",
+                            "--verify",
+                            "--quiet",
+                            &format!("{name}@{{upstream}}"),
+                        ])
+                        .await
+                        .ok();
+                    let target = upstream.as_deref().unwrap_or("HEAD");
+                    let branch_ref = format!("refs/heads/{name}");
+                    let result = git
+                        .run(["merge-base", "--is-ancestor", &branch_ref, target])
+                        .await;
+                    if let Err(error) = result {
+                        if let Some(GitBinaryCommandError { status, .. }) =
+                            error.downcast_ref::<GitBinaryCommandError>()
+                        {
+                            if status.code() == Some(1) {
+                                bail!("{BRANCH_NOT_MERGED}: {name}");
+                            }
+                        }
+                        return Err(error);
+                    }
+                }
+
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["branch", "-D", "--", &name])
+                    .output()
+                    .await?;
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to delete branch {name}:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rename_branch(&self, old_name: String, new_name: String) -> BoxFuture<Result<()>> {
+        self.run_ref_command(
+            vec!["branch".into(), "-m".into(), old_name, new_name],
+            Arc::default(),
+        )
+    }
+
+    fn set_upstream(&self, branch_name: String, upstream: Option<String>) -> BoxFuture<Result<()>> {
+        let args = match upstream {
+            Some(upstream) => vec![
+                "branch".into(),
+                format!("--set-upstream-to={upstream}"),
+                branch_name,
+            ],
+            None => vec!["branch".into(), "--unset-upstream".into(), branch_name],
+        };
+        self.run_ref_command(args, Arc::default())
+    }
+
+    fn tags(&self) -> BoxFuture<Result<Vec<Tag>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let fields = [
+                    "%(refname:short)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(objecttype)",
+                    "%(creatordate:unix)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "for-each-ref",
+                        "refs/tags",
+                        "--sort=-creatordate",
+                        "--format",
+                    ])
+                    .arg(&fields)
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list git tags:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                parse_tag_output(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["tag".to_string()];
+        if let Some(message) = message {
+            args.extend(["--annotate".into(), "--message".into(), message]);
+        }
+        args.push(name);
+        args.extend(commit);
+        self.run_ref_command(args, env)
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<Result<()>> {
+        self.run_ref_command(vec!["tag".into(), "--delete".into(), name], Arc::default())
+    }
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<Result<crate::blame::Blame>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -1060,6 +1719,194 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn stash_entries(&self) -> BoxFuture<Result<GitStash>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["stash", "list", "--format=%gd%x00%H%x00%ct%x00%gs"])
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list stash entries:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                String::from_utf8_lossy(&output.stdout).parse()
+            })
+            .boxed()
+    }
+
+    fn stash_paths(
+        &self,
+        paths: Vec<RepoPath>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(&git_binary_path);
+                cmd.current_dir(&working_directory?).envs(env.iter()).args([
+                    "stash",
+                    "push",
+                    "--quiet",
+                    "--include-untracked",
+                ]);
+
+                if let Some(message) = message {
+                    cmd.arg("--message").arg(message);
+                }
+
+                if !paths.is_empty() {
+                    cmd.arg("--").args(paths.iter().map(|p| p.to_unix_style()));
+                }
+
+                let output = cmd.output().await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to stash:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn stash_pop(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.run_stash_command("pop", index, env)
+    }
+
+    fn stash_apply(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.run_stash_command("apply", index, env)
+    }
+
+    fn stash_drop(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.run_stash_command("drop", index, env)
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["cherry-pick".to_string()];
+        args.extend(commits);
+        self.run_sequencer_command(args, env, HashMap::default())
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["revert".to_string(), "--no-edit".to_string()];
+        args.extend(commits);
+        self.run_sequencer_command(args, env, HashMap::default())
+    }
+
+    fn rebase(
+        &self,
+        onto: Option<String>,
+        todo: Option<Vec<RebaseTodoItem>>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["rebase".to_string()];
+        if todo.is_some() {
+            args.push("--interactive".to_string());
+        }
+        args.push(onto.unwrap_or_else(|| "--root".to_string()));
+        let Some(todo) = todo else {
+            return self.run_sequencer_command(args, env, HashMap::default());
+        };
+
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        let todo_path = self.path().join("ZED_REBASE_TODO");
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+
+                // The todo list can't describe the commits a merge joins, so the merge
+                // would be flattened.
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["rev-list", "--no-walk", "--min-parents=2"])
+                    .args(todo.iter().map(|item| item.sha.to_string()))
+                    .output()
+                    .await?;
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to look up the commits to rebase:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                let merges = String::from_utf8_lossy(&output.stdout);
+                if let Some(merge) = merges.lines().next() {
+                    return Err(anyhow!(
+                        "Merge commit {merge} can't be rebased interactively"
+                    ));
+                }
+
+                let contents = todo
+                    .iter()
+                    .map(|item| format!("{} {}\n", item.action.command(), item.sha))
+                    .collect::<String>();
+                std::fs::write(&todo_path, contents)?;
+
+                // Git invokes the sequence editor with the path of its generated todo list,
+                // which we overwrite with ours.
+                let quoted_todo_path = todo_path.to_string_lossy().replace('\'', "'\\''");
+                let sequence_editor = format!("cp '{quoted_todo_path}'");
+                let result = run_sequencer_command(
+                    &git_binary_path,
+                    &working_directory,
+                    &args,
+                    &env,
+                    &HashMap::from_iter([("GIT_SEQUENCE_EDITOR".to_string(), sequence_editor)]),
+                )
+                .await;
+                std::fs::remove_file(&todo_path).log_err();
+                result
+            })
+            .boxed()
+    }
+
+    fn sequencer_action(
+        &self,
+        operation: SequencerOperation,
+        action: SequencerAction,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.run_sequencer_command(
+            vec![
+                operation.subcommand().to_string(),
+                action.flag().to_string(),
+            ],
+            env,
+            HashMap::default(),
+        )
+    }
+
     fn push(
         &self,
         branch_name: String,
@@ -1083,8 +1930,34 @@ impl GitRepository for RealGitRepository {
                     PushOptions::Force => "--force-with-lease",
                 }))
                 .arg(remote_name)
-                .arg(format!("{}:{}", branch_name, branch_name))
-                .stdin(smol::process::Stdio::null())
+                .arg(format!("{}:{}", branch_name, branch_name))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn pull(
+        &self,
+        branch_name: String,
+        remote_name: String,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["pull"])
+                .arg(remote_name)
+                .arg(branch_name)
                 .stdout(smol::process::Stdio::piped())
                 .stderr(smol::process::Stdio::piped());
 
@@ -1093,10 +1966,8 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
-    fn pull(
+    fn fetch(
         &self,
-        branch_name: String,
-        remote_name: String,
         ask_pass: AskPassDelegate,
         env: Arc<HashMap<String, String>>,
         cx: AsyncApp,
@@ -1108,9 +1979,7 @@ impl GitRepository for RealGitRepository {
             command
                 .envs(env.iter())
                 .current_dir(&working_directory?)
-                .args(["pull"])
-                .arg(remote_name)
-                .arg(branch_name)
+                .args(["fetch", "--all"])
                 .stdout(smol::process::Stdio::piped())
                 .stderr(smol::process::Stdio::piped());
 
@@ -1119,8 +1988,10 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
-    fn fetch(
+    fn push_tag(
         &self,
+        tag_name: String,
+        remote_name: String,
         ask_pass: AskPassDelegate,
         env: Arc<HashMap<String, String>>,
         cx: AsyncApp,
@@ -1132,7 +2003,10 @@ impl GitRepository for RealGitRepository {
             command
                 .envs(env.iter())
                 .current_dir(&working_directory?)
-                .args(["fetch", "--all"])
+                .args(["push"])
+                .arg(remote_name)
+                .arg(format!("refs/tags/{tag_name}:refs/tags/{tag_name}"))
+                .stdin(smol::process::Stdio::null())
                 .stdout(smol::process::Stdio::piped())
                 .stderr(smol::process::Stdio::piped());
 
@@ -1732,6 +2606,67 @@ fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     Ok(branches)
 }
 
+/// Parses the output of `git for-each-ref refs/tags` with the fields requested by `tags`.
+fn parse_tag_output(input: &str) -> Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let name = fields.next().context("no refname")?;
+        let object_sha = fields.next().context("no objectname")?;
+        let peeled_sha = fields.next().context("no peeled objectname")?;
+        let is_annotated = fields.next().context("no objecttype")? == "tag";
+        let timestamp = fields
+            .next()
+            .context("no creatordate")?
+            .parse::<i64>()
+            .unwrap_or_default();
+        let subject = fields.next().context("no contents:subject")?;
+        // Annotated tags point at a tag object, which in turn points at the commit.
+        let sha = if peeled_sha.is_empty() {
+            object_sha
+        } else {
+            peeled_sha
+        };
+        tags.push(Tag {
+            name: name.to_string().into(),
+            sha: sha.to_string().into(),
+            message: is_annotated.then(|| subject.to_string().into()),
+            timestamp,
+        });
+    }
+    Ok(tags)
+}
+
+fn parse_log_output(input: &str) -> Result<Vec<CommitDetails>> {
+    let mut commits = Vec::new();
+    for record in input.split('\x1e') {
+        let record = record.trim_start_matches('\n');
+        if record.is_empty() {
+            continue;
+        }
+        let mut fields = record.split('\x00');
+        let sha = fields.next().context("no commit sha")?;
+        let message = fields.next().context("no commit message")?;
+        let commit_timestamp = fields
+            .next()
+            .context("no commit timestamp")?
+            .parse::<i64>()?;
+        let author_email = fields.next().context("no author email")?;
+        let author_name = fields.next().context("no author name")?;
+        commits.push(CommitDetails {
+            sha: sha.to_string().into(),
+            message: message.to_string().into(),
+            commit_timestamp,
+            author_email: author_email.to_string().into(),
+            author_name: author_name.to_string().into(),
+        });
+    }
+    Ok(commits)
+}
+
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
     if upstream_track == "" {
         return Ok(UpstreamTracking::Tracked(UpstreamTrackingStatus {
@@ -1965,6 +2900,459 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_stash(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let file_path = repo_dir.path().join("file");
+        smol::fs::write(&file_path, "initial").await.unwrap();
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+            .await
+            .unwrap();
+        repo.commit(
+            "Initial commit".into(),
+            None,
+            CommitOptions::default(),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+
+        smol::fs::write(&file_path, "modified").await.unwrap();
+        smol::fs::write(repo_dir.path().join("untracked"), "new")
+            .await
+            .unwrap();
+        repo.stash_paths(Vec::new(), Some("my changes".into()), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            smol::fs::read_to_string(&file_path).await.unwrap(),
+            "initial"
+        );
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("untracked"))
+                .await
+                .ok(),
+            None
+        );
+
+        let stash = repo.stash_entries().await.unwrap();
+        assert_eq!(stash.entries.len(), 1);
+        assert_eq!(stash.entries[0].index, 0);
+        assert_eq!(stash.entries[0].message, "my changes");
+
+        repo.stash_apply(Some(0), env.clone()).await.unwrap();
+        assert_eq!(
+            smol::fs::read_to_string(&file_path).await.unwrap(),
+            "modified"
+        );
+        assert_eq!(repo.stash_entries().await.unwrap().entries.len(), 1);
+
+        repo.stash_drop(None, env.clone()).await.unwrap();
+        assert!(repo.stash_entries().await.unwrap().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_sequencer_operations(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let file_path = repo_dir.path().join("file");
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let mut shas = Vec::new();
+        for contents in ["one", "two", "three"] {
+            smol::fs::write(&file_path, contents).await.unwrap();
+            repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+                .await
+                .unwrap();
+            repo.commit(contents.into(), None, CommitOptions::default(), env.clone())
+                .await
+                .unwrap();
+            shas.push(repo.head_sha().await.unwrap());
+        }
+
+        // Reverting the second commit conflicts with the third one.
+        repo.revert(vec![shas[1].clone()], env.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            repo.revparse_batch(vec!["REVERT_HEAD".into()])
+                .await
+                .unwrap(),
+            vec![Some(shas[1].clone())]
+        );
+        repo.sequencer_action(
+            SequencerOperation::Revert,
+            SequencerAction::Abort,
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(repo.head_sha().await.unwrap(), shas[2]);
+
+        repo.revert(vec![shas[2].clone()], env.clone())
+            .await
+            .unwrap();
+        assert_eq!(smol::fs::read_to_string(&file_path).await.unwrap(), "two");
+
+        // Stop at the second commit, then replay the third one on top.
+        repo.rebase(
+            Some(shas[0].clone()),
+            Some(vec![
+                RebaseTodoItem {
+                    action: RebaseTodoAction::Edit,
+                    sha: shas[1].clone().into(),
+                },
+                RebaseTodoItem {
+                    action: RebaseTodoAction::Pick,
+                    sha: shas[2].clone().into(),
+                },
+            ]),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        let progress = repo.rebase_progress().await.unwrap();
+        assert_eq!(progress.onto, Some(shas[0].clone().into()));
+        assert_eq!((progress.current_step, progress.total_steps), (1, 2));
+        assert_eq!(repo.head_sha().await.unwrap(), shas[1]);
+
+        repo.sequencer_action(
+            SequencerOperation::Rebase,
+            SequencerAction::Continue,
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(smol::fs::read_to_string(&file_path).await.unwrap(), "three");
+
+        // Rebasing from the root commit, melding the last commit into the second one.
+        repo.rebase(
+            None,
+            Some(vec![
+                RebaseTodoItem {
+                    action: RebaseTodoAction::Pick,
+                    sha: shas[0].clone().into(),
+                },
+                RebaseTodoItem {
+                    action: RebaseTodoAction::Pick,
+                    sha: shas[1].clone().into(),
+                },
+                RebaseTodoItem {
+                    action: RebaseTodoAction::Fixup,
+                    sha: shas[2].clone().into(),
+                },
+            ]),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(smol::fs::read_to_string(&file_path).await.unwrap(), "three");
+        let ancestors = repo
+            .revparse_batch(vec!["HEAD~1".into(), "HEAD~2".into()])
+            .await
+            .unwrap();
+        assert!(ancestors[0].is_some());
+        assert_eq!(ancestors[1], None);
+    }
+
+    #[gpui::test]
+    async fn test_branch_operations(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let file_path = repo_dir.path().join("file");
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        smol::fs::write(&file_path, "one").await.unwrap();
+        repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+            .await
+            .unwrap();
+        repo.commit("one".into(), None, CommitOptions::default(), env.clone())
+            .await
+            .unwrap();
+        let main_branch = repo
+            .branches()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|branch| branch.is_head)
+            .unwrap()
+            .name
+            .to_string();
+
+        // A branch pointing at HEAD is merged, so it can be deleted without forcing.
+        repo.create_branch("merged".into()).await.unwrap();
+        repo.delete_branch("merged".into(), false).await.unwrap();
+
+        repo.create_branch("feature".into()).await.unwrap();
+        repo.change_branch("feature".into()).await.unwrap();
+        smol::fs::write(&file_path, "two").await.unwrap();
+        repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+            .await
+            .unwrap();
+        repo.commit("two".into(), None, CommitOptions::default(), env.clone())
+            .await
+            .unwrap();
+        repo.change_branch(main_branch.clone()).await.unwrap();
+
+        let error = repo
+            .delete_branch("feature".into(), false)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains(BRANCH_NOT_MERGED), "{error}");
+
+        repo.rename_branch("feature".into(), "renamed".into())
+            .await
+            .unwrap();
+        repo.set_upstream("renamed".into(), Some(main_branch.clone()))
+            .await
+            .unwrap();
+        let branches = repo.branches().await.unwrap();
+        assert!(!branches.iter().any(|branch| branch.name == "feature"));
+        let renamed = branches
+            .iter()
+            .find(|branch| branch.name == "renamed")
+            .unwrap();
+        assert_eq!(
+            renamed.upstream.as_ref().unwrap().ref_name,
+            format!("refs/heads/{main_branch}")
+        );
+
+        // The renamed branch isn't merged into its upstream either.
+        repo.delete_branch("renamed".into(), false)
+            .await
+            .unwrap_err();
+        repo.set_upstream("renamed".into(), None).await.unwrap();
+        let branches = repo.branches().await.unwrap();
+        let renamed = branches
+            .iter()
+            .find(|branch| branch.name == "renamed")
+            .unwrap();
+        assert_eq!(renamed.upstream, None);
+
+        repo.delete_branch("renamed".into(), true).await.unwrap();
+        let branches = repo.branches().await.unwrap();
+        assert_eq!(
+            branches
+                .iter()
+                .map(|branch| branch.name.to_string())
+                .collect::<Vec<_>>(),
+            [main_branch]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_worktree_operations(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        smol::fs::write(repo_dir.path().join("file"), "one")
+            .await
+            .unwrap();
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+            .await
+            .unwrap();
+        repo.commit("one".into(), None, CommitOptions::default(), env.clone())
+            .await
+            .unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let worktrees_dir = temp_dir.path().canonicalize().unwrap();
+        let clean_path = worktrees_dir.join("clean");
+        let dirty_path = worktrees_dir.join("dirty");
+        repo.create_worktree(clean_path.clone(), None, Some("clean-branch".into()))
+            .await
+            .unwrap();
+        let head_sha = repo.head_sha().await.unwrap();
+        repo.create_worktree(
+            dirty_path.clone(),
+            Some(head_sha),
+            Some("dirty-branch".into()),
+        )
+        .await
+        .unwrap();
+
+        let worktrees = repo.worktrees().await.unwrap();
+        assert_eq!(worktrees.len(), 3);
+        assert!(worktrees[0].is_main);
+        assert_eq!(
+            worktrees[1..]
+                .iter()
+                .map(|worktree| (worktree.path.clone(), worktree.branch.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (clean_path.clone(), Some("clean-branch".into())),
+                (dirty_path.clone(), Some("dirty-branch".into())),
+            ]
+        );
+        assert_eq!(
+            smol::fs::read_to_string(clean_path.join("file"))
+                .await
+                .unwrap(),
+            "one"
+        );
+
+        repo.remove_worktree(clean_path.clone(), false)
+            .await
+            .unwrap();
+        assert!(!clean_path.exists());
+
+        // Removing a worktree with untracked files must be forced.
+        smol::fs::write(dirty_path.join("untracked"), "new")
+            .await
+            .unwrap();
+        let error = repo
+            .remove_worktree(dirty_path.clone(), false)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains(WORKTREE_HAS_CHANGES), "{error}");
+        assert!(dirty_path.join("untracked").exists());
+        assert_eq!(repo.worktrees().await.unwrap().len(), 2);
+
+        repo.remove_worktree(dirty_path.clone(), true)
+            .await
+            .unwrap();
+        assert!(!dirty_path.exists());
+        let worktrees = repo.worktrees().await.unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert!(worktrees[0].is_main);
+    }
+
+    #[gpui::test]
+    async fn test_log(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let alice: Option<(SharedString, SharedString)> =
+            Some(("Alice".into(), "alice@example.com".into()));
+        let bob = Some(("Bob".into(), "bob@example.com".into()));
+        for (message, author, removed_path, path, contents) in [
+            ("Add readme", alice.clone(), None, "readme", "Hello"),
+            ("Fix parser", bob.clone(), None, "parser", "one"),
+            ("Rename readme", bob, Some("readme"), "README.md", "Hello"),
+            ("fix typo in parser", alice, None, "parser", "two"),
+        ] {
+            let mut paths = vec![RepoPath::from_str(path)];
+            if let Some(removed_path) = removed_path {
+                smol::fs::remove_file(repo_dir.path().join(removed_path))
+                    .await
+                    .unwrap();
+                paths.push(RepoPath::from_str(removed_path));
+            }
+            smol::fs::write(repo_dir.path().join(path), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(paths, env.clone()).await.unwrap();
+            repo.commit(
+                message.into(),
+                author,
+                CommitOptions::default(),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let log = async |options: LogOptions| {
+            repo.log(options)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|commit| {
+                    assert_eq!(commit.sha.len(), 40);
+                    format!("{} ({})", commit.message.trim(), commit.author_name)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            log(LogOptions {
+                limit: 10,
+                ..Default::default()
+            })
+            .await,
+            [
+                "fix typo in parser (Alice)",
+                "Rename readme (Bob)",
+                "Fix parser (Bob)",
+                "Add readme (Alice)",
+            ]
+        );
+        assert_eq!(
+            log(LogOptions {
+                skip: 1,
+                limit: 2,
+                ..Default::default()
+            })
+            .await,
+            ["Rename readme (Bob)", "Fix parser (Bob)"]
+        );
+        // Following the path includes the commits from before it was renamed.
+        assert_eq!(
+            log(LogOptions {
+                limit: 10,
+                path: Some(RepoPath::from_str("README.md")),
+                ..Default::default()
+            })
+            .await,
+            ["Rename readme (Bob)", "Add readme (Alice)"]
+        );
+        assert_eq!(
+            log(LogOptions {
+                limit: 10,
+                author: Some("alice".into()),
+                ..Default::default()
+            })
+            .await,
+            ["fix typo in parser (Alice)", "Add readme (Alice)"]
+        );
+        assert_eq!(
+            log(LogOptions {
+                limit: 10,
+                author: Some("bob".into()),
+                message: Some("FIX".into()),
+                ..Default::default()
+            })
+            .await,
+            ["Fix parser (Bob)"]
+        );
+        assert_eq!(
+            log(LogOptions {
+                skip: 4,
+                limit: 10,
+                ..Default::default()
+            })
+            .await,
+            Vec::<String>::new()
+        );
+    }
+
     #[test]
     fn test_branches_parsing() {
         // suppress "help: octal escapes are not supported, `\0` is always null"
@@ -1992,6 +3380,60 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_log_parsing() {
+        // suppress "help: octal escapes are not supported, `\0` is always null"
+        #[allow(clippy::octal_escapes)]
+        let input = "060964da10574cd9bf06463a53bf6e0769c5c45e\0Fix the tests\n\nAnd explain why.\n\01733187470\0alice@example.com\0Alice\x1e\n\
+                     ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da\0Initial commit\n\01733180000\0bob@example.com\0Bob\x1e\n";
+        assert_eq!(
+            parse_log_output(input).unwrap(),
+            vec![
+                CommitDetails {
+                    sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    message: "Fix the tests\n\nAnd explain why.\n".into(),
+                    commit_timestamp: 1733187470,
+                    author_email: "alice@example.com".into(),
+                    author_name: "Alice".into(),
+                },
+                CommitDetails {
+                    sha: "ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da".into(),
+                    message: "Initial commit\n".into(),
+                    commit_timestamp: 1733180000,
+                    author_email: "bob@example.com".into(),
+                    author_name: "Bob".into(),
+                },
+            ]
+        );
+        assert!(parse_log_output("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tag_parsing() {
+        // suppress "help: octal escapes are not supported, `\0` is always null"
+        #[allow(clippy::octal_escapes)]
+        let input = "v0.2.0\0bd6e0769c5c45e060964da10574cd9bf06463a53\0060964da10574cd9bf06463a53bf6e0769c5c45e\0tag\01733187470\0Release 0.2.0\n\
+                     v0.1.0\0ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da\0\0commit\01733180000\0Initial commit\n";
+        assert_eq!(
+            parse_tag_output(input).unwrap(),
+            vec![
+                Tag {
+                    name: "v0.2.0".into(),
+                    sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    message: Some("Release 0.2.0".into()),
+                    timestamp: 1733187470,
+                },
+                Tag {
+                    name: "v0.1.0".into(),
+                    sha: "ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da".into(),
+                    message: None,
+                    timestamp: 1733180000,
+                },
+            ]
+        );
+        assert!(parse_tag_output("").unwrap().is_empty());
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<Result<()>> {
@@ -0,0 +1,123 @@
+use crate::Oid;
+use anyhow::{Context as _, Result, anyhow};
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StashEntry {
+    /// The position of this entry in the stash, as in `stash@{index}`.
+    pub index: usize,
+    pub oid: Oid,
+    pub message: String,
+    /// The branch that was checked out when the entry was created, if any.
+    pub branch: Option<String>,
+    /// This is a unix timestamp
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct GitStash {
+    pub entries: Arc<[StashEntry]>,
+}
+
+impl GitStash {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Parses the output of `git stash list --format=%gd%x00%H%x00%ct%x00%gs`.
+impl FromStr for GitStash {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for line in s.split('\n') {
+            if line.is_empty() {
+                continue;
+            }
+            let mut fields = line.split('\0');
+            let selector = fields.next().context("no stash selector")?;
+            let index = selector
+                .strip_prefix("stash@{")
+                .and_then(|rest| rest.strip_suffix('}'))
+                .ok_or_else(|| anyhow!("unexpected stash selector {selector:?}"))?
+                .parse::<usize>()?;
+            let oid = fields.next().context("no stash oid")?.parse::<Oid>()?;
+            let timestamp = fields
+                .next()
+                .context("no stash timestamp")?
+                .parse::<i64>()?;
+            let subject = fields.next().context("no stash subject")?;
+            let (branch, message) = parse_stash_subject(subject);
+            entries.push(StashEntry {
+                index,
+                oid,
+                message: message.to_string(),
+                branch: branch.map(ToString::to_string),
+                timestamp,
+            });
+        }
+        Ok(Self {
+            entries: entries.into(),
+        })
+    }
+}
+
+/// Splits a stash reflog subject such as `On main: message` or
+/// `WIP on main: 1234567 subject` into its branch and message.
+fn parse_stash_subject(subject: &str) -> (Option<&str>, &str) {
+    let rest = subject
+        .strip_prefix("WIP on ")
+        .or_else(|| subject.strip_prefix("On "));
+    let Some(rest) = rest else {
+        return (None, subject);
+    };
+    match rest.split_once(": ") {
+        Some(("(no branch)", message)) => (None, message),
+        Some((branch, message)) => (Some(branch), message),
+        None => (None, subject),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stash_list_parsing() {
+        // suppress "help: octal escapes are not supported, `\0` is always null"
+        #[allow(clippy::octal_escapes)]
+        let input = "stash@{0}\0060964da10574cd9bf06463a53bf6e0769c5c45e\01733187470\0On main: fix the tests\n\
+                     stash@{1}\0ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da\01733180000\0WIP on feature/x: 1234567 add stuff\n\
+                     stash@{2}\0bd6e0769c5c45e060964da10574cd9bf06463a53\01733170000\0On (no branch): detached\n";
+        let stash = input.parse::<GitStash>().unwrap();
+        assert_eq!(
+            stash.entries.as_ref(),
+            &[
+                StashEntry {
+                    index: 0,
+                    oid: "060964da10574cd9bf06463a53bf6e0769c5c45e".parse().unwrap(),
+                    message: "fix the tests".into(),
+                    branch: Some("main".into()),
+                    timestamp: 1733187470,
+                },
+                StashEntry {
+                    index: 1,
+                    oid: "ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da".parse().unwrap(),
+                    message: "1234567 add stuff".into(),
+                    branch: Some("feature/x".into()),
+                    timestamp: 1733180000,
+                },
+                StashEntry {
+                    index: 2,
+                    oid: "bd6e0769c5c45e060964da10574cd9bf06463a53".parse().unwrap(),
+                    message: "detached".into(),
+                    branch: None,
+                    timestamp: 1733170000,
+                },
+            ]
+        );
+        assert!("".parse::<GitStash>().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{Context as _, Result};
+use gpui::SharedString;
+use std::path::PathBuf;
+
+/// A working tree attached to a repository, as listed by `git worktree list`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GitWorktree {
+    pub path: PathBuf,
+    pub head_sha: Option<SharedString>,
+    /// The checked-out branch, or `None` if HEAD is detached.
+    pub branch: Option<SharedString>,
+    /// Whether this is the repository's main working tree, as opposed to a linked one.
+    pub is_main: bool,
+    pub is_locked: bool,
+}
+
+impl GitWorktree {
+    /// The name to display for this worktree: its branch, or its directory name if detached.
+    pub fn display_name(&self) -> SharedString {
+        self.branch.clone().unwrap_or_else(|| {
+            self.path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned().into())
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Parses the output of `git worktree list --porcelain`.
+///
+/// Bare repositories have no working tree, so they are left out.
+pub fn parse_worktree_list(input: &str) -> Result<Vec<GitWorktree>> {
+    let mut worktrees = Vec::new();
+    for (ix, record) in input.split("\n\n").enumerate() {
+        if record.trim().is_empty() {
+            continue;
+        }
+        let mut lines = record.lines();
+        let path = lines
+            .next()
+            .and_then(|line| line.strip_prefix("worktree "))
+            .context("no worktree path")?;
+        let mut worktree = GitWorktree {
+            path: PathBuf::from(path),
+            head_sha: None,
+            branch: None,
+            is_main: ix == 0,
+            is_locked: false,
+        };
+        let mut is_bare = false;
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "HEAD" => worktree.head_sha = Some(value.to_string().into()),
+                "branch" => {
+                    let branch = value.strip_prefix("refs/heads/").unwrap_or(value);
+                    worktree.branch = Some(branch.to_string().into());
+                }
+                "locked" => worktree.is_locked = true,
+                "bare" => is_bare = true,
+                _ => {}
+            }
+        }
+        if !is_bare {
+            worktrees.push(worktree);
+        }
+    }
+    Ok(worktrees)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worktree_list_parsing() {
+        let input = "worktree /home/me/zed\n\
+                     HEAD 060964da10574cd9bf06463a53bf6e0769c5c45e\n\
+                     branch refs/heads/main\n\
+                     \n\
+                     worktree /home/me/zed-feature-x\n\
+                     HEAD ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da\n\
+                     branch refs/heads/feature/x\n\
+                     locked on a removable drive\n\
+                     \n\
+                     worktree /home/me/zed-review\n\
+                     HEAD bd6e0769c5c45e060964da10574cd9bf06463a53\n\
+                     detached\n\
+                     \n";
+        assert_eq!(
+            parse_worktree_list(input).unwrap(),
+            vec![
+                GitWorktree {
+                    path: PathBuf::from("/home/me/zed"),
+                    head_sha: Some("060964da10574cd9bf06463a53bf6e0769c5c45e".into()),
+                    branch: Some("main".into()),
+                    is_main: true,
+                    is_locked: false,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/home/me/zed-feature-x"),
+                    head_sha: Some("ed0fd4bea2bc2bdf3a0e6b55ed8af6bc2ca6d1da".into()),
+                    branch: Some("feature/x".into()),
+                    is_main: false,
+                    is_locked: true,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/home/me/zed-review"),
+                    head_sha: Some("bd6e0769c5c45e060964da10574cd9bf06463a53".into()),
+                    branch: None,
+                    is_main: false,
+                    is_locked: false,
+                },
+            ]
+        );
+
+        let bare = "worktree /home/me/zed.git\nbare\n\n\
+                    worktree /home/me/zed-main\nHEAD 060964da10574cd9bf06463a53bf6e0769c5c45e\nbranch refs/heads/main\n";
+        let worktrees = parse_worktree_list(bare).unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert!(!worktrees[0].is_main);
+    }
+}
@@ -1,18 +1,19 @@
 use anyhow::{Context as _, anyhow};
+use futures::channel::oneshot;
 use fuzzy::StringMatchCandidate;
 
-use git::repository::Branch;
+use git::repository::{Branch, Tag};
 use gpui::{
     App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, Render, SharedString, Styled,
-    Subscription, Task, Window, rems,
+    IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, PromptLevel, Render,
+    SharedString, Styled, Subscription, Task, Window, rems,
 };
 use picker::{Picker, PickerDelegate, PickerEditorPosition};
 use project::git_store::Repository;
 use std::sync::Arc;
 use time::OffsetDateTime;
 use time_format::format_local_timestamp;
-use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
 use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
@@ -89,11 +90,25 @@ impl BranchList {
         let all_branches_request = repository
             .clone()
             .map(|repository| repository.update(cx, |repository, _| repository.branches()));
+        let all_tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.tags()));
+        let remotes_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.get_remotes(None)));
 
         cx.spawn_in(window, async move |this, cx| {
             let mut all_branches = all_branches_request
                 .context("No active repository")?
                 .await??;
+            let all_tags = match all_tags_request {
+                Some(request) => request.await?.log_err().unwrap_or_default(),
+                None => Vec::new(),
+            };
+            let remotes = match remotes_request {
+                Some(request) => request.await?.log_err().unwrap_or_default(),
+                None => Vec::new(),
+            };
 
             all_branches.sort_by_key(|branch| {
                 branch
@@ -105,6 +120,9 @@ impl BranchList {
             this.update_in(cx, |this, window, cx| {
                 this.picker.update(cx, |picker, cx| {
                     picker.delegate.all_branches = Some(all_branches);
+                    picker.delegate.all_tags = all_tags;
+                    picker.delegate.remotes =
+                        remotes.into_iter().map(|remote| remote.name).collect();
                     picker.refresh(window, cx);
                 })
             })?;
@@ -163,17 +181,50 @@ impl Render for BranchList {
 }
 
 #[derive(Debug, Clone)]
-struct BranchEntry {
-    branch: Branch,
-    positions: Vec<usize>,
-    is_new: bool,
+enum BranchEntry {
+    Branch {
+        branch: Branch,
+        positions: Vec<usize>,
+    },
+    NewBranch {
+        name: SharedString,
+    },
+    Tag {
+        tag: Tag,
+        positions: Vec<usize>,
+    },
+    /// A tag to create at HEAD, annotated if it has a message.
+    NewTag {
+        name: SharedString,
+        message: Option<SharedString>,
+    },
+    RenameBranch {
+        old_name: SharedString,
+        new_name: SharedString,
+    },
+    UnsetUpstream {
+        branch_name: SharedString,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PickerMode {
+    /// Switch branches, or create a branch or tag from the query.
+    Default,
+    /// Type a new name for the given branch.
+    Rename(SharedString),
+    /// Pick a remote branch as the upstream of the given branch.
+    SetUpstream(Branch),
 }
 
 pub struct BranchListDelegate {
     matches: Vec<BranchEntry>,
     all_branches: Option<Vec<Branch>>,
+    all_tags: Vec<Tag>,
+    remotes: Vec<SharedString>,
     repo: Option<Entity<Repository>>,
     style: BranchListStyle,
+    mode: PickerMode,
     selected_index: usize,
     last_query: String,
     modifiers: Modifiers,
@@ -185,13 +236,254 @@ impl BranchListDelegate {
             matches: vec![],
             repo,
             style,
+            mode: PickerMode::Default,
             all_branches: None,
+            all_tags: Vec::new(),
+            remotes: Vec::new(),
             selected_index: 0,
             last_query: Default::default(),
             modifiers: Default::default(),
         }
     }
 
+    fn is_remote_branch(&self, branch: &Branch) -> bool {
+        self.remotes.iter().any(|remote| {
+            branch
+                .name
+                .strip_prefix(remote.as_ref())
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    fn set_mode(&mut self, mode: PickerMode, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.mode = mode;
+        self.selected_index = 0;
+        cx.defer_in(window, |picker, window, cx| {
+            picker.set_query("", window, cx);
+            picker.refresh_placeholder(window, cx);
+            picker.refresh(window, cx);
+        });
+    }
+
+    /// Runs a repository operation, then dismisses the picker or reports the error.
+    fn run_and_dismiss(
+        &self,
+        error_message: &'static str,
+        operation: impl FnOnce(&mut Repository) -> oneshot::Receiver<anyhow::Result<()>> + 'static,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| operation(repo))?.await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err(error_message, window, cx, |e, _, _| Some(e.to_string()));
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_branch(
+        &self,
+        branch_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let prompt = window.prompt(
+            PromptLevel::Warning,
+            &format!("Delete branch \"{branch_name}\"?"),
+            None,
+            &["Delete", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |picker, cx| {
+            if prompt.await? != 0 {
+                return Ok(());
+            }
+            let result = repo
+                .update(cx, |repo, _| {
+                    repo.delete_branch(branch_name.to_string(), false)
+                })?
+                .await?;
+            if let Err(error) = result {
+                if !error
+                    .to_string()
+                    .contains(git::repository::BRANCH_NOT_MERGED)
+                {
+                    return Err(error);
+                }
+                let prompt = cx.update(|window, cx| {
+                    window.prompt(
+                        PromptLevel::Warning,
+                        &format!("Branch \"{branch_name}\" is not fully merged."),
+                        Some("Its unmerged commits will be lost."),
+                        &["Force Delete", "Cancel"],
+                        cx,
+                    )
+                })?;
+                if prompt.await? != 0 {
+                    return Ok(());
+                }
+                repo.update(cx, |repo, _| {
+                    repo.delete_branch(branch_name.to_string(), true)
+                })?
+                .await??;
+            }
+            picker.update_in(cx, |picker, window, cx| {
+                if let Some(all_branches) = picker.delegate.all_branches.as_mut() {
+                    all_branches.retain(|branch| branch.name != branch_name);
+                }
+                picker.refresh(window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to delete branch", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn delete_tag(
+        &self,
+        tag_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let prompt = window.prompt(
+            PromptLevel::Warning,
+            &format!("Delete tag \"{tag_name}\"?"),
+            None,
+            &["Delete", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |picker, cx| {
+            if prompt.await? != 0 {
+                return Ok(());
+            }
+            repo.update(cx, |repo, _| repo.delete_tag(tag_name.to_string()))?
+                .await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_tags.retain(|tag| tag.name != tag_name);
+                picker.refresh(window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to delete tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn push_tag(
+        &self,
+        tag_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        window.dispatch_action(
+            Box::new(git::PushTag {
+                name: tag_name.to_string(),
+            }),
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+
+    fn render_entry_actions(
+        &self,
+        ix: usize,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        if self.mode != PickerMode::Default {
+            return None;
+        }
+        match self.matches.get(ix)? {
+            BranchEntry::Branch { branch, .. } if !self.is_remote_branch(branch) => {
+                let branch = branch.clone();
+                Some(
+                    h_flex()
+                        .gap_0p5()
+                        .child(
+                            IconButton::new(("rename-branch", ix), IconName::Pencil)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Rename Branch"))
+                                .on_click(cx.listener({
+                                    let name = branch.name.clone();
+                                    move |picker, _, window, cx| {
+                                        picker.delegate.set_mode(
+                                            PickerMode::Rename(name.clone()),
+                                            window,
+                                            cx,
+                                        );
+                                    }
+                                })),
+                        )
+                        .child(
+                            IconButton::new(("set-upstream", ix), IconName::ArrowUpRight)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Set Upstream"))
+                                .on_click(cx.listener({
+                                    let branch = branch.clone();
+                                    move |picker, _, window, cx| {
+                                        picker.delegate.set_mode(
+                                            PickerMode::SetUpstream(branch.clone()),
+                                            window,
+                                            cx,
+                                        );
+                                    }
+                                })),
+                        )
+                        .when(!branch.is_head, |this| {
+                            this.child(
+                                IconButton::new(("delete-branch", ix), IconName::Trash)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::text("Delete Branch"))
+                                    .on_click(cx.listener(move |picker, _, window, cx| {
+                                        picker.delegate.delete_branch(
+                                            branch.name.clone(),
+                                            window,
+                                            cx,
+                                        );
+                                    })),
+                            )
+                        })
+                        .into_any_element(),
+                )
+            }
+            BranchEntry::Tag { tag, .. } => {
+                let name = tag.name.clone();
+                Some(
+                    h_flex()
+                        .gap_0p5()
+                        .child(
+                            IconButton::new(("push-tag", ix), IconName::ArrowUp)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Push Tag"))
+                                .on_click(cx.listener({
+                                    let name = name.clone();
+                                    move |picker, _, window, cx| {
+                                        picker.delegate.push_tag(name.clone(), window, cx);
+                                    }
+                                })),
+                        )
+                        .child(
+                            IconButton::new(("delete-tag", ix), IconName::Trash)
+                                .icon_size(IconSize::Small)
+                                .tooltip(Tooltip::text("Delete Tag"))
+                                .on_click(cx.listener(move |picker, _, window, cx| {
+                                    picker.delegate.delete_tag(name.clone(), window, cx);
+                                })),
+                        )
+                        .into_any_element(),
+                )
+            }
+            _ => None,
+        }
+    }
+
     fn create_branch(
         &self,
         new_branch_name: SharedString,
@@ -224,7 +516,13 @@ impl PickerDelegate for BranchListDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Select branch...".into()
+        match &self.mode {
+            PickerMode::Default => "Select branch...".into(),
+            PickerMode::Rename(name) => format!("Rename {name} to...").into(),
+            PickerMode::SetUpstream(branch) => {
+                format!("Select upstream for {}...", branch.name).into()
+            }
+        }
     }
 
     fn editor_position(&self) -> PickerEditorPosition {
@@ -261,17 +559,53 @@ impl PickerDelegate for BranchListDelegate {
             return Task::ready(());
         };
 
+        if let PickerMode::Rename(old_name) = &self.mode {
+            let new_name = query.trim();
+            self.matches = if new_name.is_empty() {
+                Vec::new()
+            } else {
+                vec![BranchEntry::RenameBranch {
+                    old_name: old_name.clone(),
+                    new_name: new_name.to_string().into(),
+                }]
+            };
+            self.selected_index = 0;
+            self.last_query = query;
+            return Task::ready(());
+        }
+
+        let (all_branches, all_tags, unset_upstream) = match &self.mode {
+            PickerMode::SetUpstream(branch) => (
+                all_branches
+                    .into_iter()
+                    .filter(|candidate| self.is_remote_branch(candidate))
+                    .collect::<Vec<_>>(),
+                Vec::new(),
+                branch
+                    .upstream
+                    .is_some()
+                    .then(|| BranchEntry::UnsetUpstream {
+                        branch_name: branch.name.clone(),
+                    }),
+            ),
+            _ => (all_branches, self.all_tags.clone(), None),
+        };
+        let is_default_mode = self.mode == PickerMode::Default;
+
         const RECENT_BRANCHES_COUNT: usize = 10;
         cx.spawn_in(window, async move |picker, cx| {
             let mut matches: Vec<BranchEntry> = if query.is_empty() {
-                all_branches
+                unset_upstream
                     .into_iter()
-                    .take(RECENT_BRANCHES_COUNT)
-                    .map(|branch| BranchEntry {
-                        branch,
-                        positions: Vec::new(),
-                        is_new: false,
-                    })
+                    .chain(
+                        all_branches
+                            .into_iter()
+                            .take(RECENT_BRANCHES_COUNT)
+                            .map(|branch| BranchEntry::Branch {
+                                branch,
+                                positions: Vec::new(),
+                            }),
+                    )
                     .collect()
             } else {
                 let candidates = all_branches
@@ -279,7 +613,12 @@ impl PickerDelegate for BranchListDelegate {
                     .enumerate()
                     .map(|(ix, command)| StringMatchCandidate::new(ix, &command.name.clone()))
                     .collect::<Vec<StringMatchCandidate>>();
-                fuzzy::match_strings(
+                let tag_candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                let branch_matches = fuzzy::match_strings(
                     &candidates,
                     &query,
                     true,
@@ -287,34 +626,53 @@ impl PickerDelegate for BranchListDelegate {
                     &Default::default(),
                     cx.background_executor().clone(),
                 )
-                .await
-                .iter()
-                .cloned()
-                .map(|candidate| BranchEntry {
-                    branch: all_branches[candidate.candidate_id].clone(),
-                    positions: candidate.positions,
-                    is_new: false,
-                })
-                .collect()
+                .await;
+                let tag_matches = fuzzy::match_strings(
+                    &tag_candidates,
+                    &query,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await;
+                branch_matches
+                    .into_iter()
+                    .map(|candidate| BranchEntry::Branch {
+                        branch: all_branches[candidate.candidate_id].clone(),
+                        positions: candidate.positions,
+                    })
+                    .chain(tag_matches.into_iter().map(|candidate| BranchEntry::Tag {
+                        tag: all_tags[candidate.candidate_id].clone(),
+                        positions: candidate.positions,
+                    }))
+                    .collect()
             };
             picker
                 .update(cx, |picker, _| {
-                    #[allow(clippy::nonminimal_bool)]
-                    if !query.is_empty()
-                        && !matches
-                            .first()
-                            .is_some_and(|entry| entry.branch.name == query)
-                    {
-                        matches.push(BranchEntry {
-                            branch: Branch {
-                                name: query.clone().into(),
-                                is_head: false,
-                                upstream: None,
-                                most_recent_commit: None,
-                            },
-                            positions: Vec::new(),
-                            is_new: true,
-                        })
+                    if is_default_mode && !query.trim().is_empty() {
+                        // Branch and tag names can't contain whitespace, so anything after
+                        // the first word becomes the message of an annotated tag.
+                        let (name, message) = match query.trim().split_once(char::is_whitespace) {
+                            Some((name, message)) => (name, Some(message.trim())),
+                            None => (query.trim(), None),
+                        };
+                        let exists = matches.iter().any(|entry| match entry {
+                            BranchEntry::Branch { branch, .. } => branch.name == name,
+                            BranchEntry::Tag { tag, .. } => tag.name == name,
+                            _ => false,
+                        });
+                        if !exists {
+                            if message.is_none() {
+                                matches.push(BranchEntry::NewBranch {
+                                    name: name.to_string().into(),
+                                });
+                            }
+                            matches.push(BranchEntry::NewTag {
+                                name: name.to_string().into(),
+                                message: message.map(|message| message.to_string().into()),
+                            });
+                        }
                     }
                     let delegate = &mut picker.delegate;
                     delegate.matches = matches;
@@ -334,8 +692,57 @@ impl PickerDelegate for BranchListDelegate {
         let Some(entry) = self.matches.get(self.selected_index()) else {
             return;
         };
-        if entry.is_new {
-            self.create_branch(entry.branch.name.clone(), window, cx);
+        let branch = match entry {
+            BranchEntry::Branch { branch, .. } => branch.clone(),
+            BranchEntry::NewBranch { name } => {
+                self.create_branch(name.clone(), window, cx);
+                return;
+            }
+            // Tags are managed through the buttons on their entries.
+            BranchEntry::Tag { .. } => return,
+            BranchEntry::NewTag { name, message } => {
+                let name = name.to_string();
+                let message = message.as_ref().map(ToString::to_string);
+                self.run_and_dismiss(
+                    "Failed to create tag",
+                    move |repo| repo.create_tag(name, None, message),
+                    window,
+                    cx,
+                );
+                return;
+            }
+            BranchEntry::RenameBranch { old_name, new_name } => {
+                let old_name = old_name.to_string();
+                let new_name = new_name.to_string();
+                self.run_and_dismiss(
+                    "Failed to rename branch",
+                    move |repo| repo.rename_branch(old_name, new_name),
+                    window,
+                    cx,
+                );
+                return;
+            }
+            BranchEntry::UnsetUpstream { branch_name } => {
+                let branch_name = branch_name.to_string();
+                self.run_and_dismiss(
+                    "Failed to unset upstream",
+                    move |repo| repo.set_upstream(branch_name, None),
+                    window,
+                    cx,
+                );
+                return;
+            }
+        };
+
+        if let PickerMode::SetUpstream(local_branch) = &self.mode {
+            let branch_name = local_branch.name.to_string();
+            let upstream = branch.name.to_string();
+            self.run_and_dismiss(
+                "Failed to set upstream",
+                move |repo| repo.set_upstream(branch_name, Some(upstream)),
+                window,
+                cx,
+            );
             return;
         }
 
@@ -347,14 +754,13 @@ impl PickerDelegate for BranchListDelegate {
 
         if current_branch
             .flatten()
-            .is_some_and(|current_branch| current_branch == entry.branch.name)
+            .is_some_and(|current_branch| current_branch == branch.name)
         {
             cx.emit(DismissEvent);
             return;
         }
 
         cx.spawn_in(window, {
-            let branch = entry.branch.clone();
             async move |picker, cx| {
                 let branch_change_task = picker.update(cx, |this, cx| {
                     let repo = this
@@ -403,22 +809,91 @@ impl PickerDelegate for BranchListDelegate {
     ) -> Option<Self::ListItem> {
         let entry = &self.matches[ix];
 
-        let (commit_time, subject) = entry
-            .branch
-            .most_recent_commit
-            .as_ref()
-            .map(|commit| {
-                let subject = commit.subject.clone();
-                let commit_time = OffsetDateTime::from_unix_timestamp(commit.commit_timestamp)
-                    .unwrap_or_else(|_| OffsetDateTime::now_utc());
-                let formatted_time = format_local_timestamp(
-                    commit_time,
-                    OffsetDateTime::now_utc(),
-                    time_format::TimestampFormat::Relative,
-                );
-                (Some(formatted_time), Some(subject))
+        let (commit_time, subject) = match entry {
+            BranchEntry::Branch { branch, .. } => branch
+                .most_recent_commit
+                .as_ref()
+                .map(|commit| {
+                    (
+                        Some(format_timestamp(commit.commit_timestamp)),
+                        Some(commit.subject.clone()),
+                    )
+                })
+                .unwrap_or_else(|| (None, None)),
+            BranchEntry::Tag { tag, .. } => (Some(format_timestamp(tag.timestamp)), None),
+            _ => (None, None),
+        };
+
+        let title = match entry {
+            BranchEntry::Branch { branch, positions } => {
+                HighlightedLabel::new(branch.name.clone(), positions.clone())
+                    .truncate()
+                    .into_any_element()
+            }
+            BranchEntry::Tag { tag, positions } => h_flex()
+                .gap_1()
+                .child(
+                    Icon::new(IconName::Hash)
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(HighlightedLabel::new(tag.name.clone(), positions.clone()).truncate())
+                .into_any_element(),
+            BranchEntry::NewBranch { name } => Label::new(format!("Create branch \"{name}\"…"))
+                .single_line()
+                .into_any_element(),
+            BranchEntry::NewTag { name, message } => Label::new(if message.is_some() {
+                format!("Create annotated tag \"{name}\"…")
+            } else {
+                format!("Create tag \"{name}\"…")
             })
-            .unwrap_or_else(|| (None, None));
+            .single_line()
+            .into_any_element(),
+            BranchEntry::RenameBranch { old_name, new_name } => {
+                Label::new(format!("Rename \"{old_name}\" to \"{new_name}\""))
+                    .single_line()
+                    .into_any_element()
+            }
+            BranchEntry::UnsetUpstream { branch_name } => {
+                Label::new(format!("Unset upstream of \"{branch_name}\""))
+                    .single_line()
+                    .into_any_element()
+            }
+        };
+
+        let current_branch = || {
+            self.repo
+                .as_ref()
+                .and_then(|repo| repo.read(cx).branch.as_ref().map(|b| b.name.clone()))
+        };
+        let message = match entry {
+            BranchEntry::Branch { .. } => subject.unwrap_or("no commits found".into()).to_string(),
+            BranchEntry::Tag { tag, .. } => tag
+                .message
+                .clone()
+                .unwrap_or_else(|| {
+                    format!(
+                        "at {}",
+                        &tag.sha[..tag.sha.len().min(git::SHORT_SHA_LENGTH)]
+                    )
+                    .into()
+                })
+                .to_string(),
+            BranchEntry::NewBranch { .. } | BranchEntry::NewTag { message: None, .. } => {
+                if let Some(current_branch) = current_branch() {
+                    format!("based off {}", current_branch)
+                } else {
+                    "based off the current branch".to_string()
+                }
+            }
+            BranchEntry::NewTag {
+                message: Some(message),
+                ..
+            } => message.to_string(),
+            BranchEntry::RenameBranch { .. } | BranchEntry::UnsetUpstream { .. } => String::new(),
+        };
+
+        let actions = self.render_entry_actions(ix, cx);
 
         Some(
             ListItem::new(SharedString::from(format!("vcs-menu-{ix}")))
@@ -429,6 +904,13 @@ impl PickerDelegate for BranchListDelegate {
                 })
                 .spacing(ListItemSpacing::Sparse)
                 .toggle_state(selected)
+                .map(|item| {
+                    if selected {
+                        item.end_slot::<AnyElement>(actions)
+                    } else {
+                        item.end_hover_slot::<AnyElement>(actions)
+                    }
+                })
                 .child(
                     v_flex()
                         .w_full()
@@ -439,23 +921,7 @@ impl PickerDelegate for BranchListDelegate {
                                 .overflow_x_hidden()
                                 .gap_2()
                                 .justify_between()
-                                .child(div().flex_shrink().overflow_x_hidden().child(
-                                    if entry.is_new {
-                                        Label::new(format!(
-                                            "Create branch \"{}\"…",
-                                            entry.branch.name
-                                        ))
-                                        .single_line()
-                                        .into_any_element()
-                                    } else {
-                                        HighlightedLabel::new(
-                                            entry.branch.name.clone(),
-                                            entry.positions.clone(),
-                                        )
-                                        .truncate()
-                                        .into_any_element()
-                                    },
-                                ))
+                                .child(div().flex_shrink().overflow_x_hidden().child(title))
                                 .when_some(commit_time, |el, commit_time| {
                                     el.child(
                                         Label::new(commit_time)
@@ -465,27 +931,19 @@ impl PickerDelegate for BranchListDelegate {
                                     )
                                 }),
                         )
-                        .when(self.style == BranchListStyle::Modal, |el| {
-                            el.child(div().max_w_96().child({
-                                let message = if entry.is_new {
-                                    if let Some(current_branch) =
-                                        self.repo.as_ref().and_then(|repo| {
-                                            repo.read(cx).branch.as_ref().map(|b| b.name.clone())
-                                        })
-                                    {
-                                        format!("based off {}", current_branch)
-                                    } else {
-                                        "based off the current branch".to_string()
-                                    }
-                                } else {
-                                    subject.unwrap_or("no commits found".into()).to_string()
-                                };
-                                Label::new(message)
-                                    .size(LabelSize::Small)
-                                    .truncate()
-                                    .color(Color::Muted)
-                            }))
-                        }),
+                        .when(
+                            self.style == BranchListStyle::Modal && !message.is_empty(),
+                            |el| {
+                                el.child(
+                                    div().max_w_96().child(
+                                        Label::new(message)
+                                            .size(LabelSize::Small)
+                                            .truncate()
+                                            .color(Color::Muted),
+                                    ),
+                                )
+                            },
+                        ),
                 ),
         )
     }
@@ -494,3 +952,13 @@ impl PickerDelegate for BranchListDelegate {
         None
     }
 }
+
+fn format_timestamp(timestamp: i64) -> String {
+    let time = OffsetDateTime::from_unix_timestamp(timestamp)
+        .unwrap_or_else(|_| OffsetDateTime::now_utc());
+    format_local_timestamp(
+        time,
+        OffsetDateTime::now_utc(),
+        time_format::TimestampFormat::Relative,
+    )
+}
@@ -0,0 +1,408 @@
+use git::repository::{CommitDetails, CommitSummary, LogOptions, RepoPath};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    ParentElement, Render, SharedString, Styled, Subscription, Task, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+use crate::{commit_view::CommitView, interactive_rebase};
+
+/// The number of commits loaded at a time.
+const PAGE_SIZE: usize = 100;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(view_history);
+    workspace.register_action(file_history);
+    workspace.register_action(interactive_rebase);
+}
+
+fn view_history(
+    workspace: &mut Workspace,
+    _: &git::ViewHistory,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    open(workspace, repository, None, false, window, cx);
+}
+
+fn interactive_rebase(
+    workspace: &mut Workspace,
+    _: &git::InteractiveRebase,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    open(workspace, repository, None, true, window, cx);
+}
+
+fn file_history(
+    workspace: &mut Workspace,
+    _: &git::FileHistory,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(project_path) = workspace
+        .active_item(cx)
+        .and_then(|item| item.project_path(cx))
+    else {
+        return;
+    };
+    let Some((repository, repo_path)) = workspace
+        .project()
+        .read(cx)
+        .git_store()
+        .read(cx)
+        .repository_and_path_for_project_path(&project_path, cx)
+    else {
+        return;
+    };
+    open(workspace, repository, Some(repo_path), false, window, cx);
+}
+
+/// Opens the commit history. In rebase mode, confirming a commit starts an interactive
+/// rebase of every commit from that one up to HEAD.
+fn open(
+    workspace: &mut Workspace,
+    repository: Entity<Repository>,
+    path: Option<RepoPath>,
+    rebase_mode: bool,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let workspace_handle = cx.entity().downgrade();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        CommitHistory::new(repository, path, rebase_mode, workspace_handle, window, cx)
+    })
+}
+
+pub struct CommitHistory {
+    picker: Entity<Picker<CommitHistoryDelegate>>,
+    _subscription: Subscription,
+}
+
+impl CommitHistory {
+    fn new(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        rebase_mode: bool,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = CommitHistoryDelegate::new(repository, path, rebase_mode, workspace);
+        let picker = cx.new(|cx| {
+            if rebase_mode {
+                Picker::nonsearchable_uniform_list(delegate, window, cx)
+            } else {
+                Picker::uniform_list(delegate, window, cx)
+            }
+        });
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for CommitHistory {}
+impl EventEmitter<DismissEvent> for CommitHistory {}
+
+impl Focusable for CommitHistory {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for CommitHistory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(40.))
+            .child(self.picker.clone())
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.cancel(&Default::default(), window, cx);
+                })
+            }))
+    }
+}
+
+pub struct CommitHistoryDelegate {
+    repository: Entity<Repository>,
+    path: Option<RepoPath>,
+    rebase_mode: bool,
+    workspace: WeakEntity<Workspace>,
+    commits: Vec<CommitDetails>,
+    /// Whether the last page was full, meaning more commits may be available.
+    has_more: bool,
+    selected_index: usize,
+    last_query: String,
+}
+
+impl CommitHistoryDelegate {
+    fn new(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        rebase_mode: bool,
+        workspace: WeakEntity<Workspace>,
+    ) -> Self {
+        Self {
+            repository,
+            path,
+            rebase_mode,
+            workspace,
+            commits: Vec::new(),
+            has_more: false,
+            selected_index: 0,
+            last_query: String::new(),
+        }
+    }
+
+    /// Builds log options from a query, where `author:<pattern>` filters by author and the
+    /// remaining words filter by commit message.
+    fn log_options(&self, query: &str, skip: usize) -> LogOptions {
+        let mut author = None;
+        let mut message = Vec::new();
+        for word in query.split_whitespace() {
+            if let Some(pattern) = word.strip_prefix("author:") {
+                author = Some(pattern.to_string()).filter(|pattern| !pattern.is_empty());
+            } else {
+                message.push(word);
+            }
+        }
+        LogOptions {
+            skip,
+            limit: PAGE_SIZE,
+            path: self.path.clone(),
+            author,
+            message: (!message.is_empty()).then(|| message.join(" ")),
+        }
+    }
+
+    /// Opens the interactive rebase modal for the selected commit and every commit after it.
+    fn interactive_rebase(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        // A filtered history doesn't contain every commit, and rebasing it would drop the
+        // missing ones.
+        if self.path.is_some() || !self.last_query.is_empty() {
+            return;
+        }
+        let Some(commits) = self.commits.get(..=self.selected_index) else {
+            return;
+        };
+        let Some(oldest) = commits.last() else {
+            return;
+        };
+        // The last commit of the whole history is the root one, which has no parent to
+        // rebase onto.
+        let is_root = !self.has_more && self.selected_index + 1 == self.commits.len();
+        let onto = (!is_root).then(|| format!("{}^", oldest.sha));
+        let commits = commits
+            .iter()
+            .rev()
+            .map(|commit| (commit.sha.clone(), commit_subject(commit)))
+            .collect::<Vec<_>>();
+        let repository = self.repository.clone();
+        let workspace = self.workspace.clone();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .update(cx, |workspace, cx| {
+                    interactive_rebase::open(workspace, repository, onto, commits, window, cx)
+                })
+                .log_err();
+        });
+    }
+
+    fn load_more(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let options = self.log_options(&self.last_query, self.commits.len());
+        let request = self
+            .repository
+            .update(cx, |repository, _| repository.log(options));
+        cx.spawn_in(window, async move |picker, cx| {
+            let commits = request.await??;
+            picker.update(cx, |picker, cx| {
+                picker.delegate.has_more = commits.len() == PAGE_SIZE;
+                picker.delegate.commits.extend(commits);
+                cx.notify();
+            })
+        })
+        .detach_and_prompt_err("Failed to load commits", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+}
+
+impl PickerDelegate for CommitHistoryDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        if self.rebase_mode {
+            return "Select the oldest commit to rebase...".into();
+        }
+        match &self.path {
+            Some(path) => format!("Search history of {}...", path.display()).into(),
+            None => "Search commits (author:<name> to filter by author)...".into(),
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.commits.len() + usize::from(self.has_more)
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let options = self.log_options(&query, 0);
+        let request = self
+            .repository
+            .update(cx, |repository, _| repository.log(options));
+        cx.spawn_in(window, async move |picker, cx| {
+            let commits = request.await.ok().and_then(|result| result.log_err());
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    let commits = commits.unwrap_or_default();
+                    delegate.has_more = commits.len() == PAGE_SIZE;
+                    delegate.commits = commits;
+                    delegate.selected_index = 0;
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(commit) = self.commits.get(self.selected_index) else {
+            if self.has_more {
+                self.load_more(window, cx);
+            }
+            return;
+        };
+        if secondary || self.rebase_mode {
+            self.interactive_rebase(window, cx);
+            return;
+        }
+        CommitView::open(
+            CommitSummary {
+                sha: commit.sha.clone(),
+                subject: commit_subject(commit),
+                commit_timestamp: commit.commit_timestamp,
+                has_parent: true,
+            },
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(SharedString::from(format!("commit-history-{ix}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        let Some(commit) = self.commits.get(ix) else {
+            return Some(
+                item.child(
+                    Label::new("Load more commits…")
+                        .color(Color::Muted)
+                        .single_line(),
+                ),
+            );
+        };
+
+        let commit_time = OffsetDateTime::from_unix_timestamp(commit.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let formatted_time = format_local_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+
+        Some(
+            item.child(
+                v_flex()
+                    .w_full()
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .justify_between()
+                            .child(
+                                div()
+                                    .flex_shrink()
+                                    .overflow_x_hidden()
+                                    .child(Label::new(commit_subject(commit)).truncate()),
+                            )
+                            .child(
+                                Label::new(commit.short_sha())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            ),
+                    )
+                    .child(
+                        Label::new(format!("{} · {}", commit.author_name, formatted_time))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    ),
+            ),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No commits found".into())
+    }
+}
+
+fn commit_subject(commit: &CommitDetails) -> SharedString {
+    commit
+        .message
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+        .into()
+}
@@ -2,9 +2,11 @@ use anyhow::{Result, anyhow};
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use editor::{Editor, EditorEvent, MultiBuffer};
 use git::repository::{CommitDetails, CommitDiff, CommitSummary, RepoPath};
+use git::{CherryPickCommit, RevertCommit};
 use gpui::{
     AnyElement, AnyView, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter,
-    FocusHandle, Focusable, IntoElement, Render, WeakEntity, Window,
+    FocusHandle, Focusable, InteractiveElement, IntoElement, ParentElement, Render, Styled,
+    WeakEntity, Window, div,
 };
 use language::{
     Anchor, Buffer, Capability, DiskState, File, LanguageRegistry, LineEnding, OffsetRangeExt as _,
@@ -24,6 +26,7 @@ use util::{ResultExt, truncate_and_trailoff};
 use workspace::{
     Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
     item::{BreadcrumbText, ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
     searchable::SearchableItemHandle,
 };
 
@@ -31,6 +34,7 @@ pub struct CommitView {
     commit: CommitDetails,
     editor: Entity<Editor>,
     multibuffer: Entity<MultiBuffer>,
+    repository: WeakEntity<Repository>,
 }
 
 struct GitBlob {
@@ -160,6 +164,7 @@ impl CommitView {
             });
         }
 
+        let weak_repository = repository.downgrade();
         cx.spawn(async move |this, mut cx| {
             for file in commit_diff.files {
                 let is_deleted = file.new_text.is_none();
@@ -211,8 +216,41 @@ impl CommitView {
             commit,
             editor,
             multibuffer,
+            repository: weak_repository,
         }
     }
+
+    fn cherry_pick(&mut self, _: &CherryPickCommit, window: &mut Window, cx: &mut Context<Self>) {
+        let commit = self.commit.sha.to_string();
+        let Some(task) = self
+            .repository
+            .update(cx, |repository, cx| {
+                repository.cherry_pick(vec![commit], cx)
+            })
+            .ok()
+        else {
+            return;
+        };
+        cx.spawn_in(window, async move |_, _| task.await?)
+            .detach_and_prompt_err("Failed to cherry-pick commit", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+    }
+
+    fn revert(&mut self, _: &RevertCommit, window: &mut Window, cx: &mut Context<Self>) {
+        let commit = self.commit.sha.to_string();
+        let Some(task) = self
+            .repository
+            .update(cx, |repository, cx| repository.revert(vec![commit], cx))
+            .ok()
+        else {
+            return;
+        };
+        cx.spawn_in(window, async move |_, _| task.await?)
+            .detach_and_prompt_err("Failed to revert commit", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+    }
 }
 
 impl language::File for GitBlob {
@@ -517,7 +555,11 @@ impl Item for CommitView {
 }
 
 impl Render for CommitView {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
-        self.editor.clone()
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .on_action(cx.listener(Self::cherry_pick))
+            .on_action(cx.listener(Self::revert))
+            .child(self.editor.clone())
     }
 }
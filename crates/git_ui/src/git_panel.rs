@@ -22,11 +22,16 @@ use futures::StreamExt as _;
 use git::blame::ParsedCommitMessage;
 use git::repository::{
     Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, PushOptions, Remote,
-    RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    RemoteCommandOutput, ResetMode, SequencerAction, SequencerOperation, Upstream,
+    UpstreamTracking, UpstreamTrackingStatus,
 };
+use git::stash::GitStash;
 use git::status::StageStatus;
+use git::{
+    AbortOperation, ContinueOperation, ExpandCommitEditor, RestoreTrackedFiles, SkipOperation,
+    StageAll, StashAll, StashPop, TrashUntrackedFiles, UnstageAll,
+};
 use git::{Amend, ToggleStaged, repository::RepoPath, status::FileStatus};
-use git::{ExpandCommitEditor, RestoreTrackedFiles, StageAll, TrashUntrackedFiles, UnstageAll};
 use gpui::{
     Action, Animation, AnimationExt as _, Axis, ClickEvent, Corner, DismissEvent, Entity,
     EventEmitter, FocusHandle, Focusable, KeyContext, ListHorizontalSizingBehavior,
@@ -59,8 +64,8 @@ use std::{collections::HashSet, sync::Arc, time::Duration, usize};
 use strum::{IntoEnumIterator, VariantNames};
 use time::OffsetDateTime;
 use ui::{
-    Checkbox, ContextMenu, ElevationIndex, PopoverMenu, Scrollbar, ScrollbarState, SplitButton,
-    Tooltip, prelude::*,
+    Checkbox, ContextMenu, Disclosure, ElevationIndex, PopoverMenu, Scrollbar, ScrollbarState,
+    SplitButton, Tooltip, prelude::*,
 };
 use util::{ResultExt, TryFutureExt, maybe};
 use workspace::AppState;
@@ -105,11 +110,38 @@ enum TrashCancel {
     Cancel,
 }
 
+#[derive(strum::EnumIter, strum::VariantNames)]
+#[strum(serialize_all = "title_case")]
+enum DropCancel {
+    Drop,
+    Cancel,
+}
+
+#[derive(Clone, Copy)]
+enum StashOperation {
+    Push,
+    Pop(Option<usize>),
+    Apply(usize),
+    Drop(usize),
+}
+
+impl StashOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            StashOperation::Push => "stash",
+            StashOperation::Pop(_) => "stash pop",
+            StashOperation::Apply(_) => "stash apply",
+            StashOperation::Drop(_) => "stash drop",
+        }
+    }
+}
+
 struct GitMenuState {
     has_tracked_changes: bool,
     has_staged_changes: bool,
     has_unstaged_changes: bool,
     has_new_changes: bool,
+    has_stash_entries: bool,
 }
 
 fn git_panel_context_menu(
@@ -136,6 +168,21 @@ fn git_panel_context_menu(
                 }
             })
             .separator()
+            .map(|menu| {
+                if state.has_tracked_changes || state.has_new_changes {
+                    menu.action("Stash All", StashAll.boxed_clone())
+                } else {
+                    menu.disabled_action("Stash All", StashAll.boxed_clone())
+                }
+            })
+            .map(|menu| {
+                if state.has_stash_entries {
+                    menu.action("Pop Stash", StashPop.boxed_clone())
+                } else {
+                    menu.disabled_action("Pop Stash", StashPop.boxed_clone())
+                }
+            })
+            .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
             .separator()
             .map(|menu| {
@@ -349,6 +396,8 @@ pub struct GitPanel {
     marked_entries: Vec<usize>,
     tracked_count: usize,
     tracked_staged_count: usize,
+    stash_entries: GitStash,
+    stash_list_expanded: bool,
     update_visible_entries_task: Task<()>,
     width: Option<Pixels>,
     workspace: WeakEntity<Workspace>,
@@ -515,6 +564,8 @@ impl GitPanel {
             marked_entries: Vec::new(),
             tracked_count: 0,
             tracked_staged_count: 0,
+            stash_entries: GitStash::default(),
+            stash_list_expanded: false,
             update_visible_entries_task: Task::ready(()),
             width: None,
             show_placeholders: false,
@@ -1246,6 +1297,107 @@ impl GitPanel {
         self.change_file_stage(false, entries, cx);
     }
 
+    pub fn stash_all(&mut self, _: &StashAll, _window: &mut Window, cx: &mut Context<Self>) {
+        self.perform_stash_operation(StashOperation::Push, cx);
+    }
+
+    pub fn stash_pop(&mut self, _: &StashPop, _window: &mut Window, cx: &mut Context<Self>) {
+        self.perform_stash_operation(StashOperation::Pop(None), cx);
+    }
+
+    fn drop_stash(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let prompt = prompt::<DropCancel>(
+            &format!("Drop stash@{{{index}}}?"),
+            Some("The stashed changes will be lost."),
+            window,
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            match prompt.await? {
+                DropCancel::Drop => {}
+                DropCancel::Cancel => return Ok(()),
+            }
+            this.update(cx, |this, cx| {
+                this.perform_stash_operation(StashOperation::Drop(index), cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn perform_stash_operation(&mut self, operation: StashOperation, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        cx.spawn(async move |this, cx| {
+            let result = active_repository
+                .update(cx, |repo, cx| match operation {
+                    StashOperation::Push => repo.stash_all(cx),
+                    StashOperation::Pop(index) => repo.stash_pop(index, cx),
+                    StashOperation::Apply(index) => repo.stash_apply(Some(index), cx),
+                    StashOperation::Drop(index) => repo.stash_drop(Some(index), cx),
+                })?
+                .await?;
+            this.update(cx, |this, cx| {
+                if let Err(e) = result {
+                    this.show_error_toast(operation.name(), e, cx);
+                }
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn continue_operation(
+        &mut self,
+        _: &ContinueOperation,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.perform_sequencer_action(SequencerAction::Continue, cx);
+    }
+
+    pub fn abort_operation(
+        &mut self,
+        _: &AbortOperation,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.perform_sequencer_action(SequencerAction::Abort, cx);
+    }
+
+    pub fn skip_operation(
+        &mut self,
+        _: &SkipOperation,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.perform_sequencer_action(SequencerAction::Skip, cx);
+    }
+
+    fn perform_sequencer_action(&mut self, action: SequencerAction, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let Some(operation) = active_repository.read(cx).merge.in_progress_operation else {
+            return;
+        };
+        cx.spawn(async move |this, cx| {
+            let result = active_repository
+                .update(cx, |repo, cx| repo.sequencer_action(operation, action, cx))?
+                .await?;
+            this.update(cx, |this, cx| {
+                if let Err(e) = result {
+                    this.show_error_toast(
+                        format!("{} {}", operation.subcommand(), action.flag()),
+                        e,
+                        cx,
+                    );
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn toggle_staged_for_entry(
         &mut self,
         entry: &GitListEntry,
@@ -2044,6 +2196,59 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        action: &git::PushTag,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let tag_name = SharedString::from(action.name.clone());
+        let remote = self.get_current_remote(window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {} {}", remote.name, tag_name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, cx| {
+                repo.push_tag(tag_name.clone(), remote.name.clone(), askpass_delegate, cx)
+            })?;
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::PushTag(tag_name, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn askpass_delegate(
         &self,
         operation: impl Into<SharedString>,
@@ -2312,6 +2517,7 @@ impl GitPanel {
         self.new_staged_count = 0;
         self.tracked_staged_count = 0;
         self.entry_count = 0;
+        self.stash_entries = GitStash::default();
 
         let sort_by_path = GitPanelSettings::get_global(cx).sort_by_path;
 
@@ -2329,6 +2535,7 @@ impl GitPanel {
         };
 
         let repo = repo.read(cx);
+        self.stash_entries = repo.stash_entries.clone();
 
         for entry in repo.cached_status() {
             let is_conflict = repo.has_conflict(&entry.repo_path);
@@ -2676,6 +2883,7 @@ impl GitPanel {
         let has_staged_changes = self.has_staged_changes();
         let has_unstaged_changes = self.has_unstaged_changes();
         let has_new_changes = self.new_count > 0;
+        let has_stash_entries = !self.stash_entries.is_empty();
 
         PopoverMenu::new(id.into())
             .trigger(
@@ -2691,6 +2899,7 @@ impl GitPanel {
                         has_staged_changes,
                         has_unstaged_changes,
                         has_new_changes,
+                        has_stash_entries,
                     },
                     window,
                     cx,
@@ -3253,6 +3462,194 @@ impl GitPanel {
             )
     }
 
+    fn render_operation_banner(
+        &self,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let operation = repo.merge.in_progress_operation?;
+
+        let mut description = match operation {
+            SequencerOperation::Merge => "Merging".to_string(),
+            SequencerOperation::CherryPick => "Cherry-picking".to_string(),
+            SequencerOperation::Revert => "Reverting".to_string(),
+            SequencerOperation::Rebase => {
+                let mut description = "Rebasing".to_string();
+                if let Some(progress) = repo.merge.rebase_progress.as_ref() {
+                    if let Some(head_name) = progress.head_name.as_ref() {
+                        description.push_str(&format!(" {head_name}"));
+                    }
+                    if let Some(onto) = progress.onto.as_ref() {
+                        let onto = &onto[..onto.len().min(git::SHORT_SHA_LENGTH)];
+                        description.push_str(&format!(" onto {onto}"));
+                    }
+                    if progress.total_steps > 0 {
+                        description.push_str(&format!(
+                            " ({}/{})",
+                            progress.current_step, progress.total_steps
+                        ));
+                    }
+                }
+                description
+            }
+        };
+        let has_conflicts = self.conflicted_count > 0;
+        if has_conflicts {
+            description.push_str(" · resolve conflicts to continue");
+        }
+
+        Some(
+            h_flex()
+                .id("operation-banner")
+                .w_full()
+                .px(rems(0.75))
+                .py_1()
+                .gap_1()
+                .justify_between()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .bg(cx.theme().colors().editor_background)
+                .child(
+                    div().flex_1().overflow_hidden().child(
+                        Label::new(description)
+                            .size(LabelSize::Small)
+                            .color(if has_conflicts {
+                                Color::Warning
+                            } else {
+                                Color::Muted
+                            })
+                            .truncate(),
+                    ),
+                )
+                .when(has_write_access, |this| {
+                    this.child(
+                        h_flex()
+                            .gap_1()
+                            .child(panel_button("Abort").on_click(cx.listener(
+                                |this, _, window, cx| {
+                                    this.abort_operation(&AbortOperation, window, cx)
+                                },
+                            )))
+                            .when(operation != SequencerOperation::Merge, |this| {
+                                this.child(panel_button("Skip").on_click(cx.listener(
+                                    |this, _, window, cx| {
+                                        this.skip_operation(&SkipOperation, window, cx)
+                                    },
+                                )))
+                            })
+                            .child(
+                                panel_filled_button("Continue")
+                                    .disabled(has_conflicts)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.continue_operation(&ContinueOperation, window, cx)
+                                    })),
+                            ),
+                    )
+                }),
+        )
+    }
+
+    fn render_stash_list(
+        &self,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        if self.stash_entries.is_empty() {
+            return None;
+        }
+        let entry_count = self.stash_entries.entries.len();
+
+        Some(
+            v_flex()
+                .id("stash-list")
+                .w_full()
+                .border_t_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .h(self.list_item_height())
+                        .w_full()
+                        .px(rems(0.75))
+                        .gap_1()
+                        .child(
+                            Disclosure::new("stash-list-disclosure", self.stash_list_expanded)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.stash_list_expanded = !this.stash_list_expanded;
+                                    cx.notify();
+                                })),
+                        )
+                        .child(
+                            Label::new(format!("Stashes ({entry_count})"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+                .when(self.stash_list_expanded, |this| {
+                    this.children(self.stash_entries.entries.iter().map(|entry| {
+                        let index = entry.index;
+                        h_flex()
+                            .id(("stash-entry", index))
+                            .h(self.list_item_height())
+                            .w_full()
+                            .px(rems(0.75))
+                            .gap_1()
+                            .hover(|this| this.bg(cx.theme().colors().ghost_element_hover))
+                            .child(
+                                Label::new(format!("stash@{{{index}}}"))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                div().flex_1().overflow_hidden().child(
+                                    Label::new(entry.message.clone())
+                                        .size(LabelSize::Small)
+                                        .truncate(),
+                                ),
+                            )
+                            .when_some(entry.branch.clone(), |this, branch| {
+                                this.tooltip(Tooltip::text(format!("On {branch}")))
+                            })
+                            .when(has_write_access, |this| {
+                                this.child(
+                                    IconButton::new(("stash-apply", index), IconName::Check)
+                                        .icon_size(IconSize::Small)
+                                        .icon_color(Color::Muted)
+                                        .tooltip(Tooltip::text("Apply"))
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.perform_stash_operation(
+                                                StashOperation::Apply(index),
+                                                cx,
+                                            )
+                                        })),
+                                )
+                                .child(
+                                    IconButton::new(("stash-pop", index), IconName::ArrowUp)
+                                        .icon_size(IconSize::Small)
+                                        .icon_color(Color::Muted)
+                                        .tooltip(Tooltip::text("Pop"))
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.perform_stash_operation(
+                                                StashOperation::Pop(Some(index)),
+                                                cx,
+                                            )
+                                        })),
+                                )
+                                .child(
+                                    IconButton::new(("stash-drop", index), IconName::Trash)
+                                        .icon_size(IconSize::Small)
+                                        .icon_color(Color::Muted)
+                                        .tooltip(Tooltip::text("Drop"))
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            this.drop_stash(index, window, cx)
+                                        })),
+                                )
+                            })
+                    }))
+                }),
+        )
+    }
+
     fn render_previous_commit(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
         let active_repository = self.active_repository.as_ref()?;
         let branch = active_repository.read(cx).branch.as_ref()?;
@@ -3776,6 +4173,7 @@ impl GitPanel {
                 has_staged_changes: self.has_staged_changes(),
                 has_unstaged_changes: self.has_unstaged_changes(),
                 has_new_changes: self.new_count > 0,
+                has_stash_entries: !self.stash_entries.is_empty(),
             },
             window,
             cx,
@@ -4081,6 +4479,11 @@ impl Render for GitPanel {
                     .on_action(cx.listener(GitPanel::cancel))
                     .on_action(cx.listener(Self::stage_all))
                     .on_action(cx.listener(Self::unstage_all))
+                    .on_action(cx.listener(Self::stash_all))
+                    .on_action(cx.listener(Self::stash_pop))
+                    .on_action(cx.listener(Self::continue_operation))
+                    .on_action(cx.listener(Self::abort_operation))
+                    .on_action(cx.listener(Self::skip_operation))
                     .on_action(cx.listener(Self::stage_selected))
                     .on_action(cx.listener(Self::unstage_selected))
                     .on_action(cx.listener(Self::restore_tracked_files))
@@ -4117,6 +4520,7 @@ impl Render for GitPanel {
                 v_flex()
                     .size_full()
                     .children(self.render_panel_header(window, cx))
+                    .children(self.render_operation_banner(has_write_access, cx))
                     .map(|this| {
                         if has_entries {
                             this.child(self.render_entries(has_write_access, window, cx))
@@ -4124,6 +4528,7 @@ impl Render for GitPanel {
                             this.child(self.render_empty_state(cx).into_any_element())
                         }
                     })
+                    .children(self.render_stash_list(has_write_access, cx))
                     .children(self.render_footer(window, cx))
                     .when(self.amend_pending, |this| {
                         this.child(self.render_pending_amend(cx))
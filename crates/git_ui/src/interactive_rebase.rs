@@ -0,0 +1,262 @@
+use git::repository::{RebaseTodoAction, RebaseTodoItem};
+use gpui::{
+    AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, ParentElement, Render, SharedString, Styled, Subscription, Task, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+/// Opens a modal for editing the todo list of an interactive rebase onto `onto`, or from
+/// the root commit if `onto` is `None`.
+///
+/// `commits` are the SHAs and subjects of the commits to replay, oldest first.
+pub fn open(
+    workspace: &mut Workspace,
+    repository: Entity<Repository>,
+    onto: Option<String>,
+    commits: Vec<(SharedString, SharedString)>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    workspace.toggle_modal(window, cx, |window, cx| {
+        InteractiveRebase::new(repository, onto, commits, window, cx)
+    })
+}
+
+pub struct InteractiveRebase {
+    picker: Entity<Picker<InteractiveRebaseDelegate>>,
+    _subscription: Subscription,
+}
+
+impl InteractiveRebase {
+    fn new(
+        repository: Entity<Repository>,
+        onto: Option<String>,
+        commits: Vec<(SharedString, SharedString)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let entries = commits
+            .into_iter()
+            .map(|(sha, subject)| TodoEntry {
+                item: RebaseTodoItem {
+                    action: RebaseTodoAction::Pick,
+                    sha,
+                },
+                subject,
+            })
+            .collect();
+        let delegate = InteractiveRebaseDelegate {
+            repository,
+            onto,
+            entries,
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::nonsearchable_uniform_list(delegate, window, cx));
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for InteractiveRebase {}
+impl EventEmitter<DismissEvent> for InteractiveRebase {}
+
+impl Focusable for InteractiveRebase {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for InteractiveRebase {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(40.))
+            .child(self.picker.clone())
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.cancel(&Default::default(), window, cx);
+                })
+            }))
+    }
+}
+
+struct TodoEntry {
+    item: RebaseTodoItem,
+    subject: SharedString,
+}
+
+pub struct InteractiveRebaseDelegate {
+    repository: Entity<Repository>,
+    onto: Option<String>,
+    entries: Vec<TodoEntry>,
+    selected_index: usize,
+}
+
+impl InteractiveRebaseDelegate {
+    fn cycle_action(&mut self) {
+        let is_first = self.selected_index == 0;
+        let Some(entry) = self.entries.get_mut(self.selected_index) else {
+            return;
+        };
+        entry.item.action = match entry.item.action {
+            RebaseTodoAction::Pick => RebaseTodoAction::Edit,
+            // The oldest commit has nothing to be melded into.
+            RebaseTodoAction::Edit if is_first => RebaseTodoAction::Drop,
+            RebaseTodoAction::Edit => RebaseTodoAction::Fixup,
+            RebaseTodoAction::Fixup => RebaseTodoAction::Drop,
+            RebaseTodoAction::Drop => RebaseTodoAction::Pick,
+        };
+    }
+
+    fn start_rebase(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let todo = self
+            .entries
+            .iter()
+            .map(|entry| entry.item.clone())
+            .collect::<Vec<_>>();
+        let onto = self.onto.clone();
+        let task = self
+            .repository
+            .update(cx, |repository, cx| repository.rebase(onto, Some(todo), cx));
+        cx.spawn_in(window, async move |_, _| task.await?)
+            .detach_and_prompt_err("Failed to rebase", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for InteractiveRebaseDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        _query: String,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        Task::ready(())
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if secondary {
+            self.start_rebase(window, cx);
+        } else {
+            self.cycle_action();
+            cx.notify();
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = self.entries.get(ix)?;
+        let action_color = match entry.item.action {
+            RebaseTodoAction::Pick => Color::Muted,
+            RebaseTodoAction::Edit => Color::Accent,
+            RebaseTodoAction::Fixup => Color::Warning,
+            RebaseTodoAction::Drop => Color::Error,
+        };
+        let short_sha = &entry.item.sha[..entry.item.sha.len().min(git::SHORT_SHA_LENGTH)];
+
+        Some(
+            ListItem::new(SharedString::from(format!("rebase-todo-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_2()
+                        .child(
+                            div().w(rems(3.)).child(
+                                Label::new(entry.item.action.command())
+                                    .size(LabelSize::Small)
+                                    .color(action_color),
+                            ),
+                        )
+                        .child(
+                            Label::new(short_sha.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .buffer_font(cx),
+                        )
+                        .child(
+                            div().flex_1().overflow_hidden().child(
+                                Label::new(entry.subject.clone())
+                                    .truncate()
+                                    .when(entry.item.action == RebaseTodoAction::Drop, |label| {
+                                        label.strikethrough()
+                                    }),
+                            ),
+                        ),
+                ),
+        )
+    }
+
+    fn render_footer(
+        &self,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_2()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Label::new("Press enter to change what happens to a commit")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(
+                    Button::new("start-rebase", "Start Rebase")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            picker.delegate.start_rebase(window, cx)
+                        })),
+                )
+                .into_any_element(),
+        )
+    }
+}
@@ -0,0 +1,498 @@
+use fuzzy::StringMatchCandidate;
+use git::repository::Branch;
+use git::worktree::GitWorktree;
+use gpui::{
+    AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, ParentElement, PromptLevel, Render, SharedString, Styled, Subscription, Task,
+    WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::path::PathBuf;
+use std::sync::Arc;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+fn open(
+    workspace: &mut Workspace,
+    _: &git::OpenWorktree,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    let workspace_handle = cx.entity().downgrade();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        WorktreePicker::new(repository, workspace_handle, window, cx)
+    })
+}
+
+pub struct WorktreePicker {
+    picker: Entity<Picker<WorktreePickerDelegate>>,
+    _subscription: Subscription,
+}
+
+impl WorktreePicker {
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let worktrees_request = repository.update(cx, |repository, _| repository.worktrees());
+        let branches_request = repository.update(cx, |repository, _| repository.branches());
+        let current_path = repository.read(cx).work_directory_abs_path.to_path_buf();
+
+        let delegate = WorktreePickerDelegate {
+            repository,
+            workspace,
+            current_path,
+            worktrees: Vec::new(),
+            branches: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        cx.spawn_in(window, {
+            let picker = picker.downgrade();
+            async move |_, cx| {
+                let worktrees = worktrees_request.await??;
+                let branches = branches_request.await?.log_err().unwrap_or_default();
+                picker.update_in(cx, |picker, window, cx| {
+                    picker.delegate.worktrees = worktrees;
+                    picker.delegate.branches = branches;
+                    picker.refresh(window, cx);
+                })
+            }
+        })
+        .detach_and_log_err(cx);
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for WorktreePicker {}
+impl EventEmitter<DismissEvent> for WorktreePicker {}
+
+impl Focusable for WorktreePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for WorktreePicker {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(34.))
+            .child(self.picker.clone())
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.cancel(&Default::default(), window, cx);
+                })
+            }))
+    }
+}
+
+enum WorktreeEntry {
+    Worktree {
+        worktree: GitWorktree,
+        positions: Vec<usize>,
+    },
+    /// A new worktree for the named branch, which is created if it doesn't exist.
+    New { branch: SharedString },
+}
+
+pub struct WorktreePickerDelegate {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    /// The working directory of the active repository.
+    current_path: PathBuf,
+    worktrees: Vec<GitWorktree>,
+    branches: Vec<Branch>,
+    matches: Vec<WorktreeEntry>,
+    selected_index: usize,
+}
+
+impl WorktreePickerDelegate {
+    /// Opens the worktree at `path` in a new window, or adds it to the current project.
+    ///
+    /// Projects that aren't local can't be opened in a new window by path, so the worktree is
+    /// always added to them.
+    fn open_worktree(
+        &self,
+        path: PathBuf,
+        add_to_project: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                if add_to_project || !workspace.project().read(cx).is_local() {
+                    let task = workspace.project().update(cx, |project, cx| {
+                        project.find_or_create_worktree(&path, true, cx)
+                    });
+                    cx.spawn_in(window, async move |_, _| task.await.map(|_| ()))
+                        .detach_and_prompt_err(
+                            "Failed to add worktree to project",
+                            window,
+                            cx,
+                            |e, _, _| Some(e.to_string()),
+                        );
+                } else {
+                    workspace
+                        .open_workspace_for_paths(false, vec![path], window, cx)
+                        .detach_and_prompt_err("Failed to open worktree", window, cx, |e, _, _| {
+                            Some(e.to_string())
+                        });
+                }
+            })
+            .log_err();
+        cx.emit(DismissEvent);
+    }
+
+    /// Creates a worktree next to the main one, named after the repository and the branch.
+    fn create_worktree(
+        &self,
+        branch: SharedString,
+        add_to_project: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(main_path) = self
+            .worktrees
+            .iter()
+            .find(|worktree| worktree.is_main)
+            .map(|worktree| worktree.path.clone())
+        else {
+            return;
+        };
+        let Some(parent) = main_path.parent() else {
+            return;
+        };
+        let repository_name = main_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let path = parent.join(format!("{repository_name}-{}", branch.replace('/', "-")));
+
+        let branch_exists = self.branches.iter().any(|existing| existing.name == branch);
+        let (commit, new_branch) = if branch_exists {
+            (Some(branch.to_string()), None)
+        } else {
+            (None, Some(branch.to_string()))
+        };
+
+        let create = self.repository.update(cx, |repository, _| {
+            repository.create_worktree(path.clone(), commit, new_branch)
+        });
+        cx.spawn_in(window, async move |picker, cx| {
+            create.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .open_worktree(path, add_to_project, window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to create worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn remove_worktree(&self, path: PathBuf, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let repository = self.repository.clone();
+        let prompt = window.prompt(
+            PromptLevel::Warning,
+            &format!("Remove the worktree at {}?", path.display()),
+            None,
+            &["Remove", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |picker, cx| {
+            if prompt.await? != 0 {
+                return Ok(());
+            }
+            let result = repository
+                .update(cx, |repository, _| {
+                    repository.remove_worktree(path.clone(), false)
+                })?
+                .await?;
+            if let Err(error) = result {
+                if !error
+                    .to_string()
+                    .contains(git::repository::WORKTREE_HAS_CHANGES)
+                {
+                    return Err(error);
+                }
+                let prompt = cx.update(|window, cx| {
+                    window.prompt(
+                        PromptLevel::Warning,
+                        "This worktree has uncommitted changes.",
+                        Some("They will be lost if the worktree is removed."),
+                        &["Remove Anyway", "Cancel"],
+                        cx,
+                    )
+                })?;
+                if prompt.await? != 0 {
+                    return Ok(());
+                }
+                repository
+                    .update(cx, |repository, _| {
+                        repository.remove_worktree(path.clone(), true)
+                    })?
+                    .await??;
+            }
+            picker.update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .worktrees
+                    .retain(|worktree| worktree.path != path);
+                picker.refresh(window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to remove worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+}
+
+impl PickerDelegate for WorktreePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select worktree, or type a branch name to create one...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let worktrees = self.worktrees.clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            let query = query.trim().to_string();
+            let mut matches = if query.is_empty() {
+                worktrees
+                    .into_iter()
+                    .map(|worktree| WorktreeEntry::Worktree {
+                        worktree,
+                        positions: Vec::new(),
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                let candidates = worktrees
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, worktree)| StringMatchCandidate::new(ix, &worktree.display_name()))
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| WorktreeEntry::Worktree {
+                    worktree: worktrees[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+            if !query.is_empty()
+                && !matches.iter().any(|entry| {
+                    matches!(entry, WorktreeEntry::Worktree { worktree, .. }
+                        if worktree.branch.as_deref() == Some(query.as_str()))
+                })
+            {
+                matches.push(WorktreeEntry::New {
+                    branch: query.into(),
+                });
+            }
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        match self.matches.get(self.selected_index) {
+            Some(WorktreeEntry::Worktree { worktree, .. }) => {
+                if worktree.path == self.current_path {
+                    cx.emit(DismissEvent);
+                    return;
+                }
+                self.open_worktree(worktree.path.clone(), secondary, window, cx);
+            }
+            Some(WorktreeEntry::New { branch }) => {
+                self.create_worktree(branch.clone(), secondary, window, cx);
+            }
+            None => {}
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(SharedString::from(format!("worktree-{ix}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        let (worktree, positions) = match self.matches.get(ix)? {
+            WorktreeEntry::Worktree {
+                worktree,
+                positions,
+            } => (worktree, positions),
+            WorktreeEntry::New { branch } => {
+                let exists = self
+                    .branches
+                    .iter()
+                    .any(|existing| existing.name == *branch);
+                return Some(
+                    item.child(
+                        v_flex()
+                            .child(
+                                Label::new(format!("Create worktree for \"{branch}\"…"))
+                                    .single_line(),
+                            )
+                            .child(
+                                Label::new(if exists {
+                                    "checks out the existing branch"
+                                } else {
+                                    "creates a new branch from HEAD"
+                                })
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                            ),
+                    ),
+                );
+            }
+        };
+
+        let is_current = worktree.path == self.current_path;
+        let badge = if is_current {
+            Some("current")
+        } else if worktree.is_main {
+            Some("main")
+        } else if worktree.is_locked {
+            Some("locked")
+        } else {
+            None
+        };
+        let removable = !is_current && !worktree.is_main && !worktree.is_locked;
+        let remove_button = removable.then(|| {
+            let path = worktree.path.clone();
+            IconButton::new(("remove-worktree", ix), IconName::Trash)
+                .icon_size(IconSize::Small)
+                .tooltip(Tooltip::text("Remove Worktree"))
+                .on_click(cx.listener(move |picker, _, window, cx| {
+                    picker.delegate.remove_worktree(path.clone(), window, cx);
+                }))
+                .into_any_element()
+        });
+
+        Some(
+            item.end_hover_slot::<AnyElement>(remove_button).child(
+                v_flex()
+                    .w_full()
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .justify_between()
+                            .child(
+                                div().flex_shrink().overflow_x_hidden().child(
+                                    HighlightedLabel::new(
+                                        worktree.display_name(),
+                                        positions.clone(),
+                                    )
+                                    .truncate(),
+                                ),
+                            )
+                            .when_some(badge, |this, badge| {
+                                this.child(
+                                    Label::new(badge).size(LabelSize::Small).color(Color::Muted),
+                                )
+                            }),
+                    )
+                    .child(
+                        Label::new(worktree.path.to_string_lossy().into_owned())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    ),
+            ),
+        )
+    }
+
+    fn render_footer(
+        &self,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Label::new("Enter opens in a new window, secondary enter adds to the project")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No worktrees found".into())
+    }
+}
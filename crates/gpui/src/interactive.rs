@@ -479,6 +479,7 @@ impl PlatformInput {
 
 #[cfg(test)]
 mod test {
+    use std::{cell::RefCell, rc::Rc};
 
     use crate::{
         self as gpui, AppContext as _, Context, FocusHandle, InteractiveElement, IntoElement,
@@ -549,4 +550,78 @@ mod test {
             })
             .unwrap();
     }
+
+    struct KeystrokeLogView {
+        log: Rc<RefCell<Vec<String>>>,
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for KeystrokeLogView {
+        fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .key_context("parent")
+                .track_focus(&self.focus_handle)
+                .on_action(cx.listener(|this: &mut Self, _: &TestAction, _, _| {
+                    this.log.borrow_mut().push("action".into())
+                }))
+        }
+    }
+
+    #[gpui::test]
+    fn test_intercept_keystrokes(cx: &mut TestAppContext) {
+        let log = Rc::new(RefCell::new(Vec::<String>::new()));
+        let window = cx.update(|cx| {
+            cx.open_window(Default::default(), |_, cx| {
+                cx.new(|cx| KeystrokeLogView {
+                    log: log.clone(),
+                    focus_handle: cx.focus_handle(),
+                })
+            })
+            .unwrap()
+        });
+        window
+            .update(cx, |view, window, _| window.focus(&view.focus_handle))
+            .unwrap();
+
+        let (interception, _observation) = cx.update(|cx| {
+            cx.bind_keys(vec![KeyBinding::new("g g", TestAction, Some("parent"))]);
+            let interception = cx.intercept_keystrokes({
+                let log = log.clone();
+                move |event, _, _| {
+                    assert!(event.action.is_none());
+                    log.borrow_mut()
+                        .push(format!("intercept {}", event.keystroke.unparse()));
+                }
+            });
+            let observation = cx.observe_keystrokes({
+                let log = log.clone();
+                move |event, _, _| {
+                    log.borrow_mut()
+                        .push(format!("observe {}", event.keystroke.unparse()));
+                }
+            });
+            (interception, observation)
+        });
+
+        // Interceptors see the keystrokes that only form part of a binding, and run before the
+        // action is dispatched, while observers only see the keystroke that completed it.
+        cx.simulate_keystrokes(*window, "g g");
+        assert_eq!(
+            log.borrow_mut().drain(..).collect::<Vec<_>>(),
+            ["intercept g", "intercept g", "action", "observe g"]
+        );
+
+        cx.simulate_keystrokes(*window, "a");
+        assert_eq!(
+            log.borrow_mut().drain(..).collect::<Vec<_>>(),
+            ["intercept a", "observe a"]
+        );
+
+        drop(interception);
+        cx.simulate_keystrokes(*window, "g g a");
+        assert_eq!(
+            log.borrow_mut().drain(..).collect::<Vec<_>>(),
+            ["action", "observe g", "observe a"]
+        );
+    }
 }
@@ -3295,6 +3295,17 @@ impl Window {
             return;
         };
 
+        if let Some(key_down_event) = event.downcast_ref::<KeyDownEvent>() {
+            let event = KeystrokeEvent {
+                keystroke: key_down_event.keystroke.clone(),
+                action: None,
+                context_stack: self.context_stack(),
+            };
+            cx.keystroke_interceptors
+                .clone()
+                .retain(&(), |callback| (callback)(&event, self, cx));
+        }
+
         let mut currently_pending = self.pending_input.take().unwrap_or_default();
         if currently_pending.focus.is_some() && currently_pending.focus != self.focus {
             currently_pending = PendingInput::default();
@@ -0,0 +1,769 @@
+mod hierarchy_panel_settings;
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, scroll::Autoscroll};
+use gpui::{
+    Action, AnyElement, App, AppContext as _, AsyncWindowContext, ClickEvent, Context, Entity,
+    EventEmitter, FocusHandle, Focusable, KeyContext, Pixels, Render, ScrollStrategy, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, px, uniform_list,
+};
+use hierarchy_panel_settings::HierarchyPanelSettings;
+use language::{Buffer, OffsetRangeExt as _};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{Fs, HierarchyItem, HierarchyKind, Project};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{ListItem, prelude::*};
+use util::{ResultExt as _, TryFutureExt as _};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+const HIERARCHY_PANEL_KEY: &str = "HierarchyPanel";
+
+actions!(
+    hierarchy_panel,
+    [
+        CollapseSelectedEntry,
+        ExpandSelectedEntry,
+        ShowIncomingCalls,
+        ShowOutgoingCalls,
+        ShowSubtypes,
+        ShowSupertypes,
+        ToggleFocus,
+    ]
+);
+
+pub fn init_settings(cx: &mut App) {
+    HierarchyPanelSettings::register(cx);
+}
+
+pub fn init(cx: &mut App) {
+    init_settings(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<HierarchyPanel>(window, cx);
+            })
+            .register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::IncomingCalls, window, cx);
+            })
+            .register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::OutgoingCalls, window, cx);
+            })
+            .register_action(|workspace, _: &ShowSupertypes, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::Supertypes, window, cx);
+            })
+            .register_action(|workspace, _: &ShowSubtypes, window, cx| {
+                show_hierarchy(workspace, HierarchyKind::Subtypes, window, cx);
+            });
+    })
+    .detach();
+}
+
+/// Shows the hierarchy of the symbol under the newest cursor of the active editor.
+fn show_hierarchy(
+    workspace: &mut Workspace,
+    kind: HierarchyKind,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(panel) = workspace.panel::<HierarchyPanel>(cx) else {
+        return;
+    };
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = ({
+        let editor = editor.read(cx);
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+
+    panel.update(cx, |panel, cx| panel.show(&buffer, position, kind, cx));
+    workspace.focus_panel::<HierarchyPanel>(window, cx);
+}
+
+/// A node of the hierarchy tree, whose children are only fetched from the language
+/// server once the node is expanded.
+struct HierarchyNode {
+    item: HierarchyItem,
+    children: HierarchyChildren,
+    expanded: bool,
+}
+
+enum HierarchyChildren {
+    Unresolved,
+    Loading(Task<()>),
+    Resolved(Vec<usize>),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct VisibleEntry {
+    node_ix: usize,
+    depth: usize,
+}
+
+pub struct HierarchyPanel {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    kind: HierarchyKind,
+    nodes: Vec<HierarchyNode>,
+    roots: Vec<usize>,
+    visible_entries: Vec<VisibleEntry>,
+    selected_node: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    loading: bool,
+    prepare_task: Task<()>,
+    pending_serialization: Task<Option<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedHierarchyPanel {
+    width: Option<Pixels>,
+}
+
+impl HierarchyPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(HIERARCHY_PANEL_KEY) })
+            .await
+            .context("loading hierarchy panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedHierarchyPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let workspace_handle = cx.entity().downgrade();
+        cx.new(|cx| Self {
+            workspace: workspace_handle,
+            project: workspace.project().clone(),
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            width: None,
+            kind: HierarchyKind::IncomingCalls,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_node: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            loading: false,
+            prepare_task: Task::ready(()),
+            pending_serialization: Task::ready(None),
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        HIERARCHY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedHierarchyPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Replaces the shown hierarchy with the one of the symbol at the given position.
+    pub fn show(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: language::Anchor,
+        kind: HierarchyKind,
+        cx: &mut Context<Self>,
+    ) {
+        self.kind = kind;
+        self.nodes.clear();
+        self.roots.clear();
+        self.visible_entries.clear();
+        self.selected_node = None;
+        self.loading = true;
+        cx.notify();
+
+        let roots = self.project.update(cx, |project, cx| {
+            project.prepare_hierarchy(buffer, position, kind, cx)
+        });
+        self.prepare_task = cx.spawn(async move |panel, cx| {
+            let roots = roots.await.log_err().unwrap_or_default();
+            panel
+                .update(cx, |panel, cx| {
+                    panel.loading = false;
+                    for item in roots {
+                        let node_ix = panel.push_node(item);
+                        panel.roots.push(node_ix);
+                    }
+                    // A root on its own only repeats the symbol the hierarchy was requested for.
+                    for node_ix in panel.roots.clone() {
+                        panel.expand(node_ix, cx);
+                    }
+                    panel.selected_node = panel.roots.first().copied();
+                    panel.update_visible_entries(cx);
+                })
+                .ok();
+        });
+    }
+
+    fn push_node(&mut self, item: HierarchyItem) -> usize {
+        self.nodes.push(HierarchyNode {
+            item,
+            children: HierarchyChildren::Unresolved,
+            expanded: false,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn expand(&mut self, node_ix: usize, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get_mut(node_ix) else {
+            return;
+        };
+        node.expanded = true;
+        if !matches!(node.children, HierarchyChildren::Unresolved) {
+            return;
+        }
+
+        let kind = self.kind;
+        let item = &self.nodes[node_ix].item;
+        let children = self.project.update(cx, |project, cx| {
+            project.hierarchy_item_children(item, kind, cx)
+        });
+        self.nodes[node_ix].children =
+            HierarchyChildren::Loading(cx.spawn(async move |panel, cx| {
+                let children = children.await.log_err().unwrap_or_default();
+                panel
+                    .update(cx, |panel, cx| {
+                        let children = children
+                            .into_iter()
+                            .map(|item| panel.push_node(item))
+                            .collect();
+                        if let Some(node) = panel.nodes.get_mut(node_ix) {
+                            node.children = HierarchyChildren::Resolved(children);
+                        }
+                        panel.update_visible_entries(cx);
+                    })
+                    .ok();
+            }));
+    }
+
+    fn toggle_expanded(&mut self, node_ix: usize, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get_mut(node_ix) else {
+            return;
+        };
+        if node.expanded {
+            node.expanded = false;
+        } else {
+            self.expand(node_ix, cx);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        let mut visible_entries = Vec::new();
+        let mut stack = self
+            .roots
+            .iter()
+            .rev()
+            .map(|&node_ix| VisibleEntry { node_ix, depth: 0 })
+            .collect::<Vec<_>>();
+        while let Some(entry) = stack.pop() {
+            visible_entries.push(entry);
+            let node = &self.nodes[entry.node_ix];
+            if let (true, HierarchyChildren::Resolved(children)) = (node.expanded, &node.children) {
+                stack.extend(children.iter().rev().map(|&node_ix| VisibleEntry {
+                    node_ix,
+                    depth: entry.depth + 1,
+                }));
+            }
+        }
+        self.visible_entries = visible_entries;
+        if self.selected_entry_ix().is_none() {
+            self.selected_node = None;
+        }
+        cx.notify();
+    }
+
+    fn selected_entry_ix(&self) -> Option<usize> {
+        let selected_node = self.selected_node?;
+        self.visible_entries
+            .iter()
+            .position(|entry| entry.node_ix == selected_node)
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if let Some(entry) = self.visible_entries.get(ix) {
+            self.selected_node = Some(entry.node_ix);
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_entry_ix().map_or(0, |ix| {
+            (ix + 1).min(self.visible_entries.len().saturating_sub(1))
+        });
+        self.select_entry(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self
+            .selected_entry_ix()
+            .map_or(0, |ix| ix.saturating_sub(1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_entry(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_entry(self.visible_entries.len().saturating_sub(1), cx);
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry_ix() else {
+            return;
+        };
+        let node_ix = self.visible_entries[ix].node_ix;
+        if self.nodes[node_ix].expanded {
+            let has_visible_children = self
+                .visible_entries
+                .get(ix + 1)
+                .is_some_and(|entry| entry.depth > self.visible_entries[ix].depth);
+            if has_visible_children {
+                self.select_entry(ix + 1, cx);
+            }
+        } else {
+            self.toggle_expanded(node_ix, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry_ix() else {
+            return;
+        };
+        let entry = self.visible_entries[ix];
+        if self.nodes[entry.node_ix].expanded {
+            self.toggle_expanded(entry.node_ix, cx);
+        } else if let Some(parent_ix) = self.visible_entries[..ix]
+            .iter()
+            .rposition(|parent| parent.depth < entry.depth)
+        {
+            self.select_entry(parent_ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(node_ix) = self.selected_node {
+            self.open_node(node_ix, true, window, cx);
+        }
+    }
+
+    fn open_node(
+        &mut self,
+        node_ix: usize,
+        focus_editor: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(node) = self.nodes.get(node_ix) else {
+            return;
+        };
+        let location = node.item.location.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let range = location.range.to_point(location.buffer.read(cx));
+                let pane = workspace.active_pane().clone();
+                let editor = workspace.open_project_item::<Editor>(
+                    pane,
+                    location.buffer,
+                    focus_editor,
+                    focus_editor,
+                    window,
+                    cx,
+                );
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_ranges([range])
+                    });
+                });
+            })
+            .ok();
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("HierarchyPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_entry(&self, entry: VisibleEntry, cx: &mut Context<Self>) -> AnyElement {
+        let node_ix = entry.node_ix;
+        let node = &self.nodes[node_ix];
+        let toggle = match &node.children {
+            HierarchyChildren::Resolved(children) if children.is_empty() => None,
+            _ => Some(node.expanded),
+        };
+        let description = node.item.detail.clone().or_else(|| {
+            node.item
+                .location
+                .buffer
+                .read(cx)
+                .file()
+                .map(|file| file.file_name(cx).to_string_lossy().into_owned())
+        });
+
+        ListItem::new(("hierarchy-entry", node_ix))
+            .indent_level(entry.depth)
+            .indent_step_size(px(HierarchyPanelSettings::get_global(cx).indent_size))
+            .toggle(toggle)
+            .toggle_state(self.selected_node == Some(node_ix))
+            .on_toggle(cx.listener(move |panel, _, _, cx| {
+                panel.toggle_expanded(node_ix, cx);
+            }))
+            .on_click(cx.listener(move |panel, event: &ClickEvent, window, cx| {
+                panel.selected_node = Some(node_ix);
+                panel.open_node(node_ix, event.down.click_count > 1, window, cx);
+                cx.notify();
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(node.item.name.clone()))
+                    .when_some(description, |this, description| {
+                        this.child(
+                            Label::new(description)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+fn hierarchy_title(kind: HierarchyKind) -> &'static str {
+    match kind {
+        HierarchyKind::IncomingCalls => "Incoming Calls",
+        HierarchyKind::OutgoingCalls => "Outgoing Calls",
+        HierarchyKind::Supertypes => "Supertypes",
+        HierarchyKind::Subtypes => "Subtypes",
+    }
+}
+
+impl Panel for HierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "Hierarchy Panel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        HierarchyPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<HierarchyPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| HierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        HierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
+
+impl Focusable for HierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for HierarchyPanel {}
+
+impl Render for HierarchyPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("hierarchy-panel")
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        Label::new(hierarchy_title(self.kind))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .map(|this| {
+                if self.loading {
+                    this.child(
+                        div()
+                            .p_2()
+                            .child(Label::new("Loading…").color(Color::Muted)),
+                    )
+                } else if self.visible_entries.is_empty() {
+                    this.child(
+                        div().p_2().child(
+                            Label::new(
+                                "Show the call or type hierarchy of a symbol from the editor.",
+                            )
+                            .color(Color::Muted),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "hierarchy-entries",
+                            self.visible_entries.len(),
+                            |panel, range, _, cx| {
+                                range
+                                    .filter_map(|ix| {
+                                        let entry = *panel.visible_entries.get(ix)?;
+                                        Some(panel.render_entry(entry, cx))
+                                    })
+                                    .collect()
+                            },
+                        )
+                        .flex_1()
+                        .track_scroll(self.scroll_handle.clone()),
+                    )
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_lazily_expanded_call_hierarchy(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "main.rs": "fn a() { b() }\nfn b() { c() }\nfn c() {}",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let (buffer, _handle) = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer_with_lsp(path!("/dir/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+
+        fn item(name: &str, row: u32) -> lsp::CallHierarchyItem {
+            let range = lsp::Range::new(lsp::Position::new(row, 3), lsp::Position::new(row, 4));
+            lsp::CallHierarchyItem {
+                name: name.to_string(),
+                kind: lsp::SymbolKind::FUNCTION,
+                tags: None,
+                detail: None,
+                uri: lsp::Url::from_file_path(Path::new(path!("/dir/main.rs"))).unwrap(),
+                range,
+                selection_range: range,
+                data: None,
+            }
+        }
+
+        let fake_server = fake_servers.next().await.unwrap();
+        fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+            |_, _| async move { Ok(Some(vec![item("c", 2)])) },
+        );
+        fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+            |params, _| async move {
+                let caller = match params.item.name.as_str() {
+                    "c" => item("b", 1),
+                    "b" => item("a", 0),
+                    _ => return Ok(None),
+                };
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: caller,
+                    from_ranges: Vec::new(),
+                }]))
+            },
+        );
+
+        let workspace =
+            cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let panel = workspace
+            .update(cx, |workspace, _, cx| HierarchyPanel::new(workspace, cx))
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+        let position = buffer.read_with(cx, |buffer, _| {
+            buffer.anchor_before(language::Point::new(2, 3))
+        });
+        panel.update(cx, |panel, cx| {
+            panel.show(&buffer, position, HierarchyKind::IncomingCalls, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries(&panel, cx),
+            vec!["v c", "  > b"],
+            "The roots are expanded once the hierarchy is shown"
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_next(&SelectNext, window, cx);
+            panel.expand_selected_entry(&ExpandSelectedEntry, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(visible_entries(&panel, cx), vec!["v c", "  v b", "    > a"]);
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.collapse_selected_entry(&CollapseSelectedEntry, window, cx);
+        });
+        assert_eq!(visible_entries(&panel, cx), vec!["v c", "  > b"]);
+    }
+
+    fn visible_entries(panel: &Entity<HierarchyPanel>, cx: &mut VisualTestContext) -> Vec<String> {
+        panel.read_with(cx, |panel, _| {
+            panel
+                .visible_entries
+                .iter()
+                .map(|entry| {
+                    let node = &panel.nodes[entry.node_ix];
+                    let toggle = if node.expanded { "v" } else { ">" };
+                    format!("{}{toggle} {}", "  ".repeat(entry.depth), node.item.name)
+                })
+                .collect()
+        })
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            init_settings(cx);
+        });
+    }
+}
@@ -237,6 +237,23 @@ pub struct Diagnostic {
     pub is_unnecessary: bool,
     /// Data from language server that produced this diagnostic. Passed back to the LS when we request code actions for this diagnostic.
     pub data: Option<Value>,
+    /// How the language server reported this diagnostic.
+    pub source_kind: DiagnosticSourceKind,
+}
+
+/// How a [`Diagnostic`] was reported by its language server.
+///
+/// A language server can both push and be pulled for diagnostics, and each of the two
+/// only replaces the diagnostics that were reported the same way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticSourceKind {
+    /// Returned in response to a `textDocument/diagnostic` or `workspace/diagnostic` request.
+    Pulled,
+    /// Sent in a `textDocument/publishDiagnostics` notification.
+    Pushed,
+    /// Reported by anything other than a language server, such as a task.
+    #[default]
+    Other,
 }
 
 /// An operation used to synchronize this buffer with its other replicas.
@@ -4624,6 +4641,7 @@ impl Default for Diagnostic {
             is_disk_based: false,
             is_unnecessary: false,
             data: None,
+            source_kind: DiagnosticSourceKind::Other,
         }
     }
 }
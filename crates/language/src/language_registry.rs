@@ -1067,6 +1067,8 @@ impl LanguageRegistry {
 
 impl LanguageRegistryState {
     fn next_language_server_id(&mut self) -> LanguageServerId {
+        // The last ID is reserved for the diagnostics that tasks report.
+        debug_assert!(self.next_language_server_id < usize::MAX);
         LanguageServerId(post_inc(&mut self.next_language_server_id))
     }
 
@@ -143,6 +143,14 @@ pub struct LanguageSettings {
     pub code_actions_on_format: HashMap<String, bool>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
+    /// Whether to highlight code using the language servers' semantic tokens.
+    pub semantic_tokens: bool,
+    /// How to determine the foldable ranges of a buffer.
+    pub folding_strategy: FoldingStrategy,
+    /// Where selecting larger and smaller syntax nodes gets its ranges from.
+    pub selection_ranges: SelectionRangesSource,
+    /// Whether to show swatches next to the colors that language servers find in the buffer.
+    pub document_colors: bool,
     /// Task configuration for this language.
     pub tasks: LanguageTaskConfig,
     /// Whether to pop the completions menu while typing in an editor without
@@ -531,6 +539,27 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub linked_edits: Option<bool>,
+    /// Whether to highlight code using the semantic tokens of the language server, if it supports them.
+    /// The semantic tokens are layered over the syntax highlighting, styled by the theme's syntax captures.
+    ///
+    /// Default: false
+    pub semantic_tokens: Option<bool>,
+    /// How to determine which ranges of a buffer can be folded.
+    ///
+    /// Default: indentation
+    pub folding_strategy: Option<FoldingStrategy>,
+    /// Where the `editor::SelectLargerSyntaxNode` action gets the ranges to expand the
+    /// selection to. Either source is used as a fallback when the other one can't
+    /// expand the selection.
+    ///
+    /// Default: tree_sitter
+    pub selection_ranges: Option<SelectionRangesSource>,
+    /// Whether to show a color swatch before each color that the language server finds in
+    /// the buffer. Clicking a swatch lists the other ways the language server can write
+    /// the color.
+    ///
+    /// Default: true
+    pub document_colors: Option<bool>,
     /// Whether indentation of pasted content should be adjusted based on the context.
     ///
     /// Default: true
@@ -759,6 +788,32 @@ pub enum ShowWhitespaceSetting {
     Boundary,
 }
 
+/// Controls how the foldable ranges of a buffer are determined.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FoldingStrategy {
+    /// Fold the lines that are indented further than the line preceding them.
+    #[default]
+    Indentation,
+    /// Fold the blocks delimited by the language's brackets, as parsed by Tree-sitter,
+    /// falling back to indentation for lines that don't open a block.
+    TreeSitter,
+    /// Fold the ranges reported by the language server, falling back to indentation
+    /// when no language server provides folding ranges.
+    Lsp,
+}
+
+/// Controls where the ranges to expand selections to come from.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionRangesSource {
+    /// Expand selections to the enclosing syntax nodes of the Tree-sitter syntax tree.
+    #[default]
+    TreeSitter,
+    /// Expand selections to the selection ranges reported by the language server.
+    LanguageServer,
+}
+
 /// Controls which formatter should be used when formatting code.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum SelectedFormatter {
@@ -1451,6 +1506,20 @@ impl settings::Settings for AllLanguageSettings {
         );
         vscode.bool_setting("editor.formatOnType", &mut d.use_on_type_format);
         vscode.bool_setting("editor.linkedEditing", &mut d.linked_edits);
+        vscode.bool_setting(
+            "editor.semanticHighlighting.enabled",
+            &mut d.semantic_tokens,
+        );
+        vscode.enum_setting(
+            "editor.foldingStrategy",
+            &mut d.folding_strategy,
+            |s| match s {
+                "auto" => Some(FoldingStrategy::Lsp),
+                "indentation" => Some(FoldingStrategy::Indentation),
+                _ => None,
+            },
+        );
+        vscode.bool_setting("editor.colorDecorators", &mut d.document_colors);
         vscode.bool_setting("editor.formatOnPaste", &mut d.auto_indent_on_paste);
         vscode.bool_setting(
             "editor.suggestOnTriggerCharacters",
@@ -1521,6 +1590,10 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.code_actions_on_format.clone(),
     );
     merge(&mut settings.linked_edits, src.linked_edits);
+    merge(&mut settings.semantic_tokens, src.semantic_tokens);
+    merge(&mut settings.folding_strategy, src.folding_strategy);
+    merge(&mut settings.selection_ranges, src.selection_ranges);
+    merge(&mut settings.document_colors, src.document_colors);
     merge(&mut settings.tasks, src.tasks.clone());
 
     merge(
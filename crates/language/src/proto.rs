@@ -1,6 +1,6 @@
 //! Handles conversions of `language` items to and from the [`rpc`] protocol.
 
-use crate::{CursorShape, Diagnostic, diagnostic_set::DiagnosticEntry};
+use crate::{CursorShape, Diagnostic, DiagnosticSourceKind, diagnostic_set::DiagnosticEntry};
 use anyhow::{Context as _, Result, anyhow};
 use clock::ReplicaId;
 use lsp::{DiagnosticSeverity, LanguageServerId};
@@ -222,6 +222,11 @@ pub fn serialize_diagnostics<'a>(
             is_disk_based: entry.diagnostic.is_disk_based,
             is_unnecessary: entry.diagnostic.is_unnecessary,
             data: entry.diagnostic.data.as_ref().map(|data| data.to_string()),
+            source_kind: match entry.diagnostic.source_kind {
+                DiagnosticSourceKind::Pulled => proto::diagnostic::SourceKind::Pulled,
+                DiagnosticSourceKind::Pushed => proto::diagnostic::SourceKind::Pushed,
+                DiagnosticSourceKind::Other => proto::diagnostic::SourceKind::Other,
+            } as i32,
         })
         .collect()
 }
@@ -433,6 +438,13 @@ pub fn deserialize_diagnostics(
                     is_disk_based: diagnostic.is_disk_based,
                     is_unnecessary: diagnostic.is_unnecessary,
                     data,
+                    source_kind: match proto::diagnostic::SourceKind::from_i32(
+                        diagnostic.source_kind,
+                    )? {
+                        proto::diagnostic::SourceKind::Pulled => DiagnosticSourceKind::Pulled,
+                        proto::diagnostic::SourceKind::Pushed => DiagnosticSourceKind::Pushed,
+                        proto::diagnostic::SourceKind::Other => DiagnosticSourceKind::Other,
+                    },
                 },
             })
         })
@@ -1524,6 +1524,278 @@ impl DapCommand for ThreadsCommand {
     }
 }
 
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct ReadMemoryCommand {
+    pub memory_reference: String,
+    pub offset: Option<i64>,
+    pub count: u64,
+}
+
+impl LocalDapCommand for ReadMemoryCommand {
+    type Response = dap::ReadMemoryResponse;
+    type DapRequest = dap::requests::ReadMemory;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_read_memory_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::ReadMemoryArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            count: self.count,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+impl DapCommand for ReadMemoryCommand {
+    type ProtoRequest = proto::DapReadMemoryRequest;
+    type ProtoResponse = proto::DapReadMemoryResponse;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            memory_reference: request.memory_reference.clone(),
+            offset: request.offset,
+            count: request.count,
+        }
+    }
+
+    fn to_proto(&self, debug_client_id: SessionId, upstream_project_id: u64) -> Self::ProtoRequest {
+        proto::DapReadMemoryRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            count: self.count,
+        }
+    }
+
+    fn response_to_proto(
+        _debug_client_id: SessionId,
+        message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::DapReadMemoryResponse {
+            address: message.address,
+            unreadable_bytes: message.unreadable_bytes,
+            data: message.data,
+        }
+    }
+
+    fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(dap::ReadMemoryResponse {
+            address: message.address,
+            unreadable_bytes: message.unreadable_bytes,
+            data: message.data,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct WriteMemoryCommand {
+    pub memory_reference: String,
+    pub offset: Option<i64>,
+    pub allow_partial: Option<bool>,
+    /// The bytes to write, base64 encoded.
+    pub data: String,
+}
+
+impl LocalDapCommand for WriteMemoryCommand {
+    type Response = dap::WriteMemoryResponse;
+    type DapRequest = dap::requests::WriteMemory;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_write_memory_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::WriteMemoryArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            allow_partial: self.allow_partial,
+            data: self.data.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+impl DapCommand for WriteMemoryCommand {
+    type ProtoRequest = proto::DapWriteMemoryRequest;
+    type ProtoResponse = proto::DapWriteMemoryResponse;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            memory_reference: request.memory_reference.clone(),
+            offset: request.offset,
+            allow_partial: request.allow_partial,
+            data: request.data.clone(),
+        }
+    }
+
+    fn to_proto(&self, debug_client_id: SessionId, upstream_project_id: u64) -> Self::ProtoRequest {
+        proto::DapWriteMemoryRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            allow_partial: self.allow_partial,
+            data: self.data.clone(),
+        }
+    }
+
+    fn response_to_proto(
+        _debug_client_id: SessionId,
+        message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::DapWriteMemoryResponse {
+            offset: message.offset,
+            bytes_written: message.bytes_written,
+        }
+    }
+
+    fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(dap::WriteMemoryResponse {
+            offset: message.offset,
+            bytes_written: message.bytes_written,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct DisassembleCommand {
+    pub memory_reference: String,
+    pub offset: Option<i64>,
+    pub instruction_offset: Option<i64>,
+    pub instruction_count: u64,
+    pub resolve_symbols: Option<bool>,
+}
+
+impl LocalDapCommand for DisassembleCommand {
+    type Response = Vec<dap::DisassembledInstruction>;
+    type DapRequest = dap::requests::Disassemble;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_disassemble_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::DisassembleArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            instruction_offset: self.instruction_offset,
+            instruction_count: self.instruction_count,
+            resolve_symbols: self.resolve_symbols,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.instructions)
+    }
+}
+
+impl DapCommand for DisassembleCommand {
+    type ProtoRequest = proto::DapDisassembleRequest;
+    type ProtoResponse = proto::DapDisassembleResponse;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            memory_reference: request.memory_reference.clone(),
+            offset: request.offset,
+            instruction_offset: request.instruction_offset,
+            instruction_count: request.instruction_count,
+            resolve_symbols: request.resolve_symbols,
+        }
+    }
+
+    fn to_proto(&self, debug_client_id: SessionId, upstream_project_id: u64) -> Self::ProtoRequest {
+        proto::DapDisassembleRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            instruction_offset: self.instruction_offset,
+            instruction_count: self.instruction_count,
+            resolve_symbols: self.resolve_symbols,
+        }
+    }
+
+    fn response_to_proto(
+        _debug_client_id: SessionId,
+        message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::DapDisassembleResponse {
+            instructions: message
+                .into_iter()
+                .map(|instruction| proto::DapDisassembledInstruction {
+                    address: instruction.address,
+                    instruction_bytes: instruction.instruction_bytes,
+                    instruction: instruction.instruction,
+                    symbol: instruction.symbol,
+                    location: instruction.location.map(|source| source.to_proto()),
+                    line: instruction.line,
+                    column: instruction.column,
+                    end_line: instruction.end_line,
+                    end_column: instruction.end_column,
+                })
+                .collect(),
+        }
+    }
+
+    fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(message
+            .instructions
+            .into_iter()
+            .map(|instruction| dap::DisassembledInstruction {
+                address: instruction.address,
+                instruction_bytes: instruction.instruction_bytes,
+                instruction: instruction.instruction,
+                symbol: instruction.symbol,
+                location: instruction
+                    .location
+                    .map(<dap::Source as ProtoConversion>::from_proto),
+                line: instruction.line,
+                column: instruction.column,
+                end_line: instruction.end_line,
+                end_column: instruction.end_column,
+                presentation_hint: None,
+            })
+            .collect())
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub(super) struct Initialize {
     pub(super) adapter_id: String,
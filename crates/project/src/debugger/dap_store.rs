@@ -64,13 +64,14 @@ pub enum DapStoreEvent {
     },
     Notification(String),
     RemoteHasInitialized,
+    WatchExpressionsUpdated,
 }
 
 #[allow(clippy::large_enum_variant)]
 enum DapStoreMode {
     Local(LocalDapStore),
     Ssh(SshDapStore),
-    Collab,
+    Collab(CollabDapStore),
 }
 
 pub struct LocalDapStore {
@@ -88,6 +89,11 @@ pub struct SshDapStore {
     upstream_project_id: u64,
 }
 
+pub struct CollabDapStore {
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
+}
+
 pub struct DapStore {
     mode: DapStoreMode,
     downstream_client: Option<(AnyProtoClient, u64)>,
@@ -95,6 +101,8 @@ pub struct DapStore {
     worktree_store: Entity<WorktreeStore>,
     sessions: BTreeMap<SessionId, Entity<Session>>,
     next_session_id: u32,
+    /// Expressions watched in every debug session of the project, shared with its guests.
+    watch_expressions: Vec<String>,
 }
 
 impl EventEmitter<DapStoreEvent> for DapStore {}
@@ -104,6 +112,8 @@ impl DapStore {
         static ADD_LOCATORS: Once = Once::new();
         client.add_entity_request_handler(Self::handle_run_debug_locator);
         client.add_entity_request_handler(Self::handle_get_debug_adapter_binary);
+        client.add_entity_message_handler(Self::handle_update_debug_watch_expressions);
+        client.add_entity_request_handler(Self::handle_set_debug_watch_expressions);
         ADD_LOCATORS.call_once(|| {
             DapRegistry::global(cx)
                 .add_locator("cargo".into(), Arc::new(locators::cargo::CargoLocator {}))
@@ -151,13 +161,18 @@ impl DapStore {
     }
 
     pub fn new_collab(
-        _project_id: u64,
-        _upstream_client: AnyProtoClient,
+        project_id: u64,
+        upstream_client: AnyProtoClient,
         breakpoint_store: Entity<BreakpointStore>,
         worktree_store: Entity<WorktreeStore>,
         cx: &mut Context<Self>,
     ) -> Self {
-        Self::new(DapStoreMode::Collab, breakpoint_store, worktree_store, cx)
+        let mode = DapStoreMode::Collab(CollabDapStore {
+            upstream_client,
+            upstream_project_id: project_id,
+        });
+
+        Self::new(mode, breakpoint_store, worktree_store, cx)
     }
 
     fn new(
@@ -173,6 +188,7 @@ impl DapStore {
             breakpoint_store,
             worktree_store,
             sessions: Default::default(),
+            watch_expressions: Vec::new(),
         }
     }
 
@@ -276,7 +292,7 @@ impl DapStore {
                     })
                 })
             }
-            DapStoreMode::Collab => {
+            DapStoreMode::Collab(_) => {
                 Task::ready(Err(anyhow!("Debugging is not yet supported via collab")))
             }
         }
@@ -315,6 +331,7 @@ impl DapStore {
                 initialize_args: None,
                 tcp_connection: None,
                 stop_on_entry: None,
+                inputs: Vec::new(),
             })
     }
 
@@ -378,7 +395,7 @@ impl DapStore {
                     DebugRequest::from_proto(response)
                 })
             }
-            DapStoreMode::Collab => {
+            DapStoreMode::Collab(_) => {
                 Task::ready(Err(anyhow!("Debugging is not yet supported via collab")))
             }
         }
@@ -754,6 +771,66 @@ impl DapStore {
         })
     }
 
+    pub fn watch_expressions(&self) -> &[String] {
+        &self.watch_expressions
+    }
+
+    pub fn add_watch_expression(&mut self, expression: String, cx: &mut Context<Self>) {
+        if self.watch_expressions.contains(&expression) {
+            return;
+        }
+        let mut expressions = self.watch_expressions.clone();
+        expressions.push(expression);
+        self.set_watch_expressions(expressions, cx);
+    }
+
+    pub fn remove_watch_expression(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix < self.watch_expressions.len() {
+            let mut expressions = self.watch_expressions.clone();
+            expressions.remove(ix);
+            self.set_watch_expressions(expressions, cx);
+        }
+    }
+
+    pub fn set_watch_expressions(&mut self, expressions: Vec<String>, cx: &mut Context<Self>) {
+        if self.watch_expressions == expressions {
+            return;
+        }
+        if let DapStoreMode::Collab(collab) = &self.mode {
+            cx.background_spawn(
+                collab
+                    .upstream_client
+                    .request(proto::SetDebugWatchExpressions {
+                        project_id: collab.upstream_project_id,
+                        expressions: expressions.clone(),
+                    }),
+            )
+            .detach_and_log_err(cx);
+        }
+        self.watch_expressions_updated(expressions, cx);
+    }
+
+    fn watch_expressions_updated(&mut self, expressions: Vec<String>, cx: &mut Context<Self>) {
+        if self.watch_expressions == expressions {
+            return;
+        }
+        self.watch_expressions = expressions;
+        self.broadcast_watch_expressions();
+        cx.emit(DapStoreEvent::WatchExpressionsUpdated);
+        cx.notify();
+    }
+
+    pub(crate) fn broadcast_watch_expressions(&self) {
+        if let Some((downstream_client, project_id)) = &self.downstream_client {
+            downstream_client
+                .send(proto::UpdateDebugWatchExpressions {
+                    project_id: *project_id,
+                    expressions: self.watch_expressions.clone(),
+                })
+                .log_err();
+        }
+    }
+
     pub fn shared(
         &mut self,
         project_id: u64,
@@ -786,6 +863,27 @@ impl DapStore {
         Ok(request.to_proto())
     }
 
+    async fn handle_update_debug_watch_expressions(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateDebugWatchExpressions>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            this.watch_expressions_updated(envelope.payload.expressions, cx)
+        })
+    }
+
+    async fn handle_set_debug_watch_expressions(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::SetDebugWatchExpressions>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |this, cx| {
+            this.set_watch_expressions(envelope.payload.expressions, cx)
+        })?;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_get_debug_adapter_binary(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GetDebugAdapterBinary>,
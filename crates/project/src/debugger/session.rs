@@ -2,15 +2,17 @@ use super::breakpoint_store::{
     BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand,
-    StepBackCommand, StepCommand, StepInCommand, StepOutCommand, TerminateCommand,
-    TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisassembleCommand,
+    DisconnectCommand, EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand,
+    LocationsCommand, ModulesCommand, NextCommand, PauseCommand, ReadMemoryCommand, RestartCommand,
+    RestartStackFrameCommand, ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
+    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    WriteMemoryCommand,
 };
 use super::dap_store::DapStore;
 use anyhow::{Context as _, Result, anyhow};
+use base64::prelude::*;
 use collections::{HashMap, HashSet, IndexMap, IndexSet};
 use dap::adapters::{DebugAdapterBinary, DebugTaskDefinition};
 use dap::messages::Response;
@@ -601,6 +603,47 @@ impl CompletionsQuery {
     }
 }
 
+/// A block of the debuggee's memory, as returned by a `readMemory` request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryChunk {
+    /// The address of the first byte of `data`.
+    pub address: u64,
+    pub data: Vec<u8>,
+    /// The number of bytes after `data` that couldn't be read.
+    pub unreadable_bytes: u64,
+}
+
+impl MemoryChunk {
+    fn from_dap(response: dap::ReadMemoryResponse) -> Result<Self> {
+        let address = parse_memory_address(&response.address)
+            .with_context(|| format!("invalid memory address {:?}", response.address))?;
+        let data = match response.data {
+            Some(data) => BASE64_STANDARD
+                .decode(data)
+                .context("decoding memory contents")?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            address,
+            data,
+            unreadable_bytes: response.unreadable_bytes.unwrap_or_default(),
+        })
+    }
+}
+
+/// Parses an address reported by a debug adapter, which is hexadecimal when prefixed with `0x`
+/// and decimal otherwise.
+pub fn parse_memory_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse().ok(),
+    }
+}
+
 #[derive(Debug)]
 pub enum SessionEvent {
     Modules,
@@ -1441,6 +1484,21 @@ impl Session {
         }
     }
 
+    /// Evaluates a watch expression in the given stack frame. Unlike [`Self::evaluate`], the
+    /// result isn't written to the console.
+    pub fn evaluate_watch(
+        &self,
+        expression: String,
+        frame_id: Option<u64>,
+    ) -> Task<Result<dap::EvaluateResponse>> {
+        self.mode.request_dap(EvaluateCommand {
+            expression,
+            context: Some(EvaluateArgumentsContext::Watch),
+            frame_id,
+            source: None,
+        })
+    }
+
     pub fn exception_breakpoints(
         &self,
     ) -> impl Iterator<Item = &(ExceptionBreakpointsFilter, IsEnabled)> {
@@ -2016,6 +2074,82 @@ impl Session {
         })
     }
 
+    pub fn read_memory(
+        &self,
+        memory_reference: String,
+        offset: i64,
+        count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<MemoryChunk>> {
+        if !ReadMemoryCommand::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support reading memory"
+            )));
+        }
+        let request = self.mode.request_dap(ReadMemoryCommand {
+            memory_reference,
+            offset: Some(offset),
+            count,
+        });
+        cx.background_spawn(async move { MemoryChunk::from_dap(request.await?) })
+    }
+
+    /// Writes `data` to the debuggee's memory, returning the number of bytes written.
+    pub fn write_memory(
+        &self,
+        memory_reference: String,
+        offset: i64,
+        data: &[u8],
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        if !WriteMemoryCommand::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support writing memory"
+            )));
+        }
+        let len = data.len() as u64;
+        let request = self.mode.request_dap(WriteMemoryCommand {
+            memory_reference,
+            offset: Some(offset),
+            allow_partial: Some(false),
+            data: BASE64_STANDARD.encode(data),
+        });
+        cx.spawn(async move |this, cx| {
+            let response = request.await?;
+            this.update(cx, |this, cx| {
+                this.invalidate_command_type::<VariablesCommand>();
+                this.invalidate_command_type::<ScopesCommand>();
+                cx.emit(SessionEvent::Variables);
+                cx.notify();
+            })?;
+            Ok(response.bytes_written.unwrap_or(len))
+        })
+    }
+
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset`
+    /// instructions away from the given memory reference.
+    pub fn disassemble(
+        &self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<dap::DisassembledInstruction>>> {
+        if !DisassembleCommand::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support disassembly"
+            )));
+        }
+        let request = self.mode.request_dap(DisassembleCommand {
+            memory_reference,
+            offset: None,
+            instruction_offset: Some(instruction_offset),
+            instruction_count,
+            resolve_symbols: Some(true),
+        });
+        cx.background_spawn(async move { request.await })
+    }
+
     pub fn location(
         &mut self,
         reference: u64,
@@ -24,12 +24,15 @@ use git::{
     parse_git_remote_url,
     repository::{
         Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
-        UpstreamTrackingStatus,
+        GitRepositoryCheckpoint, LogOptions, PushOptions, RebaseProgress, RebaseTodoAction,
+        RebaseTodoItem, Remote, RemoteCommandOutput, RepoPath, ResetMode, SequencerAction,
+        SequencerOperation, Tag, UpstreamTrackingStatus,
     },
+    stash::{GitStash, StashEntry},
     status::{
         FileStatus, GitSummary, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode,
     },
+    worktree::GitWorktree,
 };
 use gpui::{
     App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
@@ -43,7 +46,10 @@ use parking_lot::Mutex;
 use postage::stream::Stream as _;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
-    proto::{self, FromProto, SSH_PROJECT_ID, ToProto, git_reset, split_repository_update},
+    proto::{
+        self, FromProto, SSH_PROJECT_ID, ToProto, git_rebase_todo_item, git_reset,
+        git_sequencer_action, split_repository_update,
+    },
 };
 use serde::Deserialize;
 use std::{
@@ -234,6 +240,9 @@ pub struct MergeDetails {
     pub conflicted_paths: TreeSet<RepoPath>,
     pub message: Option<SharedString>,
     pub heads: Vec<Option<SharedString>>,
+    /// The operation that stopped midway, waiting for conflicts to be resolved.
+    pub in_progress_operation: Option<SequencerOperation>,
+    pub rebase_progress: Option<RebaseProgress>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -245,6 +254,7 @@ pub struct RepositorySnapshot {
     pub head_commit: Option<CommitDetails>,
     pub scan_id: u64,
     pub merge: MergeDetails,
+    pub stash_entries: GitStash,
 }
 
 type JobId = u64;
@@ -413,15 +423,34 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_get_branches);
         client.add_entity_request_handler(Self::handle_change_branch);
         client.add_entity_request_handler(Self::handle_create_branch);
+        client.add_entity_request_handler(Self::handle_delete_branch);
+        client.add_entity_request_handler(Self::handle_rename_branch);
+        client.add_entity_request_handler(Self::handle_set_upstream);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_get_worktrees);
+        client.add_entity_request_handler(Self::handle_create_worktree);
+        client.add_entity_request_handler(Self::handle_remove_worktree);
         client.add_entity_request_handler(Self::handle_git_init);
         client.add_entity_request_handler(Self::handle_push);
         client.add_entity_request_handler(Self::handle_pull);
         client.add_entity_request_handler(Self::handle_fetch);
         client.add_entity_request_handler(Self::handle_stage);
         client.add_entity_request_handler(Self::handle_unstage);
+        client.add_entity_request_handler(Self::handle_stash);
+        client.add_entity_request_handler(Self::handle_stash_pop);
+        client.add_entity_request_handler(Self::handle_stash_apply);
+        client.add_entity_request_handler(Self::handle_stash_drop);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_rebase);
+        client.add_entity_request_handler(Self::handle_sequencer_action);
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_reset);
         client.add_entity_request_handler(Self::handle_show);
+        client.add_entity_request_handler(Self::handle_log);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
@@ -1702,6 +1731,167 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_stash(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::Stash>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let paths = envelope
+            .payload
+            .paths
+            .into_iter()
+            .map(PathBuf::from)
+            .map(RepoPath::new)
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_paths(paths, envelope.payload.message, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_pop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::StashPop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let index = envelope.payload.stash_index.map(|index| index as usize);
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_pop(index, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_apply(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::StashApply>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let index = envelope.payload.stash_index.map(|index| index as usize);
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_apply(index, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_drop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::StashDrop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let index = envelope.payload.stash_index.map(|index| index as usize);
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_drop(index, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.cherry_pick(envelope.payload.commits, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commits, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebase>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let todo = envelope.payload.interactive.then(|| {
+            envelope
+                .payload
+                .todo
+                .iter()
+                .map(proto_to_rebase_todo_item)
+                .collect()
+        });
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase(envelope.payload.onto, todo, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_sequencer_action(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSequencerAction>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = proto_to_sequencer_operation(envelope.payload.operation());
+        let action = match envelope.payload.action() {
+            git_sequencer_action::Action::Continue => SequencerAction::Continue,
+            git_sequencer_action::Action::Abort => SequencerAction::Abort,
+            git_sequencer_action::Action::Skip => SequencerAction::Skip,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.sequencer_action(operation, action, cx)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
     async fn handle_set_index_text(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::SetIndexText>,
@@ -1833,175 +2023,404 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
-    async fn handle_show(
+    async fn handle_delete_branch(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitShow>,
+        envelope: TypedEnvelope<proto::GitDeleteBranch>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GitCommitDetails> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let branch_name = envelope.payload.branch_name;
+        let force = envelope.payload.force;
 
-        let commit = repository_handle
+        repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.show(envelope.payload.commit)
+                repository_handle.delete_branch(branch_name, force)
             })?
             .await??;
-        Ok(proto::GitCommitDetails {
-            sha: commit.sha.into(),
-            message: commit.message.into(),
-            commit_timestamp: commit.commit_timestamp,
-            author_email: commit.author_email.into(),
-            author_name: commit.author_name.into(),
-        })
-    }
-
-    async fn handle_load_commit_diff(
-        this: Entity<Self>,
-        envelope: TypedEnvelope<proto::LoadCommitDiff>,
-        mut cx: AsyncApp,
-    ) -> Result<proto::LoadCommitDiffResponse> {
-        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        let commit_diff = repository_handle
-            .update(&mut cx, |repository_handle, _| {
-                repository_handle.load_commit_diff(envelope.payload.commit)
-            })?
-            .await??;
-        Ok(proto::LoadCommitDiffResponse {
-            files: commit_diff
-                .files
-                .into_iter()
-                .map(|file| proto::CommitFile {
-                    path: file.path.to_string(),
-                    old_text: file.old_text,
-                    new_text: file.new_text,
-                })
-                .collect(),
-        })
+        Ok(proto::Ack {})
     }
 
-    async fn handle_reset(
+    async fn handle_rename_branch(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitReset>,
+        envelope: TypedEnvelope<proto::GitRenameBranch>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-
-        let mode = match envelope.payload.mode() {
-            git_reset::ResetMode::Soft => ResetMode::Soft,
-            git_reset::ResetMode::Mixed => ResetMode::Mixed,
-        };
+        let old_name = envelope.payload.old_name;
+        let new_name = envelope.payload.new_name;
 
         repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.reset(envelope.payload.commit, mode, cx)
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rename_branch(old_name, new_name)
             })?
             .await??;
+
         Ok(proto::Ack {})
     }
 
-    async fn handle_checkout_files(
+    async fn handle_set_upstream(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        envelope: TypedEnvelope<proto::GitSetUpstream>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let paths = envelope
-            .payload
-            .paths
-            .iter()
-            .map(|s| RepoPath::from_str(s))
-            .collect();
+        let branch_name = envelope.payload.branch_name;
+        let upstream = envelope.payload.upstream;
 
         repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.set_upstream(branch_name, upstream)
             })?
             .await??;
+
         Ok(proto::Ack {})
     }
 
-    async fn handle_open_commit_message_buffer(
+    async fn handle_get_tags(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenBufferResponse> {
+    ) -> Result<proto::GitTagsResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let buffer = repository
-            .update(&mut cx, |repository, cx| {
-                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
-            })?
-            .await?;
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
-        this.update(&mut cx, |this, cx| {
-            this.buffer_store.update(cx, |buffer_store, cx| {
-                buffer_store
-                    .create_buffer_for_peer(
-                        &buffer,
-                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
-                        cx,
-                    )
-                    .detach_and_log_err(cx);
-            })
-        })?;
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
 
-        Ok(proto::OpenBufferResponse {
-            buffer_id: buffer_id.to_proto(),
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
         })
     }
 
-    async fn handle_askpass(
+    async fn handle_create_tag(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::AskPassRequest>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
         mut cx: AsyncApp,
-    ) -> Result<proto::AskPassResponse> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-
-        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
-        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
-            debug_panic!("no askpass found");
-            return Err(anyhow::anyhow!("no askpass found"));
-        };
-
-        let response = askpass.ask_password(envelope.payload.prompt).await?;
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let proto::GitCreateTag {
+            name,
+            commit,
+            message,
+            ..
+        } = envelope.payload;
 
-        delegates
-            .lock()
-            .insert(envelope.payload.askpass_id, askpass);
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(name, commit, message)
+            })?
+            .await??;
 
-        Ok(proto::AskPassResponse { response })
+        Ok(proto::Ack {})
     }
 
-    async fn handle_check_for_pushed_commits(
+    async fn handle_delete_tag(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
         mut cx: AsyncApp,
-    ) -> Result<proto::CheckForPushedCommitsResponse> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let name = envelope.payload.name;
 
-        let branches = repository_handle
+        repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.check_for_pushed_commits()
+                repository_handle.delete_tag(name)
             })?
             .await??;
-        Ok(proto::CheckForPushedCommitsResponse {
-            pushed_to: branches
-                .into_iter()
-                .map(|commit| commit.to_string())
-                .collect(),
-        })
+
+        Ok(proto::Ack {})
     }
 
-    async fn handle_git_diff(
+    async fn handle_push_tag(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitDiff>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let tag_name = envelope.payload.tag_name.into();
+        let remote_name = envelope.payload.remote_name.into();
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(tag_name, remote_name, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_get_worktrees(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetWorktrees>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitWorktreesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let worktrees = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.worktrees()
+            })?
+            .await??;
+
+        Ok(proto::GitWorktreesResponse {
+            worktrees: worktrees.iter().map(worktree_to_proto).collect(),
+        })
+    }
+
+    async fn handle_create_worktree(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateWorktree>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = PathBuf::from_proto(envelope.payload.path);
+        let commit = envelope.payload.commit;
+        let new_branch = envelope.payload.new_branch;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_worktree(path, commit, new_branch)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_remove_worktree(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRemoveWorktree>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = PathBuf::from_proto(envelope.payload.path);
+        let force = envelope.payload.force;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.remove_worktree(path, force)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_show(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitShow>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCommitDetails> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let commit = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.show(envelope.payload.commit)
+            })?
+            .await??;
+        Ok(proto::GitCommitDetails {
+            sha: commit.sha.into(),
+            message: commit.message.into(),
+            commit_timestamp: commit.commit_timestamp,
+            author_email: commit.author_email.into(),
+            author_name: commit.author_name.into(),
+        })
+    }
+
+    async fn handle_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let options = LogOptions {
+            skip: envelope.payload.skip as usize,
+            limit: envelope.payload.limit as usize,
+            path: envelope.payload.path.as_deref().map(RepoPath::from_str),
+            author: envelope.payload.author,
+            message: envelope.payload.message,
+        };
+
+        let commits = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.log(options)
+            })?
+            .await??;
+        Ok(proto::GitLogResponse {
+            commits: commits.iter().map(commit_details_to_proto).collect(),
+        })
+    }
+
+    async fn handle_load_commit_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::LoadCommitDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::LoadCommitDiffResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let commit_diff = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_commit_diff(envelope.payload.commit)
+            })?
+            .await??;
+        Ok(proto::LoadCommitDiffResponse {
+            files: commit_diff
+                .files
+                .into_iter()
+                .map(|file| proto::CommitFile {
+                    path: file.path.to_string(),
+                    old_text: file.old_text,
+                    new_text: file.new_text,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let mode = match envelope.payload.mode() {
+            git_reset::ResetMode::Soft => ResetMode::Soft,
+            git_reset::ResetMode::Mixed => ResetMode::Mixed,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.reset(envelope.payload.commit, mode, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_str(s))
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_open_commit_message_buffer(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenBufferResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let buffer = repository
+            .update(&mut cx, |repository, cx| {
+                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+            })?
+            .await?;
+
+        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
+        this.update(&mut cx, |this, cx| {
+            this.buffer_store.update(cx, |buffer_store, cx| {
+                buffer_store
+                    .create_buffer_for_peer(
+                        &buffer,
+                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+            })
+        })?;
+
+        Ok(proto::OpenBufferResponse {
+            buffer_id: buffer_id.to_proto(),
+        })
+    }
+
+    async fn handle_askpass(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::AskPassRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::AskPassResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
+        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
+            debug_panic!("no askpass found");
+            return Err(anyhow::anyhow!("no askpass found"));
+        };
+
+        let response = askpass.ask_password(envelope.payload.prompt).await?;
+
+        delegates
+            .lock()
+            .insert(envelope.payload.askpass_id, askpass);
+
+        Ok(proto::AskPassResponse { response })
+    }
+
+    async fn handle_check_for_pushed_commits(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::CheckForPushedCommitsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let branches = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.check_for_pushed_commits()
+            })?
+            .await??;
+        Ok(proto::CheckForPushedCommitsResponse {
+            pushed_to: branches
+                .into_iter()
+                .map(|commit| commit.to_string())
+                .collect(),
+        })
+    }
+
+    async fn handle_git_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDiff>,
         mut cx: AsyncApp,
     ) -> Result<proto::GitDiffResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
@@ -2560,6 +2979,7 @@ impl RepositorySnapshot {
             head_commit: None,
             scan_id: 0,
             merge: Default::default(),
+            stash_entries: Default::default(),
         }
     }
 
@@ -2585,6 +3005,21 @@ impl RepositorySnapshot {
             entry_ids: vec![self.id.to_proto()],
             scan_id: self.scan_id,
             is_last_update: true,
+            stash_entries: self
+                .stash_entries
+                .entries
+                .iter()
+                .map(stash_entry_to_proto)
+                .collect(),
+            in_progress_operation: self
+                .merge
+                .in_progress_operation
+                .map(|operation| sequencer_operation_to_proto(operation).into()),
+            rebase_progress: self
+                .merge
+                .rebase_progress
+                .as_ref()
+                .map(rebase_progress_to_proto),
         }
     }
 
@@ -2647,10 +3082,25 @@ impl RepositorySnapshot {
             entry_ids: vec![],
             scan_id: self.scan_id,
             is_last_update: true,
-        }
-    }
-
-    pub fn status(&self) -> impl Iterator<Item = StatusEntry> + '_ {
+            stash_entries: self
+                .stash_entries
+                .entries
+                .iter()
+                .map(stash_entry_to_proto)
+                .collect(),
+            in_progress_operation: self
+                .merge
+                .in_progress_operation
+                .map(|operation| sequencer_operation_to_proto(operation).into()),
+            rebase_progress: self
+                .merge
+                .rebase_progress
+                .as_ref()
+                .map(rebase_progress_to_proto),
+        }
+    }
+
+    pub fn status(&self) -> impl Iterator<Item = StatusEntry> + '_ {
         self.statuses_by_path.iter().cloned()
     }
 
@@ -2708,6 +3158,17 @@ impl MergeDetails {
             .into_iter()
             .map(|opt| opt.map(SharedString::from))
             .collect::<Vec<_>>();
+        let rebase_progress = backend.rebase_progress().await;
+        let in_progress_operation = if rebase_progress.is_some() {
+            Some(SequencerOperation::Rebase)
+        } else {
+            match heads.as_slice() {
+                [Some(_), ..] => Some(SequencerOperation::Merge),
+                [_, Some(_), ..] => Some(SequencerOperation::CherryPick),
+                [_, _, _, Some(_), ..] => Some(SequencerOperation::Revert),
+                _ => None,
+            }
+        };
         let merge_heads_changed = heads != prev_snapshot.merge.heads;
         let conflicted_paths = if merge_heads_changed {
             let current_conflicted_paths = TreeSet::from_ordered_entries(
@@ -2730,6 +3191,8 @@ impl MergeDetails {
                 return Ok((
                     MergeDetails {
                         message: message.map(SharedString::from),
+                        in_progress_operation,
+                        rebase_progress,
                         ..prev_snapshot.merge.clone()
                     },
                     false,
@@ -2744,6 +3207,8 @@ impl MergeDetails {
             conflicted_paths,
             message: message.map(SharedString::from),
             heads,
+            in_progress_operation,
+            rebase_progress,
         };
         Ok((details, merge_heads_changed))
     }
@@ -3267,6 +3732,40 @@ impl Repository {
         })
     }
 
+    pub fn log(&mut self, options: LogOptions) -> oneshot::Receiver<Result<Vec<CommitDetails>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.log(options).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let resp = client
+                        .request(proto::GitLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            skip: options.skip as u64,
+                            limit: options.limit as u64,
+                            path: options.path.map(|path| path.as_ref().to_proto()),
+                            author: options.author,
+                            message: options.message,
+                        })
+                        .await?;
+
+                    Ok(resp
+                        .commits
+                        .into_iter()
+                        .map(|commit| CommitDetails {
+                            sha: commit.sha.into(),
+                            message: commit.message.into(),
+                            commit_timestamp: commit.commit_timestamp,
+                            author_email: commit.author_email.into(),
+                            author_name: commit.author_name.into(),
+                        })
+                        .collect())
+                }
+            }
+        })
+    }
+
     pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
         let id = self.id;
         self.send_job(None, move |git_repo, cx| async move {
@@ -3496,6 +3995,285 @@ impl Repository {
         })
     }
 
+    pub fn stash_paths(
+        &mut self,
+        paths: Vec<RepoPath>,
+        message: Option<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(Some("git stash".into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.stash_paths(paths, message, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::Stash {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            paths: paths
+                                .into_iter()
+                                .map(|repo_path| repo_path.as_ref().to_proto())
+                                .collect(),
+                            message,
+                        })
+                        .await
+                        .context("sending stash request")?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn stash_all(&mut self, cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        self.stash_paths(Vec::new(), None, cx)
+    }
+
+    pub fn stash_pop(
+        &mut self,
+        index: Option<usize>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some("git stash pop".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_pop(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::StashPop {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                stash_index: index.map(|index| index as u64),
+                            })
+                            .await
+                            .context("sending stash pop request")?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn stash_apply(
+        &mut self,
+        index: Option<usize>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some("git stash apply".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_apply(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::StashApply {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                stash_index: index.map(|index| index as u64),
+                            })
+                            .await
+                            .context("sending stash apply request")?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn stash_drop(
+        &mut self,
+        index: Option<usize>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some("git stash drop".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_drop(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::StashDrop {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                stash_index: index.map(|index| index as u64),
+                            })
+                            .await
+                            .context("sending stash drop request")?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn cherry_pick(
+        &mut self,
+        commits: Vec<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some("git cherry-pick".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.cherry_pick(commits, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commits,
+                            })
+                            .await
+                            .context("sending cherry-pick request")?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, commits: Vec<String>, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(Some("git revert".into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.revert(commits, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitRevert {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commits,
+                        })
+                        .await
+                        .context("sending revert request")?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn rebase(
+        &mut self,
+        onto: Option<String>,
+        todo: Option<Vec<RebaseTodoItem>>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(Some("git rebase".into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.rebase(onto, todo, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitRebase {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            onto,
+                            interactive: todo.is_some(),
+                            todo: todo
+                                .iter()
+                                .flatten()
+                                .map(rebase_todo_item_to_proto)
+                                .collect(),
+                        })
+                        .await
+                        .context("sending rebase request")?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn sequencer_action(
+        &mut self,
+        operation: SequencerOperation,
+        action: SequencerAction,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let job_message = format!("git {} {}", operation.subcommand(), action.flag());
+
+        self.send_job(Some(job_message.into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => {
+                    backend
+                        .sequencer_action(operation, action, environment)
+                        .await
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitSequencerAction {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            operation: sequencer_operation_to_proto(operation).into(),
+                            action: match action {
+                                SequencerAction::Continue => {
+                                    git_sequencer_action::Action::Continue.into()
+                                }
+                                SequencerAction::Abort => {
+                                    git_sequencer_action::Action::Abort.into()
+                                }
+                                SequencerAction::Skip => git_sequencer_action::Action::Skip.into(),
+                            },
+                        })
+                        .await
+                        .context("sending sequencer action request")?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
     pub fn fetch(
         &mut self,
         askpass: AskPassDelegate,
@@ -3604,42 +4382,468 @@ impl Repository {
                         result
                     }
                     RepositoryState::Remote { project_id, client } => {
-                        askpass_delegates.lock().insert(askpass_id, askpass);
-                        let _defer = util::defer(|| {
-                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
-                            debug_assert!(askpass_delegate.is_some());
-                        });
-                        let response = client
-                            .request(proto::Push {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::Push {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                branch_name: branch.to_string(),
+                                remote_name: remote.to_string(),
+                                options: options.map(|options| match options {
+                                    PushOptions::Force => proto::push::PushOptions::Force,
+                                    PushOptions::SetUpstream => {
+                                        proto::push::PushOptions::SetUpstream
+                                    }
+                                }
+                                    as i32),
+                            })
+                            .await
+                            .context("sending push request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn pull(
+        &mut self,
+        branch: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git pull {} {}", remote, branch).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .pull(
+                                branch.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment.clone(),
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::Pull {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                branch_name: branch.to_string(),
+                                remote_name: remote.to_string(),
+                            })
+                            .await
+                            .context("sending pull request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    fn spawn_set_index_text_job(
+        &mut self,
+        path: RepoPath,
+        content: Option<String>,
+        hunk_staging_operation_count: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<anyhow::Result<()>> {
+        let id = self.id;
+        let this = cx.weak_entity();
+        let git_store = self.git_store.clone();
+        self.send_keyed_job(
+            Some(GitJobKey::WriteIndex(path.clone())),
+            None,
+            move |git_repo, mut cx| async move {
+                log::debug!("start updating index text for buffer {}", path.display());
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .set_index_text(path.clone(), content, environment.clone())
+                            .await?;
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::SetIndexText {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                path: path.as_ref().to_proto(),
+                                text: content,
+                            })
+                            .await?;
+                    }
+                }
+                log::debug!("finish updating index text for buffer {}", path.display());
+
+                if let Some(hunk_staging_operation_count) = hunk_staging_operation_count {
+                    let project_path = this
+                        .read_with(&cx, |this, cx| this.repo_path_to_project_path(&path, cx))
+                        .ok()
+                        .flatten();
+                    git_store.update(&mut cx, |git_store, cx| {
+                        let buffer_id = git_store
+                            .buffer_store
+                            .read(cx)
+                            .get_by_path(&project_path?, cx)?
+                            .read(cx)
+                            .remote_id();
+                        let diff_state = git_store.diffs.get(&buffer_id)?;
+                        diff_state.update(cx, |diff_state, _| {
+                            diff_state.hunk_staging_operation_count_as_of_write =
+                                hunk_staging_operation_count;
+                        });
+                        Some(())
+                    })?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn get_remotes(
+        &mut self,
+        branch_name: Option<String>,
+    ) -> oneshot::Receiver<Result<Vec<Remote>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.get_remotes(branch_name).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GetRemotes {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            branch_name,
+                        })
+                        .await?;
+
+                    let remotes = response
+                        .remotes
+                        .into_iter()
+                        .map(|remotes| git::repository::Remote {
+                            name: remotes.name.into(),
+                        })
+                        .collect();
+
+                    Ok(remotes)
+                }
+            }
+        })
+    }
+
+    pub fn branches(&mut self) -> oneshot::Receiver<Result<Vec<Branch>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => {
+                    let backend = backend.clone();
+                    cx.background_spawn(async move { backend.branches().await })
+                        .await
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitGetBranches {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    let branches = response
+                        .branches
+                        .into_iter()
+                        .map(|branch| proto_to_branch(&branch))
+                        .collect();
+
+                    Ok(branches)
+                }
+            }
+        })
+    }
+
+    pub fn diff(&mut self, diff_type: DiffType, _cx: &App) -> oneshot::Receiver<Result<String>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.diff(diff_type).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitDiff {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            diff_type: match diff_type {
+                                DiffType::HeadToIndex => {
+                                    proto::git_diff::DiffType::HeadToIndex.into()
+                                }
+                                DiffType::HeadToWorktree => {
+                                    proto::git_diff::DiffType::HeadToWorktree.into()
+                                }
+                            },
+                        })
+                        .await?;
+
+                    Ok(response.diff)
+                }
+            }
+        })
+    }
+
+    pub fn create_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch -c {branch_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local { backend, .. } => {
+                        backend.create_branch(branch_name).await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCreateBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn change_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch {branch_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local { backend, .. } => {
+                        backend.change_branch(branch_name).await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitChangeBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_branch(
+        &mut self,
+        branch_name: String,
+        force: bool,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let flag = if force { "-D" } else { "-d" };
+        self.send_job(
+            Some(format!("git branch {flag} {branch_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local { backend, .. } => {
+                        backend.delete_branch(branch_name, force).await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitDeleteBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                                force,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rename_branch(
+        &mut self,
+        old_name: String,
+        new_name: String,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git branch -m {old_name} {new_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local { backend, .. } => {
+                        backend.rename_branch(old_name, new_name).await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRenameBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                old_name,
+                                new_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn set_upstream(
+        &mut self,
+        branch_name: String,
+        upstream: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = match &upstream {
+            Some(upstream) => format!("git branch --set-upstream-to={upstream} {branch_name}"),
+            None => format!("git branch --unset-upstream {branch_name}"),
+        };
+        self.send_job(Some(status.into()), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => {
+                    backend.set_upstream(branch_name, upstream).await
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitSetUpstream {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            branch_name,
+                            upstream,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.tags().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    /// Creates a tag at the given commit (or HEAD), annotated if a message is given.
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.create_tag(name, commit, message, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                                commit,
+                                message,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag --delete {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local { backend, .. } => backend.delete_tag(name).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitDeleteTag {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                askpass_id,
-                                branch_name: branch.to_string(),
-                                remote_name: remote.to_string(),
-                                options: options.map(|options| match options {
-                                    PushOptions::Force => proto::push::PushOptions::Force,
-                                    PushOptions::SetUpstream => {
-                                        proto::push::PushOptions::SetUpstream
-                                    }
-                                }
-                                    as i32),
+                                name,
                             })
-                            .await
-                            .context("sending push request")?;
+                            .await?;
 
-                        Ok(RemoteCommandOutput {
-                            stdout: response.stdout,
-                            stderr: response.stderr,
-                        })
+                        Ok(())
                     }
                 }
             },
         )
     }
 
-    pub fn pull(
+    pub fn push_tag(
         &mut self,
-        branch: SharedString,
+        tag_name: SharedString,
         remote: SharedString,
         askpass: AskPassDelegate,
         _cx: &mut App,
@@ -3649,7 +4853,7 @@ impl Repository {
         let id = self.id;
 
         self.send_job(
-            Some(format!("git pull {} {}", remote, branch).into()),
+            Some(format!("git push {remote} {tag_name}").into()),
             move |git_repo, cx| async move {
                 match git_repo {
                     RepositoryState::Local {
@@ -3658,8 +4862,8 @@ impl Repository {
                         ..
                     } => {
                         backend
-                            .pull(
-                                branch.to_string(),
+                            .push_tag(
+                                tag_name.to_string(),
                                 remote.to_string(),
                                 askpass,
                                 environment.clone(),
@@ -3674,15 +4878,15 @@ impl Repository {
                             debug_assert!(askpass_delegate.is_some());
                         });
                         let response = client
-                            .request(proto::Pull {
+                            .request(proto::GitPushTag {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
                                 askpass_id,
-                                branch_name: branch.to_string(),
+                                tag_name: tag_name.to_string(),
                                 remote_name: remote.to_string(),
                             })
                             .await
-                            .context("sending pull request")?;
+                            .context("sending push tag request")?;
 
                         Ok(RemoteCommandOutput {
                             stdout: response.stdout,
@@ -3694,171 +4898,47 @@ impl Repository {
         )
     }
 
-    fn spawn_set_index_text_job(
-        &mut self,
-        path: RepoPath,
-        content: Option<String>,
-        hunk_staging_operation_count: Option<usize>,
-        cx: &mut Context<Self>,
-    ) -> oneshot::Receiver<anyhow::Result<()>> {
-        let id = self.id;
-        let this = cx.weak_entity();
-        let git_store = self.git_store.clone();
-        self.send_keyed_job(
-            Some(GitJobKey::WriteIndex(path.clone())),
-            None,
-            move |git_repo, mut cx| async move {
-                log::debug!("start updating index text for buffer {}", path.display());
-                match git_repo {
-                    RepositoryState::Local {
-                        backend,
-                        environment,
-                        ..
-                    } => {
-                        backend
-                            .set_index_text(path.clone(), content, environment.clone())
-                            .await?;
-                    }
-                    RepositoryState::Remote { project_id, client } => {
-                        client
-                            .request(proto::SetIndexText {
-                                project_id: project_id.0,
-                                repository_id: id.to_proto(),
-                                path: path.as_ref().to_proto(),
-                                text: content,
-                            })
-                            .await?;
-                    }
-                }
-                log::debug!("finish updating index text for buffer {}", path.display());
-
-                if let Some(hunk_staging_operation_count) = hunk_staging_operation_count {
-                    let project_path = this
-                        .read_with(&cx, |this, cx| this.repo_path_to_project_path(&path, cx))
-                        .ok()
-                        .flatten();
-                    git_store.update(&mut cx, |git_store, cx| {
-                        let buffer_id = git_store
-                            .buffer_store
-                            .read(cx)
-                            .get_by_path(&project_path?, cx)?
-                            .read(cx)
-                            .remote_id();
-                        let diff_state = git_store.diffs.get(&buffer_id)?;
-                        diff_state.update(cx, |diff_state, _| {
-                            diff_state.hunk_staging_operation_count_as_of_write =
-                                hunk_staging_operation_count;
-                        });
-                        Some(())
-                    })?;
-                }
-                Ok(())
-            },
-        )
-    }
-
-    pub fn get_remotes(
-        &mut self,
-        branch_name: Option<String>,
-    ) -> oneshot::Receiver<Result<Vec<Remote>>> {
-        let id = self.id;
-        self.send_job(None, move |repo, _cx| async move {
-            match repo {
-                RepositoryState::Local { backend, .. } => backend.get_remotes(branch_name).await,
-                RepositoryState::Remote { project_id, client } => {
-                    let response = client
-                        .request(proto::GetRemotes {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            branch_name,
-                        })
-                        .await?;
-
-                    let remotes = response
-                        .remotes
-                        .into_iter()
-                        .map(|remotes| git::repository::Remote {
-                            name: remotes.name.into(),
-                        })
-                        .collect();
-
-                    Ok(remotes)
-                }
-            }
-        })
-    }
-
-    pub fn branches(&mut self) -> oneshot::Receiver<Result<Vec<Branch>>> {
-        let id = self.id;
-        self.send_job(None, move |repo, cx| async move {
-            match repo {
-                RepositoryState::Local { backend, .. } => {
-                    let backend = backend.clone();
-                    cx.background_spawn(async move { backend.branches().await })
-                        .await
-                }
-                RepositoryState::Remote { project_id, client } => {
-                    let response = client
-                        .request(proto::GitGetBranches {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                        })
-                        .await?;
-
-                    let branches = response
-                        .branches
-                        .into_iter()
-                        .map(|branch| proto_to_branch(&branch))
-                        .collect();
-
-                    Ok(branches)
-                }
-            }
-        })
-    }
-
-    pub fn diff(&mut self, diff_type: DiffType, _cx: &App) -> oneshot::Receiver<Result<String>> {
+    pub fn worktrees(&mut self) -> oneshot::Receiver<Result<Vec<GitWorktree>>> {
         let id = self.id;
         self.send_job(None, move |repo, _cx| async move {
             match repo {
-                RepositoryState::Local { backend, .. } => backend.diff(diff_type).await,
+                RepositoryState::Local { backend, .. } => backend.worktrees().await,
                 RepositoryState::Remote { project_id, client } => {
                     let response = client
-                        .request(proto::GitDiff {
+                        .request(proto::GitGetWorktrees {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            diff_type: match diff_type {
-                                DiffType::HeadToIndex => {
-                                    proto::git_diff::DiffType::HeadToIndex.into()
-                                }
-                                DiffType::HeadToWorktree => {
-                                    proto::git_diff::DiffType::HeadToWorktree.into()
-                                }
-                            },
                         })
                         .await?;
 
-                    Ok(response.diff)
+                    Ok(response.worktrees.iter().map(proto_to_worktree).collect())
                 }
             }
         })
     }
 
-    pub fn create_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+    pub fn create_worktree(
+        &mut self,
+        path: PathBuf,
+        commit: Option<String>,
+        new_branch: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         self.send_job(
-            Some(format!("git switch -c {branch_name}").into()),
+            Some(format!("git worktree add {}", path.display()).into()),
             move |repo, _cx| async move {
                 match repo {
                     RepositoryState::Local { backend, .. } => {
-                        backend.create_branch(branch_name).await
+                        backend.create_worktree(path, commit, new_branch).await
                     }
                     RepositoryState::Remote { project_id, client } => {
                         client
-                            .request(proto::GitCreateBranch {
+                            .request(proto::GitCreateWorktree {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                branch_name,
+                                path: path.to_proto(),
+                                commit,
+                                new_branch,
                             })
                             .await?;
 
@@ -3869,21 +4949,22 @@ impl Repository {
         )
     }
 
-    pub fn change_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+    pub fn remove_worktree(&mut self, path: PathBuf, force: bool) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         self.send_job(
-            Some(format!("git switch {branch_name}").into()),
+            Some(format!("git worktree remove {}", path.display()).into()),
             move |repo, _cx| async move {
                 match repo {
                     RepositoryState::Local { backend, .. } => {
-                        backend.change_branch(branch_name).await
+                        backend.remove_worktree(path, force).await
                     }
                     RepositoryState::Remote { project_id, client } => {
                         client
-                            .request(proto::GitChangeBranch {
+                            .request(proto::GitRemoveWorktree {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                branch_name,
+                                path: path.to_proto(),
+                                force,
                             })
                             .await?;
 
@@ -3956,6 +5037,21 @@ impl Repository {
             .map(proto_to_commit_details);
 
         self.snapshot.merge.conflicted_paths = conflicted_paths;
+        self.snapshot.merge.in_progress_operation = update
+            .in_progress_operation
+            .and_then(proto::GitSequencerOperation::from_i32)
+            .map(proto_to_sequencer_operation);
+        self.snapshot.merge.rebase_progress = update
+            .rebase_progress
+            .as_ref()
+            .map(proto_to_rebase_progress);
+        self.snapshot.stash_entries = GitStash {
+            entries: update
+                .stash_entries
+                .iter()
+                .filter_map(|entry| proto_to_stash_entry(entry).log_err())
+                .collect(),
+        };
 
         let edits = update
             .removed_statuses
@@ -4517,6 +5613,44 @@ fn proto_to_branch(proto: &proto::Branch) -> git::repository::Branch {
     }
 }
 
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        message: tag.message.as_ref().map(ToString::to_string),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(tag: &proto::GitTag) -> Tag {
+    Tag {
+        name: tag.name.clone().into(),
+        sha: tag.sha.clone().into(),
+        message: tag.message.clone().map(Into::into),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn worktree_to_proto(worktree: &GitWorktree) -> proto::GitWorktree {
+    proto::GitWorktree {
+        path: worktree.path.as_path().to_proto(),
+        head_sha: worktree.head_sha.as_ref().map(ToString::to_string),
+        branch: worktree.branch.as_ref().map(ToString::to_string),
+        is_main: worktree.is_main,
+        is_locked: worktree.is_locked,
+    }
+}
+
+fn proto_to_worktree(worktree: &proto::GitWorktree) -> GitWorktree {
+    GitWorktree {
+        path: PathBuf::from_proto(worktree.path.clone()),
+        head_sha: worktree.head_sha.clone().map(Into::into),
+        branch: worktree.branch.clone().map(Into::into),
+        is_main: worktree.is_main,
+        is_locked: worktree.is_locked,
+    }
+}
+
 fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
     proto::GitCommitDetails {
         sha: commit.sha.to_string(),
@@ -4527,6 +5661,87 @@ fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
     }
 }
 
+fn stash_entry_to_proto(entry: &StashEntry) -> proto::StashEntry {
+    proto::StashEntry {
+        index: entry.index as u64,
+        oid: entry.oid.to_string(),
+        message: entry.message.clone(),
+        branch: entry.branch.clone(),
+        timestamp: entry.timestamp,
+    }
+}
+
+fn proto_to_stash_entry(proto: &proto::StashEntry) -> Result<StashEntry> {
+    Ok(StashEntry {
+        index: proto.index as usize,
+        oid: proto.oid.parse()?,
+        message: proto.message.clone(),
+        branch: proto.branch.clone(),
+        timestamp: proto.timestamp,
+    })
+}
+
+fn sequencer_operation_to_proto(operation: SequencerOperation) -> proto::GitSequencerOperation {
+    match operation {
+        SequencerOperation::Merge => proto::GitSequencerOperation::Merge,
+        SequencerOperation::Rebase => proto::GitSequencerOperation::Rebase,
+        SequencerOperation::CherryPick => proto::GitSequencerOperation::CherryPick,
+        SequencerOperation::Revert => proto::GitSequencerOperation::Revert,
+    }
+}
+
+fn proto_to_sequencer_operation(operation: proto::GitSequencerOperation) -> SequencerOperation {
+    match operation {
+        proto::GitSequencerOperation::Merge => SequencerOperation::Merge,
+        proto::GitSequencerOperation::Rebase => SequencerOperation::Rebase,
+        proto::GitSequencerOperation::CherryPick => SequencerOperation::CherryPick,
+        proto::GitSequencerOperation::Revert => SequencerOperation::Revert,
+    }
+}
+
+fn rebase_progress_to_proto(progress: &RebaseProgress) -> proto::GitRebaseProgress {
+    proto::GitRebaseProgress {
+        head_name: progress.head_name.as_ref().map(|name| name.to_string()),
+        onto: progress.onto.as_ref().map(|onto| onto.to_string()),
+        current_step: progress.current_step as u64,
+        total_steps: progress.total_steps as u64,
+    }
+}
+
+fn proto_to_rebase_progress(proto: &proto::GitRebaseProgress) -> RebaseProgress {
+    RebaseProgress {
+        head_name: proto.head_name.clone().map(SharedString::from),
+        onto: proto.onto.clone().map(SharedString::from),
+        current_step: proto.current_step as usize,
+        total_steps: proto.total_steps as usize,
+    }
+}
+
+fn rebase_todo_item_to_proto(item: &RebaseTodoItem) -> proto::GitRebaseTodoItem {
+    proto::GitRebaseTodoItem {
+        action: match item.action {
+            RebaseTodoAction::Pick => git_rebase_todo_item::Action::Pick,
+            RebaseTodoAction::Edit => git_rebase_todo_item::Action::Edit,
+            RebaseTodoAction::Fixup => git_rebase_todo_item::Action::Fixup,
+            RebaseTodoAction::Drop => git_rebase_todo_item::Action::Drop,
+        }
+        .into(),
+        sha: item.sha.to_string(),
+    }
+}
+
+fn proto_to_rebase_todo_item(proto: &proto::GitRebaseTodoItem) -> RebaseTodoItem {
+    RebaseTodoItem {
+        action: match proto.action() {
+            git_rebase_todo_item::Action::Pick => RebaseTodoAction::Pick,
+            git_rebase_todo_item::Action::Edit => RebaseTodoAction::Edit,
+            git_rebase_todo_item::Action::Fixup => RebaseTodoAction::Fixup,
+            git_rebase_todo_item::Action::Drop => RebaseTodoAction::Drop,
+        },
+        sha: proto.sha.clone().into(),
+    }
+}
+
 fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
     CommitDetails {
         sha: proto.sha.clone().into(),
@@ -4560,10 +5775,14 @@ async fn compute_snapshot(
     let (merge_details, merge_heads_changed) =
         MergeDetails::load(&backend, &statuses_by_path, &prev_snapshot).await?;
     log::debug!("new merge details (changed={merge_heads_changed:?}): {merge_details:?}");
+    let stash_entries = backend.stash_entries().await.log_err().unwrap_or_default();
 
     if merge_heads_changed
+        || merge_details.in_progress_operation != prev_snapshot.merge.in_progress_operation
+        || merge_details.rebase_progress != prev_snapshot.merge.rebase_progress
         || branch != prev_snapshot.branch
         || statuses_by_path != prev_snapshot.statuses_by_path
+        || stash_entries != prev_snapshot.stash_entries
     {
         events.push(RepositoryEvent::Updated { full_scan: true });
     }
@@ -4588,6 +5807,7 @@ async fn compute_snapshot(
         branch,
         head_commit,
         merge: merge_details,
+        stash_entries,
     };
 
     Ok((snapshot, events))
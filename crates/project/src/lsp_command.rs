@@ -1,10 +1,11 @@
 mod signature_help;
 
 use crate::{
-    CodeAction, CompletionSource, CoreCompletion, DocumentHighlight, DocumentSymbol, Hover,
+    CodeAction, ColorPresentation, CompletionSource, CoreCompletion, DocumentColor,
+    DocumentHighlight, DocumentLink, DocumentSymbol, FoldingRange, HierarchyItem, Hover,
     HoverBlock, HoverBlockKind, InlayHint, InlayHintLabel, InlayHintLabelPart,
-    InlayHintLabelPartTooltip, InlayHintTooltip, Location, LocationLink, LspAction, MarkupContent,
-    PrepareRenameResponse, ProjectTransaction, ResolveState,
+    InlayHintLabelPartTooltip, InlayHintTooltip, Location, LocationLink, LspAction,
+    LspHierarchyItem, MarkupContent, PrepareRenameResponse, ProjectTransaction, ResolveState,
     lsp_store::{LocalLspStore, LspStore},
 };
 use anyhow::{Context as _, Result, anyhow};
@@ -15,8 +16,8 @@ use collections::HashSet;
 use futures::future;
 use gpui::{App, AsyncApp, Entity};
 use language::{
-    Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, OffsetRangeExt, PointUtf16,
-    ToOffset, ToPointUtf16, Transaction, Unclipped,
+    Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, OffsetRangeExt, Point,
+    PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
     language_settings::{InlayHintKind, LanguageSettings, language_settings},
     point_from_lsp, point_to_lsp,
     proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
@@ -29,7 +30,7 @@ use lsp::{
     ServerCapabilities,
 };
 use signature_help::{lsp_to_proto_signature, proto_to_lsp_signature};
-use std::{cmp::Reverse, mem, ops::Range, path::Path, sync::Arc};
+use std::{cmp::Reverse, iter, mem, ops::Range, path::Path, sync::Arc};
 use text::{BufferId, LineEnding};
 
 pub use signature_help::SignatureHelp;
@@ -256,6 +257,60 @@ pub(crate) struct LinkedEditingRange {
     pub position: Anchor,
 }
 
+#[derive(Debug)]
+pub(crate) struct PrepareCallHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetIncomingCalls {
+    pub item: lsp::CallHierarchyItem,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetOutgoingCalls {
+    pub item: lsp::CallHierarchyItem,
+}
+
+#[derive(Debug)]
+pub(crate) struct PrepareTypeHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetSupertypes {
+    pub item: lsp::TypeHierarchyItem,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetSubtypes {
+    pub item: lsp::TypeHierarchyItem,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetFoldingRanges;
+
+#[derive(Debug)]
+pub(crate) struct GetSelectionRanges {
+    pub positions: Vec<Anchor>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetDocumentLinks;
+
+#[derive(Debug)]
+pub(crate) struct ResolveDocumentLink {
+    pub link: DocumentLink,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetDocumentColors;
+
+#[derive(Debug)]
+pub(crate) struct GetColorPresentations {
+    pub color: DocumentColor,
+}
+
 #[async_trait(?Send)]
 impl LspCommand for PrepareRename {
     type Response = PrepareRenameResponse;
@@ -3643,3 +3698,1632 @@ impl LspCommand for LinkedEditingRange {
         BufferId::new(message.buffer_id)
     }
 }
+
+fn supports_call_hierarchy(capabilities: &ServerCapabilities) -> bool {
+    match &capabilities.call_hierarchy_provider {
+        Some(lsp::CallHierarchyServerCapability::Simple(supported)) => *supported,
+        Some(lsp::CallHierarchyServerCapability::Options(_)) => true,
+        None => false,
+    }
+}
+
+async fn hierarchy_items_from_lsp(
+    lsp_items: Vec<LspHierarchyItem>,
+    lsp_store: Entity<LspStore>,
+    buffer: Entity<Buffer>,
+    server_id: LanguageServerId,
+    mut cx: AsyncApp,
+) -> Result<Vec<HierarchyItem>> {
+    let (lsp_adapter, language_server) =
+        language_server_for_buffer(&lsp_store, &buffer, server_id, &mut cx)?;
+
+    let mut items = Vec::with_capacity(lsp_items.len());
+    for lsp_item in lsp_items {
+        let target_buffer_handle = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.open_local_buffer_via_lsp(
+                    lsp_item.uri().clone(),
+                    language_server.server_id(),
+                    lsp_adapter.name.clone(),
+                    cx,
+                )
+            })?
+            .await?;
+
+        let range = target_buffer_handle.update(&mut cx, |target_buffer, _| {
+            let selection_range = lsp_item.selection_range();
+            let target_start =
+                target_buffer.clip_point_utf16(point_from_lsp(selection_range.start), Bias::Left);
+            let target_end =
+                target_buffer.clip_point_utf16(point_from_lsp(selection_range.end), Bias::Left);
+            target_buffer.anchor_after(target_start)..target_buffer.anchor_before(target_end)
+        })?;
+        items.push(HierarchyItem {
+            name: lsp_item.name().to_string(),
+            kind: lsp_item.kind(),
+            detail: lsp_item.detail().map(ToString::to_string),
+            location: Location {
+                buffer: target_buffer_handle,
+                range,
+            },
+            language_server_id: language_server.server_id(),
+            lsp_item,
+        });
+    }
+    Ok(items)
+}
+
+fn hierarchy_items_to_proto(
+    items: Vec<HierarchyItem>,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::GetHierarchyItemsResponse {
+    let items = items
+        .into_iter()
+        .map(|item| {
+            lsp_store
+                .buffer_store()
+                .update(cx, |buffer_store, cx| {
+                    buffer_store.create_buffer_for_peer(&item.location.buffer, peer_id, cx)
+                })
+                .detach_and_log_err(cx);
+            proto::HierarchyItem {
+                location: Some(proto::Location {
+                    start: Some(serialize_anchor(&item.location.range.start)),
+                    end: Some(serialize_anchor(&item.location.range.end)),
+                    buffer_id: item.location.buffer.read(cx).remote_id().into(),
+                }),
+                language_server_id: item.language_server_id.to_proto(),
+                lsp_item: hierarchy_item_to_proto(&item.lsp_item),
+            }
+        })
+        .collect();
+    proto::GetHierarchyItemsResponse { items }
+}
+
+async fn hierarchy_items_from_proto(
+    message: proto::GetHierarchyItemsResponse,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<HierarchyItem>> {
+    let mut items = Vec::with_capacity(message.items.len());
+    for item in message.items {
+        let lsp_item = serde_json::from_slice::<LspHierarchyItem>(&item.lsp_item)?;
+        let location = item
+            .location
+            .ok_or_else(|| anyhow!("missing hierarchy item location"))?;
+        let buffer_id = BufferId::new(location.buffer_id)?;
+        let target_buffer = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.wait_for_remote_buffer(buffer_id, cx)
+            })?
+            .await?;
+        let start = location
+            .start
+            .and_then(deserialize_anchor)
+            .ok_or_else(|| anyhow!("missing target start"))?;
+        let end = location
+            .end
+            .and_then(deserialize_anchor)
+            .ok_or_else(|| anyhow!("missing target end"))?;
+        target_buffer
+            .update(&mut cx, |buffer, _| buffer.wait_for_anchors([start, end]))?
+            .await?;
+        items.push(HierarchyItem {
+            name: lsp_item.name().to_string(),
+            kind: lsp_item.kind(),
+            detail: lsp_item.detail().map(ToString::to_string),
+            location: Location {
+                buffer: target_buffer,
+                range: start..end,
+            },
+            language_server_id: LanguageServerId::from_proto(item.language_server_id),
+            lsp_item,
+        });
+    }
+    Ok(items)
+}
+
+async fn position_from_proto(
+    position: Option<proto::Anchor>,
+    version: &[proto::VectorClockEntry],
+    buffer: &Entity<Buffer>,
+    cx: &mut AsyncApp,
+) -> Result<PointUtf16> {
+    let position = position
+        .and_then(deserialize_anchor)
+        .ok_or_else(|| anyhow!("invalid position"))?;
+    buffer
+        .update(cx, |buffer, _| {
+            buffer.wait_for_version(deserialize_version(version))
+        })?
+        .await?;
+    buffer.update(cx, |buffer, _| position.to_point_utf16(buffer))
+}
+
+fn call_hierarchy_item_from_proto(lsp_item: &[u8]) -> Result<lsp::CallHierarchyItem> {
+    match serde_json::from_slice(lsp_item)? {
+        LspHierarchyItem::Call(item) => Ok(item),
+        LspHierarchyItem::Type(_) => Err(anyhow!("expected a call hierarchy item")),
+    }
+}
+
+fn type_hierarchy_item_from_proto(lsp_item: &[u8]) -> Result<lsp::TypeHierarchyItem> {
+    match serde_json::from_slice(lsp_item)? {
+        LspHierarchyItem::Type(item) => Ok(item),
+        LspHierarchyItem::Call(_) => Err(anyhow!("expected a type hierarchy item")),
+    }
+}
+
+fn hierarchy_item_to_proto(item: &LspHierarchyItem) -> Vec<u8> {
+    serde_json::to_vec(item).unwrap_or_default()
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareCallHierarchy {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyPrepare;
+    type ProtoRequest = proto::PrepareCallHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare call hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyPrepareParams> {
+        Ok(lsp::CallHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::CallHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::Call)
+            .collect();
+        hierarchy_items_from_lsp(items, lsp_store, buffer, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareCallHierarchy {
+        proto::PrepareCallHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareCallHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            position: position_from_proto(message.position, &message.version, &buffer, &mut cx)
+                .await?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetHierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetHierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareCallHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetIncomingCalls {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyIncomingCalls;
+    type ProtoRequest = proto::GetIncomingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get incoming calls"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyIncomingCallsParams> {
+        Ok(lsp::CallHierarchyIncomingCallsParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        calls: Option<Vec<lsp::CallHierarchyIncomingCall>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| LspHierarchyItem::Call(call.from))
+            .collect();
+        hierarchy_items_from_lsp(items, lsp_store, buffer, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetIncomingCalls {
+        proto::GetIncomingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: hierarchy_item_to_proto(&LspHierarchyItem::Call(self.item.clone())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetIncomingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: call_hierarchy_item_from_proto(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetHierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetHierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetIncomingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetOutgoingCalls {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyOutgoingCalls;
+    type ProtoRequest = proto::GetOutgoingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get outgoing calls"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        supports_call_hierarchy(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyOutgoingCallsParams> {
+        Ok(lsp::CallHierarchyOutgoingCallsParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        calls: Option<Vec<lsp::CallHierarchyOutgoingCall>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| LspHierarchyItem::Call(call.to))
+            .collect();
+        hierarchy_items_from_lsp(items, lsp_store, buffer, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetOutgoingCalls {
+        proto::GetOutgoingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: hierarchy_item_to_proto(&LspHierarchyItem::Call(self.item.clone())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetOutgoingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: call_hierarchy_item_from_proto(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetHierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetHierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetOutgoingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+// The type hierarchy provider is not part of the server capabilities exposed by `lsp`,
+// so the type hierarchy commands are sent to every server and rely on the default check.
+#[async_trait(?Send)]
+impl LspCommand for PrepareTypeHierarchy {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchyPrepare;
+    type ProtoRequest = proto::PrepareTypeHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare type hierarchy"
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchyPrepareParams> {
+        Ok(lsp::TypeHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::Type)
+            .collect();
+        hierarchy_items_from_lsp(items, lsp_store, buffer, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareTypeHierarchy {
+        proto::PrepareTypeHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareTypeHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            position: position_from_proto(message.position, &message.version, &buffer, &mut cx)
+                .await?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetHierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetHierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareTypeHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSupertypes {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySupertypes;
+    type ProtoRequest = proto::GetSupertypes;
+
+    fn display_name(&self) -> &str {
+        "Get supertypes"
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySupertypesParams> {
+        Ok(lsp::TypeHierarchySupertypesParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::Type)
+            .collect();
+        hierarchy_items_from_lsp(items, lsp_store, buffer, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSupertypes {
+        proto::GetSupertypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: hierarchy_item_to_proto(&LspHierarchyItem::Type(self.item.clone())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSupertypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: type_hierarchy_item_from_proto(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetHierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetHierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSupertypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSubtypes {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySubtypes;
+    type ProtoRequest = proto::GetSubtypes;
+
+    fn display_name(&self) -> &str {
+        "Get subtypes"
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySubtypesParams> {
+        Ok(lsp::TypeHierarchySubtypesParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::Type)
+            .collect();
+        hierarchy_items_from_lsp(items, lsp_store, buffer, server_id, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSubtypes {
+        proto::GetSubtypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            lsp_item: hierarchy_item_to_proto(&LspHierarchyItem::Type(self.item.clone())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSubtypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            item: type_hierarchy_item_from_proto(&message.lsp_item)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetHierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetHierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSubtypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetFoldingRanges {
+    type Response = Vec<FoldingRange>;
+    type LspRequest = lsp::request::FoldingRangeRequest;
+    type ProtoRequest = proto::GetFoldingRanges;
+
+    fn display_name(&self) -> &str {
+        "Get folding ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.folding_range_provider {
+            Some(lsp::FoldingRangeProviderCapability::Simple(supported)) => *supported,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::FoldingRangeParams> {
+        Ok(lsp::FoldingRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::FoldingRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<FoldingRange>> {
+        let mut lsp_ranges = message.unwrap_or_default();
+        lsp_ranges.sort_by_key(|range| (range.start_line, Reverse(range.end_line)));
+
+        // We advertise line folding only, so the character offsets are ignored and
+        // ranges that don't span more than a single line can't be folded.
+        buffer.read_with(&cx, |buffer, _| {
+            let max_row = buffer.max_point().row;
+            lsp_ranges
+                .into_iter()
+                .filter(|range| range.start_line < range.end_line && range.start_line < max_row)
+                .map(|range| {
+                    let end_row = range.end_line.min(max_row);
+                    let start = Point::new(range.start_line, 0);
+                    let end = Point::new(end_row, buffer.line_len(end_row));
+                    FoldingRange {
+                        range: buffer.anchor_after(start)..buffer.anchor_before(end),
+                        kind: range.kind,
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetFoldingRanges {
+        proto::GetFoldingRanges {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetFoldingRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<FoldingRange>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetFoldingRangesResponse {
+        proto::GetFoldingRangesResponse {
+            ranges: response
+                .into_iter()
+                .map(|range| proto::FoldingRange {
+                    start: Some(serialize_anchor(&range.range.start)),
+                    end: Some(serialize_anchor(&range.range.end)),
+                    kind: range.kind.map(|kind| {
+                        match kind {
+                            lsp::FoldingRangeKind::Comment => proto::folding_range::Kind::Comment,
+                            lsp::FoldingRangeKind::Imports => proto::folding_range::Kind::Imports,
+                            lsp::FoldingRangeKind::Region => proto::folding_range::Kind::Region,
+                        }
+                        .into()
+                    }),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetFoldingRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<FoldingRange>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let ranges = message
+            .ranges
+            .into_iter()
+            .filter_map(|range| {
+                let start = deserialize_anchor(range.start?)?;
+                let end = deserialize_anchor(range.end?)?;
+                let kind = range
+                    .kind
+                    .and_then(proto::folding_range::Kind::from_i32)
+                    .map(|kind| match kind {
+                        proto::folding_range::Kind::Comment => lsp::FoldingRangeKind::Comment,
+                        proto::folding_range::Kind::Imports => lsp::FoldingRangeKind::Imports,
+                        proto::folding_range::Kind::Region => lsp::FoldingRangeKind::Region,
+                    });
+                Some(FoldingRange {
+                    range: start..end,
+                    kind,
+                })
+            })
+            .collect::<Vec<_>>();
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(
+                    ranges
+                        .iter()
+                        .flat_map(|range| [range.range.start, range.range.end])
+                        .collect::<Vec<_>>(),
+                )
+            })?
+            .await?;
+        Ok(ranges)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetFoldingRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSelectionRanges {
+    type Response = Vec<Vec<Range<Anchor>>>;
+    type LspRequest = lsp::request::SelectionRangeRequest;
+    type ProtoRequest = proto::GetSelectionRanges;
+
+    fn display_name(&self) -> &str {
+        "Get selection ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.selection_range_provider {
+            Some(lsp::SelectionRangeProviderCapability::Simple(supported)) => *supported,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SelectionRangeParams> {
+        Ok(lsp::SelectionRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            positions: self
+                .positions
+                .iter()
+                .map(|position| point_to_lsp(position.to_point_utf16(buffer)))
+                .collect(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::SelectionRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer.read_with(&cx, |buffer, _| {
+            message
+                .unwrap_or_default()
+                .into_iter()
+                .map(|selection_range| {
+                    let mut ranges = Vec::new();
+                    let mut selection_range = Some(Box::new(selection_range));
+                    while let Some(range) = selection_range {
+                        let start =
+                            buffer.clip_point_utf16(point_from_lsp(range.range.start), Bias::Left);
+                        let end =
+                            buffer.clip_point_utf16(point_from_lsp(range.range.end), Bias::Left);
+                        ranges.push(buffer.anchor_before(start)..buffer.anchor_after(end));
+                        selection_range = range.parent;
+                    }
+                    ranges
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSelectionRanges {
+        proto::GetSelectionRanges {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            positions: self.positions.iter().map(serialize_anchor).collect(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSelectionRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let positions = message
+            .positions
+            .into_iter()
+            .map(|position| deserialize_anchor(position).context("invalid position"))
+            .collect::<Result<Vec<_>>>()?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(positions.clone())
+            })?
+            .await?;
+        Ok(Self { positions })
+    }
+
+    fn response_to_proto(
+        response: Vec<Vec<Range<Anchor>>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSelectionRangesResponse {
+        proto::GetSelectionRangesResponse {
+            selection_ranges: response
+                .into_iter()
+                .map(|ranges| proto::SelectionRanges {
+                    ranges: ranges
+                        .into_iter()
+                        .map(|range| proto::AnchorRange {
+                            start: Some(serialize_anchor(&range.start)),
+                            end: Some(serialize_anchor(&range.end)),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSelectionRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let selection_ranges = message
+            .selection_ranges
+            .into_iter()
+            .map(|ranges| {
+                ranges
+                    .ranges
+                    .into_iter()
+                    .filter_map(|range| {
+                        let start = deserialize_anchor(range.start?)?;
+                        let end = deserialize_anchor(range.end?)?;
+                        Some(start..end)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(
+                    selection_ranges
+                        .iter()
+                        .flatten()
+                        .flat_map(|range| [range.start, range.end])
+                        .collect::<Vec<_>>(),
+                )
+            })?
+            .await?;
+        Ok(selection_ranges)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSelectionRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetDocumentLinks {
+    type Response = Vec<DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkRequest;
+    type ProtoRequest = proto::GetDocumentLinks;
+
+    fn display_name(&self) -> &str {
+        "Get document links"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .document_link_provider
+            .is_some()
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLinkParams> {
+        Ok(lsp::DocumentLinkParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::DocumentLink>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer.read_with(&cx, |buffer, _| {
+            message
+                .unwrap_or_default()
+                .into_iter()
+                .map(|link| document_link_from_lsp(link, server_id, buffer))
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetDocumentLinks {
+        proto::GetDocumentLinks {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetDocumentLinks,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<DocumentLink>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetDocumentLinksResponse {
+        proto::GetDocumentLinksResponse {
+            links: response.into_iter().map(document_link_to_proto).collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetDocumentLinksResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let links = message
+            .links
+            .into_iter()
+            .filter_map(document_link_from_proto)
+            .collect::<Vec<_>>();
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(
+                    links
+                        .iter()
+                        .flat_map(|link| [link.range.start, link.range.end])
+                        .collect::<Vec<_>>(),
+                )
+            })?
+            .await?;
+        Ok(links)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetDocumentLinks) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for ResolveDocumentLink {
+    type Response = Option<DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkResolve;
+    type ProtoRequest = proto::ResolveDocumentLink;
+
+    fn display_name(&self) -> &str {
+        "Resolve document link"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .document_link_provider
+            .as_ref()
+            .and_then(|options| options.resolve_provider)
+            .unwrap_or(false)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLink> {
+        let range = self.link.range.start.to_point_utf16(buffer)
+            ..self.link.range.end.to_point_utf16(buffer);
+        Ok(lsp::DocumentLink {
+            range: range_to_lsp(range)?,
+            target: self
+                .link
+                .target
+                .as_deref()
+                .map(lsp::Url::parse)
+                .transpose()?,
+            tooltip: self.link.tooltip.clone(),
+            data: self.link.data.clone(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: lsp::DocumentLink,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Option<DocumentLink>> {
+        buffer.read_with(&cx, |buffer, _| {
+            Some(document_link_from_lsp(message, server_id, buffer))
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::ResolveDocumentLink {
+        proto::ResolveDocumentLink {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            link: Some(document_link_to_proto(self.link.clone())),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::ResolveDocumentLink,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let link = message
+            .link
+            .and_then(document_link_from_proto)
+            .context("invalid document link")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors([link.range.start, link.range.end])
+            })?
+            .await?;
+        Ok(Self { link })
+    }
+
+    fn response_to_proto(
+        response: Option<DocumentLink>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::ResolveDocumentLinkResponse {
+        proto::ResolveDocumentLinkResponse {
+            link: response.map(document_link_to_proto),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::ResolveDocumentLinkResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Option<DocumentLink>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let Some(link) = message.link.and_then(document_link_from_proto) else {
+            return Ok(None);
+        };
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors([link.range.start, link.range.end])
+            })?
+            .await?;
+        Ok(Some(link))
+    }
+
+    fn buffer_id_from_proto(message: &proto::ResolveDocumentLink) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+fn document_link_from_lsp(
+    link: lsp::DocumentLink,
+    server_id: LanguageServerId,
+    buffer: &Buffer,
+) -> DocumentLink {
+    let range = range_from_lsp(link.range);
+    let start = buffer.clip_point_utf16(range.start, Bias::Left);
+    let end = buffer.clip_point_utf16(range.end, Bias::Left);
+    DocumentLink {
+        range: buffer.anchor_after(start)..buffer.anchor_before(end),
+        target: link.target.map(String::from),
+        tooltip: link.tooltip,
+        server_id,
+        data: link.data,
+    }
+}
+
+fn document_link_to_proto(link: DocumentLink) -> proto::DocumentLink {
+    proto::DocumentLink {
+        start: Some(serialize_anchor(&link.range.start)),
+        end: Some(serialize_anchor(&link.range.end)),
+        target: link.target,
+        tooltip: link.tooltip,
+        language_server_id: link.server_id.to_proto(),
+        data: link.data.map(|data| data.to_string()),
+    }
+}
+
+fn document_link_from_proto(link: proto::DocumentLink) -> Option<DocumentLink> {
+    Some(DocumentLink {
+        range: deserialize_anchor(link.start?)?..deserialize_anchor(link.end?)?,
+        target: link.target,
+        tooltip: link.tooltip,
+        server_id: LanguageServerId::from_proto(link.language_server_id),
+        data: link.data.and_then(|data| serde_json::from_str(&data).ok()),
+    })
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetDocumentColors {
+    type Response = Vec<DocumentColor>;
+    type LspRequest = lsp::request::DocumentColor;
+    type ProtoRequest = proto::GetDocumentColors;
+
+    fn display_name(&self) -> &str {
+        "Get document colors"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.color_provider {
+            Some(lsp::ColorProviderCapability::Simple(supported)) => *supported,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentColorParams> {
+        Ok(lsp::DocumentColorParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Vec<lsp::ColorInformation>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<DocumentColor>> {
+        buffer.read_with(&cx, |buffer, _| {
+            message
+                .into_iter()
+                .map(|information| {
+                    let range = range_from_lsp(information.range);
+                    let start = buffer.clip_point_utf16(range.start, Bias::Left);
+                    let end = buffer.clip_point_utf16(range.end, Bias::Left);
+                    DocumentColor {
+                        range: buffer.anchor_after(start)..buffer.anchor_before(end),
+                        color: information.color,
+                        server_id,
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetDocumentColors {
+        proto::GetDocumentColors {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetDocumentColors,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<DocumentColor>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetDocumentColorsResponse {
+        proto::GetDocumentColorsResponse {
+            colors: response.into_iter().map(document_color_to_proto).collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetDocumentColorsResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<DocumentColor>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let colors = message
+            .colors
+            .into_iter()
+            .filter_map(document_color_from_proto)
+            .collect::<Vec<_>>();
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(
+                    colors
+                        .iter()
+                        .flat_map(|color| [color.range.start, color.range.end])
+                        .collect::<Vec<_>>(),
+                )
+            })?
+            .await?;
+        Ok(colors)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetDocumentColors) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetColorPresentations {
+    type Response = Vec<ColorPresentation>;
+    type LspRequest = lsp::request::ColorPresentationRequest;
+    type ProtoRequest = proto::GetColorPresentations;
+
+    fn display_name(&self) -> &str {
+        "Get color presentations"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        GetDocumentColors.check_capabilities(capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::ColorPresentationParams> {
+        let range = self.color.range.start.to_point_utf16(buffer)
+            ..self.color.range.end.to_point_utf16(buffer);
+        Ok(lsp::ColorPresentationParams {
+            text_document: make_text_document_identifier(path)?,
+            color: self.color.color,
+            range: range_to_lsp(range)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Vec<lsp::ColorPresentation>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<ColorPresentation>> {
+        buffer.read_with(&cx, |buffer, _| {
+            let edit_from_lsp = |edit: lsp::TextEdit| {
+                let range = range_from_lsp(edit.range);
+                let start = buffer.clip_point_utf16(range.start, Bias::Left);
+                let end = buffer.clip_point_utf16(range.end, Bias::Left);
+                (
+                    buffer.anchor_before(start)..buffer.anchor_after(end),
+                    edit.new_text,
+                )
+            };
+            message
+                .into_iter()
+                .map(|presentation| {
+                    // Without an edit, the label replaces the color.
+                    let edit = match presentation.text_edit {
+                        Some(edit) => edit_from_lsp(edit),
+                        None => (self.color.range.clone(), presentation.label.clone()),
+                    };
+                    let edits = iter::once(edit)
+                        .chain(
+                            presentation
+                                .additional_text_edits
+                                .into_iter()
+                                .flatten()
+                                .map(&edit_from_lsp),
+                        )
+                        .collect();
+                    ColorPresentation {
+                        label: presentation.label,
+                        edits,
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetColorPresentations {
+        proto::GetColorPresentations {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            color: Some(document_color_to_proto(self.color.clone())),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetColorPresentations,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let color = message
+            .color
+            .and_then(document_color_from_proto)
+            .context("invalid document color")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors([color.range.start, color.range.end])
+            })?
+            .await?;
+        Ok(Self { color })
+    }
+
+    fn response_to_proto(
+        response: Vec<ColorPresentation>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetColorPresentationsResponse {
+        proto::GetColorPresentationsResponse {
+            presentations: response
+                .into_iter()
+                .map(|presentation| proto::ColorPresentation {
+                    label: presentation.label,
+                    edits: presentation
+                        .edits
+                        .into_iter()
+                        .map(|(range, new_text)| proto::ColorPresentationEdit {
+                            start: Some(serialize_anchor(&range.start)),
+                            end: Some(serialize_anchor(&range.end)),
+                            new_text,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetColorPresentationsResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<ColorPresentation>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let presentations = message
+            .presentations
+            .into_iter()
+            .map(|presentation| ColorPresentation {
+                label: presentation.label,
+                edits: presentation
+                    .edits
+                    .into_iter()
+                    .filter_map(|edit| {
+                        let start = deserialize_anchor(edit.start?)?;
+                        let end = deserialize_anchor(edit.end?)?;
+                        Some((start..end, edit.new_text))
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(
+                    presentations
+                        .iter()
+                        .flat_map(|presentation| &presentation.edits)
+                        .flat_map(|(range, _)| [range.start, range.end])
+                        .collect::<Vec<_>>(),
+                )
+            })?
+            .await?;
+        Ok(presentations)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetColorPresentations) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+fn document_color_to_proto(color: DocumentColor) -> proto::DocumentColor {
+    proto::DocumentColor {
+        start: Some(serialize_anchor(&color.range.start)),
+        end: Some(serialize_anchor(&color.range.end)),
+        red: color.color.red,
+        green: color.color.green,
+        blue: color.color.blue,
+        alpha: color.color.alpha,
+        language_server_id: color.server_id.to_proto(),
+    }
+}
+
+fn document_color_from_proto(color: proto::DocumentColor) -> Option<DocumentColor> {
+    Some(DocumentColor {
+        range: deserialize_anchor(color.start?)?..deserialize_anchor(color.end?)?,
+        color: lsp::Color {
+            red: color.red,
+            green: color.green,
+            blue: color.blue,
+            alpha: color.alpha,
+        },
+        server_id: LanguageServerId::from_proto(color.language_server_id),
+    })
+}
@@ -1,10 +1,13 @@
 pub mod clangd_ext;
 pub mod lsp_ext_command;
+mod pull_diagnostics;
 pub mod rust_analyzer_ext;
+pub mod semantic_tokens;
 
 use crate::{
-    CodeAction, Completion, CompletionSource, CoreCompletion, Hover, InlayHint, LspAction,
-    ProjectItem, ProjectPath, ProjectTransaction, ResolveState, Symbol, ToolchainStore,
+    CodeAction, Completion, CompletionSource, CoreCompletion, DocumentColor, DocumentLink,
+    FoldingRange, Hover, InlayHint, LspAction, ProjectItem, ProjectPath, ProjectTransaction,
+    ResolveState, Symbol, ToolchainStore,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
     lsp_command::{self, *},
@@ -35,9 +38,9 @@ use http_client::HttpClient;
 use itertools::Itertools as _;
 use language::{
     Bias, BinaryStatus, Buffer, BufferSnapshot, CachedLspAdapter, CodeLabel, Diagnostic,
-    DiagnosticEntry, DiagnosticSet, Diff, File as _, Language, LanguageRegistry,
-    LanguageToolchainStore, LocalFile, LspAdapter, LspAdapterDelegate, Patch, PointUtf16,
-    TextBufferSnapshot, ToOffset, ToPointUtf16, Transaction, Unclipped,
+    DiagnosticEntry, DiagnosticSet, DiagnosticSourceKind, Diff, File as _, Language,
+    LanguageRegistry, LanguageToolchainStore, LocalFile, LspAdapter, LspAdapterDelegate, Patch,
+    PointUtf16, TextBufferSnapshot, ToOffset, ToPointUtf16, Transaction, Unclipped,
     language_settings::{
         FormatOnSave, Formatter, LanguageSettings, SelectedFormatter, language_settings,
     },
@@ -46,18 +49,21 @@ use language::{
     range_from_lsp, range_to_lsp,
 };
 use lsp::{
-    CodeActionKind, CompletionContext, DiagnosticSeverity, DiagnosticTag,
-    DidChangeWatchedFilesRegistrationOptions, Edit, FileOperationFilter, FileOperationPatternKind,
-    FileOperationRegistrationOptions, FileRename, FileSystemWatcher, LanguageServer,
-    LanguageServerBinary, LanguageServerBinaryOptions, LanguageServerId, LanguageServerName,
-    LspRequestFuture, MessageActionItem, MessageType, OneOf, RenameFilesParams, SymbolKind,
-    TextEdit, WillRenameFiles, WorkDoneProgressCancelParams, WorkspaceFolder,
-    notification::DidRenameFiles,
+    CodeActionKind, CompletionContext, CreateFilesParams, DeleteFilesParams, DiagnosticSeverity,
+    DiagnosticTag, DidChangeWatchedFilesRegistrationOptions, Edit, FileCreate, FileDelete,
+    FileOperationFilter, FileOperationPatternKind, FileOperationRegistrationOptions, FileRename,
+    FileSystemWatcher, LanguageServer, LanguageServerBinary, LanguageServerBinaryOptions,
+    LanguageServerId, LanguageServerName, LspRequestFuture, MessageActionItem, MessageType, OneOf,
+    RenameFilesParams, SymbolKind, TextEdit, WillCreateFiles, WillDeleteFiles, WillRenameFiles,
+    WorkDoneProgressCancelParams, WorkspaceFolder,
+    notification::{DidCreateFiles, DidDeleteFiles, DidRenameFiles},
 };
 use node_runtime::read_package_installed_version;
 use parking_lot::Mutex;
 use postage::watch;
+use pull_diagnostics::PulledDiagnostics;
 use rand::prelude::*;
+use semantic_tokens::{CachedSemanticTokens, SemanticToken};
 
 use rpc::{
     AnyProtoClient,
@@ -82,6 +88,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
+use task::{Problem, ProblemSeverity, TaskId};
 use text::{Anchor, BufferId, LineEnding, OffsetRangeExt};
 use url::Url;
 use util::{
@@ -101,6 +108,13 @@ pub use worktree::{
 
 const SERVER_LAUNCHING_BEFORE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 pub const SERVER_PROGRESS_THROTTLE_TIMEOUT: Duration = Duration::from_millis(100);
+/// A reserved language server ID, under which the problems that problem matchers find in
+/// tasks' output are stored as diagnostics, with [`DiagnosticSourceKind::Other`].
+///
+/// The language registry hands out language server IDs counting up from zero and never
+/// reaches this one, so no running language server replaces or clears these diagnostics,
+/// and there is no language server status for it.
+pub const TASK_DIAGNOSTICS_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FormatTrigger {
@@ -139,14 +153,16 @@ pub struct LocalLspStore {
     buffers_being_formatted: HashSet<BufferId>,
     last_workspace_edits_by_language_server: HashMap<LanguageServerId, ProjectTransaction>,
     language_server_watched_paths: HashMap<LanguageServerId, LanguageServerWatchedPaths>,
-    language_server_paths_watched_for_rename:
-        HashMap<LanguageServerId, RenamePathsWatchedForServer>,
+    language_server_paths_watched_for_file_operations:
+        HashMap<LanguageServerId, FileOperationsWatchedForServer>,
     language_server_watcher_registrations:
         HashMap<LanguageServerId, HashMap<String, Vec<FileSystemWatcher>>>,
     supplementary_language_servers:
         HashMap<LanguageServerId, (LanguageServerName, Arc<LanguageServer>)>,
     prettier_store: Entity<PrettierStore>,
     next_diagnostic_group_id: usize,
+    task_diagnostics:
+        HashMap<TaskId, HashMap<PathBuf, Vec<DiagnosticEntry<Unclipped<PointUtf16>>>>>,
     diagnostics: HashMap<
         WorktreeId,
         HashMap<
@@ -158,6 +174,10 @@ pub struct LocalLspStore {
         >,
     >,
     buffer_snapshots: HashMap<BufferId, HashMap<LanguageServerId, Vec<LspBufferSnapshot>>>, // buffer_id -> server_id -> vec of snapshots
+    semantic_tokens: HashMap<BufferId, HashMap<LanguageServerId, CachedSemanticTokens>>,
+    diagnostic_result_ids: HashMap<LanguageServerId, HashMap<lsp::Url, String>>,
+    document_diagnostics_pulls: HashMap<BufferId, Task<()>>,
+    workspace_diagnostics_pulls: HashMap<LanguageServerId, Task<()>>,
     _subscription: gpui::Subscription,
     lsp_tree: Entity<LanguageServerTree>,
     registered_buffers: HashMap<BufferId, usize>,
@@ -824,6 +844,44 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
+                let this = this.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            this.refresh_pulled_diagnostics(server_id, cx);
+                        })?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = this.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::CodeLensRefresh, _, _>({
                 let this = this.clone();
@@ -2456,6 +2514,7 @@ impl LocalLspStore {
     ) {
         buffer.update(cx, |buffer, cx| {
             let _ = self.buffer_snapshots.remove(&buffer.remote_id());
+            let _ = self.semantic_tokens.remove(&buffer.remote_id());
 
             for (_, language_server) in self.language_servers_for_buffer(buffer, cx) {
                 language_server.unregister_buffer(file_url.clone());
@@ -3015,7 +3074,7 @@ impl LocalLspStore {
                 });
             self.language_server_watched_paths
                 .remove(&server_id_to_remove);
-            self.language_server_paths_watched_for_rename
+            self.language_server_paths_watched_for_file_operations
                 .remove(&server_id_to_remove);
             self.last_workspace_edits_by_language_server
                 .remove(&server_id_to_remove);
@@ -3355,6 +3414,8 @@ pub struct LspStore {
     _maintain_buffer_languages: Task<()>,
     diagnostic_summaries:
         HashMap<WorktreeId, HashMap<Arc<Path>, HashMap<LanguageServerId, DiagnosticSummary>>>,
+    folding_ranges: HashMap<BufferId, (clock::Global, Vec<FoldingRange>)>,
+    document_links: HashMap<BufferId, (clock::Global, Vec<DocumentLink>)>,
 }
 
 pub enum LspStoreEvent {
@@ -3373,6 +3434,7 @@ pub enum LspStoreEvent {
     Notification(String),
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         language_server_id: LanguageServerId,
         path: ProjectPath,
@@ -3430,10 +3492,14 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_get_semantic_tokens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
+        client.add_entity_request_handler(Self::handle_create_project_entry);
         client.add_entity_request_handler(Self::handle_rename_project_entry);
+        client.add_entity_request_handler(Self::handle_delete_project_entry);
         client.add_entity_request_handler(Self::handle_language_server_id_for_name);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetCodeActions>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetCompletions>);
@@ -3447,6 +3513,17 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetFoldingRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentLinks>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<ResolveDocumentLink>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetColorPresentations>);
 
         client.add_entity_request_handler(Self::handle_lsp_command::<lsp_ext_command::ExpandMacro>);
         client.add_entity_request_handler(Self::handle_lsp_command::<lsp_ext_command::OpenDocs>);
@@ -3546,16 +3623,21 @@ impl LspStore {
                 language_servers: Default::default(),
                 last_workspace_edits_by_language_server: Default::default(),
                 language_server_watched_paths: Default::default(),
-                language_server_paths_watched_for_rename: Default::default(),
+                language_server_paths_watched_for_file_operations: Default::default(),
                 language_server_watcher_registrations: Default::default(),
                 buffers_being_formatted: Default::default(),
                 buffer_snapshots: Default::default(),
+                semantic_tokens: Default::default(),
+                diagnostic_result_ids: Default::default(),
+                document_diagnostics_pulls: Default::default(),
+                workspace_diagnostics_pulls: Default::default(),
                 prettier_store,
                 environment,
                 http_client,
                 fs,
                 yarn,
                 next_diagnostic_group_id: Default::default(),
+                task_diagnostics: Default::default(),
                 diagnostics: Default::default(),
                 _subscription: cx.on_app_quit(|this, cx| {
                     this.as_local_mut().unwrap().shutdown_language_servers(cx)
@@ -3572,6 +3654,8 @@ impl LspStore {
             language_server_statuses: Default::default(),
             nonce: StdRng::from_entropy().r#gen(),
             diagnostic_summaries: Default::default(),
+            folding_ranges: Default::default(),
+            document_links: Default::default(),
             active_entry: None,
 
             _maintain_workspace_config,
@@ -3628,6 +3712,8 @@ impl LspStore {
             language_server_statuses: Default::default(),
             nonce: StdRng::from_entropy().r#gen(),
             diagnostic_summaries: Default::default(),
+            folding_ranges: Default::default(),
+            document_links: Default::default(),
             active_entry: None,
             toolchain_store,
             _maintain_workspace_config,
@@ -3665,6 +3751,13 @@ impl LspStore {
                     }
                 }
             }
+            BufferStoreEvent::BufferDropped(buffer_id) => {
+                self.folding_ranges.remove(buffer_id);
+                self.document_links.remove(buffer_id);
+                if let Some(local) = self.as_local_mut() {
+                    local.document_diagnostics_pulls.remove(buffer_id);
+                }
+            }
             _ => {}
         }
     }
@@ -3755,11 +3848,13 @@ impl LspStore {
     ) {
         match event {
             language::BufferEvent::Edited { .. } => {
-                self.on_buffer_edited(buffer, cx);
+                self.on_buffer_edited(buffer.clone(), cx);
+                self.pull_document_diagnostics(&buffer, cx);
             }
 
             language::BufferEvent::Saved => {
-                self.on_buffer_saved(buffer, cx);
+                self.on_buffer_saved(buffer.clone(), cx);
+                self.pull_workspace_diagnostics_for_buffer(&buffer, cx);
             }
 
             _ => {}
@@ -3910,6 +4005,7 @@ impl LspStore {
 
             if ignore_refcounts || *refcount == 1 {
                 local.register_buffer_with_language_servers(buffer, cx);
+                self.pull_document_diagnostics(buffer, cx);
             }
             if !ignore_refcounts {
                 cx.observe_release(&handle, move |this, buffer, cx| {
@@ -5614,6 +5710,341 @@ impl LspStore {
         }
     }
 
+    /// Returns the folding ranges of the given buffer, only querying its first capable
+    /// language server when the buffer has changed since the ranges were last fetched.
+    pub fn folding_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<FoldingRange>>> {
+        let buffer_id = buffer.read(cx).remote_id();
+        let version = buffer.read(cx).version();
+        if let Some((cached_version, ranges)) = self.folding_ranges.get(&buffer_id) {
+            if *cached_version == version {
+                return Task::ready(Ok(ranges.clone()));
+            }
+        }
+
+        let request = self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetFoldingRanges,
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let ranges = request.await?;
+            // An empty response may just mean that no capable server is running yet,
+            // so only non-empty ones are cached.
+            if !ranges.is_empty() {
+                this.update(cx, |this, _| {
+                    this.folding_ranges
+                        .insert(buffer_id, (version, ranges.clone()));
+                })?;
+            }
+            Ok(ranges)
+        })
+    }
+
+    /// Returns the document links of the given buffer, only querying its first capable
+    /// language server when the buffer has changed since the links were last fetched.
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        let buffer_id = buffer.read(cx).remote_id();
+        let version = buffer.read(cx).version();
+        if let Some((cached_version, links)) = self.document_links.get(&buffer_id) {
+            if *cached_version == version {
+                return Task::ready(Ok(links.clone()));
+            }
+        }
+
+        let request = self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetDocumentLinks,
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let links = request.await?;
+            if !links.is_empty() {
+                this.update(cx, |this, _| {
+                    this.document_links
+                        .insert(buffer_id, (version, links.clone()));
+                })?;
+            }
+            Ok(links)
+        })
+    }
+
+    /// Fetches the colors in the given buffer from all of its language servers.
+    pub fn document_colors(
+        &mut self,
+        buffer_handle: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentColor>>> {
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request_task = upstream_client.request(proto::MultiLspQuery {
+                buffer_id: buffer_handle.read(cx).remote_id().into(),
+                version: serialize_version(&buffer_handle.read(cx).version()),
+                project_id,
+                strategy: Some(proto::multi_lsp_query::Strategy::All(
+                    proto::AllLanguageServers {},
+                )),
+                request: Some(proto::multi_lsp_query::Request::GetDocumentColors(
+                    GetDocumentColors.to_proto(project_id, buffer_handle.read(cx)),
+                )),
+            });
+            let buffer = buffer_handle.clone();
+            cx.spawn(async move |weak_project, cx| {
+                let Some(project) = weak_project.upgrade() else {
+                    return Ok(Vec::new());
+                };
+                let responses = request_task.await?.responses;
+                let colors = join_all(
+                    responses
+                        .into_iter()
+                        .filter_map(|lsp_response| match lsp_response.response? {
+                            proto::lsp_response::Response::GetDocumentColorsResponse(response) => {
+                                Some(response)
+                            }
+                            unexpected => {
+                                debug_panic!("Unexpected response: {unexpected:?}");
+                                None
+                            }
+                        })
+                        .map(|colors_response| {
+                            GetDocumentColors.response_from_proto(
+                                colors_response,
+                                project.clone(),
+                                buffer.clone(),
+                                cx.clone(),
+                            )
+                        }),
+                )
+                .await;
+
+                Ok(colors
+                    .into_iter()
+                    .collect::<Result<Vec<Vec<_>>>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect())
+            })
+        } else {
+            let colors_task = self.request_multiple_lsp_locally(
+                buffer_handle,
+                None::<usize>,
+                GetDocumentColors,
+                cx,
+            );
+            cx.spawn(async move |_, _| Ok(colors_task.await.into_iter().flatten().collect()))
+        }
+    }
+
+    /// Fetches the semantic tokens of the given buffer from all of its language servers,
+    /// limited to `range` for servers that support range requests.
+    ///
+    /// Servers that support it are asked for a delta against the tokens they last returned.
+    pub fn semantic_tokens(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<Anchor>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<SemanticToken>>> {
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = proto::GetSemanticTokens {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                start: range.as_ref().map(|range| serialize_anchor(&range.start)),
+                end: range.as_ref().map(|range| serialize_anchor(&range.end)),
+                version: serialize_version(&buffer.read(cx).version()),
+            };
+            let buffer = buffer.clone();
+            return cx.spawn(async move |_, cx| {
+                let response = client
+                    .request(request)
+                    .await
+                    .context("semantic tokens proto request")?;
+                buffer
+                    .update(cx, |buffer, _| {
+                        buffer.wait_for_version(deserialize_version(&response.version))
+                    })?
+                    .await?;
+                response
+                    .tokens
+                    .into_iter()
+                    .map(|token| {
+                        let start = token
+                            .start
+                            .and_then(deserialize_anchor)
+                            .context("missing token start")?;
+                        let end = token
+                            .end
+                            .and_then(deserialize_anchor)
+                            .context("missing token end")?;
+                        Ok(SemanticToken {
+                            range: start..end,
+                            token_type: token.token_type.into(),
+                            modifiers: token.modifiers.into_iter().map(Into::into).collect(),
+                        })
+                    })
+                    .collect()
+            });
+        }
+
+        let Some(local) = self.as_local() else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let Some(abs_path) = File::from_dyn(buffer.read(cx).file()).map(|file| file.abs_path(cx))
+        else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let Ok(uri) = lsp::Url::from_file_path(&abs_path) else {
+            return Task::ready(Err(anyhow!("failed to convert abs path to uri")));
+        };
+        let text_document = lsp::TextDocumentIdentifier::new(uri);
+        let snapshot = buffer.read(cx).text_snapshot();
+        let buffer_id = snapshot.remote_id();
+        let servers = buffer.update(cx, |buffer, cx| {
+            local
+                .language_servers_for_buffer(buffer, cx)
+                .map(|(_, server)| server.clone())
+                .collect::<Vec<_>>()
+        });
+
+        let mut requests = Vec::new();
+        for server in servers {
+            let capabilities = server.capabilities();
+            let Some(options) = semantic_tokens::semantic_tokens_options(&capabilities).cloned()
+            else {
+                continue;
+            };
+            let supports_full = semantic_tokens::supports_full(&options);
+            let supports_range = options.range == Some(true);
+            if !supports_full && !supports_range {
+                continue;
+            }
+
+            let server_id = server.server_id();
+            let text_document = text_document.clone();
+            let snapshot = snapshot.clone();
+            let lsp_range = if supports_range && (range.is_some() || !supports_full) {
+                let range = match &range {
+                    Some(range) => range.to_point_utf16(&snapshot),
+                    None => PointUtf16::zero()..snapshot.max_point_utf16(),
+                };
+                match range_to_lsp(range) {
+                    Ok(range) => Some(range),
+                    Err(error) => return Task::ready(Err(error)),
+                }
+            } else {
+                None
+            };
+            let previous_tokens = local
+                .semantic_tokens
+                .get(&buffer_id)
+                .and_then(|tokens| tokens.get(&server_id))
+                .filter(|_| semantic_tokens::supports_delta(&options))
+                .and_then(|tokens| Some((tokens.result_id.clone()?, tokens.data.clone())));
+
+            requests.push(cx.spawn(async move |this, cx| {
+                let data = if let Some(range) = lsp_range {
+                    match server
+                        .request::<lsp::request::SemanticTokensRangeRequest>(
+                            lsp::SemanticTokensRangeParams {
+                                text_document,
+                                range,
+                                work_done_progress_params: Default::default(),
+                                partial_result_params: Default::default(),
+                            },
+                        )
+                        .await?
+                    {
+                        Some(lsp::SemanticTokensRangeResult::Tokens(tokens)) => tokens.data,
+                        Some(lsp::SemanticTokensRangeResult::Partial(tokens)) => tokens.data,
+                        None => Vec::new(),
+                    }
+                } else {
+                    let (result_id, data) = if let Some((previous_result_id, mut data)) =
+                        previous_tokens
+                    {
+                        match server
+                            .request::<lsp::request::SemanticTokensFullDeltaRequest>(
+                                lsp::SemanticTokensDeltaParams {
+                                    text_document,
+                                    previous_result_id,
+                                    work_done_progress_params: Default::default(),
+                                    partial_result_params: Default::default(),
+                                },
+                            )
+                            .await?
+                        {
+                            Some(lsp::SemanticTokensFullDeltaResult::Tokens(tokens)) => {
+                                (tokens.result_id, tokens.data)
+                            }
+                            Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(delta)) => {
+                                semantic_tokens::apply_edits(&mut data, delta.edits);
+                                (delta.result_id, data)
+                            }
+                            Some(lsp::SemanticTokensFullDeltaResult::PartialTokensDelta {
+                                edits,
+                            }) => {
+                                semantic_tokens::apply_edits(&mut data, edits);
+                                (None, data)
+                            }
+                            None => (None, Vec::new()),
+                        }
+                    } else {
+                        match server
+                            .request::<lsp::request::SemanticTokensFullRequest>(
+                                lsp::SemanticTokensParams {
+                                    text_document,
+                                    work_done_progress_params: Default::default(),
+                                    partial_result_params: Default::default(),
+                                },
+                            )
+                            .await?
+                        {
+                            Some(lsp::SemanticTokensResult::Tokens(tokens)) => {
+                                (tokens.result_id, tokens.data)
+                            }
+                            Some(lsp::SemanticTokensResult::Partial(tokens)) => (None, tokens.data),
+                            None => (None, Vec::new()),
+                        }
+                    };
+
+                    this.update(cx, |this, _| {
+                        if let Some(local) = this.as_local_mut() {
+                            local.semantic_tokens.entry(buffer_id).or_default().insert(
+                                server_id,
+                                CachedSemanticTokens {
+                                    result_id,
+                                    data: data.clone(),
+                                },
+                            );
+                        }
+                    })?;
+                    data
+                };
+
+                anyhow::Ok(semantic_tokens::decode(&data, &options.legend, &snapshot))
+            }));
+        }
+
+        cx.background_spawn(async move {
+            let mut tokens = Vec::new();
+            for response in futures::future::join_all(requests).await {
+                if let Some(response) = response.context("semantic tokens LSP request").log_err() {
+                    tokens.extend(response);
+                }
+            }
+            tokens.sort_by(|a, b| a.range.start.cmp(&b.range.start, &snapshot));
+            Ok(tokens)
+        })
+    }
+
     pub fn signature_help<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -6383,6 +6814,87 @@ impl LspStore {
         self.merge_diagnostic_entries(server_id, abs_path, version, diagnostics, |_| false, cx)
     }
 
+    /// Replaces the diagnostics reported for the task with the problems found in its output,
+    /// whose paths have to be absolute. An empty list clears the task's diagnostics.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_id: TaskId,
+        problems: Vec<Problem>,
+        cx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        let local = self
+            .as_local_mut()
+            .context("task diagnostics can only be reported locally")?;
+
+        let mut diagnostics_by_path = HashMap::<PathBuf, Vec<_>>::default();
+        for problem in problems {
+            let start = PointUtf16::new(problem.row, problem.column.unwrap_or(0));
+            let end = match problem.end {
+                Some((end_row, end_column)) => {
+                    PointUtf16::new(end_row, end_column.unwrap_or(u32::MAX))
+                }
+                None if problem.column.is_some() => start,
+                // Without a column, the problem spans its whole line.
+                None => PointUtf16::new(problem.row, u32::MAX),
+            };
+            diagnostics_by_path
+                .entry(problem.path)
+                .or_default()
+                .push(DiagnosticEntry {
+                    range: Unclipped(start)..Unclipped(end),
+                    diagnostic: Diagnostic {
+                        source: problem.source,
+                        code: problem.code.map(lsp::NumberOrString::String),
+                        severity: match problem.severity {
+                            ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                            ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                            ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                            ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+                        },
+                        message: problem.message,
+                        group_id: post_inc(&mut local.next_diagnostic_group_id),
+                        is_primary: true,
+                        is_disk_based: true,
+                        source_kind: DiagnosticSourceKind::Other,
+                        ..Diagnostic::default()
+                    },
+                });
+        }
+
+        let mut paths = diagnostics_by_path.keys().cloned().collect::<HashSet<_>>();
+        let old_diagnostics = if diagnostics_by_path.is_empty() {
+            local.task_diagnostics.remove(&task_id)
+        } else {
+            local.task_diagnostics.insert(task_id, diagnostics_by_path)
+        };
+        paths.extend(old_diagnostics.into_iter().flat_map(|old| old.into_keys()));
+
+        // Several tasks may report problems in the same file, so their diagnostics are merged.
+        let diagnostics_by_path = paths
+            .into_iter()
+            .map(|path| {
+                let diagnostics = local
+                    .task_diagnostics
+                    .values()
+                    .filter_map(|diagnostics_by_path| diagnostics_by_path.get(&path))
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (path, diagnostics)
+            })
+            .collect::<Vec<_>>();
+        for (path, diagnostics) in diagnostics_by_path {
+            self.update_diagnostic_entries(
+                TASK_DIAGNOSTICS_SERVER_ID,
+                path,
+                None,
+                diagnostics,
+                cx,
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn merge_diagnostic_entries<F: Fn(&Diagnostic) -> bool + Clone>(
         &mut self,
         server_id: LanguageServerId,
@@ -6950,17 +7462,56 @@ impl LspStore {
                         .collect(),
                 })
             }
-            None => anyhow::bail!("empty multi lsp query request"),
-        }
-    }
+            Some(proto::multi_lsp_query::Request::GetDocumentColors(get_document_colors)) => {
+                let get_document_colors = GetDocumentColors::from_proto(
+                    get_document_colors,
+                    this.clone(),
+                    buffer.clone(),
+                    cx.clone(),
+                )
+                .await?;
 
-    async fn handle_apply_code_action(
-        this: Entity<Self>,
-        envelope: TypedEnvelope<proto::ApplyCodeAction>,
-        mut cx: AsyncApp,
-    ) -> Result<proto::ApplyCodeActionResponse> {
-        let sender_id = envelope.original_sender_id().unwrap_or_default();
-        let action = Self::deserialize_code_action(
+                let colors = this
+                    .update(&mut cx, |project, cx| {
+                        project.request_multiple_lsp_locally(
+                            &buffer,
+                            None::<usize>,
+                            get_document_colors,
+                            cx,
+                        )
+                    })?
+                    .await
+                    .into_iter();
+
+                this.update(&mut cx, |project, cx| proto::MultiLspQueryResponse {
+                    responses: colors
+                        .map(|colors| proto::LspResponse {
+                            response: Some(
+                                proto::lsp_response::Response::GetDocumentColorsResponse(
+                                    GetDocumentColors::response_to_proto(
+                                        colors,
+                                        project,
+                                        sender_id,
+                                        &buffer_version,
+                                        cx,
+                                    ),
+                                ),
+                            ),
+                        })
+                        .collect(),
+                })
+            }
+            None => anyhow::bail!("empty multi lsp query request"),
+        }
+    }
+
+    async fn handle_apply_code_action(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::ApplyCodeAction>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ApplyCodeActionResponse> {
+        let sender_id = envelope.original_sender_id().unwrap_or_default();
+        let action = Self::deserialize_code_action(
             envelope
                 .payload
                 .action
@@ -7044,6 +7595,35 @@ impl LspStore {
             })
     }
 
+    async fn handle_create_project_entry(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::CreateProjectEntry>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ProjectEntryResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let worktree = this
+            .update(&mut cx, |this, cx| {
+                this.worktree_store
+                    .read(cx)
+                    .worktree_for_id(worktree_id, cx)
+            })?
+            .ok_or_else(|| anyhow!("worktree not found"))?;
+        let is_dir = envelope.payload.is_directory;
+        let abs_path = {
+            let root_path = worktree.update(&mut cx, |this, _| this.abs_path())?;
+            root_path.join(PathBuf::from_proto(envelope.payload.path.clone()))
+        };
+
+        Self::will_create_entry(this.downgrade(), worktree_id, &abs_path, is_dir, cx.clone()).await;
+        let response =
+            Worktree::handle_create_entry(worktree, envelope.payload, cx.clone()).await?;
+        this.update(&mut cx, |this, _| {
+            this.did_create_entry(worktree_id, &abs_path, is_dir);
+        })
+        .ok();
+        Ok(response)
+    }
+
     async fn handle_rename_project_entry(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::RenameProjectEntry>,
@@ -7088,6 +7668,42 @@ impl LspStore {
         response
     }
 
+    async fn handle_delete_project_entry(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::DeleteProjectEntry>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ProjectEntryResponse> {
+        let entry_id = ProjectEntryId::from_proto(envelope.payload.entry_id);
+        let (worktree_id, worktree, path, is_dir) = this
+            .update(&mut cx, |this, cx| {
+                this.worktree_store
+                    .read(cx)
+                    .worktree_and_entry_for_id(entry_id, cx)
+                    .map(|(worktree, entry)| {
+                        (
+                            worktree.read(cx).id(),
+                            worktree,
+                            entry.path.clone(),
+                            entry.is_dir(),
+                        )
+                    })
+            })?
+            .ok_or_else(|| anyhow!("worktree not found"))?;
+        let abs_path = {
+            let root_path = worktree.update(&mut cx, |this, _| this.abs_path())?;
+            root_path.join(&path)
+        };
+
+        Self::will_delete_entry(this.downgrade(), worktree_id, &abs_path, is_dir, cx.clone()).await;
+        let response =
+            Worktree::handle_delete_entry(worktree, envelope.payload, cx.clone()).await?;
+        this.update(&mut cx, |this, _| {
+            this.did_delete_entry(worktree_id, &abs_path, is_dir);
+        })
+        .ok();
+        Ok(response)
+    }
+
     async fn handle_update_diagnostic_summary(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::UpdateDiagnosticSummary>,
@@ -7366,6 +7982,45 @@ impl LspStore {
             .map(|(key, value)| (*key, value))
     }
 
+    pub(super) fn did_create_entry(&self, worktree_id: WorktreeId, path: &Path, is_dir: bool) {
+        maybe!({
+            let uri = lsp::Url::from_file_path(path).ok().map(String::from)?;
+            self.notify_file_operation::<DidCreateFiles>(
+                worktree_id,
+                FileOperation::DidCreate,
+                &uri,
+                is_dir,
+                CreateFilesParams {
+                    files: vec![FileCreate { uri: uri.clone() }],
+                },
+            );
+            Some(())
+        });
+    }
+
+    pub(super) fn will_create_entry(
+        this: WeakEntity<Self>,
+        worktree_id: WorktreeId,
+        path: &Path,
+        is_dir: bool,
+        cx: AsyncApp,
+    ) -> Task<()> {
+        let Some(uri) = lsp::Url::from_file_path(path).ok().map(String::from) else {
+            return Task::ready(());
+        };
+        Self::will_perform_file_operation::<WillCreateFiles>(
+            this,
+            worktree_id,
+            FileOperation::WillCreate,
+            uri.clone(),
+            is_dir,
+            CreateFilesParams {
+                files: vec![FileCreate { uri }],
+            },
+            cx,
+        )
+    }
+
     pub(super) fn did_rename_entry(
         &self,
         worktree_id: WorktreeId,
@@ -7374,30 +8029,20 @@ impl LspStore {
         is_dir: bool,
     ) {
         maybe!({
-            let local_store = self.as_local()?;
-
             let old_uri = lsp::Url::from_file_path(old_path).ok().map(String::from)?;
             let new_uri = lsp::Url::from_file_path(new_path).ok().map(String::from)?;
-
-            for language_server in local_store.language_servers_for_worktree(worktree_id) {
-                let Some(filter) = local_store
-                    .language_server_paths_watched_for_rename
-                    .get(&language_server.server_id())
-                else {
-                    continue;
-                };
-
-                if filter.should_send_did_rename(&old_uri, is_dir) {
-                    language_server
-                        .notify::<DidRenameFiles>(&RenameFilesParams {
-                            files: vec![FileRename {
-                                old_uri: old_uri.clone(),
-                                new_uri: new_uri.clone(),
-                            }],
-                        })
-                        .log_err();
-                }
-            }
+            self.notify_file_operation::<DidRenameFiles>(
+                worktree_id,
+                FileOperation::DidRename,
+                &old_uri,
+                is_dir,
+                RenameFilesParams {
+                    files: vec![FileRename {
+                        old_uri: old_uri.clone(),
+                        new_uri,
+                    }],
+                },
+            );
             Some(())
         });
     }
@@ -7412,15 +8057,110 @@ impl LspStore {
     ) -> Task<()> {
         let old_uri = lsp::Url::from_file_path(old_path).ok().map(String::from);
         let new_uri = lsp::Url::from_file_path(new_path).ok().map(String::from);
+        let Some((old_uri, new_uri)) = old_uri.zip(new_uri) else {
+            return Task::ready(());
+        };
+        Self::will_perform_file_operation::<WillRenameFiles>(
+            this,
+            worktree_id,
+            FileOperation::WillRename,
+            old_uri.clone(),
+            is_dir,
+            RenameFilesParams {
+                files: vec![FileRename { old_uri, new_uri }],
+            },
+            cx,
+        )
+    }
+
+    pub(super) fn did_delete_entry(&self, worktree_id: WorktreeId, path: &Path, is_dir: bool) {
+        maybe!({
+            let uri = lsp::Url::from_file_path(path).ok().map(String::from)?;
+            self.notify_file_operation::<DidDeleteFiles>(
+                worktree_id,
+                FileOperation::DidDelete,
+                &uri,
+                is_dir,
+                DeleteFilesParams {
+                    files: vec![FileDelete { uri: uri.clone() }],
+                },
+            );
+            Some(())
+        });
+    }
+
+    pub(super) fn will_delete_entry(
+        this: WeakEntity<Self>,
+        worktree_id: WorktreeId,
+        path: &Path,
+        is_dir: bool,
+        cx: AsyncApp,
+    ) -> Task<()> {
+        let Some(uri) = lsp::Url::from_file_path(path).ok().map(String::from) else {
+            return Task::ready(());
+        };
+        Self::will_perform_file_operation::<WillDeleteFiles>(
+            this,
+            worktree_id,
+            FileOperation::WillDelete,
+            uri.clone(),
+            is_dir,
+            DeleteFilesParams {
+                files: vec![FileDelete { uri }],
+            },
+            cx,
+        )
+    }
+
+    /// Notifies the language servers of the worktree whose file operation filters
+    /// match the path that the operation has happened.
+    fn notify_file_operation<N: lsp::notification::Notification>(
+        &self,
+        worktree_id: WorktreeId,
+        operation: FileOperation,
+        uri: &str,
+        is_dir: bool,
+        params: N::Params,
+    ) {
+        let Some(local_store) = self.as_local() else {
+            return;
+        };
+        for language_server in local_store.language_servers_for_worktree(worktree_id) {
+            let Some(filter) = local_store
+                .language_server_paths_watched_for_file_operations
+                .get(&language_server.server_id())
+            else {
+                continue;
+            };
+
+            if filter.should_send(operation, uri, is_dir) {
+                language_server.notify::<N>(&params).log_err();
+            }
+        }
+    }
+
+    /// Asks the language servers of the worktree whose file operation filters match
+    /// the path for the edits to make before the operation happens, and applies them.
+    fn will_perform_file_operation<R>(
+        this: WeakEntity<Self>,
+        worktree_id: WorktreeId,
+        operation: FileOperation,
+        uri: String,
+        is_dir: bool,
+        params: R::Params,
+        cx: AsyncApp,
+    ) -> Task<()>
+    where
+        R: lsp::request::Request<Result = Option<lsp::WorkspaceEdit>>,
+        R::Params: Clone,
+    {
         cx.spawn(async move |cx| {
             let mut tasks = vec![];
             this.update(cx, |this, cx| {
                 let local_store = this.as_local()?;
-                let old_uri = old_uri?;
-                let new_uri = new_uri?;
                 for language_server in local_store.language_servers_for_worktree(worktree_id) {
                     let Some(filter) = local_store
-                        .language_server_paths_watched_for_rename
+                        .language_server_paths_watched_for_file_operations
                         .get(&language_server.server_id())
                     else {
                         continue;
@@ -7430,16 +8170,13 @@ impl LspStore {
                     else {
                         continue;
                     };
-                    if filter.should_send_will_rename(&old_uri, is_dir) {
+                    if filter.should_send(operation, &uri, is_dir) {
                         let apply_edit = cx.spawn({
-                            let old_uri = old_uri.clone();
-                            let new_uri = new_uri.clone();
+                            let params = params.clone();
                             let language_server = language_server.clone();
                             async move |this, cx| {
                                 let edit = language_server
-                                    .request::<WillRenameFiles>(RenameFilesParams {
-                                        files: vec![FileRename { old_uri, new_uri }],
-                                    })
+                                    .request::<R>(params)
                                     .log_err()
                                     .await
                                     .flatten()?;
@@ -7899,6 +8636,64 @@ impl LspStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_refresh_semantic_tokens(
+        this: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |_, cx| {
+            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_semantic_tokens(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetSemanticTokensResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = this.update(&mut cx, |this, cx| {
+            this.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&envelope.payload.version))
+            })?
+            .await
+            .with_context(|| format!("waiting for version for buffer {}", buffer.entity_id()))?;
+
+        let range = envelope
+            .payload
+            .start
+            .and_then(deserialize_anchor)
+            .zip(envelope.payload.end.and_then(deserialize_anchor))
+            .map(|(start, end)| start..end);
+        let tokens = this
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.semantic_tokens(&buffer, range, cx)
+            })?
+            .await
+            .context("semantic tokens fetch")?;
+
+        buffer.update(&mut cx, |buffer, _| proto::GetSemanticTokensResponse {
+            tokens: tokens
+                .into_iter()
+                .map(|token| proto::SemanticToken {
+                    start: Some(serialize_anchor(&token.range.start)),
+                    end: Some(serialize_anchor(&token.range.end)),
+                    token_type: token.token_type.to_string(),
+                    modifiers: token
+                        .modifiers
+                        .into_iter()
+                        .map(|modifier| modifier.to_string())
+                        .collect(),
+                })
+                .collect(),
+            version: serialize_version(&buffer.version()),
+        })
+    }
+
     async fn handle_open_buffer_for_symbol(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenBufferForSymbol>,
@@ -8430,6 +9225,8 @@ impl LspStore {
             });
         }
         local.language_server_watched_paths.remove(&server_id);
+        local.diagnostic_result_ids.remove(&server_id);
+        local.workspace_diagnostics_pulls.remove(&server_id);
         let server_state = local.language_servers.remove(&server_id);
         cx.notify();
         cx.emit(LspStoreEvent::LanguageServerRemoved(server_id));
@@ -8546,11 +9343,13 @@ impl LspStore {
         disk_based_sources: &[String],
         cx: &mut Context<Self>,
     ) -> Result<()> {
+        // Pulled diagnostics of the same server are only replaced by the next pull.
         self.merge_diagnostics(
             language_server_id,
             params,
+            DiagnosticSourceKind::Pushed,
             disk_based_sources,
-            |_| false,
+            |diagnostic| diagnostic.source_kind == DiagnosticSourceKind::Pulled,
             cx,
         )
     }
@@ -8559,6 +9358,7 @@ impl LspStore {
         &mut self,
         language_server_id: LanguageServerId,
         mut params: lsp::PublishDiagnosticsParams,
+        source_kind: DiagnosticSourceKind,
         disk_based_sources: &[String],
         filter: F,
         cx: &mut Context<Self>,
@@ -8633,6 +9433,7 @@ impl LspStore {
                         is_disk_based,
                         is_unnecessary,
                         data: diagnostic.data.clone(),
+                        source_kind,
                     },
                 });
                 if let Some(infos) = &diagnostic.related_information {
@@ -8658,6 +9459,7 @@ impl LspStore {
                                     is_disk_based,
                                     is_unnecessary: false,
                                     data: diagnostic.data.clone(),
+                                    source_kind,
                                 },
                             });
                         }
@@ -8694,6 +9496,234 @@ impl LspStore {
         Ok(())
     }
 
+    /// Pulls the diagnostics of the buffer from each of its language servers that
+    /// support the pull model, shortly after the last call for that buffer.
+    fn pull_document_diagnostics(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let buffer_id = buffer.read(cx).remote_id();
+        if !self.as_local().map_or(false, |local| {
+            local.registered_buffers.contains_key(&buffer_id)
+        }) {
+            return;
+        }
+
+        let buffer = buffer.downgrade();
+        let task = cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(pull_diagnostics::PULL_DEBOUNCE)
+                .await;
+
+            let Some(requests) = this
+                .update(cx, |this, cx| {
+                    let buffer = buffer.upgrade()?;
+                    let local = this.as_local()?;
+                    let file = File::from_dyn(buffer.read(cx).file())?;
+                    let uri = lsp::Url::from_file_path(file.as_local()?.abs_path(cx)).ok()?;
+                    let servers = buffer.update(cx, |buffer, cx| {
+                        local
+                            .language_servers_for_buffer(buffer, cx)
+                            .map(|(_, server)| server.clone())
+                            .collect::<Vec<_>>()
+                    });
+
+                    let mut requests = Vec::new();
+                    for server in servers {
+                        let server_id = server.server_id();
+                        let capabilities = server.capabilities();
+                        let Some(options) = pull_diagnostics::diagnostic_options(&capabilities)
+                        else {
+                            continue;
+                        };
+                        let version = local
+                            .buffer_snapshots
+                            .get(&buffer_id)
+                            .and_then(|snapshots| snapshots.get(&server_id)?.last())
+                            .map(|snapshot| snapshot.version);
+                        let previous_result_id = local
+                            .diagnostic_result_ids
+                            .get(&server_id)
+                            .and_then(|result_ids| result_ids.get(&uri))
+                            .cloned();
+                        let request = server.request::<lsp::request::DocumentDiagnosticRequest>(
+                            lsp::DocumentDiagnosticParams {
+                                text_document: lsp::TextDocumentIdentifier::new(uri.clone()),
+                                identifier: options.identifier.clone(),
+                                previous_result_id,
+                                work_done_progress_params: Default::default(),
+                                partial_result_params: Default::default(),
+                            },
+                        );
+                        let uri = uri.clone();
+                        requests.push(async move {
+                            let report = request.await.log_err()?;
+                            Some((
+                                server_id,
+                                pull_diagnostics::from_document_report(uri, version, report),
+                            ))
+                        });
+                    }
+                    Some(requests)
+                })
+                .ok()
+                .flatten()
+            else {
+                return;
+            };
+
+            let reports = join_all(requests).await;
+            this.update(cx, |this, cx| {
+                for (server_id, pulled) in reports.into_iter().flatten() {
+                    this.apply_pulled_diagnostics(server_id, pulled, cx);
+                }
+            })
+            .ok();
+        });
+
+        if let Some(local) = self.as_local_mut() {
+            local.document_diagnostics_pulls.insert(buffer_id, task);
+        }
+    }
+
+    /// Pulls the diagnostics of the whole workspace from the language server, if it
+    /// supports that, replacing any pull that is still in flight.
+    fn pull_workspace_diagnostics(&mut self, server_id: LanguageServerId, cx: &mut Context<Self>) {
+        let Some(local) = self.as_local_mut() else {
+            return;
+        };
+        let Some(server) = local.running_language_server_for_id(server_id).cloned() else {
+            return;
+        };
+        let capabilities = server.capabilities();
+        let Some(options) = pull_diagnostics::diagnostic_options(&capabilities)
+            .filter(|options| options.workspace_diagnostics)
+        else {
+            return;
+        };
+
+        let previous_result_ids = local
+            .diagnostic_result_ids
+            .get(&server_id)
+            .into_iter()
+            .flatten()
+            .map(|(uri, value)| lsp::PreviousResultId {
+                uri: uri.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        let request = server.request::<lsp::request::WorkspaceDiagnosticRequest>(
+            lsp::WorkspaceDiagnosticParams {
+                identifier: options.identifier.clone(),
+                previous_result_ids,
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+        );
+        let task = cx.spawn(async move |this, cx| {
+            let Some(report) = request.await.log_err() else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.apply_pulled_diagnostics(
+                    server_id,
+                    pull_diagnostics::from_workspace_report(report),
+                    cx,
+                );
+            })
+            .ok();
+        });
+        local.workspace_diagnostics_pulls.insert(server_id, task);
+    }
+
+    fn pull_workspace_diagnostics_for_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(local) = self.as_local() else {
+            return;
+        };
+        let server_ids = buffer.update(cx, |buffer, cx| {
+            local.language_server_ids_for_buffer(buffer, cx)
+        });
+        for server_id in server_ids {
+            self.pull_workspace_diagnostics(server_id, cx);
+        }
+    }
+
+    /// Pulls the diagnostics of every buffer that is open in the language server, as
+    /// well as those of the whole workspace.
+    fn refresh_pulled_diagnostics(&mut self, server_id: LanguageServerId, cx: &mut Context<Self>) {
+        let Some(local) = self.as_local() else {
+            return;
+        };
+        let buffers = self
+            .buffer_store
+            .read(cx)
+            .buffers()
+            .filter(|buffer| {
+                local
+                    .buffer_snapshots
+                    .get(&buffer.read(cx).remote_id())
+                    .map_or(false, |snapshots| snapshots.contains_key(&server_id))
+            })
+            .collect::<Vec<_>>();
+        for buffer in buffers {
+            self.pull_document_diagnostics(&buffer, cx);
+        }
+        self.pull_workspace_diagnostics(server_id, cx);
+    }
+
+    fn apply_pulled_diagnostics(
+        &mut self,
+        server_id: LanguageServerId,
+        pulled: Vec<PulledDiagnostics>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(adapter) = self.language_server_adapter_for_id(server_id) else {
+            return;
+        };
+        for pulled in pulled {
+            if let Some(local) = self.as_local_mut() {
+                let result_ids = local.diagnostic_result_ids.entry(server_id).or_default();
+                match pulled.result_id {
+                    Some(result_id) => {
+                        result_ids.insert(pulled.uri.clone(), result_id);
+                    }
+                    None => {
+                        result_ids.remove(&pulled.uri);
+                    }
+                }
+            }
+
+            let Some(diagnostics) = pulled.diagnostics else {
+                continue;
+            };
+            let mut params = lsp::PublishDiagnosticsParams {
+                uri: pulled.uri,
+                diagnostics,
+                version: pulled.version,
+            };
+            {
+                let buffer = params
+                    .uri
+                    .to_file_path()
+                    .map(|file_path| self.get_buffer(&file_path, cx))
+                    .ok()
+                    .flatten();
+                adapter.process_diagnostics(&mut params, server_id, buffer);
+            }
+            // Pushed diagnostics of the same server are only replaced by the next push.
+            self.merge_diagnostics(
+                server_id,
+                params,
+                DiagnosticSourceKind::Pulled,
+                &adapter.disk_based_diagnostic_sources,
+                |diagnostic| diagnostic.source_kind == DiagnosticSourceKind::Pushed,
+                cx,
+            )
+            .log_err();
+        }
+    }
+
     fn insert_newly_running_language_server(
         &mut self,
         adapter: Arc<CachedLspAdapter>,
@@ -8735,14 +9765,10 @@ impl LspStore {
             .as_ref()
             .and_then(|ws| ws.file_operations.as_ref())
         {
-            let did_rename_caps = file_ops_caps.did_rename.as_ref();
-            let will_rename_caps = file_ops_caps.will_rename.as_ref();
-            if did_rename_caps.or(will_rename_caps).is_some() {
-                let watcher = RenamePathsWatchedForServer::default()
-                    .with_did_rename_patterns(did_rename_caps)
-                    .with_will_rename_patterns(will_rename_caps);
+            let watcher = FileOperationsWatchedForServer::new(file_ops_caps);
+            if !watcher.is_empty() {
                 local
-                    .language_server_paths_watched_for_rename
+                    .language_server_paths_watched_for_file_operations
                     .insert(server_id, watcher);
             }
         }
@@ -8763,6 +9789,7 @@ impl LspStore {
             Some(key.0),
         ));
         cx.emit(LspStoreEvent::RefreshInlayHints);
+        cx.emit(LspStoreEvent::RefreshSemanticTokens);
 
         if let Some((downstream_client, project_id)) = self.downstream_client.as_ref() {
             downstream_client
@@ -8857,6 +9884,7 @@ impl LspStore {
             }
         });
 
+        self.refresh_pulled_diagnostics(server_id, cx);
         cx.notify();
     }
 
@@ -9430,49 +10458,71 @@ pub enum LanguageServerToQuery {
     Other(LanguageServerId),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileOperation {
+    WillCreate,
+    DidCreate,
+    WillRename,
+    DidRename,
+    WillDelete,
+    DidDelete,
+}
+
 #[derive(Default)]
-struct RenamePathsWatchedForServer {
-    did_rename: Vec<RenameActionPredicate>,
-    will_rename: Vec<RenameActionPredicate>,
+struct FileOperationsWatchedForServer {
+    will_create: Vec<FileOperationPredicate>,
+    did_create: Vec<FileOperationPredicate>,
+    will_rename: Vec<FileOperationPredicate>,
+    did_rename: Vec<FileOperationPredicate>,
+    will_delete: Vec<FileOperationPredicate>,
+    did_delete: Vec<FileOperationPredicate>,
 }
 
-impl RenamePathsWatchedForServer {
-    fn with_did_rename_patterns(
-        mut self,
-        did_rename: Option<&FileOperationRegistrationOptions>,
-    ) -> Self {
-        if let Some(did_rename) = did_rename {
-            self.did_rename = did_rename
-                .filters
-                .iter()
-                .filter_map(|filter| filter.try_into().log_err())
-                .collect();
-        }
-        self
-    }
-    fn with_will_rename_patterns(
-        mut self,
-        will_rename: Option<&FileOperationRegistrationOptions>,
-    ) -> Self {
-        if let Some(will_rename) = will_rename {
-            self.will_rename = will_rename
-                .filters
-                .iter()
+impl FileOperationsWatchedForServer {
+    fn new(capabilities: &lsp::WorkspaceFileOperationsServerCapabilities) -> Self {
+        fn predicates(
+            options: Option<&FileOperationRegistrationOptions>,
+        ) -> Vec<FileOperationPredicate> {
+            options
+                .into_iter()
+                .flat_map(|options| &options.filters)
                 .filter_map(|filter| filter.try_into().log_err())
-                .collect();
+                .collect()
         }
-        self
-    }
 
-    fn should_send_did_rename(&self, path: &str, is_dir: bool) -> bool {
-        self.did_rename.iter().any(|pred| pred.eval(path, is_dir))
-    }
-    fn should_send_will_rename(&self, path: &str, is_dir: bool) -> bool {
-        self.will_rename.iter().any(|pred| pred.eval(path, is_dir))
+        Self {
+            will_create: predicates(capabilities.will_create.as_ref()),
+            did_create: predicates(capabilities.did_create.as_ref()),
+            will_rename: predicates(capabilities.will_rename.as_ref()),
+            did_rename: predicates(capabilities.did_rename.as_ref()),
+            will_delete: predicates(capabilities.will_delete.as_ref()),
+            did_delete: predicates(capabilities.did_delete.as_ref()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.will_create.is_empty()
+            && self.did_create.is_empty()
+            && self.will_rename.is_empty()
+            && self.did_rename.is_empty()
+            && self.will_delete.is_empty()
+            && self.did_delete.is_empty()
+    }
+
+    fn should_send(&self, operation: FileOperation, path: &str, is_dir: bool) -> bool {
+        let predicates = match operation {
+            FileOperation::WillCreate => &self.will_create,
+            FileOperation::DidCreate => &self.did_create,
+            FileOperation::WillRename => &self.will_rename,
+            FileOperation::DidRename => &self.did_rename,
+            FileOperation::WillDelete => &self.will_delete,
+            FileOperation::DidDelete => &self.did_delete,
+        };
+        predicates.iter().any(|pred| pred.eval(path, is_dir))
     }
 }
 
-impl TryFrom<&FileOperationFilter> for RenameActionPredicate {
+impl TryFrom<&FileOperationFilter> for FileOperationPredicate {
     type Error = globset::Error;
     fn try_from(ops: &FileOperationFilter) -> Result<Self, globset::Error> {
         Ok(Self {
@@ -9489,12 +10539,12 @@ impl TryFrom<&FileOperationFilter> for RenameActionPredicate {
         })
     }
 }
-struct RenameActionPredicate {
+struct FileOperationPredicate {
     glob: GlobMatcher,
     kind: Option<FileOperationPatternKind>,
 }
 
-impl RenameActionPredicate {
+impl FileOperationPredicate {
     // Returns true if language server should be notified
     fn eval(&self, path: &str, is_dir: bool) -> bool {
         self.kind.as_ref().map_or(true, |kind| {
@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+/// How long to wait after a buffer changes before pulling its diagnostics, so that
+/// a burst of edits results in a single request.
+pub(super) const PULL_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// The diagnostics that a `textDocument/diagnostic` or `workspace/diagnostic`
+/// request reported for a single document.
+#[derive(Debug)]
+pub(super) struct PulledDiagnostics {
+    pub uri: lsp::Url,
+    pub version: Option<i32>,
+    pub result_id: Option<String>,
+    /// `None` if the server reported that the diagnostics did not change since the
+    /// result ID that was sent with the request.
+    pub diagnostics: Option<Vec<lsp::Diagnostic>>,
+}
+
+pub(super) fn diagnostic_options(
+    capabilities: &lsp::ServerCapabilities,
+) -> Option<&lsp::DiagnosticOptions> {
+    match capabilities.diagnostic_provider.as_ref()? {
+        lsp::DiagnosticServerCapabilities::Options(options) => Some(options),
+        lsp::DiagnosticServerCapabilities::RegistrationOptions(options) => {
+            Some(&options.diagnostic_options)
+        }
+    }
+}
+
+/// Flattens the response to a `textDocument/diagnostic` request for the document at
+/// `uri` into the diagnostics of that document and of its related documents.
+pub(super) fn from_document_report(
+    uri: lsp::Url,
+    version: Option<i32>,
+    report: lsp::DocumentDiagnosticReportResult,
+) -> Vec<PulledDiagnostics> {
+    let (document, related_documents) = match report {
+        lsp::DocumentDiagnosticReportResult::Report(lsp::DocumentDiagnosticReport::Full(
+            report,
+        )) => (
+            Some(PulledDiagnostics {
+                uri,
+                version,
+                result_id: report.full_document_diagnostic_report.result_id,
+                diagnostics: Some(report.full_document_diagnostic_report.items),
+            }),
+            report.related_documents,
+        ),
+        lsp::DocumentDiagnosticReportResult::Report(lsp::DocumentDiagnosticReport::Unchanged(
+            report,
+        )) => (
+            Some(PulledDiagnostics {
+                uri,
+                version,
+                result_id: Some(report.unchanged_document_diagnostic_report.result_id),
+                diagnostics: None,
+            }),
+            report.related_documents,
+        ),
+        lsp::DocumentDiagnosticReportResult::Partial(report) => (None, report.related_documents),
+    };
+
+    document
+        .into_iter()
+        .chain(
+            related_documents
+                .into_iter()
+                .flatten()
+                .map(|(uri, report)| match report {
+                    lsp::DocumentDiagnosticReportKind::Full(report) => PulledDiagnostics {
+                        uri,
+                        version: None,
+                        result_id: report.result_id,
+                        diagnostics: Some(report.items),
+                    },
+                    lsp::DocumentDiagnosticReportKind::Unchanged(report) => PulledDiagnostics {
+                        uri,
+                        version: None,
+                        result_id: Some(report.result_id),
+                        diagnostics: None,
+                    },
+                }),
+        )
+        .collect()
+}
+
+/// Flattens the response to a `workspace/diagnostic` request into the diagnostics
+/// of each document it reported on.
+pub(super) fn from_workspace_report(
+    report: lsp::WorkspaceDiagnosticReportResult,
+) -> Vec<PulledDiagnostics> {
+    let items = match report {
+        lsp::WorkspaceDiagnosticReportResult::Report(report) => report.items,
+        lsp::WorkspaceDiagnosticReportResult::Partial(report) => report.items,
+    };
+    items
+        .into_iter()
+        .map(|item| match item {
+            lsp::WorkspaceDocumentDiagnosticReport::Full(report) => PulledDiagnostics {
+                uri: report.uri,
+                version: report.version.map(|version| version as i32),
+                result_id: report.full_document_diagnostic_report.result_id,
+                diagnostics: Some(report.full_document_diagnostic_report.items),
+            },
+            lsp::WorkspaceDocumentDiagnosticReport::Unchanged(report) => PulledDiagnostics {
+                uri: report.uri,
+                version: report.version.map(|version| version as i32),
+                result_id: Some(report.unchanged_document_diagnostic_report.result_id),
+                diagnostics: None,
+            },
+        })
+        .collect()
+}
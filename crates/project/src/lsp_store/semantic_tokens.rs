@@ -0,0 +1,231 @@
+use std::ops::Range;
+
+use gpui::SharedString;
+use text::{Anchor, Bias, PointUtf16, Unclipped};
+
+/// A range of a buffer classified by a language server's semantic tokens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub range: Range<Anchor>,
+    /// The token type, as named in the language server's legend (e.g. `parameter`).
+    pub token_type: SharedString,
+    /// The token modifiers, as named in the language server's legend (e.g. `readonly`).
+    pub modifiers: Vec<SharedString>,
+}
+
+/// The last full set of semantic tokens a language server returned for a buffer,
+/// which subsequent delta requests are computed against.
+#[derive(Debug, Default)]
+pub(super) struct CachedSemanticTokens {
+    pub result_id: Option<String>,
+    pub data: Vec<lsp::SemanticToken>,
+}
+
+pub(super) fn semantic_tokens_options(
+    capabilities: &lsp::ServerCapabilities,
+) -> Option<&lsp::SemanticTokensOptions> {
+    match capabilities.semantic_tokens_provider.as_ref()? {
+        lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => Some(options),
+        lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+            Some(&options.semantic_tokens_options)
+        }
+    }
+}
+
+pub(super) fn supports_full(options: &lsp::SemanticTokensOptions) -> bool {
+    match options.full {
+        Some(lsp::SemanticTokensFullOptions::Bool(full)) => full,
+        Some(lsp::SemanticTokensFullOptions::Delta { .. }) => true,
+        None => false,
+    }
+}
+
+pub(super) fn supports_delta(options: &lsp::SemanticTokensOptions) -> bool {
+    matches!(
+        options.full,
+        Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) })
+    )
+}
+
+/// Applies the edits of a `textDocument/semanticTokens/full/delta` response to the
+/// previously returned tokens.
+///
+/// Edit offsets are expressed in terms of the flattened integer array, where every
+/// token takes up five integers, and all refer to the original array.
+pub(super) fn apply_edits(
+    data: &mut Vec<lsp::SemanticToken>,
+    mut edits: Vec<lsp::SemanticTokensEdit>,
+) {
+    edits.sort_by_key(|edit| edit.start);
+    for edit in edits.into_iter().rev() {
+        let start = (edit.start as usize / 5).min(data.len());
+        let end = (start + edit.delete_count as usize / 5).min(data.len());
+        data.splice(start..end, edit.data.unwrap_or_default());
+    }
+}
+
+/// Converts relatively encoded semantic tokens into anchored tokens, resolving the
+/// token types and modifiers through the server's legend.
+pub(super) fn decode(
+    data: &[lsp::SemanticToken],
+    legend: &lsp::SemanticTokensLegend,
+    snapshot: &text::BufferSnapshot,
+) -> Vec<SemanticToken> {
+    let token_types = legend
+        .token_types
+        .iter()
+        .map(|token_type| SharedString::from(token_type.as_str().to_string()))
+        .collect::<Vec<_>>();
+    let token_modifiers = legend
+        .token_modifiers
+        .iter()
+        .map(|modifier| SharedString::from(modifier.as_str().to_string()))
+        .collect::<Vec<_>>();
+
+    let mut tokens = Vec::with_capacity(data.len());
+    let mut line = 0;
+    let mut start = 0;
+    for token in data {
+        if token.delta_line == 0 {
+            start += token.delta_start;
+        } else {
+            line += token.delta_line;
+            start = token.delta_start;
+        }
+
+        let Some(token_type) = token_types.get(token.token_type as usize) else {
+            continue;
+        };
+        let modifiers = token_modifiers
+            .iter()
+            .take(u32::BITS as usize)
+            .enumerate()
+            .filter(|(ix, _)| token.token_modifiers_bitset & (1 << ix) != 0)
+            .map(|(_, modifier)| modifier.clone())
+            .collect();
+
+        let range_start =
+            snapshot.clip_point_utf16(Unclipped(PointUtf16::new(line, start)), Bias::Left);
+        let range_end = snapshot.clip_point_utf16(
+            Unclipped(PointUtf16::new(line, start + token.length)),
+            Bias::Right,
+        );
+        if range_start == range_end {
+            continue;
+        }
+
+        tokens.push(SemanticToken {
+            range: snapshot.anchor_after(range_start)..snapshot.anchor_before(range_end),
+            token_type: token_type.clone(),
+            modifiers,
+        });
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use text::{Buffer, BufferId, ToOffset as _};
+
+    fn token(
+        delta_line: u32,
+        delta_start: u32,
+        length: u32,
+        token_type: u32,
+        token_modifiers_bitset: u32,
+    ) -> lsp::SemanticToken {
+        lsp::SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset,
+        }
+    }
+
+    #[test]
+    fn test_decode_semantic_tokens() {
+        let buffer = Buffer::new(
+            0,
+            BufferId::new(1).unwrap(),
+            "fn main(a: u8) {\n    a\n}".to_string(),
+        );
+        let snapshot = buffer.snapshot();
+        let legend = lsp::SemanticTokensLegend {
+            token_types: vec![
+                lsp::SemanticTokenType::FUNCTION,
+                lsp::SemanticTokenType::PARAMETER,
+            ],
+            token_modifiers: vec![
+                lsp::SemanticTokenModifier::DECLARATION,
+                lsp::SemanticTokenModifier::READONLY,
+            ],
+        };
+
+        let tokens = decode(
+            &[
+                token(0, 3, 4, 0, 0b01),
+                token(0, 5, 1, 1, 0b11),
+                // Unknown token types are skipped.
+                token(0, 4, 2, 7, 0),
+                token(1, 4, 1, 1, 0b10),
+            ],
+            &legend,
+            &snapshot,
+        );
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| (
+                    token.range.start.to_offset(&snapshot)..token.range.end.to_offset(&snapshot),
+                    token.token_type.as_ref(),
+                    token
+                        .modifiers
+                        .iter()
+                        .map(|modifier| modifier.as_ref())
+                        .collect::<Vec<_>>(),
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (3..7, "function", vec!["declaration"]),
+                (8..9, "parameter", vec!["declaration", "readonly"]),
+                (21..22, "parameter", vec!["readonly"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_semantic_token_edits() {
+        let mut data = vec![
+            token(0, 0, 1, 0, 0),
+            token(0, 2, 1, 1, 0),
+            token(1, 0, 1, 2, 0),
+        ];
+        apply_edits(
+            &mut data,
+            vec![
+                lsp::SemanticTokensEdit {
+                    start: 10,
+                    delete_count: 5,
+                    data: Some(vec![token(2, 0, 3, 3, 0)]),
+                },
+                lsp::SemanticTokensEdit {
+                    start: 0,
+                    delete_count: 0,
+                    data: Some(vec![token(0, 0, 2, 4, 0)]),
+                },
+            ],
+        );
+        assert_eq!(
+            data,
+            vec![
+                token(0, 0, 2, 4, 0),
+                token(0, 0, 1, 0, 0),
+                token(0, 2, 1, 1, 0),
+                token(2, 0, 3, 3, 0),
+            ]
+        );
+    }
+}
@@ -90,6 +90,7 @@ use rpc::{
 };
 use search::{SearchInputKind, SearchQuery, SearchResult};
 use search_history::SearchHistory;
+use serde::{Deserialize, Serialize};
 use settings::{InvalidSettingsError, Settings, SettingsLocation, SettingsStore};
 use smol::channel::Receiver;
 use snippet::Snippet;
@@ -124,14 +125,15 @@ pub use language::Location;
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use task_inventory::{
-    BasicContextProvider, ContextProviderWithTasks, Inventory, TaskContexts, TaskSourceKind,
+    BasicContextProvider, ContextProviderWithTasks, Inventory, TaskContexts, TaskDependency,
+    TaskSourceKind,
 };
 
 pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
     DiagnosticSummary, LanguageServerLogType, LanguageServerProgress, LanguageServerPromptRequest,
     LanguageServerStatus, LanguageServerToQuery, LspStore, LspStoreEvent,
-    SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    SERVER_PROGRESS_THROTTLE_TIMEOUT, TASK_DIAGNOSTICS_SERVER_ID, semantic_tokens::SemanticToken,
 };
 pub use toolchain_store::ToolchainStore;
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
@@ -301,6 +303,7 @@ pub enum Event {
     Rejoined,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -686,6 +689,117 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A foldable range of lines reported by a language server.
+///
+/// The range starts at the beginning of its first line and ends at the end of its
+/// last line; folding it keeps the first line visible.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoldingRange {
+    pub range: Range<language::Anchor>,
+    pub kind: Option<lsp::FoldingRangeKind>,
+}
+
+/// A range of a buffer that a language server links to a URI.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentLink {
+    pub range: Range<language::Anchor>,
+    /// The URI the link points to, which is only known once the link is resolved
+    /// for servers that resolve their links lazily.
+    pub target: Option<String>,
+    pub tooltip: Option<String>,
+    pub server_id: LanguageServerId,
+    /// Data the language server needs to resolve the link.
+    pub data: Option<serde_json::Value>,
+}
+
+/// A color that a language server found in a buffer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentColor {
+    pub range: Range<language::Anchor>,
+    pub color: lsp::Color,
+    pub server_id: LanguageServerId,
+}
+
+/// A way of writing a [`DocumentColor`] in the buffer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorPresentation {
+    pub label: String,
+    /// The edits that replace the color with this presentation of it.
+    pub edits: Vec<(Range<language::Anchor>, String)>,
+}
+
+/// The relationship a call or type hierarchy is built from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HierarchyKind {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyKind {
+    pub fn is_call_hierarchy(&self) -> bool {
+        matches!(self, Self::IncomingCalls | Self::OutgoingCalls)
+    }
+}
+
+/// A symbol in a call or type hierarchy.
+#[derive(Clone, Debug)]
+pub struct HierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The range to reveal when navigating to the item, e.g. the name of a function.
+    pub location: Location,
+    pub language_server_id: LanguageServerId,
+    /// The item as returned by the language server, which has to be sent back
+    /// as is to resolve the item's neighbours in the hierarchy.
+    pub lsp_item: LspHierarchyItem,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum LspHierarchyItem {
+    Call(lsp::CallHierarchyItem),
+    Type(lsp::TypeHierarchyItem),
+}
+
+impl LspHierarchyItem {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Call(item) => &item.name,
+            Self::Type(item) => &item.name,
+        }
+    }
+
+    pub fn kind(&self) -> lsp::SymbolKind {
+        match self {
+            Self::Call(item) => item.kind,
+            Self::Type(item) => item.kind,
+        }
+    }
+
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            Self::Call(item) => item.detail.as_deref(),
+            Self::Type(item) => item.detail.as_deref(),
+        }
+    }
+
+    pub fn uri(&self) -> &lsp::Url {
+        match self {
+            Self::Call(item) => &item.uri,
+            Self::Type(item) => &item.uri,
+        }
+    }
+
+    pub fn selection_range(&self) -> lsp::Range {
+        match self {
+            Self::Call(item) => item.selection_range,
+            Self::Type(item) => item.selection_range,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -1834,8 +1948,35 @@ impl Project {
                 "No worktree for path {project_path:?}"
             ))));
         };
-        worktree.update(cx, |worktree, cx| {
-            worktree.create_entry(project_path.path, is_directory, None, cx)
+
+        let worktree_id = project_path.worktree_id;
+        let lsp_store = self.lsp_store().downgrade();
+        cx.spawn(async move |_, cx| {
+            let abs_path = {
+                let root_path = worktree.update(cx, |this, _| this.abs_path())?;
+                root_path.join(&project_path.path)
+            };
+            LspStore::will_create_entry(
+                lsp_store.clone(),
+                worktree_id,
+                &abs_path,
+                is_directory,
+                cx.clone(),
+            )
+            .await;
+
+            let entry = worktree
+                .update(cx, |worktree, cx| {
+                    worktree.create_entry(project_path.path, is_directory, None, cx)
+                })?
+                .await?;
+
+            lsp_store
+                .update(cx, |this, _| {
+                    this.did_create_entry(worktree_id, &abs_path, is_directory);
+                })
+                .ok();
+            Ok(entry)
         })
     }
 
@@ -1929,10 +2070,39 @@ impl Project {
         cx: &mut Context<Self>,
     ) -> Option<Task<Result<()>>> {
         let worktree = self.worktree_for_entry(entry_id, cx)?;
-        cx.emit(Event::DeletedEntry(worktree.read(cx).id(), entry_id));
-        worktree.update(cx, |worktree, cx| {
-            worktree.delete_entry(entry_id, trash, cx)
-        })
+        let worktree_id = worktree.read(cx).id();
+        let (abs_path, is_dir) = {
+            let worktree = worktree.read(cx);
+            let entry = worktree.entry_for_id(entry_id)?;
+            (worktree.abs_path().join(&entry.path), entry.is_dir())
+        };
+        cx.emit(Event::DeletedEntry(worktree_id, entry_id));
+
+        let lsp_store = self.lsp_store().downgrade();
+        Some(cx.spawn(async move |_, cx| {
+            LspStore::will_delete_entry(
+                lsp_store.clone(),
+                worktree_id,
+                &abs_path,
+                is_dir,
+                cx.clone(),
+            )
+            .await;
+
+            worktree
+                .update(cx, |worktree, cx| {
+                    worktree.delete_entry(entry_id, trash, cx)
+                })?
+                .ok_or_else(|| anyhow!("No entry for id {entry_id:?}"))?
+                .await?;
+
+            lsp_store
+                .update(cx, |this, _| {
+                    this.did_delete_entry(worktree_id, &abs_path, is_dir);
+                })
+                .ok();
+            Ok(())
+        }))
     }
 
     pub fn expand_entry(
@@ -2670,6 +2840,7 @@ impl Project {
             }
             LspStoreEvent::RefreshInlayHints => cx.emit(Event::RefreshInlayHints),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3225,6 +3396,59 @@ impl Project {
         )
     }
 
+    /// Resolves the symbol at the given position to the roots of a call or type hierarchy.
+    pub fn prepare_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        kind: HierarchyKind,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        if kind.is_call_hierarchy() {
+            self.request_lsp(
+                buffer.clone(),
+                LanguageServerToQuery::FirstCapable,
+                PrepareCallHierarchy { position },
+                cx,
+            )
+        } else {
+            self.request_lsp(
+                buffer.clone(),
+                LanguageServerToQuery::FirstCapable,
+                PrepareTypeHierarchy { position },
+                cx,
+            )
+        }
+    }
+
+    /// Fetches the items adjacent to the given item in a call or type hierarchy, e.g. the
+    /// callers of a function or the subtypes of a type.
+    pub fn hierarchy_item_children(
+        &mut self,
+        item: &HierarchyItem,
+        kind: HierarchyKind,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let buffer = item.location.buffer.clone();
+        let server = LanguageServerToQuery::Other(item.language_server_id);
+        match (kind, &item.lsp_item) {
+            (HierarchyKind::IncomingCalls, LspHierarchyItem::Call(item)) => {
+                self.request_lsp(buffer, server, GetIncomingCalls { item: item.clone() }, cx)
+            }
+            (HierarchyKind::OutgoingCalls, LspHierarchyItem::Call(item)) => {
+                self.request_lsp(buffer, server, GetOutgoingCalls { item: item.clone() }, cx)
+            }
+            (HierarchyKind::Supertypes, LspHierarchyItem::Type(item)) => {
+                self.request_lsp(buffer, server, GetSupertypes { item: item.clone() }, cx)
+            }
+            (HierarchyKind::Subtypes, LspHierarchyItem::Type(item)) => {
+                self.request_lsp(buffer, server, GetSubtypes { item: item.clone() }, cx)
+            }
+            _ => Task::ready(Err(anyhow!("{kind:?} can't be resolved for {}", item.name))),
+        }
+    }
+
     fn document_highlights_impl(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -3570,6 +3794,92 @@ impl Project {
         })
     }
 
+    pub fn semantic_tokens(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<Anchor>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<SemanticToken>>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.semantic_tokens(buffer, range, cx)
+        })
+    }
+
+    pub fn folding_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<FoldingRange>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.folding_ranges(buffer, cx))
+    }
+
+    /// Returns, for each of the given positions, the ranges that the first capable
+    /// language server suggests selecting around it, from the innermost to the outermost.
+    pub fn selection_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<Anchor>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<Anchor>>>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.document_links(buffer, cx))
+    }
+
+    /// Asks the language server that reported the link for its target, returning the
+    /// link unchanged if the server can't resolve links.
+    pub fn resolve_document_link(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        let request = self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(link.server_id),
+            ResolveDocumentLink { link: link.clone() },
+            cx,
+        );
+        cx.background_spawn(async move { Ok(request.await?.unwrap_or(link)) })
+    }
+
+    pub fn document_colors(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentColor>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.document_colors(buffer, cx))
+    }
+
+    /// Returns the ways that the language server that reported the color can write it.
+    pub fn color_presentations(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        color: DocumentColor,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<ColorPresentation>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(color.server_id),
+            GetColorPresentations { color },
+            cx,
+        )
+    }
+
     pub fn resolve_inlay_hint(
         &self,
         hint: InlayHint,
@@ -4213,6 +4523,7 @@ impl Project {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
             this.breakpoint_store.read(cx).broadcast();
+            this.dap_store.read(cx).broadcast_watch_expressions();
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
                 .insert(collaborator.peer_id, collaborator);
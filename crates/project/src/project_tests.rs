@@ -1,7 +1,10 @@
 #![allow(clippy::format_collect)]
 
 use crate::{
-    Event, git_store::StatusEntry, task_inventory::TaskContexts, task_store::TaskSettingsLocation,
+    Event,
+    git_store::StatusEntry,
+    task_inventory::TaskContexts,
+    task_store::{RERUN_DEBOUNCE, TaskSettingsLocation, TaskStoreEvent},
     *,
 };
 use buffer_diff::{
@@ -15,7 +18,7 @@ use git::{
     status::{StatusCode, TrackedStatus},
 };
 use git2::RepositoryInitOptions;
-use gpui::{App, BackgroundExecutor, SemanticVersion, UpdateGlobal};
+use gpui::{App, BackgroundExecutor, EntityId, SemanticVersion, UpdateGlobal};
 use http_client::Url;
 use language::{
     Diagnostic, DiagnosticEntry, DiagnosticSet, DiskState, FakeLspAdapter, LanguageConfig,
@@ -25,7 +28,8 @@ use language::{
 };
 use lsp::{
     DiagnosticSeverity, DocumentChanges, FileOperationFilter, NumberOrString, TextDocumentEdit,
-    WillRenameFiles, notification::DidRenameFiles,
+    WillCreateFiles, WillDeleteFiles, WillRenameFiles,
+    notification::{DidCreateFiles, DidDeleteFiles, DidRenameFiles},
 };
 use parking_lot::Mutex;
 use paths::{config_dir, tasks_file};
@@ -36,7 +40,7 @@ use serde_json::json;
 #[cfg(not(windows))]
 use std::os;
 use std::{env, mem, num::NonZeroU32, ops::Range, str::FromStr, sync::OnceLock, task::Poll};
-use task::{ResolvedTask, TaskContext};
+use task::{ProblemMatcher, ResolvedTask, TaskContext, TaskId, TaskTemplate};
 use unindent::Unindent as _;
 use util::{
     TryFutureExt as _, assert_set_eq, maybe, path,
@@ -513,6 +517,147 @@ async fn test_fallback_to_single_worktree_tasks(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_rerunning_watched_tasks(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    TaskStore::init(None);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".gitignore": "target\n",
+            "src": {
+                "a.rs": "",
+                "b.txt": "",
+            },
+            "target": {
+                "out.rs": "",
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let task_store = project.read_with(cx, |project, _| project.task_store().clone());
+    cx.executor().run_until_parked();
+
+    let reruns = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = cx.update(|cx| {
+        let reruns = reruns.clone();
+        cx.subscribe(&task_store, move |_, event, _| match event {
+            TaskStoreEvent::RerunWatchedTask(_, resolved_task) => {
+                reruns.lock().push(resolved_task.resolved_label.clone())
+            }
+        })
+    });
+
+    let resolved_task = TaskTemplate {
+        label: "check".to_string(),
+        command: "cargo check".to_string(),
+        rerun_on: vec!["**/*.rs".to_string()],
+        ..TaskTemplate::default()
+    }
+    .resolve_task("test", &TaskContext::default())
+    .unwrap();
+    let task_id = resolved_task.id.clone();
+    let first_terminal = EntityId::from(1);
+    task_store.update(cx, |task_store, _| {
+        task_store.watched_task_scheduled(TaskSourceKind::UserInput, resolved_task.clone());
+        task_store
+            .watch_task(
+                task_id.clone(),
+                &["src/**/*.rs".to_string()],
+                PathBuf::from(path!("/dir")),
+                first_terminal,
+            )
+            .unwrap();
+    });
+
+    // Changes to files that are ignored or do not match the globs do not rerun the task.
+    fs.save(
+        path!("/dir/src/b.txt").as_ref(),
+        &"b".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    fs.save(
+        path!("/dir/target/out.rs").as_ref(),
+        &"out".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().advance_clock(RERUN_DEBOUNCE);
+    cx.executor().run_until_parked();
+    assert!(reruns.lock().is_empty());
+
+    // A burst of changes to the matching files reruns the task once, after the changes settle.
+    fs.save(
+        path!("/dir/src/a.rs").as_ref(),
+        &"a".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RERUN_DEBOUNCE / 2);
+    fs.create_file(path!("/dir/src/c.rs").as_ref(), Default::default())
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RERUN_DEBOUNCE / 2);
+    cx.executor().run_until_parked();
+    assert!(reruns.lock().is_empty());
+    cx.executor().advance_clock(RERUN_DEBOUNCE);
+    cx.executor().run_until_parked();
+    assert_eq!(mem::take(&mut *reruns.lock()), vec!["check".to_string()]);
+
+    // The rerun replaces the task's terminal, and releasing the replaced one keeps the task
+    // watched by the new terminal.
+    let second_terminal = EntityId::from(2);
+    task_store.update(cx, |task_store, _| {
+        task_store.watched_task_scheduled(TaskSourceKind::UserInput, resolved_task);
+        task_store
+            .watch_task(
+                task_id.clone(),
+                &["src/**/*.rs".to_string()],
+                PathBuf::from(path!("/dir")),
+                second_terminal,
+            )
+            .unwrap();
+        task_store.unwatch_task(&task_id, first_terminal);
+    });
+    fs.save(
+        path!("/dir/src/a.rs").as_ref(),
+        &"aa".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RERUN_DEBOUNCE);
+    cx.executor().run_until_parked();
+    assert_eq!(mem::take(&mut *reruns.lock()), vec!["check".to_string()]);
+
+    // Once the task's terminal is released, pending reruns are cancelled.
+    fs.save(
+        path!("/dir/src/a.rs").as_ref(),
+        &"aaa".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    task_store.update(cx, |task_store, _| {
+        task_store.unwatch_task(&task_id, second_terminal)
+    });
+    cx.executor().advance_clock(RERUN_DEBOUNCE);
+    cx.executor().run_until_parked();
+    assert!(reruns.lock().is_empty());
+}
+
 #[gpui::test]
 async fn test_managing_language_servers(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1763,6 +1908,267 @@ async fn test_restarting_server_with_diagnostics_published(cx: &mut gpui::TestAp
     });
 }
 
+#[gpui::test]
+async fn test_pulled_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "a.rs": "let a = 1;", "b.rs": "let b = 2;" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                diagnostic_provider: Some(lsp::DiagnosticServerCapabilities::Options(
+                    lsp::DiagnosticOptions {
+                        workspace_diagnostics: true,
+                        ..Default::default()
+                    },
+                )),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let previous_result_ids = Arc::new(Mutex::new(Vec::new()));
+    fake_server.set_request_handler::<lsp::request::DocumentDiagnosticRequest, _, _>({
+        let previous_result_ids = previous_result_ids.clone();
+        move |params, _| {
+            let previous_result_id = params.previous_result_id.clone();
+            previous_result_ids.lock().push(previous_result_id.clone());
+            async move {
+                let report = if previous_result_id.is_some() {
+                    lsp::DocumentDiagnosticReport::Unchanged(
+                        lsp::RelatedUnchangedDocumentDiagnosticReport {
+                            related_documents: None,
+                            unchanged_document_diagnostic_report:
+                                lsp::UnchangedDocumentDiagnosticReport {
+                                    result_id: "1".to_string(),
+                                },
+                        },
+                    )
+                } else {
+                    lsp::DocumentDiagnosticReport::Full(lsp::RelatedFullDocumentDiagnosticReport {
+                        related_documents: None,
+                        full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport {
+                            result_id: Some("1".to_string()),
+                            items: vec![lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 4),
+                                    lsp::Position::new(0, 5),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                                message: "unused variable".to_string(),
+                                ..Default::default()
+                            }],
+                        },
+                    })
+                };
+                Ok(lsp::DocumentDiagnosticReportResult::Report(report))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::WorkspaceDiagnosticRequest, _, _>(
+        |_, _| async move {
+            Ok(lsp::WorkspaceDiagnosticReportResult::Report(
+                lsp::WorkspaceDiagnosticReport {
+                    items: vec![lsp::WorkspaceDocumentDiagnosticReport::Full(
+                        lsp::WorkspaceFullDocumentDiagnosticReport {
+                            uri: Url::from_file_path(path!("/dir/b.rs")).unwrap(),
+                            version: None,
+                            full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport {
+                                result_id: None,
+                                items: vec![lsp::Diagnostic {
+                                    range: lsp::Range::new(
+                                        lsp::Position::new(0, 8),
+                                        lsp::Position::new(0, 9),
+                                    ),
+                                    severity: Some(lsp::DiagnosticSeverity::ERROR),
+                                    message: "mismatched types".to_string(),
+                                    ..Default::default()
+                                }],
+                            },
+                        },
+                    )],
+                },
+            ))
+        },
+    );
+
+    // The buffer's diagnostics are pulled once the server is running.
+    cx.executor().advance_clock(Duration::from_millis(100));
+    cx.executor().run_until_parked();
+    let diagnostic_messages = |cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, usize>(0..buffer.len(), false)
+                .map(|entry| (entry.range, entry.diagnostic.message.clone()))
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(
+        diagnostic_messages(cx),
+        [(4..5, "unused variable".to_string())]
+    );
+
+    // Subsequent pulls send the previous result ID and keep unchanged diagnostics.
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+    cx.executor().advance_clock(Duration::from_millis(100));
+    cx.executor().run_until_parked();
+    assert_eq!(
+        diagnostic_messages(cx),
+        [(5..6, "unused variable".to_string())]
+    );
+    assert_eq!(
+        mem::take(&mut *previous_result_ids.lock()),
+        [None, Some("1".to_string())]
+    );
+
+    // A refresh from the server pulls the workspace's diagnostics too.
+    fake_server
+        .request::<lsp::request::WorkspaceDiagnosticRefresh>(())
+        .await
+        .expect("diagnostic refresh request failed");
+    cx.executor().advance_clock(Duration::from_millis(100));
+    cx.executor().run_until_parked();
+    assert_eq!(
+        mem::take(&mut *previous_result_ids.lock()),
+        [Some("1".to_string())]
+    );
+    project.update(cx, |project, cx| {
+        assert_eq!(
+            project.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 1,
+            }
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_pulled_and_pushed_diagnostics_are_kept_apart(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "a.rs": "let a = 1;" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                diagnostic_provider: Some(lsp::DiagnosticServerCapabilities::Options(
+                    lsp::DiagnosticOptions::default(),
+                )),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::DocumentDiagnosticRequest, _, _>(
+        |_, _| async move {
+            Ok(lsp::DocumentDiagnosticReportResult::Report(
+                lsp::DocumentDiagnosticReport::Full(lsp::RelatedFullDocumentDiagnosticReport {
+                    related_documents: None,
+                    full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport {
+                        result_id: None,
+                        items: vec![lsp::Diagnostic {
+                            range: lsp::Range::new(
+                                lsp::Position::new(0, 4),
+                                lsp::Position::new(0, 5),
+                            ),
+                            severity: Some(lsp::DiagnosticSeverity::WARNING),
+                            message: "pulled".to_string(),
+                            ..Default::default()
+                        }],
+                    },
+                }),
+            ))
+        },
+    );
+    let push = |message: Option<&str>| {
+        fake_server.notify::<lsp::notification::PublishDiagnostics>(
+            &lsp::PublishDiagnosticsParams {
+                uri: Url::from_file_path(path!("/dir/a.rs")).unwrap(),
+                version: None,
+                diagnostics: message
+                    .map(|message| lsp::Diagnostic {
+                        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 3)),
+                        severity: Some(lsp::DiagnosticSeverity::ERROR),
+                        message: message.to_string(),
+                        ..Default::default()
+                    })
+                    .into_iter()
+                    .collect(),
+            },
+        );
+    };
+    let diagnostic_messages = |cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, usize>(0..buffer.len(), false)
+                .map(|entry| entry.diagnostic.message.clone())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    cx.executor().advance_clock(Duration::from_millis(100));
+    cx.executor().run_until_parked();
+    assert_eq!(diagnostic_messages(cx), ["pulled"]);
+
+    // Pushing diagnostics keeps the pulled ones.
+    push(Some("pushed"));
+    cx.executor().run_until_parked();
+    assert_eq!(diagnostic_messages(cx), ["pushed", "pulled"]);
+
+    // Pulling diagnostics keeps the pushed ones.
+    buffer.update(cx, |buffer, cx| {
+        let len = buffer.len();
+        buffer.edit([(len..len, "\n")], None, cx)
+    });
+    cx.executor().advance_clock(Duration::from_millis(100));
+    cx.executor().run_until_parked();
+    assert_eq!(diagnostic_messages(cx), ["pushed", "pulled"]);
+
+    // Each kind only replaces the diagnostics reported the same way.
+    push(None);
+    cx.executor().run_until_parked();
+    assert_eq!(diagnostic_messages(cx), ["pulled"]);
+}
+
 #[gpui::test]
 async fn test_restarted_server_reporting_invalid_buffer_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -2403,51 +2809,164 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
 }
 
 #[gpui::test]
-async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
+async fn test_task_problems_reported_as_diagnostics(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
-    let text = "
-        fn a() {
-            f1();
-        }
-        fn b() {
-            f2();
-        }
-        fn c() {
-            f3();
-        }
-    "
-    .unindent();
-
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "a.rs": text.clone(),
+            "src": {
+                "main.rs": "fn main() {\n    let x = 1;\n}\n",
+            },
         }),
     )
     .await;
 
     let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
     let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
-
-    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
-    language_registry.add(rust_lang());
-    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
-
-    let (buffer, _handle) = project
+    let buffer = project
         .update(cx, |project, cx| {
-            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+            project.open_local_buffer(path!("/dir/src/main.rs"), cx)
         })
         .await
         .unwrap();
 
-    let mut fake_server = fake_servers.next().await.unwrap();
-    let lsp_document_version = fake_server
-        .receive_notification::<lsp::notification::DidOpenTextDocument>()
+    let task_id = TaskId("cargo check".to_string());
+    let output = concat!(
+        "warning: unused variable: `x`\n",
+        " --> src/main.rs:2:9\n",
+        "  |\n",
+    );
+    project
+        .update(cx, |project, cx| {
+            project.report_task_output(
+                task_id.clone(),
+                vec![
+                    ProblemMatcher::Named("$rustc".to_string())
+                        .definition()
+                        .unwrap(),
+                ],
+                output.to_string(),
+                Some(Arc::from(Path::new(path!("/dir")))),
+                cx,
+            )
+        })
         .await
-        .text_document
-        .version;
+        .unwrap();
+
+    buffer.update(cx, |buffer, _| {
+        let chunks = chunks_with_diagnostics(buffer, 0..buffer.len());
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|(s, d)| (s.as_str(), *d))
+                .collect::<Vec<_>>(),
+            &[
+                ("fn main() {\n    let ", None),
+                ("x", Some(DiagnosticSeverity::WARNING)),
+                (" = 1;\n}\n", None),
+            ]
+        );
+        let messages = buffer
+            .snapshot()
+            .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+            .map(|entry| {
+                (
+                    entry.diagnostic.source.clone(),
+                    entry.diagnostic.message.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            &[(
+                Some("rustc".to_string()),
+                "unused variable: `x`".to_string()
+            )]
+        );
+    });
+    lsp_store.update(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        );
+    });
+
+    // Rerunning the task clears the diagnostics of its previous run.
+    project.update(cx, |project, cx| {
+        project.clear_task_problems(task_id.clone(), cx)
+    });
+    buffer.update(cx, |buffer, _| {
+        let chunks = chunks_with_diagnostics(buffer, 0..buffer.len());
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|(s, d)| (s.as_str(), *d))
+                .collect::<Vec<_>>(),
+            &[("fn main() {\n    let x = 1;\n}\n", None)]
+        );
+    });
+    lsp_store.update(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 0,
+            }
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let text = "
+        fn a() {
+            f1();
+        }
+        fn b() {
+            f2();
+        }
+        fn c() {
+            f3();
+        }
+    "
+    .unindent();
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": text.clone(),
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let mut fake_server = fake_servers.next().await.unwrap();
+    let lsp_document_version = fake_server
+        .receive_notification::<lsp::notification::DidOpenTextDocument>()
+        .await
+        .text_document
+        .version;
 
     // Simulate editing the buffer after the language server computes some edits.
     buffer.update(cx, |buffer, cx| {
@@ -2946,6 +3465,353 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b() }",
+            "b.rs": "fn b() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    fn call_hierarchy_item(name: &str, path: &str, start: u32, end: u32) -> lsp::CallHierarchyItem {
+        let range = lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, end));
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: None,
+            uri: lsp::Url::from_file_path(path).unwrap(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+        |params, _| async move {
+            assert_eq!(
+                params.text_document_position_params.position,
+                lsp::Position::new(0, 3)
+            );
+            Ok(Some(vec![call_hierarchy_item(
+                "b",
+                path!("/dir/b.rs"),
+                3,
+                4,
+            )]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                from: call_hierarchy_item("a", path!("/dir/a.rs"), 3, 4),
+                from_ranges: vec![lsp::Range::new(
+                    lsp::Position::new(0, 9),
+                    lsp::Position::new(0, 10),
+                )],
+            }]))
+        },
+    );
+
+    let roots = project
+        .update(cx, |project, cx| {
+            project.prepare_hierarchy(&buffer, 3, HierarchyKind::IncomingCalls, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].name, "b");
+
+    let callers = project
+        .update(cx, |project, cx| {
+            project.hierarchy_item_children(&roots[0], HierarchyKind::IncomingCalls, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(callers.len(), 1);
+    cx.update(|cx| {
+        let caller = &callers[0];
+        let target_buffer = caller.location.buffer.read(cx);
+        assert_eq!(caller.name, "a");
+        assert_eq!(
+            target_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/a.rs")),
+        );
+        assert_eq!(caller.location.range.to_offset(target_buffer), 3..4);
+    });
+
+    // Type hierarchy requests can't resolve call hierarchy items.
+    assert!(
+        project
+            .update(cx, |project, cx| {
+                project.hierarchy_item_children(&roots[0], HierarchyKind::Subtypes, cx)
+            })
+            .await
+            .is_err()
+    );
+}
+
+#[gpui::test]
+async fn test_folding_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "use a;\nuse b;\n// one\n// two\nfn a() {\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let request_count = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    fake_server.set_request_handler::<lsp::request::FoldingRangeRequest, _, _>({
+        let request_count = request_count.clone();
+        move |_, _| {
+            request_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move {
+                Ok(Some(vec![
+                    lsp::FoldingRange {
+                        start_line: 2,
+                        end_line: 3,
+                        kind: Some(lsp::FoldingRangeKind::Comment),
+                        ..Default::default()
+                    },
+                    lsp::FoldingRange {
+                        start_line: 0,
+                        end_line: 1,
+                        kind: Some(lsp::FoldingRangeKind::Imports),
+                        ..Default::default()
+                    },
+                    // Ranges that don't span multiple lines can't be folded.
+                    lsp::FoldingRange {
+                        start_line: 4,
+                        end_line: 4,
+                        ..Default::default()
+                    },
+                ]))
+            }
+        }
+    });
+
+    let ranges = project
+        .update(cx, |project, cx| project.folding_ranges(&buffer, cx))
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            ranges
+                .iter()
+                .map(|range| (range.range.to_point(buffer), range.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Point::new(0, 0)..Point::new(1, 6),
+                    Some(lsp::FoldingRangeKind::Imports)
+                ),
+                (
+                    Point::new(2, 0)..Point::new(3, 6),
+                    Some(lsp::FoldingRangeKind::Comment)
+                ),
+            ]
+        );
+    });
+    assert_eq!(request_count.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    // The ranges are cached until the buffer changes.
+    project
+        .update(cx, |project, cx| project.folding_ranges(&buffer, cx))
+        .await
+        .unwrap();
+    assert_eq!(request_count.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+    project
+        .update(cx, |project, cx| project.folding_ranges(&buffer, cx))
+        .await
+        .unwrap();
+    assert_eq!(request_count.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[gpui::test]
+async fn test_document_colors(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "let red = \"#ff0000\";\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                color_provider: Some(lsp::ColorProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let red = lsp::Color {
+        red: 1.,
+        green: 0.,
+        blue: 0.,
+        alpha: 1.,
+    };
+    fake_server.set_request_handler::<lsp::request::DocumentColor, _, _>(move |_, _| async move {
+        Ok(vec![lsp::ColorInformation {
+            range: lsp::Range::new(lsp::Position::new(0, 11), lsp::Position::new(0, 18)),
+            color: red,
+        }])
+    });
+    fake_server.set_request_handler::<lsp::request::ColorPresentationRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.color, red);
+            assert_eq!(
+                params.range,
+                lsp::Range::new(lsp::Position::new(0, 11), lsp::Position::new(0, 18))
+            );
+            Ok(vec![
+                lsp::ColorPresentation {
+                    label: "rgb(255, 0, 0)".to_string(),
+                    text_edit: Some(lsp::TextEdit {
+                        range: lsp::Range::new(
+                            lsp::Position::new(0, 10),
+                            lsp::Position::new(0, 19),
+                        ),
+                        new_text: "rgb(255, 0, 0)".to_string(),
+                    }),
+                    additional_text_edits: None,
+                },
+                lsp::ColorPresentation {
+                    label: "#f00".to_string(),
+                    text_edit: None,
+                    additional_text_edits: None,
+                },
+            ])
+        },
+    );
+
+    let colors = project
+        .update(cx, |project, cx| project.document_colors(&buffer, cx))
+        .await
+        .unwrap();
+    assert_eq!(colors.len(), 1);
+    let color = colors[0].clone();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            color.range.to_point(buffer),
+            Point::new(0, 11)..Point::new(0, 18)
+        );
+        assert_eq!(color.color, red);
+    });
+
+    let presentations = project
+        .update(cx, |project, cx| {
+            project.color_presentations(&buffer, color, cx)
+        })
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            presentations
+                .iter()
+                .map(|presentation| (
+                    presentation.label.as_str(),
+                    presentation
+                        .edits
+                        .iter()
+                        .map(|(range, new_text)| (range.to_point(buffer), new_text.as_str()))
+                        .collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "rgb(255, 0, 0)",
+                    vec![(Point::new(0, 10)..Point::new(0, 19), "rgb(255, 0, 0)")]
+                ),
+                // Without an edit, the label replaces the color.
+                ("#f00", vec![(Point::new(0, 11)..Point::new(0, 18), "#f00")]),
+            ]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4655,6 +5521,132 @@ async fn test_lsp_rename_notifications(cx: &mut gpui::TestAppContext) {
     assert_eq!(resolved_workspace_edit.get(), Some(&expected_edit));
 }
 
+#[gpui::test]
+async fn test_lsp_create_and_delete_notifications(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE;",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let watched_paths = lsp::FileOperationRegistrationOptions {
+        filters: vec![FileOperationFilter {
+            scheme: Some("file".to_owned()),
+            pattern: lsp::FileOperationPattern {
+                glob: "**/*.rs".to_owned(),
+                matches: Some(lsp::FileOperationPatternKind::File),
+                options: None,
+            },
+        }],
+    };
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                workspace: Some(lsp::WorkspaceServerCapabilities {
+                    workspace_folders: None,
+                    file_operations: Some(lsp::WorkspaceFileOperationsServerCapabilities {
+                        did_create: Some(watched_paths.clone()),
+                        will_create: Some(watched_paths.clone()),
+                        did_delete: Some(watched_paths.clone()),
+                        will_delete: Some(watched_paths),
+                        ..Default::default()
+                    }),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (two_buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/two.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+
+    // Servers are asked before a file is created and told once it has been.
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let response = project.update(cx, |project, cx| {
+        project.create_entry((worktree_id, "three.rs"), false, cx)
+    });
+    fake_server
+        .set_request_handler::<WillCreateFiles, _, _>(|params, _| async move {
+            assert_eq!(params.files.len(), 1);
+            assert_eq!(params.files[0].uri, uri!("file:///dir/three.rs"));
+            Ok(None)
+        })
+        .next()
+        .await
+        .unwrap();
+    response.await.unwrap();
+    fake_server
+        .handle_notification::<DidCreateFiles, _>(|params, _| {
+            assert_eq!(params.files.len(), 1);
+            assert_eq!(params.files[0].uri, uri!("file:///dir/three.rs"));
+        })
+        .next()
+        .await
+        .unwrap();
+
+    // The edits returned before a file is deleted are applied.
+    let response = project.update(cx, |project, cx| {
+        let worktree = project.worktrees(cx).next().unwrap();
+        let entry = worktree.read(cx).entry_for_path("one.rs").unwrap();
+        project.delete_entry(entry.id, false, cx).unwrap()
+    });
+    fake_server
+        .set_request_handler::<WillDeleteFiles, _, _>(|params, _| async move {
+            assert_eq!(params.files.len(), 1);
+            assert_eq!(params.files[0].uri, uri!("file:///dir/one.rs"));
+            Ok(Some(lsp::WorkspaceEdit {
+                changes: Some(
+                    [(
+                        Url::from_str(uri!("file:///dir/two.rs")).unwrap(),
+                        vec![lsp::TextEdit {
+                            range: lsp::Range::new(
+                                lsp::Position::new(0, 19),
+                                lsp::Position::new(0, 27),
+                            ),
+                            new_text: "1".to_owned(),
+                        }],
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+                ..Default::default()
+            }))
+        })
+        .next()
+        .await
+        .unwrap();
+    response.await.unwrap();
+    fake_server
+        .handle_notification::<DidDeleteFiles, _>(|params, _| {
+            assert_eq!(params.files.len(), 1);
+            assert_eq!(params.files[0].uri, uri!("file:///dir/one.rs"));
+        })
+        .next()
+        .await
+        .unwrap();
+    two_buffer.read_with(cx, |buffer, _| {
+        assert_eq!(buffer.text(), "const TWO: usize = 1;");
+    });
+}
+
 #[gpui::test]
 async fn test_rename(cx: &mut gpui::TestAppContext) {
     // hi
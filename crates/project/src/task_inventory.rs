@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result, bail};
 use collections::{HashMap, HashSet, VecDeque};
 use gpui::{App, AppContext as _, Entity, SharedString, Task};
 use itertools::Itertools;
@@ -33,6 +33,9 @@ use crate::{task_store::TaskSettingsLocation, worktree_store::WorktreeStore};
 #[derive(Debug, Default)]
 pub struct Inventory {
     last_scheduled_tasks: VecDeque<(TaskSourceKind, ResolvedTask)>,
+    /// The values last given to the inputs of the task templates and debug scenarios, by their sources and labels.
+    /// Debug scenarios have no source.
+    last_input_values: HashMap<(Option<TaskSourceKind>, String), HashMap<String, String>>,
     templates_from_settings: InventoryFor<TaskTemplate>,
     scenarios_from_settings: InventoryFor<DebugScenario>,
 }
@@ -135,6 +138,15 @@ pub enum TaskSourceKind {
     Lsp(LanguageServerId),
 }
 
+/// A task that has to succeed before the task depending on it runs, along with the
+/// tasks that it depends on itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskDependency {
+    pub source_kind: TaskSourceKind,
+    pub template: TaskTemplate,
+    pub dependencies: Vec<TaskDependency>,
+}
+
 /// A collection of task contexts, derived from the current state of the workspace.
 /// Only contains worktrees that are visible and with their root being a directory.
 #[derive(Debug, Default)]
@@ -240,6 +252,22 @@ impl Inventory {
             .map(|val| val.1.clone())
     }
 
+    /// Looks up the tasks that the template depends on by their labels, recursively, among the
+    /// tasks of the worktree given and the global tasks.
+    /// Fails if any of the labels is unknown, or if the tasks depend on each other in a cycle.
+    pub fn task_dependencies(
+        &self,
+        template: &TaskTemplate,
+        worktree: Option<WorktreeId>,
+    ) -> Result<Vec<TaskDependency>> {
+        let templates = self
+            .worktree_templates_from_settings(worktree)
+            .chain(self.global_templates_from_settings())
+            .collect::<Vec<_>>();
+        let mut dependents = vec![template.label.clone()];
+        resolve_task_dependencies(template, &templates, &mut dependents)
+    }
+
     /// Pulls its task sources relevant to the worktree and the language given,
     /// returns all task templates with their source kinds, worktree tasks first, language tasks second
     /// and global tasks last. No specific order inside source kinds groups.
@@ -440,6 +468,33 @@ impl Inventory {
         }
     }
 
+    /// Returns the value last given to the input of the task template or debug scenario with the given source and label.
+    pub fn last_input_value(
+        &self,
+        task_source_kind: Option<&TaskSourceKind>,
+        task_label: &str,
+        input_id: &str,
+    ) -> Option<&str> {
+        self.last_input_values
+            .get(&(task_source_kind.cloned(), task_label.to_owned()))?
+            .get(input_id)
+            .map(String::as_str)
+    }
+
+    /// Remembers the values given to the inputs of the task template or debug scenario with the given source and label,
+    /// to suggest them the next time the task is spawned.
+    pub fn input_values_given(
+        &mut self,
+        task_source_kind: Option<TaskSourceKind>,
+        task_label: String,
+        input_values: impl IntoIterator<Item = (String, String)>,
+    ) {
+        self.last_input_values
+            .entry((task_source_kind, task_label))
+            .or_default()
+            .extend(input_values);
+    }
+
     /// Deletes a resolved task from history, using its id.
     /// A similar may still resurface in `used_and_current_resolved_tasks` when its [`TaskTemplate`] is resolved again.
     pub fn delete_previously_used(&mut self, id: &TaskId) {
@@ -590,6 +645,43 @@ impl Inventory {
     }
 }
 
+fn resolve_task_dependencies(
+    template: &TaskTemplate,
+    templates: &[(TaskSourceKind, TaskTemplate)],
+    dependents: &mut Vec<String>,
+) -> Result<Vec<TaskDependency>> {
+    template
+        .depends_on
+        .iter()
+        .map(|label| {
+            if dependents.contains(label) {
+                bail!(
+                    "Task {label:?} depends on itself: {} -> {label}",
+                    dependents.join(" -> ")
+                );
+            }
+            let (source_kind, dependency) = templates
+                .iter()
+                .find(|(_, template)| &template.label == label)
+                .with_context(|| {
+                    format!(
+                        "Task {:?} depends on unknown task {label:?}",
+                        template.label
+                    )
+                })?;
+
+            dependents.push(label.clone());
+            let dependencies = resolve_task_dependencies(dependency, templates, dependents)?;
+            dependents.pop();
+            Ok(TaskDependency {
+                source_kind: source_kind.clone(),
+                template: dependency.clone(),
+                dependencies,
+            })
+        })
+        .collect()
+}
+
 fn task_lru_comparator(
     (kind_a, task_a, lru_score_a): &(TaskSourceKind, ResolvedTask, u32),
     (kind_b, task_b, lru_score_b): &(TaskSourceKind, ResolvedTask, u32),
@@ -1124,6 +1216,98 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_task_dependencies(cx: &mut TestAppContext) {
+        init_test(cx);
+        let inventory = cx.update(Inventory::new);
+        let worktree = WorktreeId::from_usize(1);
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Global(tasks_file()),
+                    Some(
+                        &json!([
+                            { "label": "generate", "command": "protoc" },
+                            { "label": "cycle a", "command": "a", "depends_on": ["cycle b"] },
+                            { "label": "cycle b", "command": "b", "depends_on": ["cycle a"] },
+                        ])
+                        .to_string(),
+                    ),
+                )
+                .unwrap();
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Worktree(SettingsLocation {
+                        worktree_id: worktree,
+                        path: Path::new(".zed"),
+                    }),
+                    Some(
+                        &json!([
+                            { "label": "build", "command": "cargo", "depends_on": ["generate"] },
+                            {
+                                "label": "run",
+                                "command": "cargo",
+                                "depends_on": ["build", "generate"],
+                                "depends_order": "sequence",
+                            },
+                            { "label": "broken", "command": "true", "depends_on": ["missing"] },
+                        ])
+                        .to_string(),
+                    ),
+                )
+                .unwrap();
+        });
+
+        inventory.update(cx, |inventory, cx| {
+            let dependency_labels = |dependencies: &[TaskDependency]| {
+                dependencies
+                    .iter()
+                    .map(|dependency| {
+                        (
+                            dependency.template.label.clone(),
+                            dependency
+                                .dependencies
+                                .iter()
+                                .map(|dependency| dependency.template.label.clone())
+                                .collect::<Vec<_>>(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let tasks = inventory.list_tasks(None, None, Some(worktree), cx);
+            let template = |label: &str| {
+                tasks
+                    .iter()
+                    .find(|(_, template)| template.label == label)
+                    .unwrap()
+                    .1
+                    .clone()
+            };
+
+            let run = template("run");
+            let dependencies = inventory.task_dependencies(&run, Some(worktree)).unwrap();
+            assert_eq!(
+                dependency_labels(&dependencies),
+                vec![
+                    ("build".to_string(), vec!["generate".to_string()]),
+                    ("generate".to_string(), Vec::new()),
+                ]
+            );
+            assert!(
+                inventory
+                    .task_dependencies(&template("broken"), Some(worktree))
+                    .is_err()
+            );
+            assert!(
+                inventory
+                    .task_dependencies(&template("cycle a"), Some(worktree))
+                    .is_err()
+            );
+            // Worktree tasks can't be found without their worktree.
+            assert!(inventory.task_dependencies(&run, None).is_err());
+        });
+    }
+
     fn init_test(_cx: &mut TestAppContext) {
         if std::env::var("RUST_LOG").is_ok() {
             env_logger::try_init().ok();
@@ -1,26 +1,36 @@
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::Context as _;
 use collections::HashMap;
-use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Task, WeakEntity};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use gpui::{
+    App, AsyncApp, Context, Entity, EntityId, EventEmitter, Subscription, Task, WeakEntity,
+};
 use language::{
     ContextProvider as _, LanguageToolchainStore, Location,
     proto::{deserialize_anchor, serialize_anchor},
 };
 use rpc::{AnyProtoClient, TypedEnvelope, proto};
 use settings::{InvalidSettingsError, SettingsLocation};
-use task::{TaskContext, TaskVariables, VariableName};
+use task::{ResolvedTask, TaskContext, TaskId, TaskVariables, VariableName};
 use text::{BufferId, OffsetRangeExt};
 use util::ResultExt;
+use worktree::PathChange;
 
 use crate::{
-    BasicContextProvider, Inventory, ProjectEnvironment, buffer_store::BufferStore,
-    worktree_store::WorktreeStore,
+    BasicContextProvider, Inventory, ProjectEnvironment, TaskSourceKind,
+    buffer_store::BufferStore,
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 
+/// How long to wait after the files watched by a task change before rerunning it, so that
+/// a burst of changes results in a single rerun.
+pub(crate) const RERUN_DEBOUNCE: Duration = Duration::from_millis(300);
+
 #[allow(clippy::large_enum_variant)] // platform-dependent warning
 pub enum TaskStore {
     Functional(StoreState),
@@ -33,6 +43,21 @@ pub struct StoreState {
     buffer_store: WeakEntity<BufferStore>,
     worktree_store: Entity<WorktreeStore>,
     toolchain_store: Arc<dyn LanguageToolchainStore>,
+    /// Tasks with `rerun_on` globs that were scheduled, until their terminals start watching.
+    scheduled_watched_tasks: HashMap<TaskId, (TaskSourceKind, ResolvedTask)>,
+    watched_tasks: HashMap<TaskId, WatchedTask>,
+    _worktree_store_subscription: Subscription,
+}
+
+/// A task that reruns when the files matching its `rerun_on` globs change, until its terminal
+/// stops watching.
+struct WatchedTask {
+    task_source_kind: TaskSourceKind,
+    resolved_task: ResolvedTask,
+    cwd: PathBuf,
+    rerun_on: GlobSet,
+    terminal_id: EntityId,
+    _debounced_rerun: Option<Task<()>>,
 }
 
 enum StoreMode {
@@ -46,7 +71,12 @@ enum StoreMode {
     },
 }
 
-impl EventEmitter<crate::Event> for TaskStore {}
+pub enum TaskStoreEvent {
+    /// The files watched by the task have changed, and it should be rerun in its terminal.
+    RerunWatchedTask(TaskSourceKind, ResolvedTask),
+}
+
+impl EventEmitter<TaskStoreEvent> for TaskStore {}
 
 #[derive(Debug)]
 pub enum TaskSettingsLocation<'a> {
@@ -172,6 +202,10 @@ impl TaskStore {
             task_inventory: Inventory::new(cx),
             buffer_store,
             toolchain_store,
+            scheduled_watched_tasks: HashMap::default(),
+            watched_tasks: HashMap::default(),
+            _worktree_store_subscription: cx
+                .subscribe(&worktree_store, Self::on_worktree_store_event),
             worktree_store,
         })
     }
@@ -192,6 +226,10 @@ impl TaskStore {
             task_inventory: Inventory::new(cx),
             buffer_store,
             toolchain_store,
+            scheduled_watched_tasks: HashMap::default(),
+            watched_tasks: HashMap::default(),
+            _worktree_store_subscription: cx
+                .subscribe(&worktree_store, Self::on_worktree_store_event),
             worktree_store,
         })
     }
@@ -236,6 +274,125 @@ impl TaskStore {
         }
     }
 
+    /// Remembers the task that is about to be spawned, so that it is rerun the same way once
+    /// its terminal starts watching its files, whether or not it is in the task history.
+    pub fn watched_task_scheduled(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+    ) {
+        let TaskStore::Functional(state) = self else {
+            return;
+        };
+        state
+            .scheduled_watched_tasks
+            .insert(resolved_task.id.clone(), (task_source_kind, resolved_task));
+    }
+
+    /// Reruns the task whenever the files matching the globs, relative to the task's working
+    /// directory, change, until the terminal it runs in calls [`Self::unwatch_task`].
+    pub fn watch_task(
+        &mut self,
+        task_id: TaskId,
+        rerun_on: &[String],
+        cwd: PathBuf,
+        terminal_id: EntityId,
+    ) -> anyhow::Result<()> {
+        let TaskStore::Functional(state) = self else {
+            return Ok(());
+        };
+        let (task_source_kind, resolved_task) = state
+            .scheduled_watched_tasks
+            .remove(&task_id)
+            .with_context(|| format!("cannot watch task {:?} that was not scheduled", task_id.0))?;
+        let mut builder = GlobSetBuilder::new();
+        for glob in rerun_on {
+            builder
+                .add(Glob::new(glob).with_context(|| format!("invalid `rerun_on` glob {glob:?}"))?);
+        }
+        state.watched_tasks.insert(
+            task_id,
+            WatchedTask {
+                task_source_kind,
+                resolved_task,
+                cwd,
+                rerun_on: builder.build()?,
+                terminal_id,
+                _debounced_rerun: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stops rerunning the task, unless it has been rerun in another terminal since, cancelling
+    /// any pending rerun.
+    pub fn unwatch_task(&mut self, task_id: &TaskId, terminal_id: EntityId) {
+        let TaskStore::Functional(state) = self else {
+            return;
+        };
+        if state
+            .watched_tasks
+            .get(task_id)
+            .is_some_and(|watched_task| watched_task.terminal_id == terminal_id)
+        {
+            state.watched_tasks.remove(task_id);
+        }
+    }
+
+    fn on_worktree_store_event(
+        &mut self,
+        worktree_store: Entity<WorktreeStore>,
+        event: &WorktreeStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        let WorktreeStoreEvent::WorktreeUpdatedEntries(worktree_id, changes) = event else {
+            return;
+        };
+        let TaskStore::Functional(state) = self else {
+            return;
+        };
+        if state.watched_tasks.is_empty() {
+            return;
+        }
+        let Some(worktree) = worktree_store.read(cx).worktree_for_id(*worktree_id, cx) else {
+            return;
+        };
+
+        let worktree = worktree.read(cx);
+        let changed_paths = changes
+            .iter()
+            .filter(|(_, entry_id, change)| {
+                *change != PathChange::Loaded
+                    && !worktree
+                        .entry_for_id(*entry_id)
+                        .is_some_and(|entry| entry.is_ignored)
+            })
+            .map(|(path, _, _)| worktree.abs_path().join(path))
+            .collect::<Vec<_>>();
+        for watched_task in state.watched_tasks.values_mut() {
+            let should_rerun = changed_paths.iter().any(|changed_path| {
+                changed_path
+                    .strip_prefix(&watched_task.cwd)
+                    .is_ok_and(|path| watched_task.rerun_on.is_match(path))
+            });
+            if should_rerun {
+                let task_source_kind = watched_task.task_source_kind.clone();
+                let resolved_task = watched_task.resolved_task.clone();
+                watched_task._debounced_rerun = Some(cx.spawn(async move |task_store, cx| {
+                    cx.background_executor().timer(RERUN_DEBOUNCE).await;
+                    task_store
+                        .update(cx, |_, cx| {
+                            cx.emit(TaskStoreEvent::RerunWatchedTask(
+                                task_source_kind,
+                                resolved_task,
+                            ))
+                        })
+                        .ok();
+                }));
+            }
+        }
+    }
+
     pub fn shared(&mut self, remote_id: u64, new_downstream_client: AnyProtoClient, _cx: &mut App) {
         if let Self::Functional(StoreState {
             mode: StoreMode::Local {
@@ -1,4 +1,4 @@
-use crate::{Project, ProjectPath};
+use crate::{Event, Project, ProjectPath};
 use anyhow::{Context as _, Result};
 use collections::HashMap;
 use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, Task, WeakEntity};
@@ -12,7 +12,10 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use task::{DEFAULT_REMOTE_SHELL, Shell, ShellBuilder, SpawnInTerminal};
+use task::{
+    DEFAULT_REMOTE_SHELL, ProblemMatcher, ProblemMatcherDefinition, Shell, ShellBuilder,
+    SpawnInTerminal, TaskId,
+};
 use terminal::{
     TaskState, TaskStatus, Terminal, TerminalBuilder,
     terminal_settings::{self, TerminalSettings, VenvSettings},
@@ -234,6 +237,25 @@ impl Project {
 
         let mut python_venv_activate_command = None;
 
+        let task_problem_matchers = match &kind {
+            TerminalKind::Task(spawn_task) if !spawn_task.problem_matchers.is_empty() => Some((
+                spawn_task.id.clone(),
+                spawn_task.label.clone(),
+                spawn_task.problem_matchers.clone(),
+            )),
+            _ => None,
+        };
+        let task_rerun_on = match (&kind, &path) {
+            (TerminalKind::Task(spawn_task), Some(cwd)) if !spawn_task.rerun_on.is_empty() => {
+                Some((
+                    spawn_task.id.clone(),
+                    spawn_task.rerun_on.clone(),
+                    cwd.to_path_buf(),
+                ))
+            }
+            _ => None,
+        };
+
         let (spawn_task, shell) = match kind {
             TerminalKind::Shell(_) => {
                 if let Some(python_venv_directory) = &python_venv_directory {
@@ -278,6 +300,7 @@ impl Project {
                     show_summary: spawn_task.show_summary,
                     show_command: spawn_task.show_command,
                     show_rerun: spawn_task.show_rerun,
+                    watched: task_rerun_on.is_some(),
                     completion_rx,
                 });
 
@@ -367,6 +390,33 @@ impl Project {
             if let Some(activate_command) = python_venv_activate_command {
                 this.activate_python_virtual_environment(activate_command, &terminal_handle, cx);
             }
+            if let Some((task_id, task_label, problem_matchers)) = task_problem_matchers {
+                this.report_task_problems(
+                    task_id,
+                    task_label,
+                    problem_matchers,
+                    path,
+                    &terminal_handle,
+                    cx,
+                );
+            }
+            if let Some((task_id, rerun_on, cwd)) = task_rerun_on {
+                let watching = this
+                    .task_store
+                    .update(cx, |task_store, _| {
+                        task_store.watch_task(task_id.clone(), &rerun_on, cwd, id)
+                    })
+                    .log_err()
+                    .is_some();
+                if watching {
+                    cx.observe_release(&terminal_handle, move |project, _, cx| {
+                        project
+                            .task_store
+                            .update(cx, |task_store, _| task_store.unwatch_task(&task_id, id));
+                    })
+                    .detach();
+                }
+            }
             terminal_handle
         })
     }
@@ -517,6 +567,113 @@ impl Project {
         terminal_handle.update(cx, |terminal, _| terminal.input(command));
     }
 
+    /// Clears the diagnostics of the task's previous run, then reports the problems that the
+    /// problem matchers find in the task's output as diagnostics, once the task finishes.
+    fn report_task_problems(
+        &self,
+        task_id: TaskId,
+        task_label: String,
+        problem_matchers: Vec<ProblemMatcher>,
+        cwd: Option<Arc<Path>>,
+        terminal_handle: &Entity<Terminal>,
+        cx: &mut Context<Project>,
+    ) {
+        // Remote projects get their diagnostics from the host, which doesn't see the output of
+        // the terminals opened here.
+        if !self.is_local() {
+            cx.emit(Event::Toast {
+                notification_id: format!("task-problem-matchers-{}", task_id.0).into(),
+                message: format!(
+                    "Problem matchers of task {task_label:?} only report diagnostics in local projects"
+                ),
+            });
+            return;
+        }
+
+        self.clear_task_problems(task_id.clone(), cx);
+
+        let mut definitions = problem_matchers
+            .iter()
+            .filter_map(ProblemMatcher::definition)
+            .collect::<Vec<_>>();
+        if definitions.is_empty() {
+            return;
+        }
+
+        // The task's completion channel has a single receiver, which is awaited elsewhere,
+        // so the terminal is observed for its task status change instead.
+        let mut task_id = Some(task_id);
+        cx.observe(terminal_handle, move |project, terminal, cx| {
+            let terminal = terminal.read(cx);
+            if terminal
+                .task()
+                .is_none_or(|task| task.status == TaskStatus::Running)
+            {
+                return;
+            }
+            let Some(task_id) = task_id.take() else {
+                return;
+            };
+
+            // Only the output that is still in the terminal's scrollback is matched, which is
+            // capped by the `max_scroll_history_lines` terminal setting.
+            let output = terminal.get_content();
+            project
+                .report_task_output(
+                    task_id,
+                    std::mem::take(&mut definitions),
+                    output,
+                    cwd.clone(),
+                    cx,
+                )
+                .detach_and_log_err(cx);
+        })
+        .detach();
+    }
+
+    /// Clears the diagnostics that the task reported.
+    pub(crate) fn clear_task_problems(&self, task_id: TaskId, cx: &mut Context<Project>) {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.update_task_diagnostics(task_id, Vec::new(), cx)
+            })
+            .log_err();
+    }
+
+    /// Reports the problems that the problem matchers find in the output of a finished task as
+    /// the task's diagnostics.
+    pub(crate) fn report_task_output(
+        &self,
+        task_id: TaskId,
+        definitions: Vec<ProblemMatcherDefinition>,
+        output: String,
+        cwd: Option<Arc<Path>>,
+        cx: &mut Context<Project>,
+    ) -> Task<Result<()>> {
+        let lsp_store = self.lsp_store.clone();
+        cx.spawn(async move |_, cx| {
+            let problems = cx
+                .background_spawn(async move {
+                    definitions
+                        .iter()
+                        .flat_map(|definition| definition.find_problems(&output))
+                        .filter_map(|mut problem| {
+                            if problem.path.is_relative() {
+                                problem.path = cwd.as_ref()?.join(&problem.path);
+                            }
+                            // Drop the `.` components, so that the path matches its worktree.
+                            problem.path = problem.path.components().collect();
+                            Some(problem)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            lsp_store.update(cx, |lsp_store, cx| {
+                lsp_store.update_task_diagnostics(task_id, problems, cx)
+            })?
+        })
+    }
+
     pub fn local_terminal_handles(&self) -> &Vec<WeakEntity<terminal::Terminal>> {
         &self.terminals.local_handles
     }
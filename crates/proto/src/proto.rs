@@ -135,6 +135,27 @@ messages!(
     (IncomingCall, Foreground),
     (InlayHints, Background),
     (InlayHintsResponse, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareTypeHierarchy, Background),
+    (GetIncomingCalls, Background),
+    (GetOutgoingCalls, Background),
+    (GetSupertypes, Background),
+    (GetSubtypes, Background),
+    (GetHierarchyItemsResponse, Background),
+    (GetFoldingRanges, Background),
+    (GetFoldingRangesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
+    (GetDocumentColors, Background),
+    (GetDocumentColorsResponse, Background),
+    (GetColorPresentations, Background),
+    (GetColorPresentationsResponse, Background),
     (InstallExtension, Background),
     (InviteChannelMember, Foreground),
     (JoinChannel, Foreground),
@@ -192,6 +213,7 @@ messages!(
     (PrepareRenameResponse, Background),
     (ProjectEntryResponse, Foreground),
     (RefreshInlayHints, Foreground),
+    (RefreshSemanticTokens, Foreground),
     (RefreshLlmToken, Background),
     (RegisterBufferWithLanguageServers, Background),
     (RejoinChannelBuffers, Foreground),
@@ -235,6 +257,10 @@ messages!(
     (ShutdownRemoteServer, Foreground),
     (Stage, Background),
     (StartLanguageServer, Foreground),
+    (Stash, Background),
+    (StashApply, Background),
+    (StashDrop, Background),
+    (StashPop, Background),
     (SubscribeToChannels, Foreground),
     (SyncExtensions, Background),
     (SyncExtensionsResponse, Background),
@@ -280,6 +306,12 @@ messages!(
     (GitCheckoutFiles, Background),
     (GitShow, Background),
     (GitCommitDetails, Background),
+    (GitLog, Background),
+    (GitLogResponse, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitRebase, Background),
+    (GitSequencerAction, Background),
     (SetIndexText, Background),
     (Push, Background),
     (Fetch, Background),
@@ -291,6 +323,18 @@ messages!(
     (AskPassResponse, Background),
     (GitCreateBranch, Background),
     (GitChangeBranch, Background),
+    (GitDeleteBranch, Background),
+    (GitRenameBranch, Background),
+    (GitSetUpstream, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitPushTag, Background),
+    (GitGetWorktrees, Background),
+    (GitWorktreesResponse, Background),
+    (GitCreateWorktree, Background),
+    (GitRemoveWorktree, Background),
     (CheckForPushedCommits, Background),
     (CheckForPushedCommitsResponse, Background),
     (GitDiff, Background),
@@ -300,6 +344,8 @@ messages!(
     (DebugAdapterBinary, Background),
     (RunDebugLocators, Background),
     (DebugRequest, Background),
+    (UpdateDebugWatchExpressions, Background),
+    (SetDebugWatchExpressions, Background),
 );
 
 request_messages!(
@@ -373,6 +419,20 @@ request_messages!(
     (Ping, Ack),
     (PrepareRename, PrepareRenameResponse),
     (RefreshInlayHints, Ack),
+    (RefreshSemanticTokens, Ack),
+    (GetSemanticTokens, GetSemanticTokensResponse),
+    (PrepareCallHierarchy, GetHierarchyItemsResponse),
+    (PrepareTypeHierarchy, GetHierarchyItemsResponse),
+    (GetIncomingCalls, GetHierarchyItemsResponse),
+    (GetOutgoingCalls, GetHierarchyItemsResponse),
+    (GetSupertypes, GetHierarchyItemsResponse),
+    (GetSubtypes, GetHierarchyItemsResponse),
+    (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
+    (GetDocumentColors, GetDocumentColorsResponse),
+    (GetColorPresentations, GetColorPresentationsResponse),
     (RefreshCodeLens, Ack),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
@@ -393,6 +453,10 @@ request_messages!(
     (RespondToContactRequest, Ack),
     (SaveBuffer, BufferSaved),
     (Stage, Ack),
+    (Stash, Ack),
+    (StashPop, Ack),
+    (StashApply, Ack),
+    (StashDrop, Ack),
     (FindSearchCandidates, FindSearchCandidatesResponse),
     (SendChannelMessage, SendChannelMessageResponse),
     (SetChannelMemberRole, Ack),
@@ -441,6 +505,11 @@ request_messages!(
     (InstallExtension, Ack),
     (RegisterBufferWithLanguageServers, Ack),
     (GitShow, GitCommitDetails),
+    (GitLog, GitLogResponse),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitRebase, Ack),
+    (GitSequencerAction, Ack),
     (GitReset, Ack),
     (GitCheckoutFiles, Ack),
     (SetIndexText, Ack),
@@ -451,12 +520,23 @@ request_messages!(
     (AskPassRequest, AskPassResponse),
     (GitCreateBranch, Ack),
     (GitChangeBranch, Ack),
+    (GitDeleteBranch, Ack),
+    (GitRenameBranch, Ack),
+    (GitSetUpstream, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitGetWorktrees, GitWorktreesResponse),
+    (GitCreateWorktree, Ack),
+    (GitRemoveWorktree, Ack),
     (CheckForPushedCommits, CheckForPushedCommitsResponse),
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
     (ToggleBreakpoint, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocators, DebugRequest),
+    (SetDebugWatchExpressions, Ack),
 );
 
 entity_messages!(
@@ -512,6 +592,20 @@ entity_messages!(
     PrepareRename,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
+    GetSemanticTokens,
+    PrepareCallHierarchy,
+    PrepareTypeHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    GetSupertypes,
+    GetSubtypes,
+    GetFoldingRanges,
+    GetSelectionRanges,
+    GetDocumentLinks,
+    ResolveDocumentLink,
+    GetDocumentColors,
+    GetColorPresentations,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
@@ -519,6 +613,10 @@ entity_messages!(
     ResolveInlayHint,
     SaveBuffer,
     Stage,
+    Stash,
+    StashPop,
+    StashApply,
+    StashDrop,
     StartLanguageServer,
     SynchronizeBuffers,
     TaskContextForLocation,
@@ -560,6 +658,11 @@ entity_messages!(
     CancelLanguageServerWork,
     RegisterBufferWithLanguageServers,
     GitShow,
+    GitLog,
+    GitCherryPick,
+    GitRevert,
+    GitRebase,
+    GitSequencerAction,
     GitReset,
     GitCheckoutFiles,
     SetIndexText,
@@ -571,6 +674,16 @@ entity_messages!(
     AskPassRequest,
     GitChangeBranch,
     GitCreateBranch,
+    GitDeleteBranch,
+    GitRenameBranch,
+    GitSetUpstream,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitPushTag,
+    GitGetWorktrees,
+    GitCreateWorktree,
+    GitRemoveWorktree,
     CheckForPushedCommits,
     GitDiff,
     GitInit,
@@ -578,6 +691,8 @@ entity_messages!(
     ToggleBreakpoint,
     RunDebugLocators,
     GetDebugAdapterBinary,
+    UpdateDebugWatchExpressions,
+    SetDebugWatchExpressions,
 );
 
 entity_messages!(
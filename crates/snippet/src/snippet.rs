@@ -1,4 +1,5 @@
 use anyhow::{Context as _, Result, anyhow};
+use regex::{Captures, Regex, RegexBuilder};
 use smallvec::SmallVec;
 use std::{collections::BTreeMap, ops::Range};
 
@@ -12,25 +13,103 @@ pub struct Snippet {
 pub struct TabStop {
     pub ranges: SmallVec<[Range<isize>; 2]>,
     pub choices: Option<Vec<String>>,
+    /// Initially empty ranges that show the tabstop's text after going through a
+    /// transform, such as `${1/(.*)/${1:/upcase}/}`.
+    pub transformed_ranges: Vec<(Range<isize>, Transform)>,
+}
+
+/// A regex replacement applied to the text of a tabstop or a variable.
+#[derive(Clone, Debug)]
+pub struct Transform {
+    regex: Regex,
+    format: Vec<FormatItem>,
+    options: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum FormatItem {
+    Text(String),
+    Group {
+        index: usize,
+        modifier: Option<GroupModifier>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum GroupModifier {
+    Case(CaseChange),
+    /// Inserts `if_set`, or the group itself if `if_set` is `None`, when the group
+    /// matched a non-empty string, and `if_unset` otherwise.
+    Conditional {
+        if_set: Option<String>,
+        if_unset: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CaseChange {
+    Upcase,
+    Downcase,
+    Capitalize,
+    Camelcase,
+    Pascalcase,
+}
+
+struct ParseContext<'a> {
+    text: String,
+    tabstops: BTreeMap<usize, TabStop>,
+    /// The ranges of the variables that could not be resolved, which become tabstops
+    /// after the numbered ones.
+    variable_placeholders: Vec<Range<isize>>,
+    resolve_variable: &'a dyn Fn(&str) -> Option<String>,
 }
 
 impl Snippet {
     pub fn parse(source: &str) -> Result<Self> {
-        let mut text = String::with_capacity(source.len());
-        let mut tabstops = BTreeMap::new();
-        parse_snippet(source, false, &mut text, &mut tabstops)
-            .context("failed to parse snippet")?;
+        Self::parse_with_variables(source, &|_| None)
+    }
+
+    /// Parses a snippet, replacing its variables, such as `$TM_FILENAME`, with the
+    /// values returned by `resolve_variable`. As in VS Code, variables that it
+    /// returns `None` for are replaced by their default or their name, as a tabstop.
+    pub fn parse_with_variables(
+        source: &str,
+        resolve_variable: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        let mut cx = ParseContext {
+            text: String::with_capacity(source.len()),
+            tabstops: BTreeMap::new(),
+            variable_placeholders: Vec::new(),
+            resolve_variable,
+        };
+        parse_snippet(source, false, &mut cx).context("failed to parse snippet")?;
+        let ParseContext {
+            text,
+            mut tabstops,
+            variable_placeholders,
+            ..
+        } = cx;
 
         let len = text.len() as isize;
-        let final_tabstop = tabstops.remove(&0);
-        let mut tabstops = tabstops.into_values().collect::<Vec<_>>();
+        let final_tabstop = tabstops
+            .remove(&0)
+            .filter(|tabstop| !tabstop.ranges.is_empty());
+        // Tabstops that only appear in transforms can't be edited.
+        let mut tabstops = tabstops
+            .into_values()
+            .filter(|tabstop| !tabstop.ranges.is_empty())
+            .collect::<Vec<_>>();
+        tabstops.extend(variable_placeholders.into_iter().map(|range| TabStop {
+            ranges: [range].into_iter().collect(),
+            ..TabStop::default()
+        }));
 
         if let Some(final_tabstop) = final_tabstop {
             tabstops.push(final_tabstop);
         } else {
             let end_tabstop = TabStop {
                 ranges: [len..len].into_iter().collect(),
-                choices: None,
+                ..TabStop::default()
             };
 
             if !tabstops.last().map_or(false, |t| *t == end_tabstop) {
@@ -42,17 +121,131 @@ impl Snippet {
     }
 }
 
-fn parse_snippet<'a>(
-    mut source: &'a str,
-    nested: bool,
-    text: &mut String,
-    tabstops: &mut BTreeMap<usize, TabStop>,
-) -> Result<&'a str> {
+impl Transform {
+    /// Replaces the first match of the transform's regex in the text, or all of
+    /// them with the `g` option, with the transform's format.
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut last_match_end = 0;
+        for captures in self.regex.captures_iter(text) {
+            let Some(matched) = captures.get(0) else {
+                continue;
+            };
+            result.push_str(&text[last_match_end..matched.start()]);
+            for item in &self.format {
+                item.format(&captures, &mut result);
+            }
+            last_match_end = matched.end();
+            if !self.options.contains('g') {
+                break;
+            }
+        }
+        result.push_str(&text[last_match_end..]);
+        result
+    }
+
+    /// Parses the regex, format and options of a transform, which follow its first
+    /// slash and precede its closing brace.
+    fn parse(source: &str) -> Result<(Self, &str)> {
+        let (pattern, source) = parse_transform_regex(source)?;
+        let (format, source) = parse_transform_format(source)?;
+        let options_len = source
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(source.len());
+        let (options, source) = source.split_at(options_len);
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(options.contains('i'))
+            .multi_line(options.contains('m'))
+            .dot_matches_new_line(options.contains('s'))
+            .build()
+            .with_context(|| format!("invalid transform regex {pattern:?}"))?;
+        let transform = Transform {
+            regex,
+            format,
+            options: options.to_string(),
+        };
+        Ok((transform, source))
+    }
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+            && self.format == other.format
+            && self.options == other.options
+    }
+}
+
+impl FormatItem {
+    fn format(&self, captures: &Captures, result: &mut String) {
+        match self {
+            FormatItem::Text(text) => result.push_str(text),
+            FormatItem::Group { index, modifier } => {
+                let group = captures.get(*index).map_or("", |group| group.as_str());
+                match modifier {
+                    None => result.push_str(group),
+                    Some(GroupModifier::Case(case)) => result.push_str(&case.apply(group)),
+                    Some(GroupModifier::Conditional { if_set, if_unset }) => {
+                        if group.is_empty() {
+                            result.push_str(if_unset);
+                        } else {
+                            result.push_str(if_set.as_deref().unwrap_or(group));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl CaseChange {
+    fn apply(self, text: &str) -> String {
+        match self {
+            CaseChange::Upcase => text.to_uppercase(),
+            CaseChange::Downcase => text.to_lowercase(),
+            CaseChange::Capitalize => capitalize(text),
+            CaseChange::Camelcase | CaseChange::Pascalcase => {
+                let words = text
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|word| !word.is_empty());
+                let mut result = String::with_capacity(text.len());
+                for (ix, word) in words.enumerate() {
+                    if ix == 0 && self == CaseChange::Camelcase {
+                        let mut chars = word.chars();
+                        result.extend(chars.next().into_iter().flat_map(char::to_lowercase));
+                        result.push_str(chars.as_str());
+                    } else {
+                        result.push_str(&capitalize(word));
+                    }
+                }
+                result
+            }
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .into_iter()
+        .flat_map(char::to_uppercase)
+        .chain(chars)
+        .collect()
+}
+
+fn parse_snippet<'a>(mut source: &'a str, nested: bool, cx: &mut ParseContext) -> Result<&'a str> {
     loop {
         match source.chars().next() {
             None => return Ok(""),
             Some('$') => {
-                source = parse_tabstop(&source[1..], text, tabstops)?;
+                source = &source[1..];
+                let name = source.strip_prefix('{').unwrap_or(source);
+                source = if name.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic()) {
+                    parse_variable(source, cx)?
+                } else {
+                    parse_tabstop(source, cx)?
+                };
             }
             Some('\\') => {
                 // As specified in the LSP spec (`Grammar` section),
@@ -61,40 +254,36 @@ fn parse_snippet<'a>(
                 source = &source[1..];
                 if let Some(c) = source.chars().next() {
                     if c == '$' || c == '\\' || c == '}' {
-                        text.push(c);
+                        cx.text.push(c);
                         // All escapable characters are 1 byte long:
                         source = &source[1..];
                     } else {
-                        text.push('\\');
+                        cx.text.push('\\');
                     }
                 } else {
-                    text.push('\\');
+                    cx.text.push('\\');
                 }
             }
             Some('}') => {
                 if nested {
                     return Ok(source);
                 } else {
-                    text.push('}');
+                    cx.text.push('}');
                     source = &source[1..];
                 }
             }
             Some(_) => {
                 let chunk_end = source.find(['}', '$', '\\']).unwrap_or(source.len());
                 let (chunk, rest) = source.split_at(chunk_end);
-                text.push_str(chunk);
+                cx.text.push_str(chunk);
                 source = rest;
             }
         }
     }
 }
 
-fn parse_tabstop<'a>(
-    mut source: &'a str,
-    text: &mut String,
-    tabstops: &mut BTreeMap<usize, TabStop>,
-) -> Result<&'a str> {
-    let tabstop_start = text.len();
+fn parse_tabstop<'a>(mut source: &'a str, cx: &mut ParseContext) -> Result<&'a str> {
+    let tabstop_start = cx.text.len();
     let tabstop_index;
     let mut choices = None;
 
@@ -103,12 +292,26 @@ fn parse_tabstop<'a>(
         tabstop_index = index;
         source = rest;
 
+        if let Some(rest) = source.strip_prefix('/') {
+            let (transform, rest) = Transform::parse(rest)?;
+            let Some(rest) = rest.strip_prefix('}') else {
+                return Err(anyhow!("expected a closing brace"));
+            };
+            let start = tabstop_start as isize;
+            cx.tabstops
+                .entry(tabstop_index)
+                .or_default()
+                .transformed_ranges
+                .push((start..start, transform));
+            return Ok(rest);
+        }
+
         if source.starts_with("|") {
-            (source, choices) = parse_choices(&source[1..], text)?;
+            (source, choices) = parse_choices(&source[1..], &mut cx.text)?;
         }
 
         if source.starts_with(':') {
-            source = parse_snippet(&source[1..], true, text, tabstops)?;
+            source = parse_snippet(&source[1..], true, cx)?;
         }
 
         if source.starts_with('}') {
@@ -122,17 +325,223 @@ fn parse_tabstop<'a>(
         source = rest;
     }
 
-    tabstops
-        .entry(tabstop_index)
-        .or_insert_with(|| TabStop {
-            ranges: Default::default(),
-            choices,
-        })
+    let tabstop = cx.tabstops.entry(tabstop_index).or_default();
+    if tabstop.choices.is_none() {
+        tabstop.choices = choices;
+    }
+    tabstop
         .ranges
-        .push(tabstop_start as isize..text.len() as isize);
+        .push(tabstop_start as isize..cx.text.len() as isize);
     Ok(source)
 }
 
+fn parse_variable<'a>(mut source: &'a str, cx: &mut ParseContext) -> Result<&'a str> {
+    let braced = source.starts_with('{');
+    if braced {
+        source = &source[1..];
+    }
+    let name_len = source
+        .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+        .unwrap_or(source.len());
+    let (name, rest) = source.split_at(name_len);
+    source = rest;
+    let value = (cx.resolve_variable)(name);
+    let variable_start = cx.text.len() as isize;
+
+    if !braced {
+        push_variable(name, value, cx);
+        return Ok(source);
+    }
+
+    if let Some(rest) = source.strip_prefix('/') {
+        let (transform, rest) = Transform::parse(rest)?;
+        source = rest;
+        push_variable(name, value.map(|value| transform.apply(&value)), cx);
+    } else if let Some(rest) = source.strip_prefix(':') {
+        match value {
+            Some(value) if !value.is_empty() => {
+                // The default isn't used, but it still needs to be parsed to find its end.
+                let mut default_cx = ParseContext {
+                    text: String::new(),
+                    tabstops: BTreeMap::new(),
+                    variable_placeholders: Vec::new(),
+                    resolve_variable: cx.resolve_variable,
+                };
+                source = parse_snippet(rest, true, &mut default_cx)?;
+                cx.text.push_str(&value);
+            }
+            Some(_) => source = parse_snippet(rest, true, cx)?,
+            None => {
+                source = parse_snippet(rest, true, cx)?;
+                cx.variable_placeholders
+                    .push(variable_start..cx.text.len() as isize);
+            }
+        }
+    } else {
+        push_variable(name, value, cx);
+    }
+
+    if source.starts_with('}') {
+        Ok(&source[1..])
+    } else {
+        Err(anyhow!("expected a closing brace"))
+    }
+}
+
+fn push_variable(name: &str, value: Option<String>, cx: &mut ParseContext) {
+    if let Some(value) = value {
+        cx.text.push_str(&value);
+    } else {
+        let start = cx.text.len() as isize;
+        cx.text.push_str(name);
+        cx.variable_placeholders.push(start..cx.text.len() as isize);
+    }
+}
+
+fn parse_transform_regex(mut source: &str) -> Result<(String, &str)> {
+    let mut pattern = String::new();
+    loop {
+        match source.chars().next() {
+            None => return Err(anyhow!("expected a slash after the transform regex")),
+            Some('/') => return Ok((pattern, &source[1..])),
+            Some('\\') => {
+                source = &source[1..];
+                match source.chars().next() {
+                    Some('/') => {
+                        pattern.push('/');
+                        source = &source[1..];
+                    }
+                    // Other escapes belong to the regex.
+                    Some(c) => {
+                        pattern.push('\\');
+                        pattern.push(c);
+                        source = &source[c.len_utf8()..];
+                    }
+                    None => pattern.push('\\'),
+                }
+            }
+            Some(c) => {
+                pattern.push(c);
+                source = &source[c.len_utf8()..];
+            }
+        }
+    }
+}
+
+fn parse_transform_format(mut source: &str) -> Result<(Vec<FormatItem>, &str)> {
+    let mut items = Vec::new();
+    let mut text = String::new();
+    loop {
+        if let Some(rest) = source.strip_prefix('/') {
+            if !text.is_empty() {
+                items.push(FormatItem::Text(text));
+            }
+            return Ok((items, rest));
+        }
+
+        let Some(rest) = source.strip_prefix('$') else {
+            let (chunk, rest) = parse_format_text(source, &['/', '$'])?;
+            text.push_str(&chunk);
+            source = rest;
+            continue;
+        };
+
+        let (index, modifier);
+        if let Some(rest) = rest.strip_prefix('{') {
+            (index, source) = parse_int(rest)?;
+            if let Some(rest) = source.strip_prefix(':') {
+                let (group_modifier, rest) = parse_group_modifier(rest)?;
+                modifier = Some(group_modifier);
+                source = rest;
+            } else {
+                modifier = None;
+            }
+            source = source
+                .strip_prefix('}')
+                .ok_or_else(|| anyhow!("expected a closing brace"))?;
+        } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            (index, source) = parse_int(rest)?;
+            modifier = None;
+        } else {
+            text.push('$');
+            source = rest;
+            continue;
+        }
+
+        if !text.is_empty() {
+            items.push(FormatItem::Text(std::mem::take(&mut text)));
+        }
+        items.push(FormatItem::Group { index, modifier });
+    }
+}
+
+fn parse_group_modifier(source: &str) -> Result<(GroupModifier, &str)> {
+    if let Some(rest) = source.strip_prefix('/') {
+        let name_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (name, rest) = rest.split_at(name_len);
+        let case = match name {
+            "upcase" => CaseChange::Upcase,
+            "downcase" => CaseChange::Downcase,
+            "capitalize" => CaseChange::Capitalize,
+            "camelcase" => CaseChange::Camelcase,
+            "pascalcase" => CaseChange::Pascalcase,
+            _ => return Err(anyhow!("unknown case change {name:?}")),
+        };
+        Ok((GroupModifier::Case(case), rest))
+    } else if let Some(rest) = source.strip_prefix('+') {
+        let (if_set, rest) = parse_format_text(rest, &['}'])?;
+        let modifier = GroupModifier::Conditional {
+            if_set: Some(if_set),
+            if_unset: String::new(),
+        };
+        Ok((modifier, rest))
+    } else if let Some(rest) = source.strip_prefix('?') {
+        let (if_set, rest) = parse_format_text(rest, &[':'])?;
+        let (if_unset, rest) = parse_format_text(&rest[1..], &['}'])?;
+        let modifier = GroupModifier::Conditional {
+            if_set: Some(if_set),
+            if_unset,
+        };
+        Ok((modifier, rest))
+    } else {
+        let source = source.strip_prefix('-').unwrap_or(source);
+        let (if_unset, rest) = parse_format_text(source, &['}'])?;
+        let modifier = GroupModifier::Conditional {
+            if_set: None,
+            if_unset,
+        };
+        Ok((modifier, rest))
+    }
+}
+
+/// Parses text in a transform's format up to one of the given characters, which
+/// can be escaped with a backslash.
+fn parse_format_text<'a>(mut source: &'a str, terminators: &[char]) -> Result<(String, &'a str)> {
+    let mut text = String::new();
+    loop {
+        match source.chars().next() {
+            None => return Err(anyhow!("expected one of {terminators:?} in transform")),
+            Some(c) if terminators.contains(&c) => return Ok((text, source)),
+            Some('\\') => {
+                source = &source[1..];
+                match source.chars().next() {
+                    Some(c) if c == '\\' || c == '/' || c == '$' || c == '}' || c == ':' => {
+                        text.push(c);
+                        source = &source[1..];
+                    }
+                    _ => text.push('\\'),
+                }
+            }
+            Some(c) => {
+                text.push(c);
+                source = &source[c.len_utf8()..];
+            }
+        }
+    }
+}
+
 fn parse_int(source: &str) -> Result<(usize, &str)> {
     let len = source
         .find(|c: char| !c.is_ascii_digit())
@@ -327,6 +736,89 @@ mod tests {
         assert_eq!(tabstops(&snippet), &[vec![4..4], vec![7..7]]);
     }
 
+    #[test]
+    fn test_snippet_with_variables() {
+        let resolve_variable = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            "TM_SELECTED_TEXT" => Some(String::new()),
+            _ => None,
+        };
+
+        let snippet =
+            Snippet::parse_with_variables("// $TM_FILENAME: ${1:todo}", &resolve_variable).unwrap();
+        assert_eq!(snippet.text, "// main.rs: todo");
+        assert_eq!(tabstops(&snippet), &[vec![12..16], vec![16..16]]);
+
+        // Empty variables are replaced by their default, which can contain tabstops.
+        let snippet = Snippet::parse_with_variables(
+            "${TM_SELECTED_TEXT:${1:value}}-${TM_FILENAME:${2:unused}}",
+            &resolve_variable,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "value-main.rs");
+        assert_eq!(tabstops(&snippet), &[vec![0..5], vec![13..13]]);
+
+        // Unknown variables become tabstops after the numbered ones.
+        let snippet =
+            Snippet::parse_with_variables("$UNKNOWN ${OTHER:default} $1", &resolve_variable)
+                .unwrap();
+        assert_eq!(snippet.text, "UNKNOWN default ");
+        assert_eq!(
+            tabstops(&snippet),
+            &[vec![16..16], vec![0..7], vec![8..15], vec![16..16]]
+        );
+
+        let snippet = Snippet::parse_with_variables(
+            "${TM_FILENAME/(.*)\\.rs$/${1:/upcase}/}",
+            &resolve_variable,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "MAIN");
+    }
+
+    #[test]
+    fn test_snippet_with_transforms() {
+        let snippet = Snippet::parse("${1:name} ${1/(.*)/${1:/upcase}/}$0").unwrap();
+        assert_eq!(snippet.text, "name ");
+        assert_eq!(tabstops(&snippet), &[vec![0..4], vec![5..5]]);
+        let (range, transform) = &snippet.tabstops[0].transformed_ranges[0];
+        assert_eq!(*range, 5..5);
+        assert_eq!(transform.apply("name"), "NAME");
+
+        // Tabstops that only appear in transforms are skipped.
+        let snippet = Snippet::parse("a${2/x/y/}b$1").unwrap();
+        assert_eq!(snippet.text, "ab");
+        assert_eq!(tabstops(&snippet), &[vec![2..2]]);
+
+        assert!(Snippet::parse("${1/(/x/}").is_err());
+        assert!(Snippet::parse("${1/a/b}").is_err());
+    }
+
+    #[test]
+    fn test_transform_format() {
+        let apply = |transform: &str, text: &str| {
+            let snippet = Snippet::parse(&format!("$1${{1/{transform}}}")).unwrap();
+            snippet.tabstops[0].transformed_ranges[0].1.apply(text)
+        };
+
+        assert_eq!(apply("o/0/", "foo"), "f0o");
+        assert_eq!(apply("o/0/g", "foo"), "f00");
+        assert_eq!(apply("O/0/gi", "foo"), "f00");
+        assert_eq!(apply("\\//-/g", "a/b/c"), "a-b-c");
+        assert_eq!(apply("(\\w+) (\\w+)/$2 ${1}/", "one two"), "two one");
+        assert_eq!(apply("(.*)/${1:/upcase}/", "foo"), "FOO");
+        assert_eq!(apply("(.*)/${1:/downcase}/", "FOO"), "foo");
+        assert_eq!(apply("(.*)/${1:/capitalize}/", "foo bar"), "Foo bar");
+        assert_eq!(apply("(.*)/${1:/camelcase}/", "foo-bar_baz"), "fooBarBaz");
+        assert_eq!(apply("(.*)/${1:/pascalcase}/", "foo-bar_baz"), "FooBarBaz");
+        assert_eq!(apply("(a)?b/${1:+yes}/", "ab"), "yes");
+        assert_eq!(apply("(a)?b/${1:+yes}/", "b"), "");
+        assert_eq!(apply("(a)?b/${1:?yes:no}/", "b"), "no");
+        assert_eq!(apply("(a)?b/${1:-none}/", "ab"), "a");
+        assert_eq!(apply("(a)?b/${1:none}/", "b"), "none");
+        assert_eq!(apply("x/\\$1/", "x"), "$1");
+    }
+
     fn tabstops(snippet: &Snippet) -> Vec<Vec<Range<isize>>> {
         snippet.tabstops.iter().map(|t| t.ranges.to_vec()).collect()
     }
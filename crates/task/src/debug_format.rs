@@ -4,8 +4,11 @@ use gpui::SharedString;
 use schemars::{JsonSchema, r#gen::SchemaSettings};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::str::FromStr as _;
 use std::{net::Ipv4Addr, path::Path};
 
+use crate::{TaskContext, TaskInput, TaskVariables, VariableName};
+
 /// Represents the host information of the debug adapter
 #[derive(Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
 pub struct TcpArgumentsTemplate {
@@ -173,6 +176,9 @@ pub struct DebugScenario {
     /// Whether to tell the debug adapter to stop on entry
     #[serde(default)]
     pub stop_on_entry: Option<bool>,
+    /// Values to ask for before starting the debug session, available in its launch request as `$ZED_INPUT_<id>` variables.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<TaskInput>,
 }
 
 impl DebugScenario {
@@ -183,6 +189,58 @@ impl DebugScenario {
             None
         }
     }
+
+    /// Inputs of the scenario that have no values in the task context,
+    /// which have to be asked for before starting the debug session.
+    pub fn unresolved_inputs(&self, task_context: &TaskContext) -> Vec<&TaskInput> {
+        self.inputs
+            .iter()
+            .filter(|input| {
+                task_context
+                    .task_variables
+                    .get(&input.variable_name())
+                    .is_none()
+            })
+            .collect()
+    }
+
+    /// Replaces the input variables of the launch request with the values of the inputs in the task context.
+    /// Other variables are left as is.
+    pub fn resolve_inputs(&mut self, task_context: &TaskContext) {
+        let Some(DebugRequest::Launch(launch)) = &mut self.request else {
+            return;
+        };
+        let variables = &task_context.task_variables;
+        launch.program = substitute_input_variables(&launch.program, variables);
+        launch.cwd = launch
+            .cwd
+            .as_ref()
+            .map(|cwd| substitute_input_variables(&cwd.to_string_lossy(), variables).into());
+        for arg in &mut launch.args {
+            *arg = substitute_input_variables(arg, variables);
+        }
+        for value in launch.env.values_mut() {
+            *value = substitute_input_variables(value, variables);
+        }
+    }
+}
+
+fn substitute_input_variables(template: &str, task_variables: &TaskVariables) -> String {
+    shellexpand::env_with_context_no_errors(template, |var: &str| {
+        // Colons denote a default value in case the variable is not set. We want to preserve that default, as otherwise shellexpand will substitute it for us.
+        let colon_position = var.find(':').unwrap_or(var.len());
+        let (variable_name, default) = var.split_at(colon_position);
+        if let Ok(variable @ VariableName::Input(_)) = VariableName::from_str(variable_name) {
+            if let Some(value) = task_variables.get(&variable) {
+                return Some(value.to_owned());
+            }
+        }
+        if !default.is_empty() {
+            return Some(format!("${{{var}}}"));
+        }
+        None
+    })
+    .into_owned()
 }
 
 /// A group of Debug Tasks defined in a JSON file.
@@ -1,6 +1,7 @@
 //! Baseline interface of Tasks in Zed: all tasks in Zed are intended to use those for implementing their own logic.
 
 mod debug_format;
+mod problem_matcher;
 mod serde_helpers;
 pub mod static_source;
 mod task_template;
@@ -18,8 +19,12 @@ use std::str::FromStr;
 pub use debug_format::{
     AttachRequest, DebugRequest, DebugScenario, DebugTaskFile, LaunchRequest, TcpArgumentsTemplate,
 };
+pub use problem_matcher::{
+    Problem, ProblemMatcher, ProblemMatcherDefinition, ProblemPattern, ProblemSeverity,
+};
 pub use task_template::{
-    DebugArgsRequest, HideStrategy, RevealStrategy, TaskModal, TaskTemplate, TaskTemplates,
+    DebugArgsRequest, DependsOrder, HideStrategy, RevealStrategy, TaskInput, TaskInputKind,
+    TaskModal, TaskTemplate, TaskTemplates,
 };
 pub use vscode_debug_format::VsCodeDebugTaskFile;
 pub use vscode_format::VsCodeTaskFile;
@@ -68,6 +73,10 @@ pub struct SpawnInTerminal {
     pub show_command: bool,
     /// Whether to show the rerun button in the terminal tab.
     pub show_rerun: bool,
+    /// Problem matchers to turn the task's output into diagnostics with, once it finishes.
+    pub problem_matchers: Vec<ProblemMatcher>,
+    /// Globs of the files, relative to the task's working directory, that make the task rerun when they change.
+    pub rerun_on: Vec<String>,
 }
 
 impl SpawnInTerminal {
@@ -111,6 +120,8 @@ pub struct ResolvedTask {
     pub id: TaskId,
     /// A template the task got resolved from.
     original_task: TaskTemplate,
+    /// A context the task got resolved with, used to resolve the tasks it depends on.
+    task_context: TaskContext,
     /// Full, unshortened label of the task after all resolutions are made.
     pub resolved_label: String,
     /// Variables that were substituted during the task template resolution.
@@ -126,6 +137,11 @@ impl ResolvedTask {
         &self.original_task
     }
 
+    /// A context the task got resolved with.
+    pub fn task_context(&self) -> &TaskContext {
+        &self.task_context
+    }
+
     /// Variables that were substituted during the task template resolution.
     pub fn substituted_variables(&self) -> &HashSet<VariableName> {
         &self.substituted_variables
@@ -135,6 +151,56 @@ impl ResolvedTask {
     pub fn display_label(&self) -> &str {
         self.resolved.label.as_str()
     }
+
+    /// Inputs of the task template that have no values in the task's context,
+    /// which have to be asked for before spawning the task.
+    pub fn unresolved_inputs(&self) -> Vec<&TaskInput> {
+        self.original_task
+            .inputs
+            .iter()
+            .filter(|input| {
+                self.task_context
+                    .task_variables
+                    .get(&input.variable_name())
+                    .is_none()
+            })
+            .collect()
+    }
+
+    /// Resolves the task template again, with the values of its inputs by their ids.
+    /// The task keeps its id, along with the amendments made to its [`SpawnInTerminal`]
+    /// that do not depend on the task variables.
+    pub fn with_input_values(
+        &self,
+        input_values: impl IntoIterator<Item = (String, String)>,
+    ) -> Option<Self> {
+        let mut task_context = self.task_context.clone();
+        for (id, value) in input_values {
+            task_context
+                .task_variables
+                .insert(VariableName::Input(Cow::Owned(id)), value);
+        }
+        let resolved_task = self.original_task.resolve_task("", &task_context)?;
+        let resolved = resolved_task.resolved;
+        Some(Self {
+            id: self.id.clone(),
+            original_task: resolved_task.original_task,
+            task_context,
+            resolved_label: resolved_task.resolved_label,
+            substituted_variables: resolved_task.substituted_variables,
+            resolved: SpawnInTerminal {
+                id: self.id.clone(),
+                full_label: resolved.full_label,
+                label: resolved.label,
+                command: resolved.command,
+                args: resolved.args,
+                command_label: resolved.command_label,
+                cwd: resolved.cwd,
+                env: resolved.env,
+                ..self.resolved.clone()
+            },
+        })
+    }
 }
 
 /// Variables, available for use in [`TaskContext`] when a Zed's [`TaskTemplate`] gets resolved into a [`ResolvedTask`].
@@ -168,6 +234,9 @@ pub enum VariableName {
     /// Custom variable, provided by the plugin or other external source.
     /// Will be printed with `CUSTOM_` prefix to avoid potential conflicts with other variables.
     Custom(Cow<'static, str>),
+    /// A value of the task's [`TaskInput`] with the given id, asked for before the task is spawned.
+    /// Will be printed with `INPUT_` prefix.
+    Input(Cow<'static, str>),
 }
 
 impl VariableName {
@@ -203,6 +272,10 @@ impl FromStr for VariableName {
                     without_prefix.strip_prefix(ZED_CUSTOM_VARIABLE_NAME_PREFIX)
                 {
                     Self::Custom(Cow::Owned(custom_name.to_owned()))
+                } else if let Some(input_id) =
+                    without_prefix.strip_prefix(ZED_INPUT_VARIABLE_NAME_PREFIX)
+                {
+                    Self::Input(Cow::Owned(input_id.to_owned()))
                 } else {
                     return Err(());
                 }
@@ -215,6 +288,7 @@ impl FromStr for VariableName {
 /// A prefix that all [`VariableName`] variants are prefixed with when used in environment variables and similar template contexts.
 pub const ZED_VARIABLE_NAME_PREFIX: &str = "ZED_";
 const ZED_CUSTOM_VARIABLE_NAME_PREFIX: &str = "CUSTOM_";
+const ZED_INPUT_VARIABLE_NAME_PREFIX: &str = "INPUT_";
 
 impl std::fmt::Display for VariableName {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -234,6 +308,10 @@ impl std::fmt::Display for VariableName {
                 f,
                 "{ZED_VARIABLE_NAME_PREFIX}{ZED_CUSTOM_VARIABLE_NAME_PREFIX}{s}"
             ),
+            Self::Input(id) => write!(
+                f,
+                "{ZED_VARIABLE_NAME_PREFIX}{ZED_INPUT_VARIABLE_NAME_PREFIX}{id}"
+            ),
         }
     }
 }
@@ -514,6 +592,13 @@ impl EnvVariableReplacer {
                 let variable_name = &right[1..];
                 return Some(format!("${{{variable_name}}}"));
             }
+            if left == "input" && !right.is_empty() {
+                let input_id = right[1..].to_owned();
+                return Some(format!(
+                    "${{{}}}",
+                    VariableName::Input(Cow::Owned(input_id))
+                ));
+            }
             let (variable_name, default) = (left, right);
             let append_previous_default = |ret: &mut String| {
                 if !default.is_empty() {
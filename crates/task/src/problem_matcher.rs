@@ -0,0 +1,449 @@
+use std::path::PathBuf;
+
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use util::ResultExt as _;
+
+/// A way to find problems, such as compiler errors, in the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcher {
+    /// The name of a built-in problem matcher: `$rustc`, `$tsc`, `$gcc` or `$pytest`.
+    Named(String),
+    /// A custom problem matcher.
+    Custom(ProblemMatcherDefinition),
+}
+
+/// Regular expressions that match problems in the output of a task, line by line.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcherDefinition {
+    /// The name of the tool reporting the problems, shown as the source of their diagnostics.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// The severity of the problems whose severity isn't matched by the patterns.
+    #[serde(default)]
+    pub severity: Option<ProblemSeverity>,
+    /// Patterns that match consecutive lines of the output, which all together describe a problem.
+    pub patterns: Vec<ProblemPattern>,
+}
+
+/// A regular expression that matches a line of a task's output, along with the indices of
+/// its capture groups that contain parts of the problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    /// The regular expression to match a line with.
+    pub regex: String,
+    /// The group that contains the path of the file, absolute or relative to the task's working directory.
+    #[serde(default)]
+    pub file: Option<usize>,
+    /// The group that contains the 1-based line of the problem.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// The group that contains the 1-based column of the problem.
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// The group that contains the 1-based line where the problem ends.
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    /// The group that contains the 1-based column where the problem ends.
+    #[serde(default)]
+    pub end_column: Option<usize>,
+    /// The group that contains the severity of the problem, such as `error` or `warning`.
+    #[serde(default)]
+    pub severity: Option<usize>,
+    /// The group that contains the code of the problem.
+    #[serde(default)]
+    pub code: Option<usize>,
+    /// The group that contains the message of the problem.
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// Whether the last pattern can match several lines in a row, each of them being a problem.
+    #[serde(default, rename = "loop")]
+    pub repeat: bool,
+}
+
+/// How severe a problem found in a task's output is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+/// A problem found in a task's output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// The path of the file with the problem, as it was written in the output.
+    pub path: PathBuf,
+    /// The 0-based row of the problem.
+    pub row: u32,
+    /// The 0-based column of the problem, if it was written in the output.
+    pub column: Option<u32>,
+    /// The 0-based row and column where the problem ends, if they were written in the output.
+    pub end: Option<(u32, Option<u32>)>,
+    pub severity: ProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+    pub source: Option<String>,
+}
+
+impl ProblemMatcher {
+    /// Returns the definition of the problem matcher, looking up the built-in matchers by name.
+    pub fn definition(&self) -> Option<ProblemMatcherDefinition> {
+        match self {
+            ProblemMatcher::Named(name) => builtin_problem_matcher(name),
+            ProblemMatcher::Custom(definition) => Some(definition.clone()),
+        }
+    }
+}
+
+impl ProblemMatcherDefinition {
+    /// Finds the problems in the output of a task.
+    pub fn find_problems(&self, output: &str) -> Vec<Problem> {
+        let Some(regexes) = self
+            .patterns
+            .iter()
+            .map(|pattern| Regex::new(&pattern.regex).log_err())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Vec::new();
+        };
+        let Some(((last_pattern, patterns), (last_regex, regexes))) =
+            self.patterns.split_last().zip(regexes.split_last())
+        else {
+            return Vec::new();
+        };
+
+        let lines = output.lines().collect::<Vec<_>>();
+        let mut problems = Vec::new();
+        let mut row = 0;
+        'lines: while row < lines.len() {
+            let mut fields = ProblemFields::default();
+            let mut offset = 0;
+            for (pattern, regex) in patterns.iter().zip(regexes) {
+                let Some(captures) = regex.captures(lines[row + offset]) else {
+                    row += 1;
+                    continue 'lines;
+                };
+                fields.extend(pattern, &captures);
+                offset += 1;
+                if row + offset == lines.len() {
+                    break 'lines;
+                }
+            }
+
+            let problem_count = problems.len();
+            while let Some(captures) = lines
+                .get(row + offset)
+                .and_then(|line| last_regex.captures(line))
+            {
+                let mut fields = fields.clone();
+                fields.extend(last_pattern, &captures);
+                problems.extend(fields.into_problem(self));
+                offset += 1;
+                if !last_pattern.repeat {
+                    break;
+                }
+            }
+            row += if problems.len() == problem_count {
+                1
+            } else {
+                offset
+            };
+        }
+        problems
+    }
+}
+
+/// The parts of a problem matched so far by the patterns.
+#[derive(Clone, Default)]
+struct ProblemFields {
+    file: Option<String>,
+    line: Option<String>,
+    column: Option<String>,
+    end_line: Option<String>,
+    end_column: Option<String>,
+    severity: Option<String>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl ProblemFields {
+    fn extend(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let group = |index: Option<usize>| {
+            let group = captures.get(index?)?.as_str().trim();
+            (!group.is_empty()).then(|| group.to_string())
+        };
+        for (field, index) in [
+            (&mut self.file, pattern.file),
+            (&mut self.line, pattern.line),
+            (&mut self.column, pattern.column),
+            (&mut self.end_line, pattern.end_line),
+            (&mut self.end_column, pattern.end_column),
+            (&mut self.severity, pattern.severity),
+            (&mut self.code, pattern.code),
+            (&mut self.message, pattern.message),
+        ] {
+            if let Some(group) = group(index) {
+                *field = Some(group);
+            }
+        }
+    }
+
+    fn into_problem(self, definition: &ProblemMatcherDefinition) -> Option<Problem> {
+        let zero_based = |number: Option<String>| {
+            let number = number?.parse::<u32>().ok()?;
+            Some(number.saturating_sub(1))
+        };
+        let row = zero_based(self.line)?;
+        let column = zero_based(self.column);
+        let end_column = zero_based(self.end_column);
+        // Without an end line, a problem with an end column ends on its first line.
+        let end = match zero_based(self.end_line) {
+            Some(end_row) => Some((end_row, end_column)),
+            None => end_column.map(|end_column| (row, Some(end_column))),
+        };
+        let severity = self
+            .severity
+            .as_deref()
+            .and_then(parse_severity)
+            .or(definition.severity)
+            .unwrap_or_default();
+        Some(Problem {
+            path: PathBuf::from(self.file?),
+            row,
+            column,
+            end,
+            severity,
+            code: self.code,
+            message: self.message?,
+            source: definition.source.clone(),
+        })
+    }
+}
+
+fn parse_severity(severity: &str) -> Option<ProblemSeverity> {
+    let severity = severity.to_lowercase();
+    if severity.starts_with("err") || severity.starts_with("fatal") {
+        Some(ProblemSeverity::Error)
+    } else if severity.starts_with("warn") {
+        Some(ProblemSeverity::Warning)
+    } else if severity.starts_with("info") || severity.starts_with("note") {
+        Some(ProblemSeverity::Info)
+    } else if severity.starts_with("hint") || severity.starts_with("help") {
+        Some(ProblemSeverity::Hint)
+    } else {
+        None
+    }
+}
+
+fn builtin_problem_matcher(name: &str) -> Option<ProblemMatcherDefinition> {
+    let definition = match name {
+        "$rustc" => ProblemMatcherDefinition {
+            source: Some("rustc".to_string()),
+            severity: None,
+            patterns: vec![
+                ProblemPattern {
+                    regex: r"^(warning|error)(?:\[(\w+)\])?: (.*)$".to_string(),
+                    severity: Some(1),
+                    code: Some(2),
+                    message: Some(3),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regex: r"^\s+--> (.*):(\d+):(\d+)$".to_string(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    ..ProblemPattern::default()
+                },
+            ],
+        },
+        "$tsc" | "$tsc-watch" => ProblemMatcherDefinition {
+            source: Some("tsc".to_string()),
+            severity: None,
+            patterns: vec![ProblemPattern {
+                regex: r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+TS(\d+)\s*:\s*(.*)$".to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                code: Some(5),
+                message: Some(6),
+                ..ProblemPattern::default()
+            }],
+        },
+        "$gcc" => ProblemMatcherDefinition {
+            source: Some("gcc".to_string()),
+            severity: None,
+            patterns: vec![ProblemPattern {
+                regex: r"^(.*?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error|note):\s+(.*)$"
+                    .to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                ..ProblemPattern::default()
+            }],
+        },
+        "$pytest" => ProblemMatcherDefinition {
+            source: Some("pytest".to_string()),
+            severity: Some(ProblemSeverity::Error),
+            patterns: vec![ProblemPattern {
+                regex: r"^(.+\.py):(\d+): (.+)$".to_string(),
+                file: Some(1),
+                line: Some(2),
+                message: Some(3),
+                ..ProblemPattern::default()
+            }],
+        },
+        _ => {
+            log::error!("Unknown problem matcher {name:?}");
+            return None;
+        }
+    };
+    Some(definition)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn find_problems(matcher: &str, output: &str) -> Vec<Problem> {
+        ProblemMatcher::Named(matcher.to_string())
+            .definition()
+            .unwrap()
+            .find_problems(output)
+    }
+
+    #[test]
+    fn test_rustc_problems() {
+        let output = indoc! {"
+               Compiling demo v0.1.0 (/tmp/demo)
+            warning: unused variable: `x`
+             --> src/main.rs:2:9
+              |
+            2 |     let x = 1;
+              |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+
+            error[E0308]: mismatched types
+             --> src/lib.rs:10:5
+            error: aborting due to 1 previous error
+        "};
+        assert_eq!(
+            find_problems("$rustc", output),
+            vec![
+                Problem {
+                    path: PathBuf::from("src/main.rs"),
+                    row: 1,
+                    column: Some(8),
+                    end: None,
+                    severity: ProblemSeverity::Warning,
+                    code: None,
+                    message: "unused variable: `x`".to_string(),
+                    source: Some("rustc".to_string()),
+                },
+                Problem {
+                    path: PathBuf::from("src/lib.rs"),
+                    row: 9,
+                    column: Some(4),
+                    end: None,
+                    severity: ProblemSeverity::Error,
+                    code: Some("E0308".to_string()),
+                    message: "mismatched types".to_string(),
+                    source: Some("rustc".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_line_problems() {
+        let problems = find_problems(
+            "$tsc",
+            "src/index.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].path, PathBuf::from("src/index.ts"));
+        assert_eq!((problems[0].row, problems[0].column), (2, Some(6)));
+        assert_eq!(problems[0].code.as_deref(), Some("2322"));
+
+        let problems = find_problems(
+            "$gcc",
+            indoc! {"
+                main.c:4:5: warning: implicit declaration of function 'foo'
+                main.c:9:1: error: expected ';' before '}' token
+                collect2: error: ld returned 1 exit status
+            "},
+        );
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (problem.row, problem.severity))
+                .collect::<Vec<_>>(),
+            vec![(3, ProblemSeverity::Warning), (8, ProblemSeverity::Error)]
+        );
+    }
+
+    #[test]
+    fn test_looping_patterns() {
+        let definition = ProblemMatcherDefinition {
+            source: Some("lint".to_string()),
+            severity: Some(ProblemSeverity::Warning),
+            patterns: vec![
+                ProblemPattern {
+                    regex: r"^(\S+\.js)$".to_string(),
+                    file: Some(1),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regex: r"^\s+(\d+):(\d+)-(\d+)\s+(.*)$".to_string(),
+                    line: Some(1),
+                    column: Some(2),
+                    end_column: Some(3),
+                    message: Some(4),
+                    repeat: true,
+                    ..ProblemPattern::default()
+                },
+            ],
+        };
+        let problems = definition.find_problems(indoc! {"
+            a.js
+              1:2-5  first
+              3:1-2  second
+            b.js
+              7:7-8  third
+            done
+        "});
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.path.to_string_lossy().into_owned(),
+                    problem.row,
+                    problem.end,
+                    problem.message.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a.js".to_string(), 0, Some((0, Some(4))), "first"),
+                ("a.js".to_string(), 2, Some((2, Some(1))), "second"),
+                ("b.js".to_string(), 6, Some((6, Some(7))), "third"),
+            ]
+        );
+        assert!(
+            problems
+                .iter()
+                .all(|problem| problem.severity == ProblemSeverity::Warning)
+        );
+    }
+}
@@ -3,13 +3,13 @@ use collections::{HashMap, HashSet};
 use schemars::{JsonSchema, r#gen::SchemaSettings};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::{borrow::Cow, path::PathBuf, str::FromStr as _};
 use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX,
+    AttachRequest, ProblemMatcher, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext,
+    TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX,
     serde_helpers::{non_empty_string_vec, non_empty_string_vec_json_schema},
 };
 
@@ -25,6 +25,7 @@ pub struct TaskTemplate {
     /// Human readable name of the task to display in the UI.
     pub label: String,
     /// Executable command to spawn.
+    #[serde(default)]
     pub command: String,
     /// Arguments to the command.
     #[serde(default)]
@@ -72,6 +73,27 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks to run before this one, which only runs if all of them succeed.
+    /// A task without a command can be used to run a group of tasks.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// In which order to run the tasks from `depends_on`:
+    /// * `parallel` — start all of them at once (default)
+    /// * `sequence` — start each of them after the previous one succeeds
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// Problem matchers that turn the task's output into diagnostics, once the task finishes:
+    /// either names of the built-in ones (`$rustc`, `$tsc`, `$gcc`, `$pytest`),
+    /// or custom definitions with regular expressions to match the output lines with.
+    #[serde(default)]
+    pub problem_matchers: Vec<ProblemMatcher>,
+    /// Values to ask for before spawning the task, available in its fields as `$ZED_INPUT_<id>` variables.
+    #[serde(default)]
+    pub inputs: Vec<TaskInput>,
+    /// Globs of the files that make the task rerun when they change, relative to the task's working directory.
+    /// A rerun cancels the run that is still in progress, and happens only while the task's terminal is open.
+    #[serde(default)]
+    pub rerun_on: Vec<String>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -118,6 +140,51 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// In which order to run the tasks that a task depends on.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Start all of the tasks at once.
+    #[default]
+    Parallel,
+    /// Start each task after the previous one succeeds.
+    Sequence,
+}
+
+/// A value to ask for before spawning a task, available in the task as the `$ZED_INPUT_<id>` variable.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TaskInput {
+    /// The identifier of the input, used in the name of its variable.
+    pub id: String,
+    /// What the input is for, shown when asking for its value.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The value to suggest, until another one is given.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// How to ask for the value.
+    #[serde(flatten)]
+    pub kind: TaskInputKind,
+}
+
+/// How to ask for the value of a [`TaskInput`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskInputKind {
+    /// Let the user type any text.
+    PromptString,
+    /// Let the user pick one of the options.
+    PickString { options: Vec<String> },
+}
+
+impl TaskInput {
+    /// The variable that holds the value of the input.
+    pub fn variable_name(&self) -> VariableName {
+        VariableName::Input(Cow::Owned(self.id.clone()))
+    }
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -142,7 +209,9 @@ impl TaskTemplate {
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty()
+            || (self.command.trim().is_empty() && self.depends_on.is_empty())
+        {
             return None;
         }
 
@@ -250,6 +319,7 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            task_context: cx.clone(),
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
                 id,
@@ -276,6 +346,8 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers: self.problem_matchers.clone(),
+                rerun_on: self.rerun_on.clone(),
             },
         })
     }
@@ -323,7 +395,13 @@ fn substitute_all_template_variables_in_str<A: AsRef<str>>(
                 name.push_str(default);
             }
             return Ok(Some(name));
-        } else if variable_name.starts_with(ZED_VARIABLE_NAME_PREFIX) {
+        } else if variable_name.starts_with(ZED_VARIABLE_NAME_PREFIX)
+            // Inputs get their values right before the task is spawned, so they are left as is until then.
+            && !matches!(
+                VariableName::from_str(variable_name),
+                Ok(VariableName::Input(_))
+            )
+        {
             bail!("Unknown variable name: {variable_name}");
         }
         // This is an unknown variable.
@@ -426,6 +504,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_templates_with_dependencies_and_blank_command() {
+        let group_task = TaskTemplate {
+            label: "build all".to_string(),
+            depends_on: vec!["build client".to_string(), "build server".to_string()],
+            depends_order: DependsOrder::Sequence,
+            ..TaskTemplate::default()
+        };
+        let resolved_task = group_task
+            .resolve_task(TEST_ID_BASE, &TaskContext::default())
+            .expect("should resolve a task that only runs its dependencies");
+        assert_eq!(resolved_task.original_task(), &group_task);
+        assert_eq!(resolved_task.resolved.command, "");
+
+        let deserialized: TaskTemplate = serde_json_lenient::from_str(
+            r#"{"label": "build all", "depends_on": ["build client", "build server"], "depends_order": "sequence"}"#,
+        )
+        .unwrap();
+        assert_eq!(deserialized.command, "");
+        assert_eq!(deserialized.depends_on, group_task.depends_on);
+        assert_eq!(deserialized.depends_order, DependsOrder::Sequence);
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -691,6 +792,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolving_inputs() {
+        let task = TaskTemplate {
+            label: "deploy to $ZED_INPUT_environment".into(),
+            command: "deploy".into(),
+            args: vec!["--env".into(), "${ZED_INPUT_environment}".into()],
+            inputs: vec![TaskInput {
+                id: "environment".into(),
+                description: Some("Where to deploy".into()),
+                default: None,
+                kind: TaskInputKind::PickString {
+                    options: vec!["staging".into(), "production".into()],
+                },
+            }],
+            ..TaskTemplate::default()
+        };
+        let resolved_task = task
+            .resolve_task(TEST_ID_BASE, &TaskContext::default())
+            .expect("inputs without values should not prevent the resolution");
+        assert_eq!(resolved_task.unresolved_inputs(), vec![&task.inputs[0]]);
+        assert_eq!(
+            resolved_task.resolved.args,
+            vec!["--env", "${ZED_INPUT_environment}"]
+        );
+
+        let mut resolved_task = resolved_task;
+        resolved_task.resolved.use_new_terminal = true;
+        let with_inputs = resolved_task
+            .with_input_values([("environment".to_string(), "staging".to_string())])
+            .unwrap();
+        assert!(with_inputs.unresolved_inputs().is_empty());
+        assert_eq!(with_inputs.id, resolved_task.id);
+        assert_eq!(with_inputs.resolved_label, "deploy to staging");
+        assert_eq!(with_inputs.resolved.args, vec!["--env", "staging"]);
+        assert!(with_inputs.resolved.use_new_terminal);
+        assert_substituted_variables(
+            &with_inputs,
+            vec![VariableName::Input(Cow::Borrowed("environment"))],
+        );
+
+        let deserialized: TaskTemplate = serde_json_lenient::from_str(
+            r#"{
+                "label": "deploy",
+                "command": "deploy $ZED_INPUT_environment",
+                "inputs": [{ "id": "environment", "type": "pick_string", "options": ["staging", "production"] }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(deserialized.inputs[0].kind, task.inputs[0].kind);
+    }
+
     #[test]
     fn test_symbol_dependent_tasks() {
         let task_with_all_properties = TaskTemplate {
@@ -8,7 +8,8 @@ use util::ResultExt as _;
 
 use crate::{
     AttachRequest, DebugRequest, DebugScenario, DebugTaskFile, EnvVariableReplacer, LaunchRequest,
-    TcpArgumentsTemplate, VariableName,
+    TaskInput, TcpArgumentsTemplate, VariableName,
+    vscode_format::{VsCodeTaskInput, referenced_inputs},
 };
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
@@ -44,10 +45,14 @@ struct VsCodeDebugTaskDefinition {
 }
 
 impl VsCodeDebugTaskDefinition {
-    fn try_to_zed(self, replacer: &EnvVariableReplacer) -> anyhow::Result<DebugScenario> {
+    fn try_to_zed(
+        self,
+        replacer: &EnvVariableReplacer,
+        inputs: &[TaskInput],
+    ) -> anyhow::Result<DebugScenario> {
         let label = replacer.replace(&self.name).into();
         // TODO based on grep.app results it seems that vscode supports whitespace-splitting this field (ugh)
-        let definition = DebugScenario {
+        let mut definition = DebugScenario {
             label,
             build: None,
             request: match self.request {
@@ -87,7 +92,21 @@ impl VsCodeDebugTaskDefinition {
             stop_on_entry: self.stop_on_entry,
             // TODO
             initialize_args: None,
+            inputs: Vec::new(),
         };
+        if let Some(DebugRequest::Launch(launch)) = &definition.request {
+            let cwd = launch
+                .cwd
+                .as_ref()
+                .map(|cwd| cwd.to_string_lossy().into_owned());
+            definition.inputs = referenced_inputs(
+                inputs,
+                std::iter::once(&launch.program)
+                    .chain(&launch.args)
+                    .chain(&cwd)
+                    .chain(launch.env.values()),
+            );
+        }
         Ok(definition)
     }
 }
@@ -97,6 +116,8 @@ impl VsCodeDebugTaskDefinition {
 pub struct VsCodeDebugTaskFile {
     version: String,
     configurations: Vec<VsCodeDebugTaskDefinition>,
+    #[serde(default)]
+    inputs: Vec<VsCodeTaskInput>,
 }
 
 impl TryFrom<VsCodeDebugTaskFile> for DebugTaskFile {
@@ -110,10 +131,15 @@ impl TryFrom<VsCodeDebugTaskFile> for DebugTaskFile {
             ),
             // TODO other interesting variables?
         ]));
+        let inputs = file
+            .inputs
+            .into_iter()
+            .filter_map(|input| input.into_zed_format().log_err())
+            .collect::<Vec<_>>();
         let templates = file
             .configurations
             .into_iter()
-            .filter_map(|config| config.try_to_zed(&replacer).log_err())
+            .filter_map(|config| config.try_to_zed(&replacer, &inputs).log_err())
             .collect::<Vec<_>>();
         Ok(DebugTaskFile(templates))
     }
@@ -135,10 +161,14 @@ fn task_type_to_adapter_name(task_type: &str) -> SharedString {
 
 #[cfg(test)]
 mod tests {
+    use std::borrow::Cow;
 
     use collections::FxHashMap;
 
-    use crate::{DebugRequest, DebugScenario, DebugTaskFile, LaunchRequest, TcpArgumentsTemplate};
+    use crate::{
+        DebugRequest, DebugScenario, DebugTaskFile, LaunchRequest, TaskContext, TaskInput,
+        TaskInputKind, TcpArgumentsTemplate, VariableName,
+    };
 
     use super::VsCodeDebugTaskFile;
 
@@ -186,8 +216,84 @@ mod tests {
                     cwd: Some("${ZED_WORKTREE_ROOT}/${FOO}/sub".into()),
                     env: FxHashMap::from_iter([("X".into(), "Y".into())])
                 })),
-                build: None
+                build: None,
+                inputs: Vec::new(),
             }])
         );
     }
+
+    #[test]
+    fn test_resolving_launch_json_inputs() {
+        let raw = r#"
+            {
+                "version": "0.2.0",
+                "configurations": [
+                    {
+                        "name": "Debug the app",
+                        "request": "launch",
+                        "type": "node",
+                        "program": "${workspaceFolder}/${input:target}.js",
+                        "args": ["--port", "${input:port}"],
+                        "env": {
+                            "PORT": "${input:port}"
+                        }
+                    },
+                ],
+                "inputs": [
+                    {
+                        "id": "target",
+                        "type": "pickString",
+                        "options": ["server", "client"]
+                    },
+                    { "id": "port", "type": "promptString", "default": "8080" },
+                    { "id": "unused", "type": "promptString" }
+                ]
+            }
+        "#;
+        let parsed: VsCodeDebugTaskFile =
+            serde_json_lenient::from_str(&raw).expect("deserializing launch.json");
+        let zed = DebugTaskFile::try_from(parsed).expect("converting to Zed debug templates");
+        let mut scenario = zed.0.into_iter().next().unwrap();
+        assert_eq!(
+            scenario.inputs,
+            vec![
+                TaskInput {
+                    id: "target".into(),
+                    description: None,
+                    default: None,
+                    kind: TaskInputKind::PickString {
+                        options: vec!["server".into(), "client".into()],
+                    },
+                },
+                TaskInput {
+                    id: "port".into(),
+                    description: None,
+                    default: Some("8080".into()),
+                    kind: TaskInputKind::PromptString,
+                },
+            ]
+        );
+
+        let mut task_context = TaskContext::default();
+        assert_eq!(scenario.unresolved_inputs(&task_context).len(), 2);
+        task_context.task_variables.insert(
+            VariableName::Input(Cow::Borrowed("target")),
+            "server".into(),
+        );
+        task_context
+            .task_variables
+            .insert(VariableName::Input(Cow::Borrowed("port")), "3000".into());
+        assert!(scenario.unresolved_inputs(&task_context).is_empty());
+
+        scenario.resolve_inputs(&task_context);
+        pretty_assertions::assert_eq!(
+            scenario.request,
+            Some(DebugRequest::Launch(LaunchRequest {
+                program: "${ZED_WORKTREE_ROOT}/server.js".into(),
+                args: vec!["--port".into(), "3000".into()],
+                cwd: None,
+                env: FxHashMap::from_iter([("PORT".into(), "3000".into())]),
+            }))
+        );
+    }
 }
@@ -1,9 +1,12 @@
-use anyhow::bail;
+use anyhow::{Context as _, bail};
 use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    DependsOrder, EnvVariableReplacer, ProblemMatcher, ProblemMatcherDefinition, ProblemPattern,
+    ProblemSeverity, TaskInput, TaskInputKind, TaskTemplate, TaskTemplates, VariableName,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -22,6 +25,164 @@ struct VsCodeTaskDefinition {
     #[serde(flatten)]
     other_attributes: HashMap<String, serde_json_lenient::Value>,
     options: Option<TaskOptions>,
+    depends_on: Option<DependsOn>,
+    depends_order: Option<DependsOrder>,
+    problem_matcher: Option<OneOrMany<VsCodeProblemMatcher>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum DependsOn {
+    Label(String),
+    Labels(Vec<String>),
+    /// Task identifiers, such as `{ "type": "npm", "script": "build" }`.
+    Unsupported(serde_json_lenient::Value),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(item) => vec![item],
+            OneOrMany::Many(items) => items,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeProblemMatcher {
+    Named(String),
+    Definition(VsCodeProblemMatcherDefinition),
+    /// Problem matchers with named patterns or unsupported values.
+    Unsupported(serde_json_lenient::Value),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemMatcherDefinition {
+    base: Option<String>,
+    owner: Option<String>,
+    source: Option<String>,
+    severity: Option<ProblemSeverity>,
+    pattern: Option<OneOrMany<VsCodeProblemPattern>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemPattern {
+    regexp: String,
+    file: Option<usize>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    severity: Option<usize>,
+    code: Option<usize>,
+    message: Option<usize>,
+    #[serde(default, rename = "loop")]
+    repeat: bool,
+}
+
+impl VsCodeProblemMatcher {
+    fn into_zed_format(self) -> anyhow::Result<ProblemMatcher> {
+        let definition = match self {
+            VsCodeProblemMatcher::Named(name) => return Ok(ProblemMatcher::Named(name)),
+            VsCodeProblemMatcher::Definition(definition) => definition,
+            VsCodeProblemMatcher::Unsupported(value) => {
+                bail!("Unsupported problem matcher: {value}")
+            }
+        };
+        let Some(patterns) = definition.pattern else {
+            // Overrides of the base problem matcher's options, such as `fileLocation`, are not supported.
+            let base = definition
+                .base
+                .context("Problem matcher has neither `pattern` nor `base`")?;
+            return Ok(ProblemMatcher::Named(base));
+        };
+        Ok(ProblemMatcher::Custom(ProblemMatcherDefinition {
+            source: definition.source.or(definition.owner),
+            severity: definition.severity,
+            patterns: patterns
+                .into_vec()
+                .into_iter()
+                .map(|pattern| ProblemPattern {
+                    regex: pattern.regexp,
+                    file: pattern.file,
+                    line: pattern.line,
+                    column: pattern.column,
+                    end_line: pattern.end_line,
+                    end_column: pattern.end_column,
+                    severity: pattern.severity,
+                    code: pattern.code,
+                    message: pattern.message,
+                    repeat: pattern.repeat,
+                })
+                .collect(),
+        }))
+    }
+}
+
+/// An input of Code's `tasks.json` and `launch.json` files, shared by all the tasks in the file.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VsCodeTaskInput {
+    id: String,
+    description: Option<String>,
+    default: Option<String>,
+    #[serde(flatten)]
+    kind: VsCodeTaskInputKind,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum VsCodeTaskInputKind {
+    PromptString,
+    PickString { options: Vec<PickStringOption> },
+    Command,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum PickStringOption {
+    Value(String),
+    Labeled { value: String },
+}
+
+impl VsCodeTaskInput {
+    pub(crate) fn into_zed_format(self) -> anyhow::Result<TaskInput> {
+        let kind = match self.kind {
+            VsCodeTaskInputKind::PromptString => TaskInputKind::PromptString,
+            VsCodeTaskInputKind::PickString { options } => TaskInputKind::PickString {
+                options: options
+                    .into_iter()
+                    .map(|option| match option {
+                        PickStringOption::Value(value) | PickStringOption::Labeled { value } => {
+                            value
+                        }
+                    })
+                    .collect(),
+            },
+            VsCodeTaskInputKind::Command => {
+                bail!(
+                    "Command inputs are not supported, skipping input {}",
+                    self.id
+                )
+            }
+        };
+        Ok(TaskInput {
+            id: self.id,
+            description: self.description,
+            default: self.default,
+            kind,
+        })
+    }
 }
 
 #[derive(Clone, Deserialize, PartialEq, Debug)]
@@ -42,44 +203,83 @@ enum Command {
 }
 
 impl VsCodeTaskDefinition {
-    fn into_zed_format(self, replacer: &EnvVariableReplacer) -> anyhow::Result<TaskTemplate> {
-        if self.other_attributes.contains_key("dependsOn") {
-            bail!("Encountered unsupported `dependsOn` key during deserialization");
-        }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
-        // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
-        let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+    fn into_zed_format(
+        self,
+        replacer: &EnvVariableReplacer,
+        inputs: &[TaskInput],
+    ) -> anyhow::Result<TaskTemplate> {
+        let depends_on = match self.depends_on {
+            None => Vec::new(),
+            Some(DependsOn::Label(label)) => vec![label],
+            Some(DependsOn::Labels(labels)) => labels,
+            Some(DependsOn::Unsupported(_)) => {
+                bail!("Only task labels are supported in the `dependsOn` key")
+            }
         };
-
-        let (command, args) = match command {
-            Command::Npm { script } => ("npm".to_owned(), vec!["run".to_string(), script]),
-            Command::Shell { command, args } => (command, args),
-            Command::Gulp { task } => ("gulp".to_owned(), vec![task]),
+        // `type` might not be set in tasks that use `dependsOn` to only run other tasks,
+        // hence command is an Option.
+        let (command, args) = match self.command {
+            Some(Command::Npm { script }) => ("npm".to_owned(), vec!["run".to_string(), script]),
+            Some(Command::Shell { command, args }) => (command, args),
+            Some(Command::Gulp { task }) => ("gulp".to_owned(), vec![task]),
+            None if !depends_on.is_empty() => (String::new(), Vec::new()),
+            None => bail!("Missing `type` field in task"),
         };
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
         let args = args.into_iter().map(|arg| replacer.replace(&arg)).collect();
+        let problem_matchers = self
+            .problem_matcher
+            .map(OneOrMany::into_vec)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|problem_matcher| problem_matcher.into_zed_format().log_err())
+            .collect();
         let mut ret = TaskTemplate {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order: self.depends_order.unwrap_or_default(),
+            problem_matchers,
             ..Default::default()
         };
         if let Some(options) = self.options {
             ret.cwd = options.cwd.map(|cwd| replacer.replace(&cwd));
             ret.env = options.env;
         }
+        ret.inputs = referenced_inputs(
+            inputs,
+            std::iter::once(&ret.command)
+                .chain(&ret.args)
+                .chain(&ret.cwd)
+                .chain(ret.env.values()),
+        );
         Ok(ret)
     }
 }
 
+/// Inputs are defined for the whole file, so each task only gets the ones its values refer to.
+pub(crate) fn referenced_inputs<'a>(
+    inputs: &[TaskInput],
+    values: impl Iterator<Item = &'a String> + Clone,
+) -> Vec<TaskInput> {
+    inputs
+        .iter()
+        .filter(|input| {
+            let variable = format!("${{{}}}", input.variable_name());
+            values.clone().any(|value| value.contains(&variable))
+        })
+        .cloned()
+        .collect()
+}
+
 /// [`VsCodeTaskFile`] is a superset of Code's task definition format.
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct VsCodeTaskFile {
     tasks: Vec<VsCodeTaskDefinition>,
+    #[serde(default)]
+    inputs: Vec<VsCodeTaskInput>,
 }
 
 impl TryFrom<VsCodeTaskFile> for TaskTemplates {
@@ -98,10 +298,19 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
                 VariableName::SelectedText.to_string(),
             ),
         ]));
+        let inputs = value
+            .inputs
+            .into_iter()
+            .filter_map(|input| input.into_zed_format().log_err())
+            .collect::<Vec<_>>();
         let templates = value
             .tasks
             .into_iter()
-            .filter_map(|vscode_definition| vscode_definition.into_zed_format(&replacer).log_err())
+            .filter_map(|vscode_definition| {
+                vscode_definition
+                    .into_zed_format(&replacer, &inputs)
+                    .log_err()
+            })
             .collect();
         Ok(Self(templates))
     }
@@ -112,12 +321,30 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        TaskTemplate, TaskTemplates, VsCodeTaskFile,
-        vscode_format::{Command, VsCodeTaskDefinition},
+        ProblemMatcher, ProblemMatcherDefinition, ProblemPattern, ProblemSeverity, TaskInput,
+        TaskInputKind, TaskTemplate, TaskTemplates, VsCodeTaskFile,
+        vscode_format::{
+            Command, DependsOn, OneOrMany, VsCodeProblemMatcher, VsCodeProblemMatcherDefinition,
+            VsCodeTaskDefinition,
+        },
     };
 
     use super::EnvVariableReplacer;
 
+    fn based_on(base: &str) -> VsCodeProblemMatcher {
+        VsCodeProblemMatcher::Definition(VsCodeProblemMatcherDefinition {
+            base: Some(base.to_string()),
+            owner: None,
+            source: None,
+            severity: None,
+            pattern: None,
+        })
+    }
+
+    fn named(name: &str) -> VsCodeProblemMatcher {
+        VsCodeProblemMatcher::Named(name.to_string())
+    }
+
     fn compare_without_other_attributes(lhs: VsCodeTaskDefinition, rhs: VsCodeTaskDefinition) {
         assert_eq!(
             VsCodeTaskDefinition {
@@ -170,6 +397,9 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::Many(vec![named("$tsc")])),
             },
             VsCodeTaskDefinition {
                 label: "tsc: watch ./src".to_string(),
@@ -184,6 +414,9 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::Many(vec![named("$tsc-watch")])),
             },
             VsCodeTaskDefinition {
                 label: "npm: build:compiler".to_string(),
@@ -192,6 +425,9 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::Many(vec![named("$tsc")])),
             },
             VsCodeTaskDefinition {
                 label: "npm: build:tests".to_string(),
@@ -200,6 +436,9 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::Many(vec![named("$tsc")])),
             },
         ];
 
@@ -215,6 +454,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcher::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
@@ -226,18 +466,21 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matchers: vec![ProblemMatcher::Named("$tsc-watch".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matchers: vec![ProblemMatcher::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcher::Named("$tsc".to_string())],
                 ..Default::default()
             },
         ];
@@ -259,6 +502,9 @@ mod tests {
                 }),
                 options: None,
                 other_attributes: Default::default(),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::One(based_on("$tsc-watch"))),
             },
             VsCodeTaskDefinition {
                 label: "Build Extension".to_string(),
@@ -267,6 +513,9 @@ mod tests {
                 }),
                 options: None,
                 other_attributes: Default::default(),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::One(based_on("$tsc"))),
             },
             VsCodeTaskDefinition {
                 label: "Build Server".to_string(),
@@ -276,6 +525,9 @@ mod tests {
                 }),
                 options: None,
                 other_attributes: Default::default(),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::One(named("$rustc"))),
             },
             VsCodeTaskDefinition {
                 label: "Build Server (Release)".to_string(),
@@ -285,6 +537,9 @@ mod tests {
                 }),
                 options: None,
                 other_attributes: Default::default(),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::One(named("$rustc"))),
             },
             VsCodeTaskDefinition {
                 label: "Pretest".to_string(),
@@ -293,18 +548,33 @@ mod tests {
                 }),
                 options: None,
                 other_attributes: Default::default(),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::One(based_on("$tsc"))),
             },
             VsCodeTaskDefinition {
                 label: "Build Server and Extension".to_string(),
                 command: None,
                 options: None,
                 other_attributes: Default::default(),
+                depends_on: Some(DependsOn::Labels(vec![
+                    "Build Server".to_string(),
+                    "Build Extension".to_string(),
+                ])),
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::One(named("$rustc"))),
             },
             VsCodeTaskDefinition {
                 label: "Build Server (Release) and Extension".to_string(),
                 command: None,
                 options: None,
                 other_attributes: Default::default(),
+                depends_on: Some(DependsOn::Labels(vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ])),
+                depends_order: None,
+                problem_matcher: Some(OneOrMany::One(named("$rustc"))),
             },
         ];
         assert_eq!(vscode_definitions.tasks.len(), expected.len());
@@ -318,32 +588,149 @@ mod tests {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matchers: vec![ProblemMatcher::Named("$tsc-watch".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![ProblemMatcher::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcher::Named("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcher::Named("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![ProblemMatcher::Named("$tsc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                problem_matchers: vec![ProblemMatcher::Named("$rustc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                problem_matchers: vec![ProblemMatcher::Named("$rustc".to_string())],
                 ..Default::default()
             },
         ];
         let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
         assert_eq!(tasks.0, expected);
     }
+
+    #[test]
+    fn can_deserialize_problem_matcher_patterns() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    {
+                        "label": "lint",
+                        "type": "shell",
+                        "command": "lint",
+                        "problemMatcher": [
+                            "$gcc",
+                            {
+                                "owner": "lint",
+                                "severity": "warning",
+                                "pattern": [
+                                    { "regexp": "^(\\S+)$", "file": 1 },
+                                    { "regexp": "^\\s+(\\d+):(\\d+)\\s+(.*)$", "line": 1, "column": 2, "message": 3, "loop": true }
+                                ]
+                            },
+                            { "pattern": "$eslint-stylish" }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0[0].problem_matchers,
+            vec![
+                ProblemMatcher::Named("$gcc".to_string()),
+                ProblemMatcher::Custom(ProblemMatcherDefinition {
+                    source: Some("lint".to_string()),
+                    severity: Some(ProblemSeverity::Warning),
+                    patterns: vec![
+                        ProblemPattern {
+                            regex: r"^(\S+)$".to_string(),
+                            file: Some(1),
+                            ..ProblemPattern::default()
+                        },
+                        ProblemPattern {
+                            regex: r"^\s+(\d+):(\d+)\s+(.*)$".to_string(),
+                            line: Some(1),
+                            column: Some(2),
+                            message: Some(3),
+                            repeat: true,
+                            ..ProblemPattern::default()
+                        },
+                    ],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_deserialize_inputs() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    {
+                        "label": "deploy",
+                        "type": "shell",
+                        "command": "deploy",
+                        "args": ["--env", "${input:environment}"]
+                    },
+                    { "label": "build", "type": "shell", "command": "build" }
+                ],
+                "inputs": [
+                    {
+                        "id": "environment",
+                        "type": "pickString",
+                        "description": "Where to deploy",
+                        "options": ["staging", { "label": "Production", "value": "production" }],
+                        "default": "staging"
+                    },
+                    { "id": "name", "type": "promptString" },
+                    { "id": "picked", "type": "command", "command": "extension.pick" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(tasks.0[0].args, vec!["--env", "${ZED_INPUT_environment}"]);
+        assert_eq!(
+            tasks.0[0].inputs,
+            vec![TaskInput {
+                id: "environment".to_string(),
+                description: Some("Where to deploy".to_string()),
+                default: Some("staging".to_string()),
+                kind: TaskInputKind::PickString {
+                    options: vec!["staging".to_string(), "production".to_string()],
+                },
+            }]
+        );
+        assert!(tasks.0[1].inputs.is_empty());
+    }
 }
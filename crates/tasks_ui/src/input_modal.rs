@@ -0,0 +1,294 @@
+use std::sync::Arc;
+
+use futures::{channel::oneshot, lock::Mutex};
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, Focusable, SharedString, Subscription, Task,
+    Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::TaskSourceKind;
+use task::{TaskInput, TaskInputKind};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, TaskInputProvider, Workspace};
+
+/// Asks for the values of the tasks' inputs in modals, for one task at a time.
+#[derive(Default)]
+pub(crate) struct TaskInputPrompter {
+    prompting: Arc<Mutex<()>>,
+}
+
+impl TaskInputProvider for TaskInputPrompter {
+    fn request_input_values(
+        &self,
+        task_source_kind: Option<TaskSourceKind>,
+        task_label: String,
+        inputs: Vec<TaskInput>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Option<Vec<(String, String)>>> {
+        let prompting = self.prompting.clone();
+        cx.spawn_in(window, async move |workspace, cx| {
+            // Tasks that run in parallel would otherwise replace each other's modals.
+            let _prompting = prompting.lock().await;
+            let inventory = workspace
+                .read_with(cx, |workspace, cx| {
+                    workspace
+                        .project()
+                        .read(cx)
+                        .task_store()
+                        .read(cx)
+                        .task_inventory()
+                        .cloned()
+                })
+                .log_err()?;
+            let mut input_values = Vec::with_capacity(inputs.len());
+            for input in inputs {
+                let last_value = match &inventory {
+                    Some(inventory) => inventory
+                        .read_with(cx, |inventory, _| {
+                            inventory
+                                .last_input_value(task_source_kind.as_ref(), &task_label, &input.id)
+                                .map(ToOwned::to_owned)
+                        })
+                        .log_err()?,
+                    None => None,
+                };
+                let (value_tx, value_rx) = oneshot::channel();
+                workspace
+                    .update_in(cx, |workspace, window, cx| {
+                        workspace.toggle_modal(window, cx, |window, cx| {
+                            TaskInputModal::new(input.clone(), last_value, value_tx, window, cx)
+                        })
+                    })
+                    .log_err()?;
+                let value = value_rx.await.ok()?;
+                input_values.push((input.id, value));
+            }
+
+            if let Some(inventory) = &inventory {
+                inventory
+                    .update(cx, |inventory, _| {
+                        inventory.input_values_given(
+                            task_source_kind,
+                            task_label,
+                            input_values.clone(),
+                        )
+                    })
+                    .log_err();
+            }
+            Some(input_values)
+        })
+    }
+}
+
+/// A modal that asks for the value of a task input, either as any text or as one of its options.
+pub(crate) struct TaskInputModal {
+    picker: Entity<Picker<TaskInputModalDelegate>>,
+    _subscription: Subscription,
+}
+
+impl TaskInputModal {
+    fn new(
+        input: TaskInput,
+        last_value: Option<String>,
+        value_tx: oneshot::Sender<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let suggested_value = last_value.or_else(|| input.default.clone());
+        let is_prompt = matches!(input.kind, TaskInputKind::PromptString);
+        let delegate = TaskInputModalDelegate::new(input, suggested_value.clone(), value_tx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        if is_prompt {
+            if let Some(suggested_value) = suggested_value {
+                picker.read(cx).set_query(suggested_value, window, cx);
+            }
+        }
+        let _subscription = cx.subscribe(&picker, |_, _, _: &DismissEvent, cx| {
+            cx.emit(DismissEvent);
+        });
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl Render for TaskInputModal {
+    fn render(&mut self, _window: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TaskInputModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for TaskInputModal {}
+
+impl Focusable for TaskInputModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for TaskInputModal {}
+
+pub(crate) struct TaskInputModalDelegate {
+    input: TaskInput,
+    /// The value to select among the options, until the query changes.
+    suggested_value: Option<String>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    query: String,
+    value_tx: Option<oneshot::Sender<String>>,
+}
+
+impl TaskInputModalDelegate {
+    fn new(
+        input: TaskInput,
+        suggested_value: Option<String>,
+        value_tx: oneshot::Sender<String>,
+    ) -> Self {
+        let candidates = match &input.kind {
+            TaskInputKind::PromptString => Vec::new(),
+            TaskInputKind::PickString { options } => options
+                .iter()
+                .enumerate()
+                .map(|(id, option)| StringMatchCandidate::new(id, option))
+                .collect(),
+        };
+        Self {
+            input,
+            suggested_value,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+            query: String::new(),
+            value_tx: Some(value_tx),
+        }
+    }
+}
+
+impl PickerDelegate for TaskInputModalDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _: &mut App) -> Arc<str> {
+        match &self.input.description {
+            Some(description) => Arc::from(description.as_str()),
+            None => Arc::from(format!("Enter the value of {}", self.input.id)),
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _: &mut App) -> Option<SharedString> {
+        match self.input.kind {
+            TaskInputKind::PromptString => None,
+            TaskInputKind::PickString { .. } => Some("No matching options".into()),
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.selected_index = match delegate.suggested_value.take() {
+                        Some(suggested_value) => matches
+                            .iter()
+                            .position(|option| option.string == suggested_value)
+                            .unwrap_or_default(),
+                        None => 0,
+                    };
+                    delegate.matches = matches;
+                    delegate.query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let value = match self.input.kind {
+            TaskInputKind::PromptString => self.query.clone(),
+            TaskInputKind::PickString { .. } => {
+                let Some(option) = self.matches.get(self.selected_index) else {
+                    return;
+                };
+                option.string.clone()
+            }
+        };
+        if let Some(value_tx) = self.value_tx.take() {
+            value_tx.send(value).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let option = self.matches.get(ix)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(
+                    option.string.clone(),
+                    option.positions.clone(),
+                )),
+        )
+    }
+}
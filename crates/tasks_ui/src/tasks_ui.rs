@@ -3,6 +3,7 @@ use std::path::Path;
 use collections::HashMap;
 use editor::Editor;
 use gpui::{App, AppContext as _, Context, Entity, Task, Window};
+use input_modal::TaskInputPrompter;
 use modal::TaskOverrides;
 use project::{Location, TaskContexts, TaskSourceKind, Worktree};
 use task::{
@@ -10,6 +11,7 @@ use task::{
 };
 use workspace::Workspace;
 
+mod input_modal;
 mod modal;
 
 pub use modal::{Rerun, ShowAttachModal, Spawn, TasksModal};
@@ -17,6 +19,7 @@ pub use modal::{Rerun, ShowAttachModal, Spawn, TasksModal};
 pub fn init(cx: &mut App) {
     cx.observe_new(
         |workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>| {
+            workspace.set_task_input_provider(TaskInputPrompter::default());
             workspace
                 .register_action(spawn_task_or_modal)
                 .register_action(move |workspace, action: &modal::Rerun, window, cx| {
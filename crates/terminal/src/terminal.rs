@@ -672,6 +672,8 @@ pub struct TaskState {
     pub show_summary: bool,
     pub show_command: bool,
     pub show_rerun: bool,
+    /// Whether the task reruns when the files it watches change.
+    pub watched: bool,
 }
 
 /// A status of the current terminal tab's task.
@@ -1902,6 +1904,9 @@ impl Terminal {
         if task.show_command {
             lines_to_show.push(command_line.as_str());
         }
+        if task.show_summary && task.watched {
+            lines_to_show.push(WATCHED_TASK_LINE);
+        }
 
         if !lines_to_show.is_empty() {
             // SAFETY: the invocation happens on non `TaskStatus::Running` tasks, once,
@@ -1946,6 +1951,7 @@ fn is_path_surrounded_by_common_symbols(path: &str) -> bool {
 }
 
 const TASK_DELIMITER: &str = "⏵ ";
+const WATCHED_TASK_LINE: &str = "⏵ Waiting for file changes to rerun";
 fn task_summary(task: &TaskState, error_code: Option<i32>) -> (bool, String, String) {
     let escaped_full_label = task.full_label.replace("\r\n", "\r").replace('\n', "\r");
     let (success, task_line) = match error_code {
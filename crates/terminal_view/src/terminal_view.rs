@@ -1426,10 +1426,11 @@ impl Item for TerminalView {
                 TaskStatus::Completed { success } => {
                     let rerun_button = TerminalView::rerun_button(&terminal_task);
 
-                    if *success {
-                        (IconName::Check, Color::Success, rerun_button)
-                    } else {
-                        (IconName::XCircle, Color::Error, rerun_button)
+                    match (*success, terminal_task.watched) {
+                        (true, false) => (IconName::Check, Color::Success, rerun_button),
+                        (false, false) => (IconName::XCircle, Color::Error, rerun_button),
+                        (true, true) => (IconName::Eye, Color::Success, rerun_button),
+                        (false, true) => (IconName::Eye, Color::Error, rerun_button),
                     }
                 }
             },
@@ -51,6 +51,37 @@ impl SyntaxTheme {
         Some(ix as u32)
     }
 
+    /// Returns the style for a language server's semantic token, if the theme styles any of the
+    /// syntax captures its type maps to.
+    ///
+    /// Token modifiers are styled through captures suffixed with the modifier name, such as
+    /// `variable.readonly`, which are layered on top of the token type's style.
+    pub fn semantic_token_style(
+        &self,
+        token_type: &str,
+        modifiers: &[impl AsRef<str>],
+    ) -> Option<HighlightStyle> {
+        let captures = semantic_token_captures(token_type);
+        let mut style = captures
+            .iter()
+            .find(|capture| self.highlight_id(capture).is_some())
+            .map(|capture| self.get(capture));
+
+        for modifier in modifiers {
+            let modifier_style = captures.iter().find_map(|capture| {
+                let name = format!("{capture}.{}", modifier.as_ref());
+                self.highlight_id(&name).map(|_| self.get(&name))
+            });
+            if let Some(modifier_style) = modifier_style {
+                style
+                    .get_or_insert_with(HighlightStyle::default)
+                    .highlight(modifier_style);
+            }
+        }
+
+        style
+    }
+
     /// Returns a new [`Arc<SyntaxTheme>`] with the given syntax styles merged in.
     pub fn merge(base: Arc<Self>, user_syntax_styles: Vec<(String, HighlightStyle)>) -> Arc<Self> {
         if user_syntax_styles.is_empty() {
@@ -87,12 +118,89 @@ impl SyntaxTheme {
     }
 }
 
+/// The syntax captures a semantic token type is styled with, from most to least specific.
+fn semantic_token_captures(token_type: &str) -> Vec<&str> {
+    match token_type {
+        "namespace" => vec!["namespace", "module"],
+        "type" | "class" | "struct" | "interface" => vec!["type"],
+        "enum" => vec!["enum", "type"],
+        "typeParameter" => vec!["type.parameter", "type"],
+        "parameter" => vec!["variable.parameter", "variable"],
+        "variable" => vec!["variable"],
+        "property" | "event" => vec!["property"],
+        "enumMember" => vec!["variant", "constant"],
+        "function" => vec!["function"],
+        "method" => vec!["function.method", "function"],
+        "macro" => vec!["function.macro", "function.special", "function"],
+        "keyword" | "modifier" => vec!["keyword"],
+        "comment" => vec!["comment"],
+        "string" => vec!["string"],
+        "number" => vec!["number"],
+        "regexp" => vec!["string.regex", "string"],
+        "operator" => vec!["operator"],
+        "decorator" => vec!["attribute"],
+        _ => vec![token_type],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use gpui::FontStyle;
 
     use super::*;
 
+    #[test]
+    fn test_semantic_token_style() {
+        let syntax_theme = SyntaxTheme::new_test_styles([
+            (
+                "variable",
+                HighlightStyle {
+                    color: Some(gpui::red()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "variable.readonly",
+                HighlightStyle {
+                    font_style: Some(FontStyle::Italic),
+                    ..Default::default()
+                },
+            ),
+            (
+                "function",
+                HighlightStyle {
+                    color: Some(gpui::blue()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        // Token types fall back to less specific captures.
+        assert_eq!(
+            syntax_theme.semantic_token_style("method", &[] as &[&str]),
+            Some(HighlightStyle {
+                color: Some(gpui::blue()),
+                ..Default::default()
+            })
+        );
+
+        // Modifiers are layered on top of the token type's style.
+        assert_eq!(
+            syntax_theme.semantic_token_style("parameter", &["readonly", "declaration"]),
+            Some(HighlightStyle {
+                color: Some(gpui::red()),
+                font_style: Some(FontStyle::Italic),
+                ..Default::default()
+            })
+        );
+
+        // Tokens the theme has no style for are left to the syntax highlighting.
+        assert_eq!(
+            syntax_theme.semantic_token_style("keyword", &[] as &[&str]),
+            None
+        );
+    }
+
     #[test]
     fn test_syntax_theme_merge() {
         // Merging into an empty `SyntaxTheme` keeps all the user-defined styles.
@@ -7,7 +7,10 @@ use editor::{
     display_map::ToDisplayPoint,
     scroll::Autoscroll,
 };
-use gpui::{Action, App, AppContext as _, Context, Global, Window, actions, impl_internal_actions};
+use gpui::{
+    Action, App, AppContext as _, Context, Focusable, Global, Keystroke, Window, actions,
+    impl_internal_actions,
+};
 use itertools::Itertools;
 use language::Point;
 use multi_buffer::MultiBufferRow;
@@ -35,6 +38,7 @@ use crate::{
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
+        repeat::{keystrokes_from_text, replay_keystroke},
         search::{FindCommand, ReplaceCommand, Replacement},
     },
     object::Object,
@@ -166,6 +170,8 @@ impl_internal_actions!(
         WithRange,
         WithCount,
         OnMatchingLines,
+        NormalCommand,
+        SetRegister,
         ShellExec,
         VimSet,
     ]
@@ -352,6 +358,16 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
         action.run(vim, window, cx)
     });
 
+    Vim::action(editor, cx, |vim, action: &NormalCommand, window, cx| {
+        action.run(vim, window, cx)
+    });
+
+    Vim::action(editor, cx, |_, action: &SetRegister, _, cx| {
+        Vim::update_globals(cx, |globals, cx| {
+            globals.set_register_text(action.register, action.text.clone(), cx)
+        })
+    });
+
     Vim::action(editor, cx, |vim, action: &ShellExec, window, cx| {
         action.run(vim, window, cx)
     })
@@ -1016,6 +1032,10 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
         } else {
             None
         }
+    } else if let Some(action) = NormalCommand::parse(query, range.clone()) {
+        Some(action)
+    } else if query.starts_with("let ") {
+        SetRegister::parse(query)
     } else if query.contains('!') {
         ShellExec::parse(query, range.clone())
     } else {
@@ -1263,6 +1283,159 @@ impl OnMatchingLines {
     }
 }
 
+/// `:normal {commands}` runs the given keys as normal mode commands on every line in the
+/// range, or on each cursor's line when there is no range (as with `:g/pattern/normal ...`).
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct NormalCommand {
+    range: Option<CommandRange>,
+    keystrokes: Vec<Keystroke>,
+}
+
+impl NormalCommand {
+    pub fn parse(query: &str, range: Option<CommandRange>) -> Option<Box<dyn Action>> {
+        let name_len = query
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(query.len());
+        let (name, args) = query.split_at(name_len);
+        if name.len() < 4 || !"normal".starts_with(name) {
+            return None;
+        }
+        // There is no remapping of keys in Zed, so `:normal!` is the same as `:normal`.
+        let args = args.strip_prefix('!').unwrap_or(args);
+        if !args.starts_with(' ') {
+            return None;
+        }
+        let keystrokes = keystrokes_from_text(args.trim_start());
+        if keystrokes.is_empty() {
+            return None;
+        }
+        Some(NormalCommand { range, keystrokes }.boxed_clone())
+    }
+
+    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let result = vim.update_editor(window, cx, |vim, editor, window, cx| {
+            let rows = if let Some(range) = &self.range {
+                let range = range.buffer_range(vim, editor, window, cx)?;
+                (range.start.0..=range.end.0).collect::<Vec<_>>()
+            } else {
+                editor
+                    .selections
+                    .all::<Point>(cx)
+                    .iter()
+                    .map(|selection| selection.head().row)
+                    .dedup()
+                    .collect()
+            };
+            // Anchor each line so that edits made on earlier lines don't shift later ones.
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            anyhow::Ok(
+                rows.into_iter()
+                    .map(|row| snapshot.anchor_before(Point::new(row, 0)))
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        let lines = match result {
+            None => return,
+            Some(e @ Err(_)) => {
+                let Some(workspace) = vim.workspace(window) else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    e.notify_err(workspace, cx);
+                });
+                return;
+            }
+            Some(Ok(result)) => result,
+        };
+        let Some(editor) = vim.editor() else {
+            return;
+        };
+        let keystrokes = self.keystrokes.clone();
+
+        cx.spawn_in(window, async move |vim, cx| {
+            editor.update_in(cx, |editor, window, cx| {
+                window.focus(&editor.focus_handle(cx));
+                editor.start_transaction_at(Instant::now(), window, cx);
+            })?;
+            for line in lines {
+                vim.update_in(cx, |vim, window, cx| {
+                    vim.clear_operator(window, cx);
+                    vim.switch_mode(Mode::Normal, false, window, cx);
+                    vim.update_editor(window, cx, |_, editor, window, cx| {
+                        editor.change_selections(None, window, cx, |s| {
+                            s.select_anchor_ranges([line..line]);
+                        });
+                    });
+                })?;
+                for keystroke in &keystrokes {
+                    cx.update(|window, cx| replay_keystroke(keystroke.clone(), window, cx))?;
+                }
+                // An incomplete command is completed with <Esc>, which also leaves insert mode.
+                let needs_escape = vim.update(cx, |vim, _| {
+                    vim.mode != Mode::Normal || vim.active_operator().is_some()
+                })? || cx.update(|window, _| window.has_pending_keystrokes())?;
+                if needs_escape {
+                    cx.update(|window, cx| {
+                        replay_keystroke(Keystroke::parse("escape").unwrap(), window, cx)
+                    })?;
+                }
+            }
+            vim.update_in(cx, |vim, window, cx| {
+                vim.clear_operator(window, cx);
+                vim.switch_mode(Mode::Normal, false, window, cx);
+            })?;
+            editor.update(cx, |editor, cx| {
+                editor.end_transaction_at(Instant::now(), cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+/// `:let @{register} = '{text}'` sets the contents of a register. Double quoted text may
+/// contain `\n`, `\"` and `\\` escapes.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SetRegister {
+    register: char,
+    text: String,
+}
+
+impl SetRegister {
+    pub fn parse(query: &str) -> Option<Box<dyn Action>> {
+        let rest = query.strip_prefix("let")?.trim_start().strip_prefix('@')?;
+        let register = rest.chars().next()?;
+        if !register.is_ascii_alphanumeric() && register != '"' {
+            return None;
+        }
+        let value = rest[register.len_utf8()..]
+            .trim_start()
+            .strip_prefix('=')?
+            .trim();
+        let text = if let Some(quoted) = value.strip_prefix('\'') {
+            quoted.strip_suffix('\'')?.replace("''", "'")
+        } else {
+            let quoted = value.strip_prefix('"')?.strip_suffix('"')?;
+            let mut text = String::new();
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                if c != '\\' {
+                    text.push(c);
+                    continue;
+                }
+                match chars.next()? {
+                    'n' => text.push('\n'),
+                    't' => text.push('\t'),
+                    c => text.push(c),
+                }
+            }
+            text
+        };
+        Some(SetRegister { register, text }.boxed_clone())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ShellExec {
     command: String,
@@ -1465,6 +1638,8 @@ impl ShellExec {
                     show_summary: false,
                     show_command: false,
                     show_rerun: false,
+                    problem_matchers: Vec::new(),
+                    rerun_on: Vec::new(),
                 };
                 workspace
                     .spawn_in_terminal(spawn_in_terminal, window, cx)
@@ -1917,4 +2092,27 @@ mod test {
             a
             ˇa"});
     }
+
+    #[gpui::test]
+    async fn test_normal_command(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇa\nb\nc\n", Mode::Normal);
+        cx.simulate_keystrokes(": n o r m space A ; enter");
+        cx.assert_state("aˇ;\nb\nc\n", Mode::Normal);
+
+        cx.simulate_keystrokes("j V j : n o r m a l space I # enter");
+        cx.assert_state("a;\n#b\nˇ#c\n", Mode::Normal);
+
+        // all lines are changed in a single undo step
+        cx.simulate_keystrokes("u");
+        assert_eq!(cx.buffer_text(), "a;\nb\nc\n");
+
+        cx.set_state("ˇa\nb\na\nb\n", Mode::Normal);
+        cx.simulate_keystrokes(": g / a / n o r m space A < l t > < e s c > x enter");
+        assert_eq!(cx.buffer_text(), "a\nb\na\nb\n");
+
+        cx.simulate_keystrokes(": g / b / n o r m space d d enter");
+        assert_eq!(cx.buffer_text(), "a\na\n");
+    }
 }
@@ -1,16 +1,47 @@
-use editor::{DisplayPoint, Editor, movement, scroll::Autoscroll};
+use std::ops::Range;
+
+use editor::{Anchor, DisplayPoint, Editor, ToOffset, movement, scroll::Autoscroll};
 use gpui::{Action, actions};
 use gpui::{Context, Window};
-use language::{CharClassifier, CharKind};
+use language::{CharClassifier, CharKind, Point};
+use regex::{Regex, RegexBuilder};
+use search::{BufferSearchBar, SearchOptions};
+use workspace::notifications::NotifyResultExt;
 
 use crate::motion::MotionKind;
+use crate::object::Object;
+use crate::state::{HelixSelect, Operator, SearchState};
 use crate::{Vim, motion::Motion, state::Mode};
 
-actions!(vim, [HelixNormalAfter, HelixDelete]);
+actions!(
+    vim,
+    [
+        HelixNormalAfter,
+        HelixDelete,
+        HelixSelectLine,
+        HelixCollapseSelection,
+        HelixKeepPrimarySelection,
+        HelixFlipSelections,
+        HelixSelectRegex,
+        HelixSplitSelection,
+        HelixSplitSelectionOnNewline,
+    ]
+);
 
 pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_normal_after);
     Vim::action(editor, cx, Vim::helix_delete);
+    Vim::action(editor, cx, Vim::helix_select_line);
+    Vim::action(editor, cx, Vim::helix_collapse_selection);
+    Vim::action(editor, cx, Vim::helix_keep_primary_selection);
+    Vim::action(editor, cx, Vim::helix_flip_selections);
+    Vim::action(editor, cx, |vim, _: &HelixSelectRegex, window, cx| {
+        vim.helix_search(HelixSelect::Matches, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &HelixSplitSelection, window, cx| {
+        vim.helix_search(HelixSelect::Split, window, cx)
+    });
+    Vim::action(editor, cx, Vim::helix_split_selection_on_newline);
 }
 
 impl Vim {
@@ -259,10 +290,251 @@ impl Vim {
             editor.insert("", window, cx);
         });
     }
+    /// Extends each selection to cover whole lines. A selection that already covers whole
+    /// lines is extended by another line.
+    fn helix_select_line(
+        &mut self,
+        _: &HelixSelectLine,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let times = Vim::take_count(cx).unwrap_or(1) as u32;
+        Vim::take_forced_motion(cx);
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            let buffer = editor.buffer().read(cx).snapshot(cx);
+            let max_point = buffer.max_point();
+            let ranges = editor
+                .selections
+                .all::<Point>(cx)
+                .into_iter()
+                .map(|selection| {
+                    let start = Point::new(selection.start.row, 0);
+                    // A selection already covering whole lines ends at the start of the next
+                    // row, so this extends it by further lines.
+                    let end_row = selection.end.row + times;
+                    start..Point::new(end_row, 0).min(max_point)
+                })
+                .collect::<Vec<_>>();
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_ranges(ranges);
+            });
+        });
+    }
+
+    /// Collapses each selection to the character under its cursor.
+    fn helix_collapse_selection(
+        &mut self,
+        _: &HelixCollapseSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|map, selection| {
+                    let cursor = if selection.is_empty() || selection.reversed {
+                        selection.head()
+                    } else {
+                        movement::left(map, selection.head())
+                    };
+                    selection.collapse_to(cursor, selection.goal);
+                });
+            });
+        });
+    }
+
+    fn helix_keep_primary_selection(
+        &mut self,
+        _: &HelixKeepPrimarySelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            let primary = editor.selections.newest_anchor().clone();
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_anchors(vec![primary]);
+            });
+        });
+    }
+
+    fn helix_flip_selections(
+        &mut self,
+        _: &HelixFlipSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|_, selection| selection.swap_head_tail());
+            });
+        });
+    }
+
+    /// Opens the search bar to enter the regex for `s` or `S`. The selections are changed
+    /// once the search is submitted.
+    fn helix_search(&mut self, kind: HelixSelect, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pane) = self.pane(window, cx) else {
+            return;
+        };
+        let prior_selections = self.editor_selections(window, cx);
+        let prior_mode = self.mode;
+        pane.update(cx, |pane, cx| {
+            let Some(search_bar) = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>() else {
+                return;
+            };
+            search_bar.update(cx, |search_bar, cx| {
+                if !search_bar.show(window, cx) {
+                    return;
+                }
+                search_bar.select_query(window, cx);
+                cx.focus_self(window);
+                search_bar.set_replacement(None, cx);
+                search_bar.set_search_options(SearchOptions::REGEX, cx);
+                self.search = SearchState {
+                    count: 1,
+                    prior_selections,
+                    prior_mode,
+                    helix_select: Some(kind),
+                    ..Default::default()
+                };
+            });
+        });
+    }
+
+    pub(crate) fn helix_search_submit(
+        &mut self,
+        kind: HelixSelect,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(pane) = self.pane(window, cx) else {
+            return;
+        };
+        let query = pane.update(cx, |pane, cx| {
+            let search_bar = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>()?;
+            search_bar.update(cx, |search_bar, cx| {
+                let query = search_bar.query(cx);
+                search_bar.dismiss(&Default::default(), window, cx);
+                Some(query)
+            })
+        });
+        let Some(query) = query.filter(|query| !query.is_empty()) else {
+            return;
+        };
+        let prior_selections = std::mem::take(&mut self.search.prior_selections);
+
+        // Like in helix, the search is only case sensitive if the query contains uppercase.
+        let regex = RegexBuilder::new(&query)
+            .case_insensitive(!query.chars().any(char::is_uppercase))
+            .build();
+        let regex = match regex {
+            Ok(regex) => regex,
+            e @ Err(_) => {
+                let Some(workspace) = self.workspace(window) else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    e.notify_err(workspace, cx);
+                });
+                return;
+            }
+        };
+        self.helix_select_matches(prior_selections, &regex, kind, window, cx);
+    }
+
+    fn helix_split_selection_on_newline(
+        &mut self,
+        _: &HelixSplitSelectionOnNewline,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let selections = self.editor_selections(window, cx);
+        let regex = Regex::new(r"\r?\n").unwrap();
+        self.helix_select_matches(selections, &regex, HelixSelect::Split, window, cx);
+    }
+
+    /// Replaces each of the given ranges with the matches of the regex inside it or, when
+    /// splitting, with the text between those matches. The selections are left alone if this
+    /// would leave none.
+    fn helix_select_matches(
+        &mut self,
+        ranges: Vec<Range<Anchor>>,
+        regex: &Regex,
+        kind: HelixSelect,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            let buffer = editor.buffer().read(cx).snapshot(cx);
+            let mut new_ranges = Vec::new();
+            for range in ranges {
+                let (start, end) = (range.start.to_offset(&buffer), range.end.to_offset(&buffer));
+                let range = start.min(end)..start.max(end);
+                let text = buffer.text_for_range(range.clone()).collect::<String>();
+                match kind {
+                    HelixSelect::Matches => new_ranges.extend(
+                        regex
+                            .find_iter(&text)
+                            .filter(|found| !found.is_empty())
+                            .map(|found| range.start + found.start()..range.start + found.end()),
+                    ),
+                    HelixSelect::Split => {
+                        let mut piece_start = range.start;
+                        for found in regex.find_iter(&text) {
+                            new_ranges.push(piece_start..range.start + found.start());
+                            piece_start = range.start + found.end();
+                        }
+                        new_ranges.push(piece_start..range.end);
+                    }
+                }
+            }
+            new_ranges.retain(|range| !range.is_empty());
+            if new_ranges.is_empty() {
+                return;
+            }
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_ranges(new_ranges);
+            });
+        });
+    }
+
+    /// Selects the text object at each selection's cursor, for match mode's `mi` and `ma`.
+    pub(crate) fn helix_select_object(
+        &mut self,
+        object: Object,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(Operator::Object { around }) = self.active_operator() else {
+            return;
+        };
+        self.pop_operator(window, cx);
+        self.update_editor(window, cx, |_, editor, window, cx| {
+            editor.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.move_with(|map, selection| {
+                    let cursor = if selection.is_empty() || selection.reversed {
+                        selection.head()
+                    } else {
+                        movement::left(map, selection.head())
+                    };
+                    let mut cursor_selection = selection.clone();
+                    cursor_selection.collapse_to(cursor, selection.goal);
+                    if let Some(range) = object.range(map, cursor_selection, around) {
+                        if !range.is_empty() {
+                            selection.start = range.start;
+                            selection.end = range.end;
+                            selection.reversed = false;
+                        }
+                    }
+                });
+            });
+        });
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use editor::{Editor, test::editor_lsp_test_context::EditorLspTestContext};
+    use gpui::{AppContext as _, Entity};
     use indoc::indoc;
 
     use crate::{state::Mode, test::VimTestContext};
@@ -302,83 +574,312 @@ mod test {
         );
     }
 
-    // #[gpui::test]
-    // async fn test_delete(cx: &mut gpui::TestAppContext) {
-    //     let mut cx = VimTestContext::new(cx, true).await;
-
-    //     // test delete a selection
-    //     cx.set_state(
-    //         indoc! {"
-    //         The qu«ick ˇ»brown
-    //         fox jumps over
-    //         the lazy dog."},
-    //         Mode::HelixNormal,
-    //     );
-
-    //     cx.simulate_keystrokes("d");
-
-    //     cx.assert_state(
-    //         indoc! {"
-    //         The quˇbrown
-    //         fox jumps over
-    //         the lazy dog."},
-    //         Mode::HelixNormal,
-    //     );
-
-    //     // test deleting a single character
-    //     cx.simulate_keystrokes("d");
-
-    //     cx.assert_state(
-    //         indoc! {"
-    //         The quˇrown
-    //         fox jumps over
-    //         the lazy dog."},
-    //         Mode::HelixNormal,
-    //     );
-    // }
-
-    // #[gpui::test]
-    // async fn test_delete_character_end_of_line(cx: &mut gpui::TestAppContext) {
-    //     let mut cx = VimTestContext::new(cx, true).await;
-
-    //     cx.set_state(
-    //         indoc! {"
-    //         The quick brownˇ
-    //         fox jumps over
-    //         the lazy dog."},
-    //         Mode::HelixNormal,
-    //     );
-
-    //     cx.simulate_keystrokes("d");
-
-    //     cx.assert_state(
-    //         indoc! {"
-    //         The quick brownˇfox jumps over
-    //         the lazy dog."},
-    //         Mode::HelixNormal,
-    //     );
-    // }
-
-    // #[gpui::test]
-    // async fn test_delete_character_end_of_buffer(cx: &mut gpui::TestAppContext) {
-    //     let mut cx = VimTestContext::new(cx, true).await;
-
-    //     cx.set_state(
-    //         indoc! {"
-    //         The quick brown
-    //         fox jumps over
-    //         the lazy dog.ˇ"},
-    //         Mode::HelixNormal,
-    //     );
-
-    //     cx.simulate_keystrokes("d");
-
-    //     cx.assert_state(
-    //         indoc! {"
-    //         The quick brown
-    //         fox jumps over
-    //         the lazy dog.ˇ"},
-    //         Mode::HelixNormal,
-    //     );
-    // }
+    #[gpui::test]
+    async fn test_select_line(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state(
+            indoc! {"
+            The quˇick brown
+            fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            ˇ»fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        // a selection covering whole lines is extended
+        cx.simulate_keystrokes("x");
+        cx.assert_state(
+            indoc! {"
+            «The quick brown
+            fox jumps over
+            ˇ»the lazy dog."},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_selection_manipulation(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("«oneˇ» two «threeˇ»", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("alt-;");
+        cx.assert_state("«ˇone» two «ˇthree»", Mode::HelixNormal);
+
+        cx.simulate_keystrokes(",");
+        cx.assert_state("one two «ˇthree»", Mode::HelixNormal);
+
+        cx.simulate_keystrokes(";");
+        cx.assert_state("one two ˇthree", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("%");
+        cx.assert_state("«one two threeˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_select_regex(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("«One two one ˇ»three one", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("s o n e enter");
+        cx.assert_state("«Oneˇ» two «oneˇ» three one", Mode::HelixNormal);
+
+        cx.set_state("«one,two,threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-s , enter");
+        cx.assert_state("«oneˇ»,«twoˇ»,«threeˇ»", Mode::HelixNormal);
+
+        cx.set_state(
+            indoc! {"
+            «one
+            two
+            thrˇ»ee"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("alt-s");
+        cx.assert_state(
+            indoc! {"
+            «oneˇ»
+            «twoˇ»
+            «thrˇ»ee"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_match_mode_objects(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("The quˇick brown", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("m i w");
+        cx.assert_state("The «quickˇ» brown", Mode::HelixNormal);
+
+        cx.set_state("The quˇick brown", Mode::HelixNormal);
+        cx.simulate_keystrokes("m a w");
+        cx.assert_state("The «quick ˇ»brown", Mode::HelixNormal);
+
+        cx.set_state("call(arˇgs)", Mode::HelixNormal);
+        cx.simulate_keystrokes("m i (");
+        cx.assert_state("call(«argsˇ»)", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_add_selection_above(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state(
+            indoc! {"
+            The quick brown
+            fox juˇmps over"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("alt-shift-c");
+        cx.assert_state(
+            indoc! {"
+            The quˇick brown
+            fox juˇmps over"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_goto_bindings(cx: &mut gpui::TestAppContext) {
+        VimTestContext::init(cx);
+        let mut cx = VimTestContext::new_with_lsp(
+            EditorLspTestContext::new_rust(
+                lsp::ServerCapabilities {
+                    definition_provider: Some(lsp::OneOf::Left(true)),
+                    declaration_provider: Some(lsp::DeclarationCapability::Simple(true)),
+                    implementation_provider: Some(lsp::ImplementationProviderCapability::Simple(
+                        true,
+                    )),
+                    ..Default::default()
+                },
+                cx,
+            )
+            .await,
+            true,
+        );
+        cx.set_request_handler::<lsp::request::GotoDefinition, _, _>(|url, _, _| async move {
+            Ok(Some(lsp::GotoDefinitionResponse::Scalar(
+                lsp::Location::new(
+                    url,
+                    lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 6)),
+                ),
+            )))
+        });
+        cx.set_request_handler::<lsp::request::GotoDeclaration, _, _>(|url, _, _| async move {
+            Ok(Some(lsp::GotoDefinitionResponse::Scalar(
+                lsp::Location::new(
+                    url,
+                    lsp::Range::new(lsp::Position::new(1, 3), lsp::Position::new(1, 6)),
+                ),
+            )))
+        });
+        cx.set_request_handler::<lsp::request::GotoImplementation, _, _>(|url, _, _| async move {
+            Ok(Some(lsp::GotoDefinitionResponse::Scalar(
+                lsp::Location::new(
+                    url,
+                    lsp::Range::new(lsp::Position::new(2, 3), lsp::Position::new(2, 8)),
+                ),
+            )))
+        });
+
+        let text = indoc! {"
+            fn one() {}
+            fn two() {}
+            fn three() {}
+            fn main() { one(); }"};
+        cx.set_state(
+            indoc! {"
+            fn one() {}
+            fn two() {}
+            fn three() {}
+            fn main() { ˇone(); }"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g d");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+            fn ˇone() {}
+            fn two() {}
+            fn three() {}
+            fn main() { one(); }"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g shift-d");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+            fn one() {}
+            fn ˇtwo() {}
+            fn three() {}
+            fn main() { one(); }"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("g i");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+            fn one() {}
+            fn two() {}
+            fn ˇthree() {}
+            fn main() { one(); }"},
+            Mode::HelixNormal,
+        );
+        assert_eq!(cx.buffer_text(), text);
+
+        // `g a` goes back to the previously active item.
+        let other_editor: Entity<Editor> = cx.update_workspace(|workspace, window, cx| {
+            let other_editor = cx.new(|cx| Editor::multi_line(window, cx));
+            workspace.add_item_to_active_pane(
+                Box::new(other_editor.clone()),
+                None,
+                true,
+                window,
+                cx,
+            );
+            workspace.active_pane().update(cx, |pane, cx| {
+                pane.activate_prev_item(true, window, cx);
+            });
+            other_editor
+        });
+        cx.run_until_parked();
+        cx.simulate_keystrokes("g a");
+        cx.run_until_parked();
+        cx.update_workspace(|workspace, _, cx| {
+            let active_item = workspace.active_item(cx).unwrap();
+            assert_eq!(active_item.item_id(), other_editor.entity_id());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_delete(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        // test delete a selection
+        cx.set_state(
+            indoc! {"
+            The qu«ick ˇ»brown
+            fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("d");
+
+        cx.assert_state(
+            indoc! {"
+            The quˇbrown
+            fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        // test deleting a single character
+        cx.simulate_keystrokes("d");
+
+        cx.assert_state(
+            indoc! {"
+            The quˇrown
+            fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_delete_character_end_of_line(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            The quick brownˇ
+            fox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("d");
+
+        cx.assert_state(
+            indoc! {"
+            The quick brownˇfox jumps over
+            the lazy dog."},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_delete_character_end_of_buffer(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            The quick brown
+            fox jumps over
+            the lazy dog.ˇ"},
+            Mode::HelixNormal,
+        );
+
+        cx.simulate_keystrokes("d");
+
+        cx.assert_state(
+            indoc! {"
+            The quick brown
+            fox jumps over
+            the lazy dog.ˇ"},
+            Mode::HelixNormal,
+        );
+    }
 }
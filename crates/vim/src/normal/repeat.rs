@@ -8,7 +8,8 @@ use crate::{
     state::{Mode, Operator, RecordedSelection, ReplayableAction, VimGlobals},
 };
 use editor::Editor;
-use gpui::{Action, App, Context, Window, actions};
+use gpui::{Action, App, Context, Keystroke, Window, actions};
+use util::ResultExt;
 use workspace::Workspace;
 
 actions!(vim, [Repeat, EndRepeat, ToggleRecord, ReplayLastRecording]);
@@ -40,7 +41,7 @@ fn repeatable_insert(action: &ReplayableAction) -> Option<Box<dyn Action>> {
                 None
             }
         }
-        ReplayableAction::Insertion { .. } => None,
+        ReplayableAction::Insertion { .. } | ReplayableAction::Keystroke(_) => None,
     }
 }
 
@@ -55,10 +56,7 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 
     Vim::action(editor, cx, |vim, _: &ToggleRecord, window, cx| {
-        let globals = Vim::globals(cx);
-        if let Some(char) = globals.recording_register.take() {
-            globals.last_recorded_register = Some(char)
-        } else {
+        if !Vim::update_globals(cx, |globals, cx| globals.stop_recording_register(cx)) {
             vim.push_operator(Operator::RecordRegister, window, cx);
         }
     });
@@ -71,6 +69,136 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 }
 
+/// Names for special keys in register contents and `:normal` commands, as in Vim.
+/// The first name for each key is the one used when writing keystrokes out.
+const KEY_NAMES: &[(&str, &str)] = &[
+    ("Esc", "escape"),
+    ("CR", "enter"),
+    ("BS", "backspace"),
+    ("Del", "delete"),
+    ("Tab", "tab"),
+    ("Space", "space"),
+    ("lt", "<"),
+    ("Up", "up"),
+    ("Down", "down"),
+    ("Left", "left"),
+    ("Right", "right"),
+    ("Home", "home"),
+    ("End", "end"),
+    ("PageUp", "pageup"),
+    ("PageDown", "pagedown"),
+    ("Insert", "insert"),
+    ("Escape", "escape"),
+    ("Enter", "enter"),
+    ("Return", "enter"),
+];
+
+/// Parses text into the keystrokes that would type it. Special keys are written in Vim's
+/// key notation, such as `<Esc>`, `<CR>`, `<C-r>` or `<lt>` for a literal `<`.
+pub(crate) fn keystrokes_from_text(text: &str) -> Vec<Keystroke> {
+    let mut keystrokes = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let notation = rest
+            .strip_prefix('<')
+            .and_then(|rest| rest.split_once('>'))
+            .and_then(|(notation, after)| Some((parse_key_notation(notation)?, after)));
+        if let Some((keystroke, after)) = notation {
+            keystrokes.push(keystroke);
+            rest = after;
+            continue;
+        }
+        let key = match c {
+            ' ' => "space".to_string(),
+            '\n' => "enter".to_string(),
+            '\t' => "tab".to_string(),
+            c => c.to_string(),
+        };
+        keystrokes.extend(Keystroke::parse(&key).log_err());
+        rest = &rest[c.len_utf8()..];
+    }
+    keystrokes
+}
+
+fn parse_key_notation(notation: &str) -> Option<Keystroke> {
+    let mut source = String::new();
+    let mut key = notation;
+    while let Some((modifier, rest)) = key.split_once('-').filter(|(_, rest)| !rest.is_empty()) {
+        source.push_str(match modifier.to_ascii_lowercase().as_str() {
+            "c" => "ctrl-",
+            "s" => "shift-",
+            "a" | "m" => "alt-",
+            "d" => "cmd-",
+            _ => return None,
+        });
+        key = rest;
+    }
+    let is_function_key = key.len() > 1
+        && key.starts_with(['f', 'F'])
+        && key[1..].chars().all(|c| c.is_ascii_digit());
+    if let Some((_, name)) = KEY_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+    {
+        source.push_str(name);
+    } else if is_function_key {
+        source.push_str(&key.to_ascii_lowercase());
+    } else if key.chars().count() == 1 && !source.is_empty() {
+        source.push_str(key);
+    } else {
+        return None;
+    }
+    Keystroke::parse(&source).ok()
+}
+
+/// Writes keystrokes out as text that [`keystrokes_from_text`] parses back.
+pub(crate) fn keystrokes_to_text(keystrokes: &[Keystroke]) -> String {
+    let mut text = String::new();
+    for keystroke in keystrokes {
+        let modifiers = &keystroke.modifiers;
+        let has_modifiers = modifiers.control || modifiers.alt || modifiers.platform;
+        if let Some(key_char) = keystroke.key_char.as_deref().filter(|key_char| {
+            !has_modifiers
+                && *key_char != "<"
+                && !key_char.is_empty()
+                && !key_char.chars().any(char::is_control)
+        }) {
+            text.push_str(key_char);
+            continue;
+        }
+
+        text.push('<');
+        if modifiers.control {
+            text.push_str("C-");
+        }
+        if modifiers.alt {
+            text.push_str("A-");
+        }
+        if modifiers.platform {
+            text.push_str("D-");
+        }
+        if modifiers.shift {
+            text.push_str("S-");
+        }
+        if let Some((name, _)) = KEY_NAMES.iter().find(|(_, key)| *key == keystroke.key) {
+            text.push_str(name);
+        } else if keystroke.key.len() > 1 && keystroke.key.starts_with('f') {
+            text.push_str(&keystroke.key.to_ascii_uppercase());
+        } else {
+            text.push_str(&keystroke.key);
+        }
+        text.push('>');
+    }
+    text
+}
+
+/// Dispatches a keystroke as though it had been typed, without recording it into a macro.
+pub(crate) fn replay_keystroke(keystroke: Keystroke, window: &mut Window, cx: &mut App) {
+    Vim::globals(cx).replaying_keystroke = true;
+    window.dispatch_keystroke(keystroke, cx);
+    Vim::globals(cx).replaying_keystroke = false;
+}
+
 pub struct ReplayerState {
     actions: Vec<ReplayableAction>,
     running: bool,
@@ -144,6 +272,7 @@ impl Replayer {
                     editor.replay_insert_event(&text, utf16_range_to_replace.clone(), window, cx)
                 })
             }
+            ReplayableAction::Keystroke(keystroke) => replay_keystroke(keystroke, window, cx),
         }
         window.defer(cx, move |window, cx| self.next(window, cx));
     }
@@ -159,6 +288,7 @@ impl Vim {
         let globals = Vim::globals(cx);
         globals.recording_register = Some(register);
         globals.recordings.remove(&register);
+        globals.recording_keystrokes.clear();
         globals.ignore_current_insertion = true;
         self.clear_operator(window, cx)
     }
@@ -180,8 +310,19 @@ impl Vim {
             };
             register = last;
         }
-        let Some(actions) = globals.recordings.get(&register) else {
-            return;
+        // Registers that were edited or restored from a previous session have no recorded
+        // actions, so their text is typed out instead, as Vim does.
+        let actions = if let Some(actions) = globals.recordings.get(&register) {
+            actions.clone()
+        } else {
+            let lower = register.to_ascii_lowercase();
+            let Some(contents) = globals.registers.get(&lower) else {
+                return;
+            };
+            keystrokes_from_text(&contents.text)
+                .into_iter()
+                .map(ReplayableAction::Keystroke)
+                .collect()
         };
 
         let mut repeated_actions = vec![];
@@ -354,7 +495,8 @@ mod test {
     use gpui::EntityInputHandler;
 
     use crate::{
-        state::Mode,
+        normal::repeat::{keystrokes_from_text, keystrokes_to_text},
+        state::{Mode, VimGlobals},
         test::{NeovimBackedTestContext, VimTestContext},
     };
 
@@ -779,4 +921,63 @@ mod test {
         cx.simulate_shared_keystrokes("@ b").await;
         cx.shared_state().await.assert_eq("aaaaaaabbbˇd");
     }
+
+    #[gpui::test]
+    async fn test_edit_recorded_macro(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone\ntwo\nthree\n", Mode::Normal);
+        cx.simulate_keystrokes("q a A ! escape j q");
+        cx.assert_state("one!\ntwˇo\nthree\n", Mode::Normal);
+        let text =
+            cx.update_editor(|_, _, cx| cx.global::<VimGlobals>().registers[&'a'].text.to_string());
+        assert_eq!(text, "A!<Esc>j");
+
+        // paste the macro, edit it, and yank it back into the register
+        cx.simulate_keystrokes("shift-g \" a p 0 f ! r ? 0 \" a y $ u u");
+        cx.simulate_keystrokes("g g j @ a");
+        cx.assert_state("one!\ntwo?\nthrˇee\n", Mode::Normal);
+
+        cx.simulate_keystrokes(": l e t space @ b space = space ' A x < e s c > ' enter");
+        cx.simulate_keystrokes("@ b");
+        cx.assert_state("one!\ntwo?\nthreeˇx\n", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_append_recorded_macro(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone\ntwo\nthree\n", Mode::Normal);
+        cx.simulate_keystrokes("q a A ! escape q q shift-a j q");
+        cx.assert_state("one!\ntwˇo\nthree\n", Mode::Normal);
+        let text =
+            cx.update_editor(|_, _, cx| cx.global::<VimGlobals>().registers[&'a'].text.to_string());
+        assert_eq!(text, "A!<Esc>j");
+
+        cx.simulate_keystrokes("@ a");
+        cx.assert_state("one!\ntwo!\nthrˇee\n", Mode::Normal);
+    }
+
+    #[test]
+    fn test_keystroke_text() {
+        let text = "ab <lt>x><Esc><C-r>A<CR><f12>";
+        let keystrokes = keystrokes_from_text(text);
+        assert_eq!(
+            keystrokes
+                .iter()
+                .map(|keystroke| keystroke.unparse())
+                .collect::<Vec<_>>(),
+            [
+                "a", "b", "space", "<", "x", ">", "escape", "ctrl-r", "shift-a", "enter", "f12"
+            ]
+        );
+        let keystrokes = keystrokes
+            .into_iter()
+            .map(|keystroke| keystroke.with_simulated_ime())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keystrokes_to_text(&keystrokes),
+            "ab <lt>x><Esc><C-r>A<CR><F12>"
+        );
+    }
 }
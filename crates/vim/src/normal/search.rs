@@ -171,6 +171,7 @@ impl Vim {
                         prior_selections,
                         prior_operator: self.operator_stack.last().cloned(),
                         prior_mode,
+                        helix_select: None,
                     }
                 });
             }
@@ -184,6 +185,10 @@ impl Vim {
     }
 
     pub fn search_submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(helix_select) = self.search.helix_select.take() {
+            self.helix_search_submit(helix_select, window, cx);
+            return;
+        }
         self.store_visual_marks(window, cx);
         let Some(pane) = self.pane(window, cx) else {
             return;
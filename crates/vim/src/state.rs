@@ -1,6 +1,6 @@
 use crate::command::command_interceptor;
 use crate::motion::MotionKind;
-use crate::normal::repeat::Replayer;
+use crate::normal::repeat::{Replayer, keystrokes_to_text};
 use crate::surrounds::SurroundsType;
 use crate::{ToggleMarksView, ToggleRegistersView, UseSystemClipboard, Vim, VimAddon, VimSettings};
 use crate::{motion::Motion, object::Object};
@@ -13,7 +13,8 @@ use editor::display_map::{is_invisible, replacement};
 use editor::{Anchor, ClipboardSelection, Editor, MultiBuffer, ToPoint as EditorToPoint};
 use gpui::{
     Action, App, AppContext, BorrowAppContext, ClipboardEntry, ClipboardItem, DismissEvent, Entity,
-    EntityId, Global, HighlightStyle, StyledText, Subscription, Task, TextStyle, WeakEntity,
+    EntityId, Global, HighlightStyle, Keystroke, StyledText, Subscription, Task, TextStyle,
+    WeakEntity,
 };
 use language::{Buffer, BufferEvent, BufferId, Chunk, Point};
 use multi_buffer::MultiBufferRow;
@@ -23,6 +24,7 @@ use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
 use std::borrow::BorrowMut;
 use std::collections::HashSet;
+use std::mem;
 use std::path::Path;
 use std::{fmt::Display, ops::Range, sync::Arc};
 use text::{Bias, ToPoint};
@@ -211,6 +213,9 @@ pub struct VimGlobals {
     pub recorded_selection: RecordedSelection,
 
     pub recording_register: Option<char>,
+    /// The keystrokes typed since the recording began, which become the register's text.
+    pub recording_keystrokes: Vec<Keystroke>,
+    pub replaying_keystroke: bool,
     pub last_recorded_register: Option<char>,
     pub last_replayed_register: Option<char>,
     pub replayer: Option<Replayer>,
@@ -627,6 +632,11 @@ impl VimGlobals {
         })
         .detach();
 
+        cx.intercept_keystrokes(|event, _, cx| {
+            Vim::globals(cx).observe_keystroke(&event.keystroke)
+        })
+        .detach();
+
         cx.observe_new(|workspace: &mut Workspace, window, _| {
             RegistersView::register(workspace, window);
         })
@@ -653,6 +663,7 @@ impl VimGlobals {
                 CommandPaletteInterceptor::update_global(cx, |interceptor, _| {
                     interceptor.set(Box::new(command_interceptor));
                 });
+                Self::load_registers(cx);
                 for window in cx.windows() {
                     if let Some(workspace) = window.downcast::<Workspace>() {
                         workspace
@@ -682,6 +693,82 @@ impl VimGlobals {
         .detach()
     }
 
+    fn load_registers(cx: &mut App) {
+        cx.spawn(async move |cx| {
+            let registers = cx
+                .background_spawn(async move { DB.get_registers() })
+                .await?;
+            cx.update(|cx| {
+                Vim::update_globals(cx, |globals, _| {
+                    for (name, text) in registers {
+                        if let Some(name) = name.chars().next() {
+                            globals.registers.entry(name).or_insert(text.into());
+                        }
+                    }
+                })
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Named registers are the only ones that are kept across sessions, as in Vim.
+    fn persist_register(&self, register: char, cx: &App) {
+        if !register.is_ascii_lowercase() {
+            return;
+        }
+        let text = self
+            .registers
+            .get(&register)
+            .map(|register| register.text.to_string())
+            .unwrap_or_default();
+        cx.background_spawn(async move { DB.set_register(register.to_string(), text).await })
+            .detach_and_log_err(cx);
+    }
+
+    /// Sets the text of a register, as with `:let @a = '...'`. A macro recorded into the
+    /// register is replaced by the new text.
+    pub(crate) fn set_register_text(&mut self, register: char, text: String, cx: &App) {
+        let register = register.to_ascii_lowercase();
+        self.recordings.remove(&register);
+        self.registers.insert(register, text.into());
+        self.persist_register(register, cx);
+    }
+
+    /// Stops recording a macro, if one is being recorded. The keys typed while recording are
+    /// stored as the register's text, so that the macro can be pasted, edited and yanked back
+    /// like any other register. Recording into an uppercase register appends to it, as in Vim.
+    pub(crate) fn stop_recording_register(&mut self, cx: &App) -> bool {
+        let Some(register) = self.recording_register.take() else {
+            return false;
+        };
+        let mut keystrokes = mem::take(&mut self.recording_keystrokes);
+        // The `q` that stopped the recording is not part of the macro.
+        if keystrokes
+            .last()
+            .is_some_and(|keystroke| keystroke.key == "q" && !keystroke.modifiers.modified())
+        {
+            keystrokes.pop();
+        }
+        let lower = register.to_ascii_lowercase();
+        let mut text = keystrokes_to_text(&keystrokes);
+        if lower != register {
+            let actions = self.recordings.remove(&register).unwrap_or_default();
+            if let Some(current) = self.registers.get(&lower) {
+                text = current.text.to_string() + &text;
+                // Registers without recorded actions are replayed from their text.
+                if let Some(recording) = self.recordings.get_mut(&lower) {
+                    recording.extend(actions);
+                }
+            } else {
+                self.recordings.insert(lower, actions);
+            }
+        }
+        self.last_recorded_register = Some(lower);
+        self.registers.insert(lower, text.into());
+        self.persist_register(lower, cx);
+        true
+    }
+
     fn register_workspace(&mut self, workspace: &Workspace, cx: &mut Context<Workspace>) {
         let entity_id = cx.entity_id();
         self.marks.insert(entity_id, MarksState::new(workspace, cx));
@@ -710,6 +797,8 @@ impl VimGlobals {
                 current.clipboard_selections.take();
                 let yanked = current.clone();
                 self.registers.insert('"', yanked);
+                self.recordings.remove(&lower);
+                self.persist_register(lower, cx);
             } else {
                 match lower {
                     '_' | ':' | '.' | '%' | '#' | '=' | '/' => {}
@@ -731,6 +820,8 @@ impl VimGlobals {
                     _ => {
                         self.registers.insert('"', content.clone());
                         self.registers.insert(lower, content);
+                        self.recordings.remove(&lower);
+                        self.persist_register(lower, cx);
                     }
                 }
             }
@@ -849,6 +940,13 @@ impl VimGlobals {
         }
     }
 
+    pub fn observe_keystroke(&mut self, keystroke: &Keystroke) {
+        if self.recording_register.is_some() && self.replayer.is_none() && !self.replaying_keystroke
+        {
+            self.recording_keystrokes.push(keystroke.clone());
+        }
+    }
+
     pub fn observe_insertion(&mut self, text: &Arc<str>, range_to_replace: Option<Range<isize>>) {
         if self.ignore_current_insertion {
             self.ignore_current_insertion = false;
@@ -900,6 +998,7 @@ pub enum ReplayableAction {
         text: Arc<str>,
         utf16_range_to_replace: Option<Range<isize>>,
     },
+    Keystroke(Keystroke),
 }
 
 impl Clone for ReplayableAction {
@@ -913,6 +1012,7 @@ impl Clone for ReplayableAction {
                 text: text.clone(),
                 utf16_range_to_replace: utf16_range_to_replace.clone(),
             },
+            Self::Keystroke(keystroke) => Self::Keystroke(keystroke.clone()),
         }
     }
 }
@@ -925,6 +1025,15 @@ pub struct SearchState {
     pub prior_selections: Vec<Range<Anchor>>,
     pub prior_operator: Option<Operator>,
     pub prior_mode: Mode,
+    /// Set when the search was started by helix's `s` or `S`, which select the matches within
+    /// the prior selections instead of moving to the next match.
+    pub helix_select: Option<HelixSelect>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HelixSelect {
+    Matches,
+    Split,
 }
 
 impl Operator {
@@ -1628,6 +1737,12 @@ define_connection! (
             CREATE UNIQUE INDEX idx_vim_global_marks_paths
             ON vim_global_marks_paths(workspace_id, mark_name);
         ),
+        sql! (
+            CREATE TABLE vim_registers(
+                register_name TEXT PRIMARY KEY,
+                value TEXT
+            );
+        ),
     ];
 );
 
@@ -1716,4 +1831,23 @@ impl VimDb {
             WHERE workspace_id = ?
         ))?(workspace_id)
     }
+
+    pub(crate) async fn set_register(&self, register_name: String, value: String) -> Result<()> {
+        log::debug!("Setting register {register_name}");
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                INSERT OR REPLACE INTO vim_registers
+                    (register_name, value)
+                VALUES
+                    (?, ?)
+            ))?((register_name, value))
+        })
+        .await
+    }
+
+    fn get_registers(&self) -> Result<Vec<(String, String)>> {
+        self.select(sql!(
+            SELECT register_name, value FROM vim_registers
+        ))?()
+    }
 }
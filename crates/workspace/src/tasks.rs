@@ -1,11 +1,19 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, process::ExitStatus, rc::Rc, sync::Arc};
 
-use anyhow::{Result, anyhow};
-use gpui::{Context, Entity, Task};
+use anyhow::{Context as _, Result, anyhow};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{self, LocalBoxFuture, Shared},
+};
+use gpui::{AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::TaskSourceKind;
+use project::{TaskDependency, TaskSourceKind};
 use remote::ConnectionState;
-use task::{DebugScenario, ResolvedTask, SpawnInTerminal, TaskContext, TaskTemplate};
+use task::{
+    DebugScenario, DependsOrder, ResolvedTask, RevealStrategy, SpawnInTerminal, TaskContext,
+    TaskInput, TaskTemplate,
+};
 use ui::Window;
 
 use crate::Workspace;
@@ -54,7 +62,42 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
+        if !resolved_task.unresolved_inputs().is_empty() {
+            let resolve_inputs =
+                self.resolve_task_inputs(task_source_kind.clone(), resolved_task, window, cx);
+            cx.spawn_in(window, async move |workspace, cx| {
+                if let Some(resolved_task) = resolve_inputs.await? {
+                    workspace.update_in(cx, |workspace, window, cx| {
+                        workspace.schedule_resolved_task(
+                            task_source_kind,
+                            resolved_task,
+                            omit_history,
+                            window,
+                            cx,
+                        )
+                    })?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+            return;
+        }
+
         let spawn_in_terminal = resolved_task.resolved.clone();
+        let task_with_dependencies = if resolved_task.original_task().depends_on.is_empty() {
+            None
+        } else {
+            Some((task_source_kind.clone(), resolved_task.clone()))
+        };
+        if !resolved_task.resolved.rerun_on.is_empty() {
+            self.project
+                .read(cx)
+                .task_store()
+                .update(cx, |task_store, _| {
+                    task_store
+                        .watched_task_scheduled(task_source_kind.clone(), resolved_task.clone())
+                });
+        }
         if !omit_history {
             self.project().update(cx, |project, cx| {
                 if let Some(task_inventory) =
@@ -67,13 +110,148 @@ impl Workspace {
             });
         }
 
-        if let Some(terminal_provider) = self.terminal_provider.as_ref() {
+        if let Some((task_source_kind, resolved_task)) = task_with_dependencies {
+            self.schedule_task_with_dependencies(task_source_kind, resolved_task, window, cx);
+        } else if let Some(terminal_provider) = self.terminal_provider.as_ref() {
             terminal_provider
                 .spawn(spawn_in_terminal, window, cx)
                 .detach_and_log_err(cx);
         }
     }
 
+    /// Asks for the values of the task's inputs that have none yet, resolving to `None` if any of
+    /// them gets dismissed.
+    fn resolve_task_inputs(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ResolvedTask>>> {
+        let inputs = resolved_task
+            .unresolved_inputs()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        if inputs.is_empty() {
+            return Task::ready(Ok(Some(resolved_task)));
+        }
+        let input_values = self.request_task_input_values(
+            Some(task_source_kind),
+            resolved_task.original_task().label.clone(),
+            inputs,
+            window,
+            cx,
+        );
+        cx.background_spawn(async move {
+            let Some(input_values) = input_values.await? else {
+                return Ok(None);
+            };
+            resolved_task
+                .with_input_values(input_values)
+                .context("resolving the task with its inputs")
+                .map(Some)
+        })
+    }
+
+    /// Asks for the values of the inputs of a task or a debug scenario, which has no source kind,
+    /// resolving to `None` if any of them gets dismissed.
+    pub fn request_task_input_values(
+        &mut self,
+        task_source_kind: Option<TaskSourceKind>,
+        task_label: String,
+        inputs: Vec<TaskInput>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Vec<(String, String)>>>> {
+        let Some(task_input_provider) = self.task_input_provider.as_ref() else {
+            return Task::ready(Err(anyhow!(
+                "No task input provider to ask for the inputs of {task_label:?}"
+            )));
+        };
+        let input_values = task_input_provider.request_input_values(
+            task_source_kind,
+            task_label,
+            inputs,
+            window,
+            cx,
+        );
+        cx.background_spawn(async move { Ok(input_values.await) })
+    }
+
+    /// Reruns the task in its terminal after the files it watches change, cancelling the run
+    /// that is still in progress, without revealing the terminal.
+    pub(crate) fn rerun_watched_task(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        mut resolved_task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        resolved_task.resolved.allow_concurrent_runs = true;
+        resolved_task.resolved.use_new_terminal = false;
+        resolved_task.resolved.reveal = RevealStrategy::Never;
+        self.schedule_resolved_task(task_source_kind, resolved_task, true, window, cx);
+    }
+
+    /// Runs the tasks that the task depends on, and then the task itself, unless any of
+    /// them fails.
+    fn schedule_task_with_dependencies(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        else {
+            return;
+        };
+        let worktree = match &task_source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => None,
+        };
+        let dependencies = match inventory
+            .read(cx)
+            .task_dependencies(resolved_task.original_task(), worktree)
+        {
+            Ok(dependencies) => dependencies,
+            Err(e) => {
+                self.show_error(&e, cx);
+                return;
+            }
+        };
+
+        cx.spawn_in(window, async move |workspace, cx| {
+            run_task_dependencies(
+                workspace.clone(),
+                dependencies,
+                resolved_task.original_task().depends_order,
+                resolved_task.task_context().clone(),
+                DependencyRuns::default(),
+                cx.clone(),
+            )
+            .await?;
+
+            // Tasks without a command only run their dependencies.
+            if !resolved_task.resolved.command.trim().is_empty() {
+                workspace
+                    .update_in(cx, |workspace, window, cx| {
+                        workspace.spawn_in_terminal(resolved_task.resolved, window, cx)
+                    })?
+                    .await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn start_debug_session(
         &mut self,
         scenario: DebugScenario,
@@ -100,3 +278,105 @@ impl Workspace {
         }
     }
 }
+
+/// The runs of the dependencies of one scheduled task, by task label, so that a task that
+/// several others depend on runs once.
+type DependencyRuns =
+    Rc<RefCell<HashMap<String, Shared<LocalBoxFuture<'static, Result<(), Arc<anyhow::Error>>>>>>>;
+
+/// Runs the tasks in the given order, each after its own dependencies, and fails as soon as one of
+/// them fails. A task that is already running, or has run, for the same scheduled task is awaited
+/// instead of being run again.
+fn run_task_dependencies(
+    workspace: WeakEntity<Workspace>,
+    dependencies: Vec<TaskDependency>,
+    order: DependsOrder,
+    task_cx: TaskContext,
+    runs: DependencyRuns,
+    cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<()>> {
+    async move {
+        let dependency_runs = dependencies.into_iter().map(|dependency| {
+            let run = runs
+                .borrow_mut()
+                .entry(dependency.template.label.clone())
+                .or_insert_with(|| {
+                    run_task_dependency(
+                        workspace.clone(),
+                        dependency,
+                        task_cx.clone(),
+                        runs.clone(),
+                        cx.clone(),
+                    )
+                    .map(|result| result.map_err(Arc::new))
+                    .boxed_local()
+                    .shared()
+                })
+                .clone();
+            async move { run.await.map_err(|e| anyhow!("{e:#}")) }
+        });
+
+        match order {
+            DependsOrder::Parallel => {
+                future::try_join_all(dependency_runs).await?;
+            }
+            DependsOrder::Sequence => {
+                for run in dependency_runs {
+                    run.await?;
+                }
+            }
+        }
+        Ok(())
+    }
+    .boxed_local()
+}
+
+/// Runs the task after its own dependencies, failing if any of them fails.
+async fn run_task_dependency(
+    workspace: WeakEntity<Workspace>,
+    dependency: TaskDependency,
+    task_cx: TaskContext,
+    runs: DependencyRuns,
+    mut cx: AsyncWindowContext,
+) -> Result<()> {
+    let TaskDependency {
+        source_kind,
+        template,
+        dependencies,
+    } = dependency;
+    run_task_dependencies(
+        workspace.clone(),
+        dependencies,
+        template.depends_order,
+        task_cx.clone(),
+        runs,
+        cx.clone(),
+    )
+    .await?;
+    if template.command.trim().is_empty() {
+        return Ok(());
+    }
+
+    let resolved_task = template
+        .resolve_task(&source_kind.to_id_base(), &task_cx)
+        .with_context(|| format!("resolving task {:?}", template.label))?;
+    let Some(resolved_task) = workspace
+        .update_in(&mut cx, |workspace, window, cx| {
+            workspace.resolve_task_inputs(source_kind, resolved_task, window, cx)
+        })?
+        .await?
+    else {
+        anyhow::bail!("Task {:?} was cancelled", template.label);
+    };
+    let exit_status = workspace
+        .update_in(&mut cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(resolved_task.resolved, window, cx)
+        })?
+        .await?;
+    anyhow::ensure!(
+        exit_status.success(),
+        "Task {:?} failed: {exit_status}",
+        template.label
+    );
+    Ok(())
+}
@@ -67,8 +67,8 @@ pub use persistence::{
 };
 use postage::stream::Stream;
 use project::{
-    DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
-    debugger::breakpoint_store::BreakpointStoreEvent,
+    DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, TaskSourceKind, Worktree,
+    WorktreeId, debugger::breakpoint_store::BreakpointStoreEvent, task_store::TaskStoreEvent,
 };
 use remote::{SshClientDelegate, SshConnectionOptions, ssh_session::ConnectionIdentifier};
 use schemars::JsonSchema;
@@ -96,7 +96,7 @@ use std::{
     sync::{Arc, LazyLock, Weak, atomic::AtomicUsize},
     time::Duration,
 };
-use task::{DebugScenario, SpawnInTerminal, TaskContext};
+use task::{DebugScenario, SpawnInTerminal, TaskContext, TaskInput};
 use theme::{ActiveTheme, SystemAppearance, ThemeSettings};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
@@ -151,6 +151,19 @@ pub trait DebuggerProvider {
     );
 }
 
+pub trait TaskInputProvider {
+    // `task_source_kind` is `None` for debug scenarios.
+    // Resolves to `None` if the user dismisses any of the inputs.
+    fn request_input_values(
+        &self,
+        task_source_kind: Option<TaskSourceKind>,
+        task_label: String,
+        inputs: Vec<TaskInput>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Option<Vec<(String, String)>>>;
+}
+
 actions!(
     workspace,
     [
@@ -873,6 +886,7 @@ pub struct Workspace {
     on_prompt_for_open_path: Option<PromptForOpenPath>,
     terminal_provider: Option<Box<dyn TerminalProvider>>,
     debugger_provider: Option<Box<dyn DebuggerProvider>>,
+    task_input_provider: Option<Box<dyn TaskInputProvider>>,
     serializable_items_tx: UnboundedSender<Box<dyn SerializableItemHandle>>,
     serialized_ssh_project: Option<SerializedSshProject>,
     _items_serializer: Task<Result<()>>,
@@ -1003,6 +1017,22 @@ impl Workspace {
         )
         .detach();
 
+        cx.subscribe_in(
+            project.read(cx).task_store(),
+            window,
+            |workspace, _, event, window, cx| match event {
+                TaskStoreEvent::RerunWatchedTask(task_source_kind, resolved_task) => {
+                    workspace.rerun_watched_task(
+                        task_source_kind.clone(),
+                        resolved_task.clone(),
+                        window,
+                        cx,
+                    );
+                }
+            },
+        )
+        .detach();
+
         cx.on_focus_lost(window, |this, window, cx| {
             let focus_handle = this.focus_handle(cx);
             window.focus(&focus_handle);
@@ -1200,6 +1230,7 @@ impl Workspace {
             on_prompt_for_open_path: None,
             terminal_provider: None,
             debugger_provider: None,
+            task_input_provider: None,
             serializable_items_tx,
             _items_serializer,
             session_id: Some(session_id),
@@ -1744,6 +1775,10 @@ impl Workspace {
         self.debugger_provider = Some(Box::new(provider));
     }
 
+    pub fn set_task_input_provider(&mut self, provider: impl TaskInputProvider + 'static) {
+        self.task_input_provider = Some(Box::new(provider));
+    }
+
     pub fn serialized_ssh_project(&self) -> Option<SerializedSshProject> {
         self.serialized_ssh_project.clone()
     }
@@ -9004,6 +9039,125 @@ mod tests {
         });
     }
 
+    struct RecordingTerminalProvider(Rc<RefCell<Vec<SpawnInTerminal>>>);
+
+    impl TerminalProvider for RecordingTerminalProvider {
+        fn spawn(
+            &self,
+            task: SpawnInTerminal,
+            _: &mut Window,
+            _: &mut App,
+        ) -> Task<Result<ExitStatus>> {
+            self.0.borrow_mut().push(task);
+            Task::ready(Ok(ExitStatus::default()))
+        }
+    }
+
+    #[gpui::test]
+    async fn test_rerunning_watched_task(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let spawned = Rc::new(RefCell::new(Vec::new()));
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(RecordingTerminalProvider(spawned.clone()))
+        });
+
+        let resolved_task = task::TaskTemplate {
+            label: "check".to_string(),
+            command: "cargo check".to_string(),
+            rerun_on: vec!["**/*.rs".to_string()],
+            reveal: task::RevealStrategy::Always,
+            ..task::TaskTemplate::default()
+        }
+        .resolve_task("test", &TaskContext::default())
+        .unwrap();
+        let task_store = project.read_with(cx, |project, _| project.task_store().clone());
+        task_store.update(cx, |_, cx| {
+            cx.emit(TaskStoreEvent::RerunWatchedTask(
+                TaskSourceKind::UserInput,
+                resolved_task,
+            ))
+        });
+        cx.run_until_parked();
+
+        // The rerun cancels the run in progress and takes its place in the same terminal,
+        // without stealing the focus.
+        let spawned = spawned.borrow();
+        assert_eq!(spawned.len(), 1);
+        assert_eq!(spawned[0].label, "check");
+        assert!(spawned[0].allow_concurrent_runs);
+        assert!(!spawned[0].use_new_terminal);
+        assert_eq!(spawned[0].reveal, task::RevealStrategy::Never);
+    }
+
+    #[gpui::test]
+    async fn test_shared_task_dependencies_run_once(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                ".zed": {
+                    "tasks.json": json!([
+                        { "label": "generate", "command": "protoc" },
+                        { "label": "build", "command": "cargo build", "depends_on": ["generate"] },
+                        { "label": "run", "command": "cargo run", "depends_on": ["build", "generate"] },
+                    ])
+                    .to_string(),
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        let spawned = Rc::new(RefCell::new(Vec::new()));
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(RecordingTerminalProvider(spawned.clone()))
+        });
+
+        let (task_source_kind, run) = project.update(cx, |project, cx| {
+            let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+            project
+                .task_store()
+                .read(cx)
+                .task_inventory()
+                .unwrap()
+                .read(cx)
+                .list_tasks(None, None, Some(worktree_id), cx)
+                .into_iter()
+                .find(|(_, template)| template.label == "run")
+                .unwrap()
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.schedule_task(
+                task_source_kind,
+                &run,
+                &TaskContext::default(),
+                true,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        // `generate` runs once, even though both `run` and `build` depend on it.
+        assert_eq!(
+            spawned
+                .borrow()
+                .iter()
+                .map(|spawned| spawned.label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["generate", "build", "run"]
+        );
+    }
+
     mod register_project_item_tests {
 
         use super::*;